@@ -5,9 +5,12 @@
 //!
 //! The compiler and the VM are generic over a [`Pairing`](https://docs.rs/ark-ec/latest/ark_ec/pairing/trait.Pairing.html). Currently, we support the curves `bn254` and `bls12-381`.
 //!
-//! The [`CoCircomCompiler`], provides two methods for interacting with circom files
+//! The [`CoCircomCompiler`], provides five methods for interacting with circom files
 //!     * [`CoCircomCompiler::parse`] - to parse a circuit
 //!     * [`CoCircomCompiler::get_public_inputs`] - to obtain the name of the public inputs of the circuit
+//!     * [`CoCircomCompiler::get_public_signals`] - to obtain the names of the public signals (outputs and public inputs) in the order of the verifier
+//!     * [`CoCircomCompiler::get_input_signals`] - to obtain the names and sizes of the input signals of the circuit
+//!     * [`CoCircomCompiler::get_inputs`] - to obtain the public inputs and the input signals with a single compilation
 //!
 //! To configure the compiler, have a look at [`CompilerConfig`].
 //!
@@ -28,7 +31,7 @@ use circom_compiler::{
 use circom_constraint_generation::BuildConfig;
 use circom_mpc_vm::{
     op_codes::{CodeBlock, MpcOpCode},
-    types::{CoCircomCompilerParsed, FunDecl, OutputMapping, TemplateDecl},
+    types::{AssertLocation, CoCircomCompilerParsed, FunDecl, OutputMapping, TemplateDecl},
 };
 use circom_program_structure::{
     ast::SignalType, error_definition::Report, program_archive::ProgramArchive,
@@ -109,8 +112,15 @@ pub struct CoCircomCompiler<P: Pairing> {
     pub(crate) fun_decls: HashMap<String, FunDecl>,
     pub(crate) templ_decls: HashMap<String, TemplateDecl>,
     pub(crate) current_code_block: CodeBlock,
+    pub(crate) assert_table: Vec<AssertLocation>,
+    // the source file and the signals of the template or function we are currently compiling, used to locate failing assertions
+    current_file: String,
+    current_signals: Vec<SignalName>,
 }
 
+/// The offset of a signal in its component, its size and its name
+type SignalName = (usize, usize, String);
+
 impl<P: Pairing> CoCircomCompiler<P>
 where
     P: CircomArkworksPairingBridge,
@@ -126,6 +136,9 @@ where
             current_code_block: vec![],
             fun_decls: HashMap::new(),
             templ_decls: HashMap::new(),
+            assert_table: vec![],
+            current_file: String::new(),
+            current_signals: vec![],
             phantom_data: PhantomData,
         }
     }
@@ -168,10 +181,7 @@ where
         output_mappings
     }
 
-    fn build_circuit(
-        &self,
-        program_archive: ProgramArchive,
-    ) -> Result<(CircomCircuit, OutputMapping)> {
+    fn build_vcp(&self, program_archive: ProgramArchive) -> Result<VCP> {
         let build_config = BuildConfig {
             no_rounds: if let SimplificationLevel::O2(r) = self.config.simplification {
                 r
@@ -190,7 +200,53 @@ where
         };
         let (_, vcp) = circom_constraint_generation::build_circuit(program_archive, build_config)
             .map_err(|_| eyre!("cannot build vcp"))?;
+        Ok(vcp)
+    }
+
+    fn get_source_files(program_archive: &ProgramArchive) -> HashMap<String, String> {
+        let files = program_archive.file_library.to_storage();
+        let file_name = |file_id| {
+            files
+                .get(file_id)
+                .map(|file| file.name().to_owned())
+                .unwrap_or_default()
+        };
+        let templates = program_archive
+            .get_templates()
+            .iter()
+            .map(|(name, data)| (name.to_owned(), file_name(data.get_file_id())));
+        let functions = program_archive
+            .get_functions()
+            .iter()
+            .map(|(name, data)| (name.to_owned(), file_name(data.get_file_id())));
+        templates.chain(functions).collect()
+    }
+
+    fn get_signal_names(vcp: &VCP) -> HashMap<String, Vec<SignalName>> {
+        vcp.templates
+            .iter()
+            .map(|templ| {
+                let signals = templ
+                    .signals
+                    .iter()
+                    .map(|s| (s.local_id, s.size(), s.name.clone()))
+                    .collect();
+                (templ.template_header.clone(), signals)
+            })
+            .collect()
+    }
+
+    fn build_circuit(
+        &self,
+        program_archive: ProgramArchive,
+    ) -> Result<(
+        CircomCircuit,
+        OutputMapping,
+        HashMap<String, Vec<SignalName>>,
+    )> {
+        let vcp = self.build_vcp(program_archive)?;
         let output_mapping = self.get_output_mapping(&vcp);
+        let signal_names = Self::get_signal_names(&vcp);
 
         let flags = CompilationFlags {
             main_inputs_log: false,
@@ -199,6 +255,7 @@ where
         Ok((
             CircomCircuit::build(vcp, flags, &self.config.version),
             output_mapping,
+            signal_names,
         ))
     }
 
@@ -416,7 +473,46 @@ where
     fn handle_assert_bucket(&mut self, assert_bucket: &AssertBucket) {
         //evaluate the assertion
         self.handle_instruction(&assert_bucket.evaluate);
-        self.emit_opcode(MpcOpCode::Assert(assert_bucket.line));
+        let signal = self.find_asserted_signal(&assert_bucket.evaluate);
+        self.assert_table.push(AssertLocation::new(
+            self.current_file.clone(),
+            assert_bucket.line,
+            signal,
+        ));
+        self.emit_opcode(MpcOpCode::Assert(self.assert_table.len() - 1));
+    }
+
+    // finds the first signal of the current template that is read by the assertion, e.g., `out` for `out === a * b`
+    fn find_asserted_signal(&self, inst: &Instruction) -> Option<String> {
+        match inst {
+            Instruction::Load(load_bucket) => {
+                if !matches!(load_bucket.address_type, AddressType::Signal) {
+                    return None;
+                }
+                let LocationRule::Indexed { location, .. } = &load_bucket.src else {
+                    return None;
+                };
+                let Instruction::Value(value_bucket) = &**location else {
+                    return None;
+                };
+                let offset = value_bucket.value;
+                self.current_signals
+                    .iter()
+                    .find(|(start, size, _)| (*start..start + size).contains(&offset))
+                    .map(|(start, size, name)| {
+                        if *size > 1 {
+                            format!("{name}[{}]", offset - start)
+                        } else {
+                            name.to_owned()
+                        }
+                    })
+            }
+            Instruction::Compute(compute_bucket) => compute_bucket
+                .stack
+                .iter()
+                .find_map(|inst| self.find_asserted_signal(inst)),
+            _ => None,
+        }
     }
 
     fn handle_return_bucket(&mut self, return_bucket: &ReturnBucket) {
@@ -590,6 +686,84 @@ where
         Self::new(file, config).get_public_inputs_inner()
     }
 
+    /// Returns a `Result<Vec<String>>`
+    /// containing the names of all public signals of the provided .circom file, i.e., the outputs and the public inputs of the main component.
+    ///
+    /// The names are in the order in which the values of the signals are passed to the verifier. This method is useful to bring named public inputs into this order.
+    ///
+    /// # Params
+    /// * **file** - a `String` denoting the path to circom file.
+    /// * **config** - the [CompilerConfig]
+    /// # Returns
+    ///
+    /// Returns a `Result` where:
+    ///
+    /// - `Ok(signals)` contains a vector of the names of the public signals.
+    /// - `Err(err)` indicates an error occurred during parsing or compilation.
+    pub fn get_public_signals(file: String, config: CompilerConfig) -> Result<Vec<String>> {
+        Self::new(file, config).get_public_signals_inner()
+    }
+
+    /// Returns a `Result<Vec<(String, usize)>>`
+    /// containing the names of all public signals of the provided .circom file in the same order as [get_public_signals](Self::get_public_signals), together with their number of field elements.
+    ///
+    /// Array signals occupy as many consecutive public inputs as they have elements, in row-major order. This method is useful to determine the position of every public signal in the public inputs.
+    ///
+    /// # Params
+    /// * **file** - a `String` denoting the path to circom file.
+    /// * **config** - the [CompilerConfig]
+    /// # Returns
+    ///
+    /// Returns a `Result` where:
+    ///
+    /// - `Ok(signals)` contains a vector of the names and sizes of the public signals.
+    /// - `Err(err)` indicates an error occurred during parsing or compilation.
+    pub fn get_public_signals_with_sizes(
+        file: String,
+        config: CompilerConfig,
+    ) -> Result<Vec<(String, usize)>> {
+        Self::new(file, config).get_public_signals_with_sizes_inner()
+    }
+
+    /// Returns a `Result<Vec<(String, usize)>>`
+    /// containing the names of all input signals of the main component of the provided .circom file, together with their number of field elements.
+    ///
+    /// This method is useful to validate the length of array inputs before secret-sharing them.
+    ///
+    /// # Params
+    /// * **file** - a `String` denoting the path to circom file.
+    /// * **config** - the [CompilerConfig]
+    /// # Returns
+    ///
+    /// Returns a `Result` where:
+    ///
+    /// - `Ok(signals)` contains a vector of the names and sizes of the input signals.
+    /// - `Err(err)` indicates an error occurred during parsing or compilation.
+    pub fn get_input_signals(file: String, config: CompilerConfig) -> Result<Vec<(String, usize)>> {
+        Self::new(file, config).get_input_signals_inner()
+    }
+
+    /// Returns a `Result<(Vec<String>, Vec<(String, usize)>)>`
+    /// containing the names of the public inputs as [get_public_inputs](Self::get_public_inputs) and the names and sizes of the input signals as [get_input_signals](Self::get_input_signals).
+    ///
+    /// The circuit is only compiled once, so prefer this method if both are needed.
+    ///
+    /// # Params
+    /// * **file** - a `String` denoting the path to circom file.
+    /// * **config** - the [CompilerConfig]
+    /// # Returns
+    ///
+    /// Returns a `Result` where:
+    ///
+    /// - `Ok((public_inputs, input_signals))` contains the names of the public inputs and the names and sizes of the input signals.
+    /// - `Err(err)` indicates an error occurred during parsing or compilation.
+    pub fn get_inputs(
+        file: String,
+        config: CompilerConfig,
+    ) -> Result<(Vec<String>, Vec<(String, usize)>)> {
+        Self::new(file, config).get_inputs_inner()
+    }
+
     /// Parsed the circuit provided by `file` and returns a `Result` of [`CoCircomCompilerParsed`].
     ///
     /// # Params
@@ -610,6 +784,63 @@ where
         Self::new(file, config).parse_inner()
     }
 
+    fn get_public_signals_inner(self) -> Result<Vec<String>> {
+        Ok(self
+            .get_public_signals_with_sizes_inner()?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect())
+    }
+
+    fn get_public_signals_with_sizes_inner(self) -> Result<Vec<(String, usize)>> {
+        let program_archive = self.get_program_archive()?;
+        let public_inputs = program_archive.public_inputs.clone();
+        let vcp = self.build_vcp(program_archive)?;
+        let main = &vcp.templates[vcp.get_main_id()];
+        // the witness starts with the outputs of main, followed by its public inputs
+        let mut public_signals = main
+            .signals
+            .iter()
+            .filter(|s| {
+                s.xtype == SignalType::Output
+                    || (s.xtype == SignalType::Input && public_inputs.contains(&s.name))
+            })
+            .map(|s| (s.dag_local_id, s.name.clone(), s.size()))
+            .collect::<Vec<_>>();
+        public_signals.sort_unstable();
+        tracing::debug!("get public signals: {:?}", public_signals);
+        Ok(public_signals
+            .into_iter()
+            .map(|(_, name, size)| (name, size))
+            .collect())
+    }
+
+    fn get_input_signals_inner(self) -> Result<Vec<(String, usize)>> {
+        let program_archive = self.get_program_archive()?;
+        let vcp = self.build_vcp(program_archive)?;
+        Ok(Self::input_signals(&vcp))
+    }
+
+    fn get_inputs_inner(self) -> Result<(Vec<String>, Vec<(String, usize)>)> {
+        let program_archive = self.get_program_archive()?;
+        let public_inputs = program_archive.public_inputs.clone();
+        tracing::debug!("get public inputs: {:?}", public_inputs);
+        let vcp = self.build_vcp(program_archive)?;
+        Ok((public_inputs, Self::input_signals(&vcp)))
+    }
+
+    fn input_signals(vcp: &VCP) -> Vec<(String, usize)> {
+        let main = &vcp.templates[vcp.get_main_id()];
+        let input_signals = main
+            .signals
+            .iter()
+            .filter(|s| s.xtype == SignalType::Input)
+            .map(|s| (s.name.clone(), s.size()))
+            .collect::<Vec<_>>();
+        tracing::debug!("get input signals: {:?}", input_signals);
+        input_signals
+    }
+
     fn get_public_inputs_inner(self) -> Result<Vec<String>> {
         let program_archive = self.get_program_archive()?;
         tracing::debug!("get public inputs: {:?}", program_archive.public_inputs);
@@ -619,7 +850,8 @@ where
     fn parse_inner(mut self) -> Result<CoCircomCompilerParsed<P::ScalarField>> {
         tracing::debug!("compiler starts parsing..");
         let program_archive = self.get_program_archive()?;
-        let (circuit, output_mapping) = self.build_circuit(program_archive)?;
+        let source_files = Self::get_source_files(&program_archive);
+        let (circuit, output_mapping, mut signal_names) = self.build_circuit(program_archive)?;
         tracing::debug!("output mapping: {output_mapping:?}");
         let constant_table = circuit
             .c_producer
@@ -632,6 +864,8 @@ where
         //build functions
         for fun in circuit.functions.iter() {
             tracing::debug!("parsing function: {}", fun.header);
+            self.current_file = source_files.get(&fun.name).cloned().unwrap_or_default();
+            self.current_signals = vec![];
             fun.body.iter().for_each(|inst| {
                 self.handle_instruction(inst);
             });
@@ -655,6 +889,8 @@ where
         }
         for templ in circuit.templates.iter() {
             tracing::debug!("parsing template: {}", templ.header);
+            self.current_file = source_files.get(&templ.name).cloned().unwrap_or_default();
+            self.current_signals = signal_names.remove(&templ.header).unwrap_or_default();
             templ.body.iter().for_each(|inst| {
                 self.handle_instruction(inst);
             });
@@ -686,6 +922,7 @@ where
             circuit.c_producer.total_number_of_signals,
             constant_table,
             string_table,
+            self.assert_table,
             self.fun_decls,
             self.templ_decls,
             circuit.c_producer.witness_to_signal_list,
@@ -748,4 +985,61 @@ mod tests {
             .get_output("SomeThingThatIsNotAnOutput")
             .is_none());
     }
+
+    #[test]
+    fn failing_assertion_names_template_and_line() {
+        let parsed = CoCircomCompiler::<Bn254>::parse(
+            "../../test_vectors/WitnessExtension/tests/failing_assert.circom".to_owned(),
+            CompilerConfig::default(),
+        )
+        .unwrap();
+
+        let mut plain_vm = parsed.clone().to_plain_vm(VMConfig::default());
+        plain_vm
+            .run_with_flat(to_field_vec!(vec!["2", "3", "6"]), 0)
+            .unwrap();
+
+        let mut plain_vm = parsed.to_plain_vm(VMConfig::default());
+        let err = plain_vm
+            .run_with_flat(to_field_vec!(vec!["2", "3", "7"]), 0)
+            .err()
+            .expect("assertion fails");
+        let err = format!("{err:#}");
+        assert!(
+            err.contains("failing_assert.circom:7 (signal `c`)"),
+            "{err}"
+        );
+        assert!(err.contains("CheckProduct"), "{err}");
+    }
+
+    #[test]
+    fn get_inputs_matches_separate_calls() {
+        let circuit = "../../test_vectors/WitnessExtension/tests/winner.circom".to_owned();
+        let (public_inputs, input_signals) =
+            CoCircomCompiler::<Bn254>::get_inputs(circuit.clone(), CompilerConfig::default())
+                .unwrap();
+        assert_eq!(public_inputs, vec!["inp_address", "commitments"]);
+        assert_eq!(
+            input_signals,
+            vec![
+                ("inp_address".to_owned(), 10),
+                ("commitments".to_owned(), 10),
+                ("inp_guess".to_owned(), 10),
+                ("inp_r".to_owned(), 10),
+            ]
+        );
+        assert_eq!(
+            public_inputs,
+            CoCircomCompiler::<Bn254>::get_public_inputs(
+                circuit.clone(),
+                CompilerConfig::default()
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            input_signals,
+            CoCircomCompiler::<Bn254>::get_input_signals(circuit, CompilerConfig::default())
+                .unwrap()
+        );
+    }
 }
@@ -20,6 +20,8 @@ pub mod mpc;
 pub mod mpc_vm;
 /// Defines the bytecode for the MPC-VM
 pub mod op_codes;
+/// Defines the [Profile](profile::Profile) of a witness extension
+pub mod profile;
 mod stack;
 /// Defines the types for the MPC-VM, including [template declaration](types::TemplateDecl) and [function declarations](types::FunDecl).
 pub mod types;
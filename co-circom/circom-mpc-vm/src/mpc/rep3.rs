@@ -71,7 +71,20 @@ pub struct CircomRep3VmWitnessExtension<F: PrimeField, N: Rep3Network> {
 
 impl<F: PrimeField, N: Rep3Network> CircomRep3VmWitnessExtension<F, N> {
     pub fn from_network(network: N) -> io::Result<Self> {
-        let mut io_context = IoContext::init(network)?;
+        Self::from_io_context(IoContext::init(network)?)
+    }
+
+    /// Same as [from_network](Self::from_network), but derives all randomness from `prf_seed`, see [`IoContext::init_with_prf_seed`].
+    pub fn from_network_with_prf_seed(network: N, prf_seed: [u8; 32]) -> io::Result<Self> {
+        Self::from_io_context(IoContext::init_with_prf_seed(network, prf_seed)?)
+    }
+
+    /// Returns the network of the first [IoContext], the connections of its fork are closed.
+    pub fn into_network(self) -> N {
+        self.io_context0.network
+    }
+
+    fn from_io_context(mut io_context: IoContext<N>) -> io::Result<Self> {
         let io_context_fork = io_context.fork()?;
         Ok(Self {
             io_context0: io_context,
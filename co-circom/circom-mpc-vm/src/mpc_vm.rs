@@ -1,8 +1,11 @@
 use crate::mpc::plain::CircomPlainVmWitnessExtension;
 use crate::mpc::rep3::{CircomRep3VmWitnessExtension, Rep3VmType};
-use crate::types::{CoCircomCompilerParsed, FunDecl, InputList, OutputMapping, TemplateDecl};
+use crate::types::{
+    AssertLocation, CoCircomCompilerParsed, FunDecl, InputList, OutputMapping, TemplateDecl,
+};
 
 use super::accelerator::MpcAccelerator;
+use super::profile::{Profile, Profiler};
 use super::{
     op_codes::{self, CodeBlock},
     stack::Stack,
@@ -25,6 +28,9 @@ pub struct VMConfig {
     /// Allow leaking of secret values in logs
     #[serde(default)]
     pub allow_leaky_logs: bool,
+    /// Record the time spent in every template, see [Profile]
+    #[serde(default)]
+    pub profile: bool,
 }
 
 /// The MPC-VM that performs the witness extension.
@@ -86,7 +92,9 @@ struct WitnessExtensionCtx<F: PrimeField, C: VmCircomWitnessExtension<F>> {
     templ_decls: HashMap<String, TemplateDecl>,
     constant_table: Vec<C::VmType>,
     string_table: Vec<String>,
+    assert_table: Vec<AssertLocation>,
     mpc_accelerator: MpcAccelerator<F, C>,
+    profiler: Option<Profiler>,
 }
 
 #[derive(Clone)]
@@ -175,6 +183,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtensionCtx<F, C> {
         fun_decls: HashMap<String, FunDecl>,
         templ_decls: HashMap<String, TemplateDecl>,
         string_table: Vec<String>,
+        assert_table: Vec<AssertLocation>,
         mpc_accelerator: MpcAccelerator<F, C>,
     ) -> Self {
         Self {
@@ -183,7 +192,9 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtensionCtx<F, C> {
             fun_decls,
             templ_decls,
             string_table,
+            assert_table,
             mpc_accelerator,
+            profiler: None,
         }
     }
 }
@@ -276,6 +287,22 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
         protocol: &mut C,
         ctx: &mut WitnessExtensionCtx<F, C>,
         config: &VMConfig,
+    ) -> Result<()> {
+        if let Some(profiler) = ctx.profiler.as_mut() {
+            profiler.enter(&self.symbol);
+        }
+        self.run_body(protocol, ctx, config)?;
+        if let Some(profiler) = ctx.profiler.as_mut() {
+            profiler.exit();
+        }
+        Ok(())
+    }
+
+    fn run_body(
+        &mut self,
+        protocol: &mut C,
+        ctx: &mut WitnessExtensionCtx<F, C>,
+        config: &VMConfig,
     ) -> Result<()> {
         let mut ip = 0;
         let mut current_body = Arc::clone(&self.component_body);
@@ -452,11 +479,17 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                         component.run(protocol, ctx, config)?;
                     }
                 }
-                op_codes::MpcOpCode::Assert(line) => {
+                op_codes::MpcOpCode::Assert(idx) => {
                     let assertion = self.pop_field();
                     if protocol.is_zero(assertion, true)? {
+                        let location = ctx.assert_table.get(*idx).ok_or_else(|| {
+                            eyre!(
+                                "trying to access assertion on pos: {idx} but len is {}",
+                                ctx.assert_table.len()
+                            )
+                        })?;
                         bail!(
-                            "Assertion failed during execution on line {line} in component {}",
+                            "constraint at {location} not satisfied in component {}",
                             self.symbol
                         );
                     }
@@ -824,6 +857,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
             },
             // TODO take instead of clone? or consume self again and close network in here?
             output_mapping: self.output_mapping.clone(),
+            profile: self.ctx.profiler.take().map(Profiler::finish),
         })
     }
 
@@ -874,6 +908,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
             .get(&self.main)
             .ok_or(eyre!("cannot find main template: {}", self.main))?;
         let mut main_component = Component::init(main_templ, 1);
+        self.ctx.profiler = self.config.profile.then(Profiler::default);
         main_component.run(&mut self.driver, &mut self.ctx, &self.config)?;
         Ok(())
     }
@@ -943,6 +978,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
 pub struct FinalizedWitnessExtension<F: PrimeField, C: VmCircomWitnessExtension<F>> {
     shared_witness: SharedWitness<F, C::ArithmeticShare>,
     output_mapping: OutputMapping,
+    profile: Option<Profile>,
 }
 
 impl<F: PrimeField, C: VmCircomWitnessExtension<F>> From<FinalizedWitnessExtension<F, C>>
@@ -959,6 +995,11 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> FinalizedWitnessExtension<F,
         self.shared_witness
     }
 
+    /// Returns the [`Profile`] of the witness extension if [`VMConfig::profile`] was set.
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
     /// Returns the signals associated with the provided name in the circom file.
     ///
     /// # Example
@@ -1003,6 +1044,7 @@ impl<F: PrimeField> PlainWitnessExtension<F> {
                 parser.fun_decls,
                 parser.templ_decls,
                 parser.string_table,
+                parser.assert_table,
                 MpcAccelerator::full_mpc_accelerator(),
             ),
             main_inputs: parser.main_inputs,
@@ -1015,13 +1057,12 @@ impl<F: PrimeField> PlainWitnessExtension<F> {
 }
 
 impl<F: PrimeField, N: Rep3Network> Rep3WitnessExtension<F, N> {
-    pub(crate) fn from_network(
+    pub(crate) fn from_driver(
         parser: CoCircomCompilerParsed<F>,
-        network: N,
+        driver: CircomRep3VmWitnessExtension<F, N>,
         mpc_accelerator: MpcAccelerator<F, CircomRep3VmWitnessExtension<F, N>>,
         config: VMConfig,
     ) -> Result<Self> {
-        let driver = CircomRep3VmWitnessExtension::from_network(network)?;
         let mut signals = vec![Rep3VmType::default(); parser.amount_signals];
         signals[0] = Rep3VmType::Public(F::one());
         let constant_table = parser
@@ -1039,6 +1080,7 @@ impl<F: PrimeField, N: Rep3Network> Rep3WitnessExtension<F, N> {
                 parser.fun_decls,
                 parser.templ_decls,
                 parser.string_table,
+                parser.assert_table,
                 mpc_accelerator,
             ),
             main_inputs: parser.main_inputs,
@@ -1048,6 +1090,11 @@ impl<F: PrimeField, N: Rep3Network> Rep3WitnessExtension<F, N> {
             config,
         })
     }
+
+    /// Consumes the MPC-VM and returns its network, so that, e.g., a prover can use the established connections after [`run()`](WitnessExtension::run).
+    pub fn into_network(self) -> N {
+        self.driver.into_network()
+    }
 }
 
 impl<F: PrimeField> Rep3WitnessExtension<F, Rep3MpcNet> {
@@ -1058,6 +1105,7 @@ impl<F: PrimeField> Rep3WitnessExtension<F, Rep3MpcNet> {
         config: VMConfig,
     ) -> Result<Self> {
         let network = Rep3MpcNet::new(network_config)?;
-        Self::from_network(parser, network, mpc_accelerator, config)
+        let driver = CircomRep3VmWitnessExtension::from_network(network)?;
+        Self::from_driver(parser, driver, mpc_accelerator, config)
     }
 }
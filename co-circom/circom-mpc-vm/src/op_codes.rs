@@ -60,7 +60,7 @@ pub enum MpcOpCode {
     /// Runtime assertion for witness extension.
     ///
     /// Pops the result of a predicate from the field stack and returns an error if the
-    /// predicate is false. Stores the line number of the assertion in the circom file.
+    /// predicate is false. Stores the index of the assertion's source location in the assertion table.
     Assert(usize),
     /// Branching operation. Pops the result of a predicate from the field stack. If the predicate
     /// is false, jumps the specified amount within the [CodeBlock].
@@ -155,7 +155,7 @@ impl std::fmt::Display for MpcOpCode {
             MpcOpCode::CreateCmp(header, amount) => {
                 format!("CREATE_CMP_OP {} [{amount}]", header)
             }
-            MpcOpCode::Assert(line) => format!("ASSERT_OP {line}"),
+            MpcOpCode::Assert(idx) => format!("ASSERT_OP {idx}"),
             MpcOpCode::If(jump) => format!("IF_OP {jump}"),
             MpcOpCode::EndTruthyBranch(jump) => format!("END_TRUTHY_OP {jump}"),
             MpcOpCode::EndFalsyBranch => "END_FALSY_OP".to_owned(),
@@ -0,0 +1,132 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// The time spent in the templates of a circuit during the witness extension, recorded if [`VMConfig::profile`](crate::mpc_vm::VMConfig::profile) is set.
+///
+/// Times are wall-clock times, so they include the time spent waiting for the other parties. Every component is attributed to its call stack of templates, e.g., `Main;Poseidon;Ark`, and only accounts for the time spent in the component itself, not in its sub-components. Functions called by a component count towards the component.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    self_times: BTreeMap<String, Duration>,
+}
+
+impl Profile {
+    /// Returns the time spent in every call stack of templates, joined by `;`.
+    pub fn self_times(&self) -> &BTreeMap<String, Duration> {
+        &self.self_times
+    }
+
+    /// Returns the time spent in every template, summed over all call stacks and sorted descending.
+    pub fn per_template(&self) -> Vec<(&str, Duration)> {
+        let mut per_template = BTreeMap::<&str, Duration>::new();
+        for (stack, time) in self.self_times.iter() {
+            let template = stack.rsplit(';').next().unwrap_or(stack);
+            *per_template.entry(template).or_default() += *time;
+        }
+        let mut per_template = per_template.into_iter().collect::<Vec<_>>();
+        per_template.sort_by(|(_, a), (_, b)| b.cmp(a));
+        per_template
+    }
+
+    /// Returns the profile in the folded stack format, which is understood by flamegraph tools such as `inferno-flamegraph` or `flamegraph.pl`.
+    ///
+    /// Every line consists of a call stack and the time spent in it in microseconds, e.g., `Main;Poseidon;Ark 1234`.
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        for (stack, time) in self.self_times.iter() {
+            writeln!(folded, "{stack} {}", time.as_micros()).expect("can write to string");
+        }
+        folded
+    }
+}
+
+/// Records the [Profile] while the components are executed.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    /// The template, the start and the time spent in sub-components of every running component
+    stack: Vec<(String, Instant, Duration)>,
+    profile: Profile,
+}
+
+impl Profiler {
+    pub(crate) fn enter(&mut self, symbol: &str) {
+        self.stack
+            .push((symbol.to_owned(), Instant::now(), Duration::ZERO));
+    }
+
+    pub(crate) fn exit(&mut self) {
+        let (_, start, in_sub_components) = self.stack.last().expect("entered a component");
+        let elapsed = start.elapsed();
+        let self_time = elapsed.saturating_sub(*in_sub_components);
+        let stack = self
+            .stack
+            .iter()
+            .map(|(symbol, _, _)| symbol.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        *self.profile.self_times.entry(stack).or_default() += self_time;
+        self.stack.pop();
+        if let Some((_, _, in_sub_components)) = self.stack.last_mut() {
+            *in_sub_components += elapsed;
+        }
+    }
+
+    pub(crate) fn finish(self) -> Profile {
+        self.profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_is_attributed_to_innermost_template() {
+        let sleep = |ms| std::thread::sleep(Duration::from_millis(ms));
+        let start = Instant::now();
+        let mut profiler = Profiler::default();
+        profiler.enter("Main");
+        sleep(10);
+        profiler.enter("Poseidon");
+        profiler.enter("Ark");
+        sleep(30);
+        profiler.exit();
+        profiler.exit();
+        profiler.enter("Ark");
+        sleep(20);
+        profiler.exit();
+        profiler.exit();
+        let total = start.elapsed();
+        let profile = profiler.finish();
+
+        let self_times = profile.self_times();
+        assert_eq!(
+            self_times.keys().collect::<Vec<_>>(),
+            ["Main", "Main;Ark", "Main;Poseidon", "Main;Poseidon;Ark"]
+        );
+        assert!(self_times["Main;Poseidon;Ark"] >= Duration::from_millis(30));
+        assert!(self_times["Main;Ark"] >= Duration::from_millis(20));
+        assert!(self_times["Main"] >= Duration::from_millis(10));
+        // the time of the sub-components is not counted again for their parents
+        assert!(self_times["Main"] <= total - Duration::from_millis(50));
+        assert!(self_times["Main;Poseidon"] <= total - Duration::from_millis(60));
+        assert!(self_times.values().sum::<Duration>() <= total);
+
+        let per_template = profile.per_template();
+        assert_eq!(per_template[0].0, "Ark");
+        assert_eq!(
+            per_template[0].1,
+            self_times["Main;Ark"] + self_times["Main;Poseidon;Ark"]
+        );
+        assert_eq!(per_template.len(), 3);
+
+        let folded = profile.to_folded();
+        assert_eq!(folded.lines().count(), 4);
+        assert_eq!(
+            folded.lines().next().unwrap(),
+            format!("Main {}", self_times["Main"].as_micros())
+        );
+    }
+}
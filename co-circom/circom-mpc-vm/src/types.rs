@@ -6,7 +6,7 @@ use mpc_net::config::NetworkConfig;
 
 use crate::{
     accelerator::MpcAccelerator,
-    mpc::plain::CircomPlainVmWitnessExtension,
+    mpc::{plain::CircomPlainVmWitnessExtension, rep3::CircomRep3VmWitnessExtension},
     mpc_vm::{PlainWitnessExtension, Rep3WitnessExtension, VMConfig, WitnessExtension},
     op_codes::CodeBlock,
 };
@@ -75,6 +75,34 @@ impl FunDecl {
     }
 }
 
+/// The source location of an assertion in the circom file, used to report failing constraints.
+///
+/// > **Warning**: Users should usually not interact directly with this struct. It is only public because the
+/// > compiler requires these locations, and the compiler is a separate crate due to licensing constraints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssertLocation {
+    pub(crate) file: String,
+    pub(crate) line: usize,
+    pub(crate) signal: Option<String>,
+}
+
+impl AssertLocation {
+    /// Creates a new assertion location. Only the MPC-compiler should use this method!
+    pub fn new(file: String, line: usize, signal: Option<String>) -> Self {
+        Self { file, line, signal }
+    }
+}
+
+impl std::fmt::Display for AssertLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(signal) = &self.signal {
+            write!(f, " (signal `{signal}`)")?;
+        }
+        Ok(())
+    }
+}
+
 /// A type that stores the name of an output signal and maps it to
 /// the respective offset in the witness.
 ///
@@ -93,6 +121,7 @@ pub struct CoCircomCompilerParsed<F: PrimeField> {
     pub(crate) amount_signals: usize,
     pub(crate) constant_table: Vec<F>,
     pub(crate) string_table: Vec<String>,
+    pub(crate) assert_table: Vec<AssertLocation>,
     pub(crate) fun_decls: HashMap<String, FunDecl>,
     pub(crate) templ_decls: HashMap<String, TemplateDecl>,
     pub(crate) signal_to_witness: Vec<usize>,
@@ -111,6 +140,7 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
         amount_signals: usize,
         constant_table: Vec<F>,
         string_table: Vec<String>,
+        assert_table: Vec<AssertLocation>,
         fun_decls: HashMap<String, FunDecl>,
         templ_decls: HashMap<String, TemplateDecl>,
         signal_to_witness: Vec<usize>,
@@ -124,6 +154,7 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
             amount_signals,
             constant_table,
             string_table,
+            assert_table,
             fun_decls,
             templ_decls,
             signal_to_witness,
@@ -186,9 +217,26 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
         network: N,
         vm_config: VMConfig,
     ) -> Result<Rep3WitnessExtension<F, N>> {
-        Rep3WitnessExtension::from_network(
+        Rep3WitnessExtension::from_driver(
+            self,
+            CircomRep3VmWitnessExtension::from_network(network)?,
+            MpcAccelerator::full_mpc_accelerator(),
+            vm_config,
+        )
+    }
+
+    /// Same as [to_rep3_vm_with_network](Self::to_rep3_vm_with_network), but derives all randomness of this party from `prf_seed`.
+    ///
+    /// Together with the messages received from the other parties, the seed determines the whole execution, so it can be replayed, e.g., to resume an interrupted witness extension (see [`IoContext::init_with_prf_seed`](mpc_core::protocols::rep3::network::IoContext::init_with_prf_seed)). The seed must be kept secret.
+    pub fn to_rep3_vm_with_network_and_prf_seed<N: Rep3Network>(
+        self,
+        network: N,
+        prf_seed: [u8; 32],
+        vm_config: VMConfig,
+    ) -> Result<Rep3WitnessExtension<F, N>> {
+        Rep3WitnessExtension::from_driver(
             self,
-            network,
+            CircomRep3VmWitnessExtension::from_network_with_prf_seed(network, prf_seed)?,
             MpcAccelerator::full_mpc_accelerator(),
             vm_config,
         )
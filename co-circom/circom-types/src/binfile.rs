@@ -12,6 +12,26 @@ use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge}
 
 pub(crate) type ZKeyParserResult<T> = std::result::Result<T, ZKeyParserError>;
 
+/// The zkey versions written by the snarkjs releases we know of. snarkjs itself accepts zkeys up to version 2, which share the section layout of version 1.
+const SUPPORTED_ZKEY_VERSIONS: std::ops::RangeInclusive<u32> = 1..=2;
+
+/// The largest section id we accept. The zkeys of snarkjs use less than 20 ids, the limit only bounds the section table allocated for the ids read from the file.
+const MAX_SECTION_ID: usize = 64;
+
+/// The id snarkjs stores in the first section of a Groth16 zkey.
+pub(crate) const GROTH16_PROTOCOL_ID: u32 = 1;
+/// The id snarkjs stores in the first section of a Plonk zkey.
+pub(crate) const PLONK_PROTOCOL_ID: u32 = 2;
+
+fn protocol_name(id: &u32) -> &'static str {
+    match *id {
+        GROTH16_PROTOCOL_ID => "Groth16",
+        PLONK_PROTOCOL_ID => "Plonk",
+        10 => "FFLONK",
+        _ => "unknown",
+    }
+}
+
 /// Error type describing errors during parsing zkey files
 #[derive(Debug, Error)]
 pub enum ZKeyParserError {
@@ -21,13 +41,33 @@ pub enum ZKeyParserError {
     /// Error describing that an invalid modulus was found in the header for the chosen curve
     #[error("invalid modulus found in header for chosen curve")]
     InvalidPrimeInHeader,
+    /// Error describing that the byte size of a field in the header does not match the chosen curve
     #[error("Unexpected field size for curve in header. Expected {0} but got {1}")]
     UnexpectedByteSize(u32, u32),
     /// Error during IO operations (reading/opening file, etc.)
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+    /// Error describing that the bin file is malformed
     #[error("bin file corrupted: \"{0}\"")]
     CorruptedBinFile(String),
+    /// Error describing that the zkey was written in a version we cannot read
+    #[error("unsupported zkey version {0}, supported are versions {min} to {max}", min = SUPPORTED_ZKEY_VERSIONS.start(), max = SUPPORTED_ZKEY_VERSIONS.end())]
+    UnsupportedVersion(u32),
+    /// Error describing that the zkey is for a different proof system
+    #[error(
+        "zkey is for {} (protocol {found}), but expected a {} zkey",
+        protocol_name(found),
+        protocol_name(expected)
+    )]
+    UnexpectedProtocol {
+        /// The protocol id of the proof system the zkey is read for
+        expected: u32,
+        /// The protocol id found in the zkey
+        found: u32,
+    },
+    /// Error describing that a section needed by the proof system is missing in the zkey
+    #[error("zkey has no section {0}")]
+    MissingSection(usize),
 }
 
 #[derive(Debug)]
@@ -36,11 +76,9 @@ where
     P::BaseField: CircomArkworksPrimeFieldBridge,
     P::ScalarField: CircomArkworksPrimeFieldBridge,
 {
-    #[allow(dead_code)]
     ftype: String,
-    #[allow(dead_code)]
     version: u32,
-    sections: Vec<Vec<u8>>,
+    sections: Vec<Option<Vec<u8>>>,
     phantom_data: PhantomData<P>,
 }
 
@@ -50,6 +88,18 @@ where
     P::ScalarField: CircomArkworksPrimeFieldBridge,
 {
     pub(crate) fn new<R: Read>(reader: &mut R) -> ZKeyParserResult<Self> {
+        Self::read(reader, None)
+    }
+
+    /// Reads only the sections with the given ids and stops reading once all of them are found. The other sections are skipped without keeping them in memory.
+    pub(crate) fn new_with_sections<R: Read>(
+        reader: &mut R,
+        ids: &[usize],
+    ) -> ZKeyParserResult<Self> {
+        Self::read(reader, Some(ids))
+    }
+
+    fn read<R: Read>(reader: &mut R, ids: Option<&[usize]>) -> ZKeyParserResult<Self> {
         tracing::debug!("reading bin file");
         let mut magic = [0u8; 4];
         reader.read_exact(&mut magic)?;
@@ -66,8 +116,15 @@ where
             .try_into()
             .expect("u32 fits into usize");
         tracing::debug!("we got {} sections in binfile", num_sections);
-        let mut sections = vec![vec![]; num_sections];
+        // every section has a distinct id, so there can not be more sections than ids
+        if num_sections > MAX_SECTION_ID {
+            return Err(ZKeyParserError::CorruptedBinFile(format!(
+                "{num_sections} sections, but at most {MAX_SECTION_ID} are supported"
+            )));
+        }
+        let mut sections = vec![None; num_sections];
 
+        // the sections may come in any order, depending on the snarkjs version and command that wrote the file
         for _ in 0..num_sections {
             let section_id: usize = reader
                 .read_u32::<LittleEndian>()?
@@ -78,14 +135,44 @@ where
                 .try_into()
                 .expect("u64 fits into usize");
 
-            let section = &mut sections[section_id - 1];
-            if !section.is_empty() {
+            if section_id == 0 {
                 return Err(ZKeyParserError::CorruptedBinFile(
-                    "sections are empty!".to_owned(),
+                    "section ids start at 1".to_owned(),
                 ));
             }
-            section.resize(section_length, 0);
-            reader.read_exact(section)?;
+            if section_id > MAX_SECTION_ID {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "section id {section_id} is larger than the supported maximum {MAX_SECTION_ID}"
+                )));
+            }
+            if section_id > sections.len() {
+                sections.resize(section_id, None);
+            }
+            let section = &mut sections[section_id - 1];
+            if section.is_some() {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "section {section_id} appears twice"
+                )));
+            }
+            if ids.is_some_and(|ids| !ids.contains(&section_id)) {
+                let skipped = std::io::copy(
+                    &mut reader.by_ref().take(section_length as u64),
+                    &mut std::io::sink(),
+                )?;
+                if skipped != section_length as u64 {
+                    return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+                }
+                continue;
+            }
+            let mut content = vec![0; section_length];
+            reader.read_exact(&mut content)?;
+            *section = Some(content);
+            if ids.is_some_and(|ids| {
+                ids.iter()
+                    .all(|id| sections.get(id - 1).is_some_and(Option::is_some))
+            }) {
+                break;
+            }
         }
         tracing::debug!("successfully read bin file!");
         Ok(Self {
@@ -96,11 +183,35 @@ where
         })
     }
 
-    pub(crate) fn take_section(&mut self, id: usize) -> Cursor<Vec<u8>> {
-        Cursor::new(std::mem::take(&mut self.sections[id - 1]))
+    /// Checks that this is a zkey of a supported version for the proof system with the given protocol id.
+    pub(crate) fn check_zkey(&mut self, protocol_id: u32) -> ZKeyParserResult<()> {
+        if self.ftype != "zkey" {
+            return Err(ZKeyParserError::CorruptedBinFile(format!(
+                "expected a zkey, but file type is \"{}\"",
+                self.ftype
+            )));
+        }
+        if !SUPPORTED_ZKEY_VERSIONS.contains(&self.version) {
+            return Err(ZKeyParserError::UnsupportedVersion(self.version));
+        }
+        let found = self.take_section(1)?.read_u32::<LittleEndian>()?;
+        if found != protocol_id {
+            return Err(ZKeyParserError::UnexpectedProtocol {
+                expected: protocol_id,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_section(&mut self, id: usize) -> ZKeyParserResult<Cursor<Vec<u8>>> {
+        Ok(Cursor::new(self.take_section_raw(id)?))
     }
 
-    pub(crate) fn take_section_raw(&mut self, id: usize) -> Vec<u8> {
-        std::mem::take(&mut self.sections[id - 1])
+    pub(crate) fn take_section_raw(&mut self, id: usize) -> ZKeyParserResult<Vec<u8>> {
+        self.sections
+            .get_mut(id - 1)
+            .and_then(Option::take)
+            .ok_or(ZKeyParserError::MissingSection(id))
     }
 }
@@ -1,9 +1,14 @@
-//! This module defines the [`Groth16Proof`] struct that implements de/serialization using [`serde`].
+//! This module defines the [`Groth16Proof`] struct that implements de/serialization using [`serde`] and [`ark_serialize`].
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
 use ark_ec::pairing::Pairing;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use serde::{Deserialize, Serialize};
 
 /// Represents a Groth16 proof in JSON format that was created by circom. Supports de/serialization using [`serde`].
+///
+/// The proof also implements [`CanonicalSerialize`] and [`CanonicalDeserialize`], which only encode the three proof elements in the same way as `ark_groth16::Proof`. The `protocol` and `curve` tags are restored from `P` when deserializing.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Groth16Proof<P: Pairing + CircomArkworksPairingBridge>
 where
@@ -27,6 +32,62 @@ where
     /// The curve used to generate the proof
     pub curve: String,
 }
+
+impl<P: Pairing + CircomArkworksPairingBridge> CanonicalSerialize for Groth16Proof<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    fn serialize_with_mode<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.pi_a.serialize_with_mode(&mut writer, compress)?;
+        self.pi_b.serialize_with_mode(&mut writer, compress)?;
+        self.pi_c.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.pi_a.serialized_size(compress)
+            + self.pi_b.serialized_size(compress)
+            + self.pi_c.serialized_size(compress)
+    }
+}
+
+impl<P: Pairing + CircomArkworksPairingBridge> Valid for Groth16Proof<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.pi_a.check()?;
+        self.pi_b.check()?;
+        self.pi_c.check()
+    }
+}
+
+impl<P: Pairing + CircomArkworksPairingBridge> CanonicalDeserialize for Groth16Proof<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    fn deserialize_with_mode<R: std::io::Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        // the points check themselves if validate is set, so there is nothing left to check here
+        Ok(Self {
+            pi_a: P::G1Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+            pi_b: P::G2Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+            pi_c: P::G1Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+            protocol: "groth16".to_owned(),
+            curve: P::get_circom_name(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::groth16::test_utils;
@@ -67,6 +128,24 @@ mod tests {
         assert_eq!(der_proof, proof);
     }
 
+    #[test]
+    pub fn compressed_bn254_proof_roundtrip() {
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+        let proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), proof.compressed_size());
+        let der_proof = Groth16Proof::<Bn254>::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert_eq!(der_proof, proof);
+
+        // flipping a bit of the x-coordinate of pi_a moves it off the curve
+        let mut invalid = bytes.clone();
+        invalid[0] ^= 1;
+        assert!(Groth16Proof::<Bn254>::deserialize_compressed(invalid.as_slice()).is_err());
+    }
+
     #[test]
     pub fn deserialize_bls12_381_proof() {
         let proof_string =
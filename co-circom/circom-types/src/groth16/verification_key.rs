@@ -10,6 +10,8 @@ use serde::{
 
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
 
+use super::ZKey;
+
 /// Represents a verification key in JSON format that was created by circom. Supports de/serialization using [`serde`].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonVerificationKey<P: Pairing + CircomArkworksPairingBridge>
@@ -19,6 +21,9 @@ where
 {
     /// The protocol used to generate the proof (always `"groth16"`)
     pub protocol: String,
+    /// The curve, as named by circom (e.g., `"bn128"`). Defaults to the curve of `P` if missing.
+    #[serde(default = "P::get_circom_name")]
+    pub curve: String,
     /// The number of public inputs
     #[serde(rename = "nPublic")]
     pub n_public: usize,
@@ -54,6 +59,27 @@ where
     pub ic: Vec<P::G1Affine>,
 }
 
+impl<P: Pairing + CircomArkworksPairingBridge> JsonVerificationKey<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// Derives the verification key from a [`ZKey`], yielding the same key as `snarkjs zkey export verificationkey`.
+    pub fn from_zkey(zkey: &ZKey<P>) -> Self {
+        Self {
+            protocol: "groth16".to_owned(),
+            curve: P::get_circom_name(),
+            n_public: zkey.n_public,
+            alpha_1: zkey.vk.alpha_g1,
+            beta_2: zkey.vk.beta_g2,
+            gamma_2: zkey.vk.gamma_g2,
+            delta_2: zkey.vk.delta_g2,
+            alpha_beta_gt: P::pairing(zkey.vk.alpha_g1, zkey.vk.beta_g2).0,
+            ic: zkey.vk.gamma_abc_g1.clone(),
+        }
+    }
+}
+
 fn serialize_g1_sequence<S: Serializer, P: Pairing + CircomArkworksPairingBridge>(
     p: &[P::G1Affine],
     ser: S,
@@ -149,8 +175,8 @@ mod test {
 
     use crate::groth16::test_utils;
 
-    use super::JsonVerificationKey;
-    use std::{fs, str::FromStr};
+    use super::{JsonVerificationKey, ZKey};
+    use std::{fs, fs::File, str::FromStr};
 
     #[test]
     fn can_serde_vk_bn254() {
@@ -215,6 +241,7 @@ mod test {
         )));
 
         assert_eq!(vk.protocol, "groth16");
+        assert_eq!(vk.curve, "bn128");
         assert_eq!(vk.n_public, 1);
         assert_eq!(vk.alpha_1, alpha_1);
         assert_eq!(vk.beta_2, beta_2);
@@ -298,4 +325,16 @@ mod test {
         let der_vk = serde_json::from_str::<JsonVerificationKey<Bls12_381>>(&ser_vk).unwrap();
         assert_eq!(der_vk, vk);
     }
+
+    #[test]
+    fn vk_from_zkey_matches_snarkjs() {
+        let zkey = File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let zkey = ZKey::<Bn254>::from_reader(zkey).unwrap();
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        assert_eq!(JsonVerificationKey::from_zkey(&zkey), vk);
+    }
 }
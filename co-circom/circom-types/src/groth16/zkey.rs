@@ -34,7 +34,7 @@ use ark_serialize::CanonicalDeserialize;
 use std::io::Read;
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{BinFile, ZKeyParserError, ZKeyParserResult, GROTH16_PROTOCOL_ID},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
 
@@ -100,6 +100,23 @@ struct HeaderGroth<P: Pairing> {
     delta_g2: P::G2Affine,
 }
 
+impl<P: Pairing> ZKey<P> {
+    /// Returns the number of constraints of the circuit, without the constraints circom adds for the public inputs.
+    pub fn num_constraints(&self) -> usize {
+        self.matrices.num_constraints
+    }
+
+    /// Returns the number of public inputs (including the outputs) of the circuit, without the constant 1.
+    pub fn num_public_inputs(&self) -> usize {
+        self.n_public
+    }
+
+    /// Returns the size of the evaluation domain, i.e., `2^pow`.
+    pub fn domain_size(&self) -> usize {
+        1 << self.pow
+    }
+}
+
 impl<P: Pairing + CircomArkworksPairingBridge> ZKey<P>
 where
     P::BaseField: CircomArkworksPrimeFieldBridge,
@@ -110,6 +127,15 @@ where
         BinFile::<P>::new(&mut reader)?.try_into()
     }
 
+    /// Reads the number of public inputs (including the outputs) from the header of a zkey, without the constant 1.
+    ///
+    /// In contrast to [`ZKey::from_reader`], this stops reading after the header and does not deserialize any points of the proving key.
+    pub fn read_num_public_inputs<R: Read>(mut reader: R) -> ZKeyParserResult<usize> {
+        let mut binfile = BinFile::<P>::new_with_sections(&mut reader, &[1, 2])?;
+        binfile.check_zkey(GROTH16_PROTOCOL_ID)?;
+        Ok(HeaderGroth::<P>::read(&mut binfile.take_section(2)?)?.n_public)
+    }
+
     fn ic<R: Read>(n_public: usize, reader: R) -> ZKeyParserResult<Vec<P::G1Affine>> {
         // the range is non-inclusive so we do +1 to get all inputs
         Ok(P::g1_vec_from_reader(reader, n_public + 1)?)
@@ -144,19 +170,20 @@ where
     type Error = ZKeyParserError;
     fn try_from(mut binfile: BinFile<P>) -> Result<Self, Self::Error> {
         tracing::debug!("start transforming bin file into zkey...");
-        let header = HeaderGroth::<P>::read(&mut binfile.take_section(2))?;
+        binfile.check_zkey(GROTH16_PROTOCOL_ID)?;
+        let header = HeaderGroth::<P>::read(&mut binfile.take_section(2)?)?;
         let n_vars = header.n_vars;
         let n_public = header.n_public;
         let domain_size = header.domain_size;
 
         // parse proving key
 
-        let ic_section = binfile.take_section(3);
-        let a_section = binfile.take_section(5);
-        let b_g1_section = binfile.take_section(6);
-        let b_g2_section = binfile.take_section(7);
-        let l_section = binfile.take_section(8);
-        let h_section = binfile.take_section(9);
+        let ic_section = binfile.take_section(3)?;
+        let a_section = binfile.take_section(5)?;
+        let b_g1_section = binfile.take_section(6)?;
+        let b_g2_section = binfile.take_section(7)?;
+        let l_section = binfile.take_section(8)?;
+        let h_section = binfile.take_section(9)?;
 
         let mut ic = None;
         let mut a_query = None;
@@ -179,7 +206,7 @@ where
         // parse matrices
 
         tracing::debug!("reading matrices...");
-        let mut matrices_section = binfile.take_section(4);
+        let mut matrices_section = binfile.take_section(4)?;
 
         // this function (an all following uses) assumes that values are encoded in little-endian
         let num_coeffs = u32::deserialize_uncompressed(&mut matrices_section)?;
@@ -318,7 +345,7 @@ where
 #[cfg(test)]
 mod tests {
 
-    use crate::groth16::test_utils;
+    use crate::groth16::{test_utils, JsonVerificationKey};
 
     use super::*;
     use ark_bls12_381::Bls12_381;
@@ -332,6 +359,15 @@ mod tests {
 
     use std::convert::TryFrom;
 
+    #[test]
+    fn metadata_mult2_key() {
+        let zkey = File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let pk = ZKey::<Bn254>::from_reader(zkey).unwrap();
+        assert_eq!(pk.num_constraints(), 1);
+        assert_eq!(pk.num_public_inputs(), 1);
+        assert_eq!(pk.domain_size(), 4);
+    }
+
     #[test]
     fn can_deser_bls12_381_mult2_key() {
         let zkey =
@@ -716,4 +752,173 @@ mod tests {
             .unwrap();
         assert_eq!(expected, de);
     }
+
+    /// Splits a bin file into its version and its sections in file order.
+    fn split_bin_file(bytes: &[u8]) -> (u32, Vec<(u32, Vec<u8>)>) {
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let num_sections = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let mut offset = 12;
+        let mut sections = vec![];
+        for _ in 0..num_sections {
+            let id = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+            let len = u64::from_le_bytes(bytes[offset + 4..offset + 12].try_into().unwrap());
+            let start = offset + 12;
+            offset = start + len as usize;
+            sections.push((id, bytes[start..offset].to_vec()));
+        }
+        (version, sections)
+    }
+
+    fn join_bin_file(version: u32, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = b"zkey".to_vec();
+        bytes.extend(version.to_le_bytes());
+        bytes.extend((sections.len() as u32).to_le_bytes());
+        for (id, section) in sections {
+            bytes.extend(id.to_le_bytes());
+            bytes.extend((section.len() as u64).to_le_bytes());
+            bytes.extend(section);
+        }
+        bytes
+    }
+
+    /// Returns the section ids of a bin file in file order.
+    fn section_ids(bytes: &[u8]) -> Vec<u32> {
+        split_bin_file(bytes)
+            .1
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    #[test]
+    fn reads_zkeys_with_any_section_order() {
+        // real zkeys written by snarkjs, one with the sections in order and one with a different order
+        let ordered =
+            std::fs::read("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let contributed =
+            std::fs::read("../../test_vectors/Groth16/bn254/poseidon/circuit.zkey").unwrap();
+        assert_eq!(section_ids(&ordered), (1..=10).collect::<Vec<_>>());
+        assert_eq!(section_ids(&contributed), [1, 2, 4, 3, 9, 8, 5, 6, 7, 10]);
+        for (zkey, circuit) in [(ordered, "multiplier2"), (contributed, "poseidon")] {
+            let zkey = ZKey::<Bn254>::from_reader(zkey.as_slice()).unwrap();
+            // the verification key was exported by snarkjs from the same zkey
+            let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(
+                File::open(format!(
+                    "../../test_vectors/Groth16/bn254/{circuit}/verification_key.json"
+                ))
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(JsonVerificationKey::from_zkey(&zkey), vk);
+        }
+
+        // Plonk zkeys put the header sections last
+        for circuit in ["multiplier2", "poseidon"] {
+            let plonk = std::fs::read(format!(
+                "../../test_vectors/Plonk/bn254/{circuit}/circuit.zkey"
+            ))
+            .unwrap();
+            assert_eq!(section_ids(&plonk)[12..], [1, 2]);
+            assert!(crate::plonk::ZKey::<Bn254>::from_reader(plonk.as_slice()).is_ok());
+        }
+    }
+
+    #[test]
+    fn reads_num_public_inputs_from_the_header() {
+        for circuit in ["multiplier2", "poseidon"] {
+            let bytes = std::fs::read(format!(
+                "../../test_vectors/Groth16/bn254/{circuit}/circuit.zkey"
+            ))
+            .unwrap();
+            let zkey = ZKey::<Bn254>::from_reader(bytes.as_slice()).unwrap();
+            // the zkeys of snarkjs start with the protocol and the header, nothing after them is read
+            let (_, sections) = split_bin_file(&bytes);
+            assert_eq!(sections[0].0, 1);
+            assert_eq!(sections[1].0, 2);
+            let header_end = 12 + 12 + sections[0].1.len() + 12 + sections[1].1.len();
+            let truncated = &bytes[..header_end];
+            assert!(ZKey::<Bn254>::from_reader(truncated).is_err());
+            assert_eq!(
+                ZKey::<Bn254>::read_num_public_inputs(truncated).unwrap(),
+                zkey.num_public_inputs()
+            );
+        }
+
+        let plonk = File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap();
+        assert!(matches!(
+            ZKey::<Bn254>::read_num_public_inputs(plonk),
+            Err(ZKeyParserError::UnexpectedProtocol { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_zkey_versions() {
+        let bytes =
+            std::fs::read("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let (_, sections) = split_bin_file(&bytes);
+        for version in [0, 3] {
+            let unknown = join_bin_file(version, &sections);
+            let err = ZKey::<Bn254>::from_reader(unknown.as_slice())
+                .err()
+                .unwrap();
+            assert!(matches!(err, ZKeyParserError::UnsupportedVersion(v) if v == version));
+            assert_eq!(
+                err.to_string(),
+                format!("unsupported zkey version {version}, supported are versions 1 to 2")
+            );
+        }
+
+        let without_h = sections
+            .iter()
+            .filter(|(id, _)| *id != 9)
+            .cloned()
+            .collect::<Vec<_>>();
+        let zkey = join_bin_file(1, &without_h);
+        assert!(matches!(
+            ZKey::<Bn254>::from_reader(zkey.as_slice()),
+            Err(ZKeyParserError::MissingSection(9))
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_range_section_ids() {
+        let bytes =
+            std::fs::read("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let (_, mut sections) = split_bin_file(&bytes);
+        sections[9].0 = u32::MAX;
+        let zkey = join_bin_file(1, &sections);
+        assert!(matches!(
+            ZKey::<Bn254>::from_reader(zkey.as_slice()),
+            Err(ZKeyParserError::CorruptedBinFile(_))
+        ));
+
+        let mut too_many = b"zkey".to_vec();
+        too_many.extend(1u32.to_le_bytes());
+        too_many.extend(u32::MAX.to_le_bytes());
+        assert!(matches!(
+            ZKey::<Bn254>::from_reader(too_many.as_slice()),
+            Err(ZKeyParserError::CorruptedBinFile(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_zkeys_of_other_proof_systems() {
+        let groth16 =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let err = crate::plonk::ZKey::<Bn254>::from_reader(groth16)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "zkey is for Groth16 (protocol 1), but expected a Plonk zkey"
+        );
+        let plonk = File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap();
+        assert!(matches!(
+            ZKey::<Bn254>::from_reader(plonk),
+            Err(ZKeyParserError::UnexpectedProtocol {
+                expected: 1,
+                found: 2
+            })
+        ));
+    }
 }
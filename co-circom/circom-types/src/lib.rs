@@ -8,6 +8,10 @@ mod r1cs;
 pub mod traits;
 mod witness;
 
+pub use binfile::ZKeyParserError;
+
+pub use r1cs::R1CSConstraintIter;
+pub use r1cs::R1CSHeader;
 pub use r1cs::R1CSParserError;
 pub use r1cs::R1CS;
 
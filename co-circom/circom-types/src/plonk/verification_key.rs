@@ -1,11 +1,14 @@
 //! This module defines the [`JsonVerificationKey`] struct that implements de/serialization using [`serde`].
 
 use ark_ec::pairing::Pairing;
+use ark_ff::{FftField, Field, LegendreSymbol, One, PrimeField};
 
 use serde::{Deserialize, Serialize};
 
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
 
+use super::ZKey;
+
 /// Represents a verification key in JSON format that was created by circom. Supports de/serialization using [`serde`].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonVerificationKey<P: Pairing + CircomArkworksPairingBridge>
@@ -75,13 +78,55 @@ where
     #[serde(serialize_with = "P::serialize_g2::<_>")]
     #[serde(deserialize_with = "P::deserialize_g2_element::<_>")]
     pub x2: P::G2Affine,
-    // This is curve.Fr.toObject(curve.Fr.w[zkey.power]) so some root of unity (can be computed as in groth16.rs fn root_of_unity() I guess)
+    // This is curve.Fr.toObject(curve.Fr.w[zkey.power]), see JsonVerificationKey::root_of_unity
     #[serde(rename = "w")]
     #[serde(serialize_with = "P::serialize_fr::<_>")]
     #[serde(deserialize_with = "P::deserialize_fr_element::<_>")]
     w: P::ScalarField,
 }
 
+impl<P: Pairing + CircomArkworksPairingBridge> JsonVerificationKey<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// Derives the verification key from a [`ZKey`], yielding the same key as `snarkjs zkey export verificationkey`.
+    pub fn from_zkey(zkey: &ZKey<P>) -> Self {
+        let vk = &zkey.verifying_key;
+        Self {
+            protocol: "plonk".to_owned(),
+            curve: P::get_circom_name(),
+            n_public: zkey.n_public,
+            power: zkey.pow,
+            k1: vk.k1,
+            k2: vk.k2,
+            qm: vk.qm,
+            ql: vk.ql,
+            qr: vk.qr,
+            qo: vk.qo,
+            qc: vk.qc,
+            s1: vk.s1,
+            s2: vk.s2,
+            s3: vk.s3,
+            x2: vk.x_2,
+            w: Self::root_of_unity(zkey.pow),
+        }
+    }
+
+    /// The 2^pow-th root of unity as chosen by snarkjs: the smallest quadratic non-residue q raised to the odd part of p - 1, squared down to order 2^pow.
+    fn root_of_unity(pow: usize) -> P::ScalarField {
+        let mut q = P::ScalarField::one();
+        while q.legendre() != LegendreSymbol::QuadraticNonResidue {
+            q += P::ScalarField::one();
+        }
+        let mut root = q.pow(P::ScalarField::TRACE);
+        for _ in pow..P::ScalarField::TWO_ADICITY as usize {
+            root.square_in_place();
+        }
+        root
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ark_bls12_381::Bls12_381;
@@ -90,8 +135,8 @@ mod test {
 
     use crate::groth16::test_utils;
 
-    use super::JsonVerificationKey;
-    use std::{fs, str::FromStr};
+    use super::{JsonVerificationKey, ZKey};
+    use std::{fs, fs::File, str::FromStr};
 
     #[test]
     fn can_serde_vk_bn254() {
@@ -228,4 +273,15 @@ mod test {
         let der_vk = serde_json::from_str::<JsonVerificationKey<Bls12_381>>(&ser_vk).unwrap();
         assert_eq!(der_vk, vk);
     }
+
+    #[test]
+    fn vk_from_zkey_matches_snarkjs() {
+        let zkey = File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap();
+        let zkey = ZKey::<Bn254>::from_reader(zkey).unwrap();
+        let vk_string =
+            fs::read_to_string("../../test_vectors/Plonk/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        assert_eq!(JsonVerificationKey::from_zkey(&zkey), vk);
+    }
 }
@@ -32,7 +32,7 @@ use ark_serialize::CanonicalDeserialize;
 use std::io::{Cursor, Read};
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{BinFile, ZKeyParserError, ZKeyParserResult, PLONK_PROTOCOL_ID},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
 
@@ -146,6 +146,23 @@ pub struct VerifyingKey<P: Pairing> {
     pub x_2: P::G2Affine,
 }
 
+impl<P: Pairing> ZKey<P> {
+    /// Returns the number of PLONK constraints, which includes a constraint for every public input and the constraints the linear combinations of the r1cs are split into.
+    pub fn num_constraints(&self) -> usize {
+        self.n_constraints
+    }
+
+    /// Returns the number of public inputs (including the outputs) of the circuit, without the constant 1.
+    pub fn num_public_inputs(&self) -> usize {
+        self.n_public
+    }
+
+    /// Returns the size of the evaluation domain, a power of two.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+}
+
 impl<P: Pairing + CircomArkworksPairingBridge> ZKey<P>
 where
     P::BaseField: CircomArkworksPrimeFieldBridge,
@@ -233,7 +250,8 @@ where
     type Error = ZKeyParserError;
     fn try_from(mut binfile: BinFile<P>) -> Result<Self, Self::Error> {
         tracing::debug!("start transforming bin file into zkey...");
-        let header = PlonkHeader::<P>::read(&mut binfile.take_section(2))?;
+        binfile.check_zkey(PLONK_PROTOCOL_ID)?;
+        let header = PlonkHeader::<P>::read(&mut binfile.take_section(2)?)?;
         let n_vars = header.n_vars;
         let n_additions = header.n_additions;
         let n_constraints = header.n_constraints;
@@ -242,18 +260,18 @@ where
         //the sigmas are in the same section - so we split it here in separate chunks
         let sigma_section_size = domain_size * header.n8r + domain_size * 4 * header.n8r;
 
-        let add_section = binfile.take_section(3);
-        let a_section = binfile.take_section(4);
-        let b_section = binfile.take_section(5);
-        let c_section = binfile.take_section(6);
-        let qm_section = binfile.take_section(7);
-        let ql_section = binfile.take_section(8);
-        let qr_section = binfile.take_section(9);
-        let q0_section = binfile.take_section(10);
-        let qc_section = binfile.take_section(11);
-        let sigma_sections = binfile.take_section_raw(12);
-        let l_section = binfile.take_section(13);
-        let t_section = binfile.take_section(14);
+        let add_section = binfile.take_section(3)?;
+        let a_section = binfile.take_section(4)?;
+        let b_section = binfile.take_section(5)?;
+        let c_section = binfile.take_section(6)?;
+        let qm_section = binfile.take_section(7)?;
+        let ql_section = binfile.take_section(8)?;
+        let qr_section = binfile.take_section(9)?;
+        let q0_section = binfile.take_section(10)?;
+        let qc_section = binfile.take_section(11)?;
+        let sigma_sections = binfile.take_section_raw(12)?;
+        let l_section = binfile.take_section(13)?;
+        let t_section = binfile.take_section(14)?;
         let sigma1_section = Cursor::new(&sigma_sections[..sigma_section_size]);
         let sigma2_section =
             Cursor::new(&sigma_sections[sigma_section_size..sigma_section_size * 2]);
@@ -24,7 +24,8 @@
 //IN CONNECTION WITH THE SOFTWARE O THE USE OR OTHER
 //DEALINGS IN THE SOFTWARE.R
 
-//! This module provides the [`R1CS`] type which implements [`R1CS::from_reader`] for parsing the R1CS file format used by circom.
+//! This module provides the [`R1CS`] type which implements [`R1CS::from_reader`] for parsing the R1CS file format used by circom,
+//! as well as [`R1CS::constraints_iter`] for streaming over the constraints of large circuits.
 //! Inspired by <https://github.com/arkworks-rs/circom-compat/blob/170b10fc9ed182b5f72ecf379033dda023d0bf07/src/circom/r1cs_reader.rs>
 use ark_ff::PrimeField;
 use byteorder::{LittleEndian, ReadBytesExt};
@@ -35,7 +36,7 @@ use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, SerializationError};
 use ark_std::io::{Read, Seek, SeekFrom};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, marker::PhantomData};
 
 use crate::{
     reader_utils::{self, InvalidHeaderError},
@@ -95,6 +96,98 @@ pub struct R1CS<P: Pairing> {
     pub n_constraints: usize,
 }
 
+/// The header section of an R1CS file, i.e., everything except the constraints and the wire mapping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1CSHeader {
+    /// Number of public inputs (including the constant 1 wire)
+    pub num_inputs: usize,
+    /// Number of auxiliary variables
+    pub num_aux: usize,
+    /// Number of variables
+    pub num_variables: usize,
+    /// Number of public outputs
+    pub n_pub_out: u32,
+    /// Number of public inputs
+    pub n_pub_in: u32,
+    /// Number of private inputs
+    pub n_prv_in: u32,
+    /// Number of labels
+    pub n_labels: u64,
+    /// Number of constraints
+    pub n_constraints: usize,
+}
+
+/// An iterator over the constraints of an R1CS file that reads one constraint at a time.
+///
+/// Obtained from [`R1CS::constraints_iter`]. In contrast to [`R1CS::from_reader`], the constraints are never materialized all at once, so memory usage stays bounded by the size of a single constraint.
+pub struct R1CSConstraintIter<P: Pairing, R> {
+    reader: R,
+    header: R1CSHeader,
+    remaining: usize,
+    phantom_data: PhantomData<P>,
+}
+
+impl<P: Pairing, R> R1CSConstraintIter<P, R> {
+    /// Returns the parsed header of the R1CS file.
+    pub fn header(&self) -> &R1CSHeader {
+        &self.header
+    }
+}
+
+impl<P: Pairing + CircomArkworksPairingBridge, R: Read> Iterator for R1CSConstraintIter<P, R>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    type Item = Result<Constraints<P>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let constraint = read_constraint::<&mut R, P>(&mut self.reader);
+        if constraint.is_err() {
+            // do not continue reading after an error
+            self.remaining = 0;
+        }
+        Some(constraint)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+struct Sections {
+    offsets: HashMap<u32, u64>,
+    sizes: HashMap<u32, u64>,
+}
+
+const HEADER_TYPE: u32 = 1;
+const CONSTRAINT_TYPE: u32 = 2;
+const WIRE2LABEL_TYPE: u32 = 3;
+
+impl Sections {
+    fn offset(&self, sec_type: u32, name: &str) -> Result<u64> {
+        self.offsets.get(&sec_type).copied().ok_or_else(|| {
+            R1CSParserError::from(Error::new(
+                ErrorKind::InvalidData,
+                format!("No section offset for {name} type found"),
+            ))
+        })
+    }
+
+    fn size(&self, sec_type: u32, name: &str) -> Result<u64> {
+        self.sizes.get(&sec_type).copied().ok_or_else(|| {
+            R1CSParserError::from(Error::new(
+                ErrorKind::InvalidData,
+                format!("No section size for {name} type found"),
+            ))
+        })
+    }
+}
+
 impl<P: Pairing + CircomArkworksPairingBridge> R1CS<P>
 where
     P::BaseField: CircomArkworksPrimeFieldBridge,
@@ -102,6 +195,52 @@ where
 {
     /// Parses an [`R1CS`] file from a reader and returns [`R1CSParserError`] on failure
     pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+        let (sections, header) = Self::read_sections_and_header(&mut reader)?;
+
+        reader.seek(SeekFrom::Start(
+            sections.offset(CONSTRAINT_TYPE, "constraint")?,
+        ))?;
+
+        let constraints = read_constraints::<&mut R, P>(&mut reader, header.n_constraints)?;
+
+        reader.seek(SeekFrom::Start(
+            sections.offset(WIRE2LABEL_TYPE, "wire2label")?,
+        ))?;
+        let wire2label_size = sections.size(WIRE2LABEL_TYPE, "wire2label")?;
+
+        let wire_mapping = read_map(&mut reader, wire2label_size, header.num_variables)?;
+
+        Ok(R1CS {
+            num_aux: header.num_aux,
+            num_inputs: header.num_inputs,
+            num_variables: header.num_variables,
+            constraints,
+            wire_mapping: wire_mapping.iter().map(|e| *e as usize).collect(),
+            n_pub_out: header.n_pub_out,
+            n_pub_in: header.n_pub_in,
+            n_prv_in: header.n_prv_in,
+            n_labels: header.n_labels,
+            n_constraints: header.n_constraints,
+        })
+    }
+
+    /// Parses only the header of an R1CS file and returns an iterator that lazily reads the constraints one at a time.
+    ///
+    /// Use this instead of [`R1CS::from_reader`] for large circuits if the constraints only need to be visited once (e.g., to check a witness constraint by constraint) or if only the header is of interest. The wire mapping is not read.
+    pub fn constraints_iter<R: Read + Seek>(mut reader: R) -> Result<R1CSConstraintIter<P, R>> {
+        let (sections, header) = Self::read_sections_and_header(&mut reader)?;
+        reader.seek(SeekFrom::Start(
+            sections.offset(CONSTRAINT_TYPE, "constraint")?,
+        ))?;
+        Ok(R1CSConstraintIter {
+            reader,
+            remaining: header.n_constraints,
+            header,
+            phantom_data: PhantomData,
+        })
+    }
+
+    fn read_sections_and_header<R: Read + Seek>(mut reader: R) -> Result<(Sections, R1CSHeader)> {
         reader_utils::read_header(&mut reader, R1CS_HEADER)?;
         let version = reader.read_u32::<LittleEndian>()?;
         if version != MAX_VERSION {
@@ -111,38 +250,23 @@ where
 
         // todo: handle sec_size correctly
         // section type -> file offset
-        let mut sec_offsets = HashMap::<u32, u64>::new();
-        let mut sec_sizes = HashMap::<u32, u64>::new();
+        let mut sections = Sections {
+            offsets: HashMap::new(),
+            sizes: HashMap::new(),
+        };
 
         // get file offset of each section
         for _ in 0..num_sections {
             let sec_type = reader.read_u32::<LittleEndian>()?;
             let sec_size = reader.read_u64::<LittleEndian>()?;
             let offset = reader.stream_position()?;
-            sec_offsets.insert(sec_type, offset);
-            sec_sizes.insert(sec_type, sec_size);
+            sections.offsets.insert(sec_type, offset);
+            sections.sizes.insert(sec_type, sec_size);
             reader.seek(SeekFrom::Current(sec_size as i64))?;
         }
 
-        let header_type = 1;
-        let constraint_type = 2;
-        let wire2label_type = 3;
-
-        let header_offset = sec_offsets.get(&header_type).ok_or_else(|| {
-            Error::new(
-                ErrorKind::InvalidData,
-                "No section offset for header type found",
-            )
-        });
-
-        reader.seek(SeekFrom::Start(*header_offset?))?;
-
-        let header_size = sec_sizes.get(&header_type).ok_or_else(|| {
-            Error::new(
-                ErrorKind::InvalidData,
-                "No section size for header type found",
-            )
-        });
+        reader.seek(SeekFrom::Start(sections.offset(HEADER_TYPE, "header")?))?;
+        let header_size = sections.size(HEADER_TYPE, "header")?;
 
         let field_size =
             usize::try_from(reader.read_u32::<LittleEndian>()?).expect("u32 fits into usize");
@@ -150,7 +274,7 @@ where
             return Err(R1CSParserError::WrongScalarField);
         }
 
-        if *header_size? != 32 + field_size as u64 {
+        if header_size != 32 + field_size as u64 {
             return Err(R1CSParserError::WrongScalarField);
         }
 
@@ -169,49 +293,21 @@ where
         let n_constraints =
             usize::try_from(reader.read_u32::<LittleEndian>()?).expect("u32 fits into usize");
 
-        let constraint_offset = sec_offsets.get(&constraint_type).ok_or_else(|| {
-            Error::new(
-                ErrorKind::InvalidData,
-                "No section offset for constraint type found",
-            )
-        });
-
-        reader.seek(SeekFrom::Start(*constraint_offset?))?;
-
-        let constraints = read_constraints::<&mut R, P>(&mut reader, n_constraints)?;
-
-        let wire2label_offset = sec_offsets.get(&wire2label_type).ok_or_else(|| {
-            Error::new(
-                ErrorKind::InvalidData,
-                "No section offset for wire2label type found",
-            )
-        });
-
-        reader.seek(SeekFrom::Start(*wire2label_offset?))?;
-
-        let wire2label_size = sec_sizes.get(&wire2label_type).ok_or_else(|| {
-            Error::new(
-                ErrorKind::InvalidData,
-                "No section size for wire2label type found",
-            )
-        })?;
-
-        let wire_mapping = read_map(&mut reader, *wire2label_size, num_variables)?;
-
         let num_inputs = (1 + n_pub_in + n_pub_out) as usize;
         let num_aux = num_variables - num_inputs;
-        Ok(R1CS {
-            num_aux,
-            num_inputs,
-            num_variables,
-            constraints,
-            wire_mapping: wire_mapping.iter().map(|e| *e as usize).collect(),
-            n_pub_out,
-            n_pub_in,
-            n_prv_in,
-            n_labels,
-            n_constraints,
-        })
+        Ok((
+            sections,
+            R1CSHeader {
+                num_inputs,
+                num_aux,
+                num_variables,
+                n_pub_out,
+                n_pub_in,
+                n_prv_in,
+                n_labels,
+                n_constraints,
+            },
+        ))
     }
 }
 
@@ -244,15 +340,25 @@ where
     // todo check section size
     let mut vec = Vec::with_capacity(n_constraints);
     for _ in 0..n_constraints {
-        vec.push((
-            read_constraint_vec::<&mut R, P>(&mut reader)?,
-            read_constraint_vec::<&mut R, P>(&mut reader)?,
-            read_constraint_vec::<&mut R, P>(&mut reader)?,
-        ));
+        vec.push(read_constraint::<&mut R, P>(&mut reader)?);
     }
     Ok(vec)
 }
 
+fn read_constraint<R: Read, P: Pairing + CircomArkworksPairingBridge>(
+    mut reader: R,
+) -> Result<Constraints<P>>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    Ok((
+        read_constraint_vec::<&mut R, P>(&mut reader)?,
+        read_constraint_vec::<&mut R, P>(&mut reader)?,
+        read_constraint_vec::<&mut R, P>(&mut reader)?,
+    ))
+}
+
 fn read_map<R: Read>(mut reader: R, size: u64, n_wires: usize) -> Result<Vec<u64>> {
     if size != u64::try_from(n_wires).expect("usize fits into u64") * 8 {
         Err(std::io::Error::new(
@@ -333,4 +439,22 @@ mod tests {
         );
         assert_eq!(r1cs.wire_mapping, vec![0, 1, 2, 3]);
     }
+
+    #[test]
+    fn test_constraints_iter_matches_from_reader() {
+        let path = "../../test_vectors/Groth16/bn254/poseidon/circuit.r1cs";
+        let r1cs = R1CS::<Bn254>::from_reader(File::open(path).unwrap()).unwrap();
+        let iter = R1CS::<Bn254>::constraints_iter(File::open(path).unwrap()).unwrap();
+        let header = iter.header().clone();
+        assert_eq!(header.num_inputs, r1cs.num_inputs);
+        assert_eq!(header.num_aux, r1cs.num_aux);
+        assert_eq!(header.num_variables, r1cs.num_variables);
+        assert_eq!(header.n_constraints, r1cs.n_constraints);
+        assert_eq!(
+            iter.size_hint(),
+            (r1cs.n_constraints, Some(r1cs.n_constraints))
+        );
+        let constraints = iter.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(constraints, r1cs.constraints);
+    }
 }
@@ -1,4 +1,5 @@
 //! This module contains traits for serializing and deserializing field elements and curve points into and from circom files to arkworks representation.
+use std::cell::Cell;
 use std::io::Read;
 use std::marker::PhantomData;
 
@@ -12,6 +13,29 @@ use std::str::FromStr;
 
 type IoResult<T> = Result<T, SerializationError>;
 
+thread_local! {
+    static CHECK_SUBGROUP: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Runs `f` without checking that the curve points parsed from circom JSON on the current thread (e.g., of proofs and verification keys) are in the prime order subgroup. The points are still checked to be on the curve.
+///
+/// The subgroup check is the most expensive part of parsing a point. Only skip it for trusted inputs, as points outside of the subgroup can break the soundness of a verifier on curves with a cofactor.
+pub fn without_subgroup_checks<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CHECK_SUBGROUP.with(|check| check.set(self.0));
+        }
+    }
+    let _restore = Restore(CHECK_SUBGROUP.with(|check| check.replace(false)));
+    f()
+}
+
+/// Returns `false` while running in [without_subgroup_checks].
+pub fn subgroup_checks_enabled() -> bool {
+    CHECK_SUBGROUP.with(Cell::get)
+}
+
 macro_rules! impl_bn256 {
     () => {
         //TODO use stringify
@@ -170,14 +194,14 @@ mod $mod_name {
                 let x = parse_field(x)?;
                 let y = parse_field(y)?;
                 let z = parse_field(z)?;
-                let p = Self::G1Affine::from($curve::G1Projective::new(x, y, z));
+                let p = Self::G1Affine::from($curve::G1Projective::new_unchecked(x, y, z));
                 if p.is_zero() {
                     return Ok(p);
                 }
                 if !p.is_on_curve() {
                     return Err(SerializationError::InvalidData);
                 }
-                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                if super::subgroup_checks_enabled() && !p.is_in_correct_subgroup_assuming_on_curve() {
                     return Err(SerializationError::InvalidData);
                 }
                 Ok(p)
@@ -210,14 +234,14 @@ mod $mod_name {
                 let x = $curve::Fq2::new(x0, x1);
                 let y = $curve::Fq2::new(y0, y1);
                 let z = $curve::Fq2::new(z0, z1);
-                let p = $curve::G2Affine::from($curve::G2Projective::new(x, y, z));
+                let p = $curve::G2Affine::from($curve::G2Projective::new_unchecked(x, y, z));
                 if p.is_zero() {
                     return Ok(p);
                 }
                 if !p.is_on_curve() {
                     return Err(SerializationError::InvalidData);
                 }
-                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                if super::subgroup_checks_enabled() && !p.is_in_correct_subgroup_assuming_on_curve() {
                     return Err(SerializationError::InvalidData);
                 }
                 Ok(p)
@@ -495,7 +519,8 @@ where
                 z.len()
             )))
         } else {
-            Ok(P::g2_from_strings_projective(&x[0], &x[1], &y[0], &y[1], &z[0], &z[1]).unwrap())
+            P::g2_from_strings_projective(&x[0], &x[1], &y[0], &y[1], &z[0], &z[1])
+                .map_err(|_| de::Error::custom("Invalid projective point on G2.".to_owned()))
         }
     }
 }
@@ -1,9 +1,9 @@
-//! This module defines the [`Witness`] struct that implements deserialization of circom witness files via [`Witness::from_reader`].
+//! This module defines the [`Witness`] struct that implements deserialization of circom witness files via [`Witness::from_reader`] and serialization via [`Witness::to_writer`].
 
-use std::io;
+use std::io::{self, Write};
 
 use ark_serialize::{Read, SerializationError};
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use thiserror::Error;
 
 use crate::traits::CircomArkworksPrimeFieldBridge;
@@ -15,6 +15,8 @@ type Result<T> = std::result::Result<T, WitnessParserError>;
 const WITNESS_HEADER: &str = "wtns";
 const MAX_VERSION: u32 = 2;
 const N_SECTIONS: u32 = 2;
+const HEADER_SECTION: u32 = 1;
+const WITNESS_SECTION: u32 = 2;
 
 /// Error type describing errors during parsing witness files
 #[derive(Debug, Error)]
@@ -34,6 +36,21 @@ pub enum WitnessParserError {
     /// Error describing that the ScalarField from curve does not match in witness file
     #[error("ScalarField from curve does not match in witness file")]
     WrongScalarField,
+    /// Error describing that the field size in the witness file does not match the ScalarField from curve
+    #[error("Field size of ScalarField is {0} bytes, but got {1}")]
+    WrongFieldSize(usize, u32),
+    /// Error describing that a section is missing in the witness file
+    #[error("Section {0} is missing")]
+    MissingSection(u32),
+    /// Error describing that a section appears more than once in the witness file
+    #[error("Section {0} appears more than once")]
+    DuplicateSection(u32),
+    /// Error describing that the witness file contains an unknown section
+    #[error("Unknown section {0}")]
+    UnknownSection(u32),
+    /// Error describing that the size of the witness section does not match the number of witnesses in the header
+    #[error("Witness section should have {0} bytes, but got {1}")]
+    InvalidSectionSize(u64, u64),
     /// Error during reading circom file header
     #[error(transparent)]
     WrongHeader(#[from] InvalidHeaderError),
@@ -48,16 +65,19 @@ pub struct Witness<F> {
 
 impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
     /// Deserializes a [`Witness`] from a reader.
+    ///
+    /// Supports the versions 1 and 2 of the wtns format. The sections may appear in any order, the field size and prime in the header section are validated against `F`.
     pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
         tracing::trace!("trying to read witness");
         reader_utils::read_header(&mut reader, WITNESS_HEADER)?;
         let version = reader.read_u32::<LittleEndian>()?;
-        if version > MAX_VERSION {
+        if version == 0 || version > MAX_VERSION {
             return Err(WitnessParserError::VersionNotSupported(
                 MAX_VERSION,
                 version,
             ));
         }
+        tracing::trace!("wtns version {version}");
 
         let n_sections = reader.read_u32::<LittleEndian>()?;
         if n_sections > N_SECTIONS {
@@ -65,29 +85,105 @@ impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
                 N_SECTIONS, n_sections,
             ));
         }
-        //this is the section id and length
-        //don't know if we need them, maybe at least log them later
-        let _ = reader.read_u32::<LittleEndian>()?;
-        let _ = reader.read_u64::<LittleEndian>()?;
+        // newer circom versions do not necessarily write the header section first. We read both sections directly from the
+        // reader and check the witness values against the header once both are read
+        let mut header = None;
+        let mut values = None;
+        for _ in 0..n_sections {
+            let section_id = reader.read_u32::<LittleEndian>()?;
+            let section_len = reader.read_u64::<LittleEndian>()?;
+            let mut section = (&mut reader).take(section_len);
+            match section_id {
+                HEADER_SECTION if header.is_none() => {
+                    header = Some(Self::read_header_section(&mut section)?)
+                }
+                WITNESS_SECTION if values.is_none() => {
+                    values = Some(Self::read_witness_section(&mut section, section_len)?)
+                }
+                HEADER_SECTION | WITNESS_SECTION => {
+                    return Err(WitnessParserError::DuplicateSection(section_id))
+                }
+                _ => return Err(WitnessParserError::UnknownSection(section_id)),
+            }
+            // like snarkjs, we ignore additional bytes at the end of the header section
+            io::copy(&mut section, &mut io::sink())?;
+        }
+        let (n8, n_witness) = header.ok_or(WitnessParserError::MissingSection(HEADER_SECTION))?;
+        let values = values.ok_or(WitnessParserError::MissingSection(WITNESS_SECTION))?;
+
+        if u64::try_from(values.len()).expect("usize fits into u64") != u64::from(n_witness) {
+            let size = u64::from(n8);
+            return Err(WitnessParserError::InvalidSectionSize(
+                u64::from(n_witness) * size,
+                u64::try_from(values.len()).expect("usize fits into u64") * size,
+            ));
+        }
+        Ok(Self { values })
+    }
+
+    /// Reads the header section and checks its field size and prime against `F`. Returns the field size and the number of witness values.
+    fn read_header_section<R: Read>(mut reader: R) -> Result<(u32, u32)> {
         let n8 = reader.read_u32::<LittleEndian>()?;
-        let mut buf = vec![0; usize::try_from(n8).expect("u32 fits into usize")];
+        if usize::try_from(n8).expect("u32 fits into usize") != F::SERIALIZED_BYTE_SIZE {
+            return Err(WitnessParserError::WrongFieldSize(
+                F::SERIALIZED_BYTE_SIZE,
+                n8,
+            ));
+        }
+        let mut buf = vec![0; F::SERIALIZED_BYTE_SIZE];
         reader.read_exact(buf.as_mut_slice())?;
         if F::MODULUS.to_bytes_le() != buf {
             tracing::trace!("wrong scalar field");
             return Err(WitnessParserError::WrongScalarField);
         }
         let n_witness = reader.read_u32::<LittleEndian>()?;
-        //this is the section id and length
-        //don't know if we need them, maybe at least log them later
-        let _ = reader.read_u32::<LittleEndian>()?;
-        let _ = reader.read_u64::<LittleEndian>()?;
-        Ok(Self {
-            values: (0..n_witness)
-                .map(|_| {
-                    F::from_reader(&mut reader).map_err(WitnessParserError::SerializationError)
-                })
-                .collect::<Result<Vec<F>>>()?,
-        })
+        Ok((n8, n_witness))
+    }
+
+    /// Reads the values of the witness section. If the section comes first, the field size of `F` is only checked against the header afterwards.
+    fn read_witness_section<R: Read>(mut reader: R, section_len: u64) -> Result<Vec<F>> {
+        let size = u64::try_from(F::SERIALIZED_BYTE_SIZE).expect("usize fits into u64");
+        if section_len % size != 0 {
+            return Err(WitnessParserError::InvalidSectionSize(
+                section_len - section_len % size,
+                section_len,
+            ));
+        }
+        (0..section_len / size)
+            .map(|_| F::from_reader(&mut reader).map_err(WitnessParserError::SerializationError))
+            .collect()
+    }
+
+    /// Serializes the [`Witness`] to a writer in the wtns format of circom, i.e., version 2 with the header section first, so it can be read by snarkjs.
+    pub fn to_writer<W: Write>(&self, mut writer: W) -> Result<()> {
+        let n8 = u32::try_from(F::SERIALIZED_BYTE_SIZE).expect("field size fits into u32");
+        let n_witness = u32::try_from(self.values.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many witness values"))?;
+        let header_len = 4 + u64::from(n8) + 4;
+        let witness_len = u64::from(n_witness) * u64::from(n8);
+
+        writer.write_all(WITNESS_HEADER.as_bytes())?;
+        writer.write_u32::<LittleEndian>(MAX_VERSION)?;
+        writer.write_u32::<LittleEndian>(N_SECTIONS)?;
+
+        writer.write_u32::<LittleEndian>(HEADER_SECTION)?;
+        writer.write_u64::<LittleEndian>(header_len)?;
+        writer.write_u32::<LittleEndian>(n8)?;
+        writer.write_all(&Self::to_le_bytes(F::MODULUS.to_bytes_le()))?;
+        writer.write_u32::<LittleEndian>(n_witness)?;
+
+        writer.write_u32::<LittleEndian>(WITNESS_SECTION)?;
+        writer.write_u64::<LittleEndian>(witness_len)?;
+        for value in &self.values {
+            writer.write_all(&Self::to_le_bytes(value.into_bigint().to_bytes_le()))?;
+        }
+        Ok(())
+    }
+
+    // the limbs of the bigint may have more bytes than the field size given in the header
+    fn to_le_bytes(mut bytes: Vec<u8>) -> Vec<u8> {
+        bytes.resize(F::SERIALIZED_BYTE_SIZE, 0);
+        bytes
     }
 }
 
@@ -95,7 +191,134 @@ impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
 mod tests {
     use std::fs::File;
 
-    use super::Witness;
+    use super::{Witness, WitnessParserError};
+
+    const FIXTURES: [&str; 2] = [
+        "../../test_vectors/Groth16/bn254/multiplier2/witness.wtns",
+        "../../test_vectors/Groth16/bn254/poseidon/witness.wtns",
+    ];
+
+    // splits a wtns file into its version and its sections in file order
+    fn split_wtns(bytes: &[u8]) -> (u32, Vec<(u32, Vec<u8>)>) {
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let n_sections = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let mut offset = 12;
+        let mut sections = vec![];
+        for _ in 0..n_sections {
+            let id = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+            let len = u64::from_le_bytes(bytes[offset + 4..offset + 12].try_into().unwrap());
+            let start = offset + 12;
+            offset = start + usize::try_from(len).unwrap();
+            sections.push((id, bytes[start..offset].to_vec()));
+        }
+        assert_eq!(offset, bytes.len());
+        (version, sections)
+    }
+
+    fn join_wtns(version: u32, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut wtns = b"wtns".to_vec();
+        wtns.extend(version.to_le_bytes());
+        wtns.extend(u32::try_from(sections.len()).unwrap().to_le_bytes());
+        for (id, section) in sections {
+            wtns.extend(id.to_le_bytes());
+            wtns.extend(u64::try_from(section.len()).unwrap().to_le_bytes());
+            wtns.extend(section);
+        }
+        wtns
+    }
+
+    #[test]
+    fn can_deser_circom_v2_witness_in_any_section_order() {
+        for path in FIXTURES {
+            let bytes = std::fs::read(path).unwrap();
+            // the fixtures are written by circom in version 2 with the header section first
+            let (version, mut sections) = split_wtns(&bytes);
+            assert_eq!(version, 2);
+            assert_eq!(
+                sections.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+                [1, 2]
+            );
+            let should_witness = Witness::<ark_bn254::Fr>::from_reader(bytes.as_slice()).unwrap();
+            assert_eq!(
+                join_wtns(2, &sections),
+                bytes,
+                "splitting the fixture is lossless"
+            );
+
+            sections.reverse();
+            // version 1 has the same layout
+            for version in [1, 2] {
+                let wtns = join_wtns(version, &sections);
+                let is_witness = Witness::<ark_bn254::Fr>::from_reader(wtns.as_slice()).unwrap();
+                assert_eq!(is_witness, should_witness);
+            }
+        }
+    }
+
+    #[test]
+    fn reject_malformed_sections_bn254() {
+        let bytes = std::fs::read(FIXTURES[0]).unwrap();
+        let (_, sections) = split_wtns(&bytes);
+
+        let duplicate = join_wtns(2, &[sections[1].clone(), sections[1].clone()]);
+        assert!(matches!(
+            Witness::<ark_bn254::Fr>::from_reader(duplicate.as_slice()),
+            Err(WitnessParserError::DuplicateSection(2))
+        ));
+        let missing = join_wtns(2, &sections[..1]);
+        assert!(matches!(
+            Witness::<ark_bn254::Fr>::from_reader(missing.as_slice()),
+            Err(WitnessParserError::MissingSection(2))
+        ));
+
+        // one value less than announced in the header
+        let mut short = sections.clone();
+        short[1].1.truncate(3 * 32);
+        assert!(matches!(
+            Witness::<ark_bn254::Fr>::from_reader(join_wtns(2, &short).as_slice()),
+            Err(WitnessParserError::InvalidSectionSize(128, 96))
+        ));
+        let mut partial = sections.clone();
+        partial[1].1.pop();
+        assert!(matches!(
+            Witness::<ark_bn254::Fr>::from_reader(join_wtns(2, &partial).as_slice()),
+            Err(WitnessParserError::InvalidSectionSize(96, 127))
+        ));
+
+        // a file that ends within the witness section
+        assert!(Witness::<ark_bn254::Fr>::from_reader(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn to_writer_matches_circom() {
+        for path in FIXTURES {
+            let bytes = std::fs::read(path).unwrap();
+            let witness = Witness::<ark_bn254::Fr>::from_reader(bytes.as_slice()).unwrap();
+            let mut written = vec![];
+            witness.to_writer(&mut written).unwrap();
+            assert_eq!(bytes, written);
+        }
+        let bytes =
+            std::fs::read("../../test_vectors/Groth16/bls12_381/multiplier2/witness.wtns").unwrap();
+        let witness = Witness::<ark_bls12_381::Fr>::from_reader(bytes.as_slice()).unwrap();
+        let mut written = vec![];
+        witness.to_writer(&mut written).unwrap();
+        assert_eq!(bytes, written);
+    }
+
+    #[test]
+    fn reject_wrong_field_size_bn254() {
+        let bytes = std::fs::read(FIXTURES[0]).unwrap();
+        let (_, mut sections) = split_wtns(&bytes);
+        sections[0].1[..4].copy_from_slice(&48u32.to_le_bytes());
+        // the field size is checked regardless of the section order
+        sections.reverse();
+        let wtns = join_wtns(2, &sections);
+        assert!(matches!(
+            Witness::<ark_bn254::Fr>::from_reader(wtns.as_slice()),
+            Err(WitnessParserError::WrongFieldSize(32, 48))
+        ));
+    }
 
     #[test]
     fn can_deser_witness_bn254() {
@@ -0,0 +1,533 @@
+#![warn(missing_docs)]
+//! This crate verifies circom Groth16 and PLONK proofs from their JSON encoding.
+//!
+//! It implements the `verify` command of co-circom, but does not touch the file system and only depends on [circom_types], [co_groth16] and [co_plonk], so the verifier can be embedded into other applications.
+//!
+//! For both Groth16 and Plonk, the public inputs are given without the constant 1 the witness starts with, i.e., exactly as in the `public.json` written by snarkjs or `generate-proof`.
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::CanonicalDeserialize;
+use circom_types::{
+    groth16::{Groth16Proof, JsonVerificationKey as Groth16JsonVerificationKey},
+    plonk::{JsonVerificationKey as PlonkJsonVerificationKey, PlonkProof},
+    traits::{
+        subgroup_checks_enabled, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge,
+    },
+};
+use co_groth16::Groth16;
+use co_plonk::{Plonk, PlonkProofError};
+use sha3::{Digest, Sha3_256};
+
+/// The proof system of a proof and its verification key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofSystem {
+    /// The Groth16 proof system.
+    Groth16,
+    /// The Plonk proof system.
+    Plonk,
+}
+
+/// An error type for the verification of proofs.
+///
+/// A proof that is well-formed but does not verify is not an error, see [verify].
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    /// The proof could not be parsed.
+    #[error("Could not parse proof: {0}")]
+    InvalidProof(serde_json::Error),
+    /// The proof in compressed byte form could not be parsed, e.g., because a point is not on the curve or not in the prime order subgroup.
+    #[error("Could not parse compressed proof: {0}")]
+    InvalidCompressedProof(ark_serialize::SerializationError),
+    /// The verification key could not be parsed.
+    #[error("Could not parse verification key: {0}")]
+    InvalidVerificationKey(serde_json::Error),
+    /// A public input is not a field element.
+    #[error("Could not parse public input \"{0}\" as field element")]
+    InvalidPublicInput(String),
+    /// The number of public inputs does not match the verification key.
+    #[error("Expected {expected} public inputs, got {got}")]
+    PublicInputCount {
+        /// The number of public inputs of the verification key.
+        expected: usize,
+        /// The number of given public inputs.
+        got: usize,
+    },
+    /// The public inputs start with the constant 1 of the witness, which is not part of the public inputs.
+    #[error("Expected {expected} public inputs, got {} starting with 1. Remove the constant 1 of the witness from the public inputs", expected + 1)]
+    LeadingConstantOne {
+        /// The number of public inputs of the verification key.
+        expected: usize,
+    },
+    /// The proof or the verification key is tagged with another curve than the one we verify on.
+    #[error("Curve mismatch: the {what} is for curve \"{found}\", but the selected curve is \"{expected}\"")]
+    CurveMismatch {
+        /// Either `"proof"` or `"verification key"`.
+        what: &'static str,
+        /// The curve tag of the file.
+        found: String,
+        /// The circom name of the selected curve.
+        expected: String,
+    },
+    /// The Groth16 verifier could not check the proof.
+    #[error("Could not run Groth16 verifier: {0}")]
+    Groth16Verifier(SynthesisError),
+    /// The PLONK verifier could not check the proof, e.g., because the domain size of the verification key is invalid.
+    #[error("Could not run PLONK verifier: {0}")]
+    PlonkVerifier(PlonkProofError),
+}
+
+/// Parses public inputs given as decimal strings, e.g., the contents of a `public.json`.
+pub fn parse_public_inputs<F: PrimeField>(public_inputs: &[String]) -> Result<Vec<F>, VerifyError> {
+    public_inputs
+        .iter()
+        .map(|s| {
+            s.parse::<F>()
+                .map_err(|_| VerifyError::InvalidPublicInput(s.to_owned()))
+        })
+        .collect()
+}
+
+/// Verifies a proof given as circom JSON against a verification key given as circom JSON.
+///
+/// The public inputs do not include the constant 1 of the witness (see the [module documentation](self)). Returns `Ok(true)` if the proof is valid and `Ok(false)` if it is not. Malformed inputs result in a [VerifyError].
+pub fn verify<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    proof: &[u8],
+    vk: &[u8],
+    public_inputs: &[P::ScalarField],
+) -> Result<bool, VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof = serde_json::from_slice(proof).map_err(VerifyError::InvalidProof)?;
+    let vk = serde_json::from_slice(vk).map_err(VerifyError::InvalidVerificationKey)?;
+    verify_json::<P>(proof_system, proof, vk, public_inputs)
+}
+
+/// Same as [verify], but for a proof and a verification key that are already parsed as JSON.
+pub fn verify_json<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    proof: serde_json::Value,
+    vk: serde_json::Value,
+    public_inputs: &[P::ScalarField],
+) -> Result<bool, VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    match parse_json::<P>(proof_system, proof, vk, public_inputs)? {
+        ParsedProof::Groth16(proof, vk) => {
+            Groth16::<P>::verify(&vk, &proof, public_inputs).map_err(VerifyError::Groth16Verifier)
+        }
+        ParsedProof::Plonk(proof, vk) => {
+            Plonk::<P>::verify(&vk, &proof, public_inputs).map_err(VerifyError::PlonkVerifier)
+        }
+    }
+}
+
+/// Performs all checks of [verify_json] except for the pairing check, i.e., checks that the proof and the verification key are for the selected curve, that all their points are on the curve and in the prime order subgroup, and that the number of public inputs matches the verification key.
+///
+/// This is much cheaper than [verify_json] and can be used to reject malformed proofs early. A proof that passes this check may still be invalid.
+pub fn check_structure_json<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    proof: serde_json::Value,
+    vk: serde_json::Value,
+    public_inputs: &[P::ScalarField],
+) -> Result<(), VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    parse_json::<P>(proof_system, proof, vk, public_inputs).map(|_| ())
+}
+
+/// A proof and its verification key that passed all checks before the pairing check.
+enum ParsedProof<P: Pairing + CircomArkworksPairingBridge>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    Groth16(Groth16Proof<P>, Groth16JsonVerificationKey<P>),
+    Plonk(PlonkProof<P>, PlonkJsonVerificationKey<P>),
+}
+
+/// Parses proof and verification key, which checks that their points are on the curve and in the prime order subgroup, and checks the curve tags and the number of public inputs.
+fn parse_json<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    proof: serde_json::Value,
+    vk: serde_json::Value,
+    public_inputs: &[P::ScalarField],
+) -> Result<ParsedProof<P>, VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    // the points of the other curve may still deserialize, so a wrong curve would only show up as a failed pairing check
+    check_curve_tag::<P>(&proof, "proof")?;
+    check_curve_tag::<P>(&vk, "verification key")?;
+
+    match proof_system {
+        ProofSystem::Groth16 => {
+            let proof: Groth16Proof<P> =
+                serde_json::from_value(proof).map_err(VerifyError::InvalidProof)?;
+            let vk: Groth16JsonVerificationKey<P> =
+                serde_json::from_value(vk).map_err(VerifyError::InvalidVerificationKey)?;
+            check_public_input_count(vk.n_public, public_inputs)?;
+            Ok(ParsedProof::Groth16(proof, vk))
+        }
+        ProofSystem::Plonk => {
+            let proof: PlonkProof<P> =
+                serde_json::from_value(proof).map_err(VerifyError::InvalidProof)?;
+            let vk: PlonkJsonVerificationKey<P> =
+                serde_json::from_value(vk).map_err(VerifyError::InvalidVerificationKey)?;
+            check_public_input_count(vk.n_public, public_inputs)?;
+            Ok(ParsedProof::Plonk(proof, vk))
+        }
+    }
+}
+
+/// Computes a fingerprint of a verification key given as circom JSON, as hex encoded SHA3-256 hash.
+///
+/// The hash is taken over the verification key re-serialized from its parsed form, so verification keys that only differ in the JSON formatting, the order of the keys or the representation of the points (e.g., projective coordinates with `z != 1`) have the same fingerprint. The verification key exported from a zkey (see [`JsonVerificationKey::from_zkey`](circom_types::groth16::JsonVerificationKey::from_zkey)) has the same fingerprint as the one written by snarkjs.
+pub fn vk_fingerprint_json<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    vk: serde_json::Value,
+) -> Result<String, VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    check_curve_tag::<P>(&vk, "verification key")?;
+    let canonical = match proof_system {
+        ProofSystem::Groth16 => serde_json::to_vec(
+            &serde_json::from_value::<Groth16JsonVerificationKey<P>>(vk)
+                .map_err(VerifyError::InvalidVerificationKey)?,
+        ),
+        ProofSystem::Plonk => serde_json::to_vec(
+            &serde_json::from_value::<PlonkJsonVerificationKey<P>>(vk)
+                .map_err(VerifyError::InvalidVerificationKey)?,
+        ),
+    }
+    .expect("can serialize verification key to JSON");
+    Ok(Sha3_256::digest(canonical)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Parses a Groth16 proof in the compressed byte form of arkworks and returns its circom JSON encoding, which can be passed to [verify_json].
+///
+/// Deserialization checks that all proof elements are on the curve and in the prime order subgroup. In [`without_subgroup_checks`](circom_types::traits::without_subgroup_checks), the points are not checked at all, but [verify_json] still checks that they are on the curve.
+pub fn groth16_proof_from_compressed<P: Pairing + CircomArkworksPairingBridge>(
+    proof: &[u8],
+) -> Result<serde_json::Value, VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof = if subgroup_checks_enabled() {
+        Groth16Proof::<P>::deserialize_compressed(proof)
+    } else {
+        // the points are checked to be on the curve when the JSON is parsed again
+        Groth16Proof::<P>::deserialize_compressed_unchecked(proof)
+    }
+    .map_err(VerifyError::InvalidCompressedProof)?;
+    Ok(serde_json::to_value(proof).expect("can serialize proof to JSON"))
+}
+
+/// Checks that we got the `n_public` public inputs of the verification key. Both verifiers would reject other counts as well, but with less helpful errors.
+fn check_public_input_count<F: PrimeField>(
+    n_public: usize,
+    public_inputs: &[F],
+) -> Result<(), VerifyError> {
+    match public_inputs.len() {
+        got if got == n_public => Ok(()),
+        got if got == n_public + 1 && public_inputs[0].is_one() => {
+            Err(VerifyError::LeadingConstantOne { expected: n_public })
+        }
+        got => Err(VerifyError::PublicInputCount {
+            expected: n_public,
+            got,
+        }),
+    }
+}
+
+/// Compares the `curve` tag of a circom JSON file (e.g., `"bn128"`) with the curve we verify on. Files without a tag are accepted.
+fn check_curve_tag<P: Pairing + CircomArkworksPairingBridge>(
+    json: &serde_json::Value,
+    what: &'static str,
+) -> Result<(), VerifyError>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let expected = P::get_circom_name();
+    match json.get("curve").and_then(serde_json::Value::as_str) {
+        Some(found) if found != expected => Err(VerifyError::CurveMismatch {
+            what,
+            found: found.to_owned(),
+            expected,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Bn254;
+    use ark_ff::One;
+
+    fn read_test_vector(proof_system: &str) -> (Vec<u8>, Vec<u8>, Vec<String>) {
+        let dir = format!("../../test_vectors/{proof_system}/bn254/multiplier2");
+        let proof = std::fs::read(format!("{dir}/circom.proof")).unwrap();
+        let vk = std::fs::read(format!("{dir}/verification_key.json")).unwrap();
+        let public_inputs =
+            serde_json::from_slice(&std::fs::read(format!("{dir}/public.json")).unwrap()).unwrap();
+        (proof, vk, public_inputs)
+    }
+
+    fn check_public_input_handling(proof_system: ProofSystem, name: &str) {
+        let (proof, vk, public_inputs) = read_test_vector(name);
+        let public_inputs = parse_public_inputs::<ark_bn254::Fr>(&public_inputs).unwrap();
+        assert!(verify::<Bn254>(proof_system, &proof, &vk, &public_inputs).unwrap());
+
+        let with_one = std::iter::once(ark_bn254::Fr::one())
+            .chain(public_inputs.iter().copied())
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            verify::<Bn254>(proof_system, &proof, &vk, &with_one),
+            Err(VerifyError::LeadingConstantOne { expected }) if expected == public_inputs.len()
+        ));
+
+        let mut wrong = public_inputs.clone();
+        wrong[0] += ark_bn254::Fr::one();
+        assert!(!verify::<Bn254>(proof_system, &proof, &vk, &wrong).unwrap());
+    }
+
+    #[test]
+    fn groth16_public_inputs_without_constant_one() {
+        check_public_input_handling(ProofSystem::Groth16, "Groth16");
+    }
+
+    #[test]
+    fn plonk_public_inputs_without_constant_one() {
+        check_public_input_handling(ProofSystem::Plonk, "Plonk");
+    }
+
+    #[test]
+    fn plonk_verifier_error_is_typed() {
+        let (proof, vk, public_inputs) = read_test_vector("Plonk");
+        let public_inputs = parse_public_inputs::<ark_bn254::Fr>(&public_inputs).unwrap();
+        let proof: serde_json::Value = serde_json::from_slice(&proof).unwrap();
+        let mut vk: serde_json::Value = serde_json::from_slice(&vk).unwrap();
+        // the two-adicity of the scalar field of BN254 is 28, so there is no domain of size 2^40
+        vk["power"] = serde_json::json!(40);
+        assert!(matches!(
+            verify_json::<Bn254>(ProofSystem::Plonk, proof, vk, &public_inputs),
+            Err(VerifyError::PlonkVerifier(
+                PlonkProofError::PolynomialDegreeTooLarge
+            ))
+        ));
+    }
+
+    fn check_structure_only(proof_system: ProofSystem, name: &str) {
+        let (proof, vk, public_inputs) = read_test_vector(name);
+        let mut public_inputs = parse_public_inputs::<ark_bn254::Fr>(&public_inputs).unwrap();
+        let proof: serde_json::Value = serde_json::from_slice(&proof).unwrap();
+        let vk: serde_json::Value = serde_json::from_slice(&vk).unwrap();
+        let check = |proof: &serde_json::Value, public_inputs: &[ark_bn254::Fr]| {
+            check_structure_json::<Bn254>(proof_system, proof.clone(), vk.clone(), public_inputs)
+        };
+
+        assert!(check(&proof, &public_inputs).is_ok());
+        // the pairing check is skipped, so wrong public inputs are not detected
+        public_inputs[0] += ark_bn254::Fr::one();
+        assert!(check(&proof, &public_inputs).is_ok());
+        assert!(matches!(
+            check(&proof, &public_inputs[1..]),
+            Err(VerifyError::PublicInputCount { .. })
+        ));
+
+        // (1, 1) is not on the curve
+        let mut off_curve = proof;
+        let point = match proof_system {
+            ProofSystem::Groth16 => "pi_a",
+            ProofSystem::Plonk => "A",
+        };
+        off_curve[point] = serde_json::json!(["1", "1", "1"]);
+        assert!(matches!(
+            check(&off_curve, &public_inputs),
+            Err(VerifyError::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn groth16_structure_only() {
+        check_structure_only(ProofSystem::Groth16, "Groth16");
+    }
+
+    #[test]
+    fn plonk_structure_only() {
+        check_structure_only(ProofSystem::Plonk, "Plonk");
+    }
+
+    fn check_vk_fingerprint(proof_system: ProofSystem, name: &str) {
+        let (_, vk, _) = read_test_vector(name);
+        let vk: serde_json::Value = serde_json::from_slice(&vk).unwrap();
+        let fingerprint = vk_fingerprint_json::<Bn254>(proof_system, vk.clone()).unwrap();
+        assert_eq!(fingerprint.len(), 64);
+
+        // the formatting and the order of the keys do not matter
+        let mut reordered = serde_json::Map::new();
+        for (key, value) in vk.as_object().unwrap().iter().rev() {
+            reordered.insert(key.clone(), value.clone());
+        }
+        let pretty = serde_json::to_vec_pretty(&reordered).unwrap();
+        let reordered = serde_json::from_slice(&pretty).unwrap();
+        assert_eq!(
+            vk_fingerprint_json::<Bn254>(proof_system, reordered).unwrap(),
+            fingerprint
+        );
+
+        let mut other = vk.clone();
+        other["nPublic"] = serde_json::json!(vk["nPublic"].as_u64().unwrap() + 1);
+        assert_ne!(
+            vk_fingerprint_json::<Bn254>(proof_system, other).unwrap(),
+            fingerprint
+        );
+        let mut other_curve = vk;
+        other_curve["curve"] = serde_json::json!("bls12381");
+        assert!(matches!(
+            vk_fingerprint_json::<Bn254>(proof_system, other_curve),
+            Err(VerifyError::CurveMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn groth16_vk_fingerprint() {
+        check_vk_fingerprint(ProofSystem::Groth16, "Groth16");
+
+        let (_, vk, _) = read_test_vector("Groth16");
+        let zkey = std::fs::File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey")
+            .unwrap();
+        let zkey = circom_types::groth16::ZKey::<Bn254>::from_reader(zkey).unwrap();
+        let exported = serde_json::to_value(Groth16JsonVerificationKey::from_zkey(&zkey)).unwrap();
+        assert_eq!(
+            vk_fingerprint_json::<Bn254>(ProofSystem::Groth16, exported).unwrap(),
+            vk_fingerprint_json::<Bn254>(
+                ProofSystem::Groth16,
+                serde_json::from_slice(&vk).unwrap()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn plonk_vk_fingerprint() {
+        check_vk_fingerprint(ProofSystem::Plonk, "Plonk");
+
+        let (_, vk, _) = read_test_vector("Plonk");
+        let zkey =
+            std::fs::File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap();
+        let zkey = circom_types::plonk::ZKey::<Bn254>::from_reader(zkey).unwrap();
+        let exported = serde_json::to_value(PlonkJsonVerificationKey::from_zkey(&zkey)).unwrap();
+        assert_eq!(
+            vk_fingerprint_json::<Bn254>(ProofSystem::Plonk, exported).unwrap(),
+            vk_fingerprint_json::<Bn254>(ProofSystem::Plonk, serde_json::from_slice(&vk).unwrap())
+                .unwrap()
+        );
+    }
+
+    /// Returns a random point on the curve that is not in the prime order subgroup, the curve must have a cofactor.
+    fn point_outside_subgroup<C: ark_ec::short_weierstrass::SWCurveConfig>(
+    ) -> ark_ec::short_weierstrass::Affine<C> {
+        let mut rng = rand::thread_rng();
+        loop {
+            let x = <C::BaseField as ark_ff::UniformRand>::rand(&mut rng);
+            if let Some(p) =
+                ark_ec::short_weierstrass::Affine::<C>::get_point_from_x_unchecked(x, false)
+            {
+                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                    return p;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_points_outside_subgroup() {
+        use circom_types::traits::without_subgroup_checks;
+        use std::str::FromStr;
+
+        // G1 of BN254 has no cofactor, so we use the G2 point of a Groth16 proof on BN254 and a G1 point of a Plonk proof on BLS12-381
+        let (proof, vk, public_inputs) = read_test_vector("Groth16");
+        let public_inputs = parse_public_inputs::<ark_bn254::Fr>(&public_inputs).unwrap();
+        let vk: serde_json::Value = serde_json::from_slice(&vk).unwrap();
+        let mut proof = serde_json::from_slice::<Groth16Proof<Bn254>>(&proof).unwrap();
+        proof.pi_b = point_outside_subgroup::<ark_bn254::g2::Config>();
+        let proof = serde_json::to_value(proof).unwrap();
+        let check = || {
+            check_structure_json::<Bn254>(
+                ProofSystem::Groth16,
+                proof.clone(),
+                vk.clone(),
+                &public_inputs,
+            )
+        };
+        assert!(matches!(check(), Err(VerifyError::InvalidProof(_))));
+        assert!(without_subgroup_checks(check).is_ok());
+        // the checks are enabled again afterwards
+        assert!(check().is_err());
+
+        let dir = "../../test_vectors/Plonk/bls12_381/multiplier2";
+        let proof = std::fs::read(format!("{dir}/circom.proof")).unwrap();
+        let vk: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(format!("{dir}/verification_key.json")).unwrap())
+                .unwrap();
+        let public_inputs: Vec<String> =
+            serde_json::from_slice(&std::fs::read(format!("{dir}/public.json")).unwrap()).unwrap();
+        let public_inputs = public_inputs
+            .iter()
+            .map(|s| ark_bls12_381::Fr::from_str(s).unwrap())
+            .collect::<Vec<_>>();
+        let mut proof =
+            serde_json::from_slice::<PlonkProof<ark_bls12_381::Bls12_381>>(&proof).unwrap();
+        proof.a = point_outside_subgroup::<ark_bls12_381::g1::Config>();
+        let proof = serde_json::to_value(proof).unwrap();
+        let check = || {
+            check_structure_json::<ark_bls12_381::Bls12_381>(
+                ProofSystem::Plonk,
+                proof.clone(),
+                vk.clone(),
+                &public_inputs,
+            )
+        };
+        assert!(matches!(check(), Err(VerifyError::InvalidProof(_))));
+        assert!(without_subgroup_checks(check).is_ok());
+    }
+
+    #[test]
+    fn groth16_compressed_proof() {
+        use ark_serialize::CanonicalSerialize;
+
+        let (proof, vk, public_inputs) = read_test_vector("Groth16");
+        let public_inputs = parse_public_inputs::<ark_bn254::Fr>(&public_inputs).unwrap();
+        let mut compressed = Vec::new();
+        serde_json::from_slice::<Groth16Proof<Bn254>>(&proof)
+            .unwrap()
+            .serialize_compressed(&mut compressed)
+            .unwrap();
+
+        let proof = groth16_proof_from_compressed::<Bn254>(&compressed).unwrap();
+        let vk = serde_json::from_slice(&vk).unwrap();
+        assert!(verify_json::<Bn254>(ProofSystem::Groth16, proof, vk, &public_inputs).unwrap());
+
+        assert!(matches!(
+            groth16_proof_from_compressed::<Bn254>(&compressed[1..]),
+            Err(VerifyError::InvalidCompressedProof(_))
+        ));
+    }
+}
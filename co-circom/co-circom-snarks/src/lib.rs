@@ -5,14 +5,17 @@
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use circom_types::Witness;
+use itertools::{izip, Itertools};
 use mpc_core::protocols::{
-    rep3::{self, Rep3PrimeFieldShare, Rep3ShareVecType},
+    rep3::{self, id::PartyID, Rep3PrimeFieldShare, Rep3ShareVecType},
     shamir::{self, ShamirPrimeFieldShare},
 };
 use rand::{distributions::Standard, prelude::Distribution, CryptoRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+pub mod parse;
+
 /// This type represents the serialized version of a Rep3 witness. Its share can be either additive or replicated, and in both cases also compressed.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -144,6 +147,34 @@ where
         [share1, share2, share3]
     }
 
+    /// Shares a given input into compressed additive shares, where the shares of party 1 and party 2 are expanded from `seed1` and `seed2`, respectively.
+    pub fn share_rep3_additive_from_seeds(
+        input: &[F],
+        seed1: U::Seed,
+        seed2: U::Seed,
+    ) -> [Rep3ShareVecType<F, U>; 3] {
+        rep3::share_field_elements_additive_from_seeds::<_, U>(input, seed1, seed2)
+            .map(Rep3ShareVecType::SeededAdditive)
+    }
+
+    /// Same as [Self::share_rep3], but shares the elements in parallel, which pays off for large array inputs. This only affects the non-seeded variants, as the seeded variants expand the shares of two parties from a single seed each. The shares are deterministic for a given `rng`, but differ from the ones [Self::share_rep3] produces for the same `rng`.
+    pub fn share_rep3_par<R: Rng + CryptoRng>(
+        input: &[F],
+        rng: &mut R,
+        seeded: bool,
+        additive: bool,
+    ) -> [Rep3ShareVecType<F, U>; 3] {
+        match (seeded, additive) {
+            (true, _) => Self::share_rep3(input, rng, seeded, additive),
+            (false, true) => {
+                rep3::share_field_elements_additive_par(input, rng).map(Rep3ShareVecType::Additive)
+            }
+            (false, false) => {
+                rep3::share_field_elements_par(input, rng).map(Rep3ShareVecType::Replicated)
+            }
+        }
+    }
+
     /// Merges two [SerializeableSharedRep3Input]s into one, performing basic sanity checks.
     pub fn merge(self, other: Self) -> eyre::Result<Self> {
         let mut shared_inputs = self.shared_inputs;
@@ -159,20 +190,147 @@ where
             }
             shared_inputs.insert(key, value);
         }
-        for (key, value) in other.public_inputs {
-            if !public_inputs.contains_key(&key) {
-                eyre::bail!("Public input \"{key}\" must be present in all files");
-            }
-            if public_inputs.get(&key).expect("is there we checked") != &value {
-                eyre::bail!("Public input \"{key}\" must be same in all files");
+        check_public_inputs_match(&public_inputs, &other.public_inputs)?;
+
+        Ok(Self {
+            shared_inputs,
+            public_inputs,
+        })
+    }
+
+    /// Merges two [SerializeableSharedRep3Input]s of the same party into one. In contrast to [Self::merge], an input present in both shares is not rejected, but its shares are added elementwise, i.e., the merged share is a share of the sum of both inputs.
+    ///
+    /// Compressed shares are expanded for the addition. If one of the two shares is only an additive share, so is the sum.
+    pub fn merge_additive(self, other: Self) -> eyre::Result<Self> {
+        let mut shared_inputs = self.shared_inputs;
+        let public_inputs = self.public_inputs;
+        for (key, value) in other.shared_inputs {
+            if public_inputs.contains_key(&key) || other.public_inputs.contains_key(&key) {
+                eyre::bail!(
+                    "Input name is once in shared inputs and once in public inputs: \"{key}\""
+                );
             }
+            let value = match shared_inputs.remove(&key) {
+                Some(ours) => add_rep3_share_vecs(&key, ours, value)?,
+                None => value,
+            };
+            shared_inputs.insert(key, value);
         }
+        check_public_inputs_match(&public_inputs, &other.public_inputs)?;
 
         Ok(Self {
             shared_inputs,
             public_inputs,
         })
     }
+
+    /// Checks that the public and shared inputs together are exactly the input signals of a circuit, given by their names and number of elements.
+    ///
+    /// Reports all missing signals, signals the circuit does not have and signals with a wrong number of elements at once.
+    pub fn check_signals(&self, signals: &[(String, usize)]) -> eyre::Result<()> {
+        let mut problems = vec![];
+        for (name, size) in signals {
+            let len = match (self.public_inputs.get(name), self.shared_inputs.get(name)) {
+                (Some(values), _) => values.len(),
+                (None, Some(Rep3ShareVecType::Replicated(values))) => values.len(),
+                (None, Some(Rep3ShareVecType::SeededReplicated(values))) => values.length()?,
+                (None, Some(Rep3ShareVecType::Additive(values))) => values.len(),
+                (None, Some(Rep3ShareVecType::SeededAdditive(values))) => values.length(),
+                (None, None) => {
+                    problems.push(format!("missing signal \"{name}\""));
+                    continue;
+                }
+            };
+            if len != *size {
+                problems.push(format!(
+                    "signal \"{name}\" has {len} elements, but the circuit expects {size}"
+                ));
+            }
+        }
+        for name in self.public_inputs.keys().chain(self.shared_inputs.keys()) {
+            if !signals.iter().any(|(signal, _)| signal == name) {
+                problems.push(format!("\"{name}\" is not an input signal of the circuit"));
+            }
+        }
+        if !problems.is_empty() {
+            eyre::bail!(
+                "the input share does not match the circuit: {}",
+                problems.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Adds two shares of the input `key` of the same party elementwise. The result is replicated if both shares are replicated, and additive otherwise.
+fn add_rep3_share_vecs<F: PrimeField, U: Rng + SeedableRng + CryptoRng>(
+    key: &str,
+    lhs: Rep3ShareVecType<F, U>,
+    rhs: Rep3ShareVecType<F, U>,
+) -> eyre::Result<Rep3ShareVecType<F, U>>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    let expand = |share| -> eyre::Result<_> {
+        Ok(match share {
+            Rep3ShareVecType::SeededReplicated(seeded) => {
+                Rep3ShareVecType::Replicated(seeded.expand_vec()?)
+            }
+            Rep3ShareVecType::SeededAdditive(seeded) => {
+                Rep3ShareVecType::Additive(seeded.expand_vec())
+            }
+            share => share,
+        })
+    };
+    let check_len = |lhs: usize, rhs: usize| {
+        if lhs != rhs {
+            eyre::bail!(
+                "Input \"{key}\" has {lhs} elements in one input share and {rhs} in another"
+            );
+        }
+        Ok(())
+    };
+    // the first element of a replicated share is the additive share of the party
+    Ok(match (expand(lhs)?, expand(rhs)?) {
+        (Rep3ShareVecType::Replicated(lhs), Rep3ShareVecType::Replicated(rhs)) => {
+            check_len(lhs.len(), rhs.len())?;
+            Rep3ShareVecType::Replicated(izip!(lhs, rhs).map(|(lhs, rhs)| lhs + rhs).collect())
+        }
+        (Rep3ShareVecType::Replicated(rep), Rep3ShareVecType::Additive(add))
+        | (Rep3ShareVecType::Additive(add), Rep3ShareVecType::Replicated(rep)) => {
+            check_len(rep.len(), add.len())?;
+            Rep3ShareVecType::Additive(izip!(rep, add).map(|(rep, add)| rep.a + add).collect())
+        }
+        (Rep3ShareVecType::Additive(lhs), Rep3ShareVecType::Additive(rhs)) => {
+            check_len(lhs.len(), rhs.len())?;
+            Rep3ShareVecType::Additive(izip!(lhs, rhs).map(|(lhs, rhs)| lhs + rhs).collect())
+        }
+        _ => unreachable!("compressed shares are expanded above"),
+    })
+}
+
+/// Checks that two input shares carry the same public inputs, i.e., the same names with the same values.
+/// Input shares that disagree on a public input would make the parties prove different statements.
+fn check_public_inputs_match<F: PrimeField>(
+    ours: &BTreeMap<String, Vec<F>>,
+    theirs: &BTreeMap<String, Vec<F>>,
+) -> eyre::Result<()> {
+    let fmt_values = |values: &[F]| format!("[{}]", values.iter().join(", "));
+    for (key, value) in theirs {
+        match ours.get(key) {
+            None => eyre::bail!("Public input \"{key}\" must be present in all files"),
+            Some(ours) if ours != value => eyre::bail!(
+                "Public input \"{key}\" must be same in all files, got {} and {}",
+                fmt_values(ours),
+                fmt_values(value)
+            ),
+            Some(_) => {}
+        }
+    }
+    if let Some(key) = ours.keys().find(|key| !theirs.contains_key(*key)) {
+        eyre::bail!("Public input \"{key}\" must be present in all files");
+    }
+    Ok(())
 }
 
 /// A shared input for a collaborative circom witness extension.
@@ -264,14 +422,7 @@ where
             }
             shared_inputs.insert(key, value);
         }
-        for (key, value) in other.public_inputs {
-            if !public_inputs.contains_key(&key) {
-                eyre::bail!("Public input \"{key}\" must be present in all files");
-            }
-            if public_inputs.get(&key).expect("is there we checked") != &value {
-                eyre::bail!("Public input \"{key}\" must be same in all files");
-            }
-        }
+        check_public_inputs_match(&public_inputs, &other.public_inputs)?;
 
         Ok(Self {
             shared_inputs,
@@ -316,7 +467,80 @@ where
     }
 }
 
+impl<F: PrimeField, S> SharedWitness<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    /// Returns the public signals of the circuit without the constant 1, i.e., the values the verifier expects as public inputs.
+    ///
+    /// These are taken from the witness in witness order, so the public outputs of the circuit come first, followed by its public inputs. Also the outputs that are computed from private inputs are included. Fails if the number of public signals does not match `num_public_inputs` from the zkey, e.g., as the share was created from a witness of another circuit.
+    pub fn public_signals(&self, num_public_inputs: usize) -> eyre::Result<&[F]> {
+        match self.public_inputs.split_first() {
+            Some((one, signals)) if signals.len() == num_public_inputs => {
+                if !one.is_one() {
+                    eyre::bail!("the first public input of the witness share is not the constant 1");
+                }
+                Ok(signals)
+            }
+            _ => eyre::bail!(
+                "the witness share has {} public signals (outputs and public inputs), but the zkey expects {}",
+                self.public_inputs.len().saturating_sub(1),
+                num_public_inputs
+            ),
+        }
+    }
+
+    /// Checks that all shares agree on the public inputs and have the same number of witness elements, and returns the public inputs.
+    fn common_public_inputs(shares: &[Self]) -> eyre::Result<&[F]> {
+        let first = shares
+            .first()
+            .ok_or_else(|| eyre::eyre!("no shares provided"))?;
+        for (i, share) in shares.iter().enumerate().skip(1) {
+            if share.public_inputs != first.public_inputs {
+                eyre::bail!("public inputs of share {i} do not match the ones of share 0");
+            }
+            if share.witness.len() != first.witness.len() {
+                eyre::bail!(
+                    "share {i} has {} witness elements, but share 0 has {}",
+                    share.witness.len(),
+                    first.witness.len()
+                );
+            }
+        }
+        Ok(&first.public_inputs)
+    }
+}
+
+impl<F: PrimeField> SharedWitness<F, F> {
+    /// Wraps a plaintext witness for the plain provers, i.e., the first `num_pub_inputs` values (including the constant 1) become the public inputs and the remaining values are used as is.
+    ///
+    /// INSECURE, only meant for tests and local experiments: the witness is not hidden at all, which is fine for the plain provers as they run on a single party without a network anyway.
+    pub fn from_plaintext(witness: Witness<F>, num_pub_inputs: usize) -> Self {
+        let mut public_inputs = witness.values;
+        let witness = public_inputs.split_off(num_pub_inputs);
+        Self {
+            public_inputs,
+            witness,
+        }
+    }
+}
+
 impl<F: PrimeField> SharedWitness<F, Rep3PrimeFieldShare<F>> {
+    /// Trivially shares a plaintext witness using the rep3 protocol, i.e., every witness element is held by party 0 and the shares of the other parties are zero.
+    ///
+    /// INSECURE, only meant for tests and local simulations of the three parties: the share of party 0 (as well as the one of party 1) reveals the whole witness. Use [Self::share_rep3] for real sharing.
+    pub fn from_plaintext_rep3(witness: Witness<F>, num_pub_inputs: usize) -> [Self; 3] {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness = &witness.values[num_pub_inputs..];
+        [PartyID::ID0, PartyID::ID1, PartyID::ID2].map(|id| Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: witness
+                .iter()
+                .map(|value| rep3::arithmetic::promote_to_trivial_share(id, *value))
+                .collect(),
+        })
+    }
+
     /// Shares a given witness and public input vector using the rep3 protocol.
     pub fn share_rep3<R: Rng + CryptoRng>(
         witness: Witness<F>,
@@ -340,9 +564,108 @@ impl<F: PrimeField> SharedWitness<F, Rep3PrimeFieldShare<F>> {
         };
         [witness1, witness2, witness3]
     }
+
+    /// Reconstructs the plaintext witness (public inputs followed by the witness elements) from the shares of all three parties, given in the order of the party ids. Fails if the shares disagree on the public inputs or if the replicated shares are inconsistent, i.e., the second component of a party's share does not match the first component of the previous party's share.
+    pub fn open_from_shares(shares: &[Self]) -> eyre::Result<Witness<F>> {
+        if shares.len() != 3 {
+            eyre::bail!("REP3 requires exactly 3 shares, got {}", shares.len());
+        }
+        let public_inputs = Self::common_public_inputs(shares)?;
+        for i in 0..3 {
+            let prev = &shares[(i + 2) % 3];
+            if izip!(&shares[i].witness, &prev.witness).any(|(share, prev)| share.b != prev.a) {
+                eyre::bail!(
+                    "REP3 shares of party {i} are not consistent with party {}",
+                    (i + 2) % 3
+                );
+            }
+        }
+        let mut values = public_inputs.to_vec();
+        values.extend(
+            izip!(&shares[0].witness, &shares[1].witness, &shares[2].witness)
+                .map(|(a, b, c)| rep3::combine_field_element(*a, *b, *c)),
+        );
+        Ok(Witness { values })
+    }
 }
 
 impl<F: PrimeField> SharedWitness<F, ShamirPrimeFieldShare<F>> {
+    /// Reconstructs the plaintext witness (public inputs followed by the witness elements) from the shares of parties 0 to shares.len() - 1, where the sharing polynomial has the given degree. If more than degree + 1 shares are provided, the witness is reconstructed from every window of degree + 1 consecutive parties and all reconstructions have to agree, which detects inconsistent shares.
+    pub fn open_from_shares(shares: &[Self], degree: usize) -> eyre::Result<Witness<F>> {
+        let party_ids = (0..shares.len()).collect::<Vec<_>>();
+        Self::open_from_party_shares(shares, &party_ids, degree)
+    }
+
+    /// Same as [Self::open_from_shares], but for the shares of an explicit set of parties, e.g., if some parties are offline. `shares[i]` has to be the share of party `party_ids[i]`, which is the evaluation of the sharing polynomial at `party_ids[i] + 1`. Fails if fewer than degree + 1 shares are provided.
+    pub fn open_from_party_shares(
+        shares: &[Self],
+        party_ids: &[usize],
+        degree: usize,
+    ) -> eyre::Result<Witness<F>> {
+        if shares.len() != party_ids.len() {
+            eyre::bail!(
+                "got {} shares, but {} party ids",
+                shares.len(),
+                party_ids.len()
+            );
+        }
+        if let Some(id) = party_ids.iter().duplicates().next() {
+            eyre::bail!("party {id} is given more than once");
+        }
+        if shares.len() <= degree {
+            eyre::bail!(
+                "reconstruction needs the shares of at least {} parties, got {}",
+                degree + 1,
+                shares.len()
+            );
+        }
+        let public_inputs = Self::common_public_inputs(shares)?;
+        let shares = shares
+            .iter()
+            .map(|share| share.witness.clone())
+            .collect::<Vec<_>>();
+        let points = party_ids.iter().map(|id| id + 1).collect::<Vec<_>>();
+        let opened = shamir::combine_field_elements(&shares, &points, degree)?;
+        for start in 1..shares.len() - degree {
+            let window = start..=start + degree;
+            let other = shamir::combine_field_elements(
+                &shares[window.clone()],
+                &points[window.clone()],
+                degree,
+            )?;
+            if other != opened {
+                eyre::bail!(
+                    "Shamir shares of parties {:?} do not reconstruct to the same witness as parties {:?}",
+                    &party_ids[window],
+                    &party_ids[..=degree]
+                );
+            }
+        }
+        let mut values = public_inputs.to_vec();
+        values.extend(opened);
+        Ok(Witness { values })
+    }
+
+    /// Trivially shares a plaintext witness using the Shamir protocol for `num_parties` parties, i.e., the sharing polynomial of every witness element is constant, so all parties hold the element itself. A constant polynomial is a valid sharing for every degree.
+    ///
+    /// INSECURE, only meant for tests and benchmarks: every share reveals the whole witness. Use [Self::share_shamir] for real sharing.
+    pub fn from_plaintext_shamir(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        num_parties: usize,
+    ) -> Vec<Self> {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness =
+            shamir::arithmetic::promote_to_trivial_shares(&witness.values[num_pub_inputs..]);
+        vec![
+            Self {
+                public_inputs: public_inputs.to_vec(),
+                witness,
+            };
+            num_parties
+        ]
+    }
+
     /// Shares a given witness and public input vector using the Shamir protocol.
     pub fn share_shamir<R: Rng + CryptoRng>(
         witness: Witness<F>,
@@ -362,8 +685,54 @@ impl<F: PrimeField> SharedWitness<F, ShamirPrimeFieldShare<F>> {
             })
             .collect()
     }
+
+    /// Shares a given witness and public input vector using the Shamir protocol for an explicit set of parties. The result contains one share per entry of `party_ids` (in the same order), where the share of party `i` is the same as the one produced by [`Self::share_shamir`] for party `i` given the same sharing polynomial, i.e., it uses the evaluation point `i + 1`.
+    pub fn share_shamir_for_parties<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        degree: usize,
+        party_ids: &[usize],
+        rng: &mut R,
+    ) -> eyre::Result<Vec<Self>> {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness = &witness.values[num_pub_inputs..];
+        let shares = shamir::share_field_elements_for_parties(witness, degree, party_ids, rng)?;
+        Ok(shares
+            .into_iter()
+            .map(|share| Self {
+                public_inputs: public_inputs.to_vec(),
+                witness: share,
+            })
+            .collect())
+    }
+}
+
+/// The phases a coSNARK prover moves through, reported to a [ProgressCallback].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProverPhase {
+    /// Loading the witness and computing the polynomials that depend on it.
+    Witness,
+    /// Computing the multi-scalar multiplications for the commitments.
+    Msm,
+    /// Opening the shared proof elements and assembling the proof.
+    Finalize,
 }
 
+impl std::fmt::Display for ProverPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProverPhase::Witness => write!(f, "witness"),
+            ProverPhase::Msm => write!(f, "MSM"),
+            ProverPhase::Finalize => write!(f, "finalization"),
+        }
+    }
+}
+
+/// A callback that is invoked whenever a prover enters a new [ProverPhase]. The second argument is the overall progress of the proof in the range `[0, 1]`.
+///
+/// The callback is invoked one last time with [ProverPhase::Finalize] and a progress of `1.0` once the proof is done.
+pub type ProgressCallback = std::sync::Arc<dyn Fn(ProverPhase, f32) + Send + Sync>;
+
 /// Gathers utility methods for proving coSNARKs.
 pub mod utils {
     use ark_ff::{FftField, LegendreSymbol, PrimeField};
@@ -390,3 +759,233 @@ pub mod utils {
         (q, roots)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    fn random_witness(size: usize) -> Witness<ark_bn254::Fr> {
+        let mut rng = thread_rng();
+        Witness {
+            values: (0..size).map(|_| ark_bn254::Fr::rand(&mut rng)).collect(),
+        }
+    }
+
+    #[test]
+    fn merge_rejects_inconsistent_public_inputs() {
+        let share = |public: &[(&str, u64)], shared: &str| {
+            let mut input =
+                SharedInput::<ark_bn254::Fr, Rep3PrimeFieldShare<ark_bn254::Fr>>::default();
+            for (key, value) in public {
+                input.add_public_input(key.to_string(), vec![ark_bn254::Fr::from(*value)]);
+            }
+            input.add_shared_input(shared.to_owned(), vec![]);
+            input
+        };
+
+        let merged = share(&[("p", 1)], "a").merge(share(&[("p", 1)], "b"));
+        assert!(merged.is_ok());
+
+        let err = share(&[("p", 1)], "a")
+            .merge(share(&[("p", 2)], "b"))
+            .unwrap_err();
+        assert!(err.to_string().contains("\"p\""));
+        assert!(err.to_string().contains("[1] and [2]"));
+
+        assert!(share(&[("p", 1), ("q", 1)], "a")
+            .merge(share(&[("p", 1)], "b"))
+            .is_err());
+        assert!(share(&[("p", 1)], "a")
+            .merge(share(&[("p", 1), ("q", 1)], "b"))
+            .is_err());
+    }
+
+    #[test]
+    fn merge_additive_adds_shares_of_same_input() {
+        type Input = SerializeableSharedRep3Input<ark_bn254::Fr, rand::rngs::StdRng>;
+        let mut rng = thread_rng();
+        let x = (0..4)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let y = (0..4)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let open = |shares: [&Rep3ShareVecType<_, _>; 3]| {
+            shares
+                .into_iter()
+                .map(|share| match share {
+                    Rep3ShareVecType::Replicated(vec) => vec.iter().map(|s| s.a).collect(),
+                    Rep3ShareVecType::Additive(vec) => vec.clone(),
+                    _ => panic!("merged shares are expanded"),
+                })
+                .reduce(|acc: Vec<_>, share| izip!(acc, share).map(|(a, b)| a + b).collect())
+                .unwrap()
+        };
+        let sum = izip!(&x, &y).map(|(x, y)| x + y).collect::<Vec<_>>();
+
+        for (seeded, additive) in [(true, false), (false, true), (true, true)] {
+            let lhs = Input::share_rep3(&x, &mut rng, seeded, false);
+            let rhs = Input::share_rep3(&y, &mut rng, false, additive);
+            let merged = izip!(lhs, rhs)
+                .map(|(lhs, rhs)| {
+                    let (mut ours, mut theirs) = (Input::default(), Input::default());
+                    ours.shared_inputs.insert("a".to_owned(), lhs);
+                    theirs.shared_inputs.insert("a".to_owned(), rhs);
+                    theirs
+                        .shared_inputs
+                        .insert("b".to_owned(), Rep3ShareVecType::Additive(vec![]));
+                    let merged = ours.merge_additive(theirs).unwrap();
+                    assert!(merged.shared_inputs.contains_key("b"));
+                    merged
+                })
+                .collect::<Vec<_>>();
+            let shares = [0, 1, 2].map(|i| &merged[i].shared_inputs["a"]);
+            assert_eq!(open(shares), sum);
+        }
+
+        let mut ours = Input::default();
+        ours.shared_inputs
+            .insert("a".to_owned(), Rep3ShareVecType::Additive(x.clone()));
+        let mut theirs = Input::default();
+        theirs
+            .shared_inputs
+            .insert("a".to_owned(), Rep3ShareVecType::Additive(y[..2].to_vec()));
+        assert!(ours.merge_additive(theirs).is_err());
+    }
+
+    #[test]
+    fn check_signals_reports_missing_extra_and_wrong_size() {
+        type Input = SerializeableSharedRep3Input<ark_bn254::Fr, rand::rngs::StdRng>;
+        let mut rng = thread_rng();
+        let values = (0..2)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let [share, _, _] = Input::share_rep3(&values, &mut rng, true, false);
+        let mut input = Input::default();
+        input.shared_inputs.insert("a".to_owned(), share);
+        input
+            .public_inputs
+            .insert("p".to_owned(), vec![ark_bn254::Fr::from(1u64)]);
+
+        let signals = |signals: &[(&str, usize)]| {
+            signals
+                .iter()
+                .map(|(name, size)| (name.to_string(), *size))
+                .collect::<Vec<_>>()
+        };
+        assert!(input.check_signals(&signals(&[("a", 2), ("p", 1)])).is_ok());
+
+        let err = input
+            .check_signals(&signals(&[("a", 3), ("b", 1)]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing signal \"b\""));
+        assert!(err.contains("\"a\" has 2 elements, but the circuit expects 3"));
+        assert!(err.contains("\"p\" is not an input signal"));
+    }
+
+    #[test]
+    fn share_rep3_par_is_deterministic() {
+        use rand::rngs::StdRng;
+        type Input = SerializeableSharedRep3Input<ark_bn254::Fr, StdRng>;
+        let mut rng = thread_rng();
+        // spans multiple chunks, the last one incomplete
+        let values = (0..10_000)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+
+        for additive in [false, true] {
+            let shares =
+                Input::share_rep3_par(&values, &mut StdRng::seed_from_u64(42), false, additive);
+            let again =
+                Input::share_rep3_par(&values, &mut StdRng::seed_from_u64(42), false, additive);
+            let opened = match (shares, again) {
+                (
+                    [Rep3ShareVecType::Replicated(a), Rep3ShareVecType::Replicated(b), Rep3ShareVecType::Replicated(c)],
+                    [Rep3ShareVecType::Replicated(a_), _, _],
+                ) => {
+                    assert_eq!(a, a_);
+                    rep3::combine_field_elements(a, b, c)
+                }
+                (
+                    [Rep3ShareVecType::Additive(a), Rep3ShareVecType::Additive(b), Rep3ShareVecType::Additive(c)],
+                    [Rep3ShareVecType::Additive(a_), _, _],
+                ) => {
+                    assert_eq!(a, a_);
+                    izip!(a, b, c).map(|(a, b, c)| a + b + c).collect()
+                }
+                _ => panic!("unexpected share type"),
+            };
+            assert_eq!(opened, values);
+        }
+    }
+
+    #[test]
+    fn rep3_open_from_shares() {
+        let witness = random_witness(10);
+        let mut shares = SharedWitness::share_rep3(witness.clone(), 2, &mut thread_rng());
+        let opened = SharedWitness::<_, Rep3PrimeFieldShare<_>>::open_from_shares(&shares).unwrap();
+        assert_eq!(opened.values, witness.values);
+
+        shares[1].witness[3].a += ark_bn254::Fr::from(1u64);
+        assert!(SharedWitness::<_, Rep3PrimeFieldShare<_>>::open_from_shares(&shares).is_err());
+    }
+
+    #[test]
+    fn from_plaintext() {
+        let witness = random_witness(10);
+        let plain = SharedWitness::from_plaintext(witness.clone(), 2);
+        assert_eq!(plain.public_inputs, witness.values[..2]);
+        assert_eq!(plain.witness, witness.values[2..]);
+
+        let shares = SharedWitness::from_plaintext_rep3(witness.clone(), 2);
+        let zero = Rep3PrimeFieldShare::new(ark_bn254::Fr::from(0u64), ark_bn254::Fr::from(0u64));
+        assert!(shares[2].witness.iter().all(|share| *share == zero));
+        let opened = SharedWitness::<_, Rep3PrimeFieldShare<_>>::open_from_shares(&shares).unwrap();
+        assert_eq!(opened.values, witness.values);
+
+        let shares = SharedWitness::from_plaintext_shamir(witness.clone(), 2, 5);
+        assert_eq!(shares.len(), 5);
+        let opened =
+            SharedWitness::<_, ShamirPrimeFieldShare<_>>::open_from_shares(&shares, 2).unwrap();
+        assert_eq!(opened.values, witness.values);
+    }
+
+    #[test]
+    fn shamir_open_from_shares() {
+        let witness = random_witness(10);
+        let mut shares = SharedWitness::share_shamir(witness.clone(), 2, 1, 4, &mut thread_rng());
+        let opened =
+            SharedWitness::<_, ShamirPrimeFieldShare<_>>::open_from_shares(&shares, 1).unwrap();
+        assert_eq!(opened.values, witness.values);
+
+        shares[3].witness[3] =
+            shamir::arithmetic::add_public(shares[3].witness[3], ark_bn254::Fr::from(1u64));
+        assert!(
+            SharedWitness::<_, ShamirPrimeFieldShare<_>>::open_from_shares(&shares, 1).is_err()
+        );
+    }
+
+    #[test]
+    fn shamir_open_from_party_shares() {
+        type Share = SharedWitness<ark_bn254::Fr, ShamirPrimeFieldShare<ark_bn254::Fr>>;
+        let witness = random_witness(10);
+        let shares = Share::share_shamir(witness.clone(), 2, 1, 4, &mut thread_rng());
+        let subset = [shares[3].clone(), shares[1].clone()];
+
+        let opened = Share::open_from_party_shares(&subset, &[3, 1], 1).unwrap();
+        assert_eq!(opened.values, witness.values);
+
+        let subset = [shares[0].clone(), shares[2].clone(), shares[3].clone()];
+        let opened = Share::open_from_party_shares(&subset, &[0, 2, 3], 1).unwrap();
+        assert_eq!(opened.values, witness.values);
+        // the third share does not lie on the polynomial through the first two for wrong ids
+        assert!(Share::open_from_party_shares(&subset, &[0, 2, 1], 1).is_err());
+
+        assert!(Share::open_from_party_shares(&subset[..1], &[0], 1).is_err());
+        assert!(Share::open_from_party_shares(&subset[..2], &[0, 0], 1).is_err());
+        assert!(Share::open_from_party_shares(&subset[..2], &[0, 2, 3], 1).is_err());
+    }
+}
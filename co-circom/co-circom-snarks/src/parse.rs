@@ -0,0 +1,136 @@
+//! Parsing of secret field elements from their string representation.
+//!
+//! The parsers of `num-bigint` and arkworks return early and branch on the digits and the magnitude of the parsed value, so the time they take leaks information about it. [parse_field_ct] accumulates the digits in a fixed number of limbs with branch-free arithmetic instead, so its running time only depends on the length of the string.
+use ark_ff::PrimeField;
+
+/// Parses a field element from a decimal string or a `0x`-prefixed hex string, both optionally prefixed with `-`, without branching on the value.
+///
+/// In contrast to the `FromStr` implementation of the arkworks fields, values that are not in the range `[0, p)` are rejected instead of being reduced modulo `p`. The running time only depends on the length of the string and whether it has a `-` or `0x` prefix, so pad the strings to a common length to hide the magnitude of the values completely.
+pub fn parse_field_ct<F: PrimeField>(s: &str) -> eyre::Result<F> {
+    let bytes = s.as_bytes();
+    // the sign is secret as well, so we strip it without branching
+    let negative = u64::from(bytes.first() == Some(&b'-'));
+    let unsigned = &bytes[negative as usize..];
+    let (digits, hex) = match unsigned.strip_prefix(b"0x") {
+        Some(digits) => (digits, 1),
+        None => (unsigned, 0),
+    };
+    if digits.is_empty() {
+        eyre::bail!("expected a decimal or 0x-prefixed hex field element, got an empty string");
+    }
+
+    let radix = 10 + 6 * hex;
+    let mut value = F::BigInt::default();
+    let mut invalid = 0u64;
+    let mut overflow = 0u64;
+    for c in digits.iter().copied() {
+        let (digit, is_dec, is_hex) = decode_digit(c);
+        invalid |= 1 ^ (is_dec | (is_hex & hex));
+        overflow |= mul_add(value.as_mut(), radix, digit);
+    }
+    if invalid != 0 {
+        eyre::bail!("expected a decimal or 0x-prefixed hex field element, but the string contains invalid digits");
+    }
+    let mut diff = value;
+    let less_than_modulus = sub_assign(diff.as_mut(), F::MODULUS.as_ref()) == 1;
+    if overflow != 0 || !less_than_modulus {
+        eyre::bail!("field element is not less than the modulus of the field");
+    }
+
+    // negate by computing p - value, unless value is zero
+    let mut negated = F::MODULUS;
+    sub_assign(negated.as_mut(), value.as_ref());
+    let nonzero = value.as_ref().iter().fold(0, |acc, limb| acc | limb);
+    let nonzero = (nonzero | nonzero.wrapping_neg()) >> 63;
+    let mask = (negative & nonzero).wrapping_neg();
+    for (limb, negated) in value.as_mut().iter_mut().zip(negated.as_ref()) {
+        *limb = (*limb & !mask) | (negated & mask);
+    }
+    Ok(F::from_bigint(value).expect("value is less than the modulus"))
+}
+
+/// Decodes a decimal or hex digit. Returns the value of the digit and whether it is a decimal digit or a hex letter as `0` or `1`.
+fn decode_digit(c: u8) -> (u64, u64, u64) {
+    let dec = u64::from(c.wrapping_sub(b'0'));
+    let letter = u64::from((c | 0x20).wrapping_sub(b'a'));
+    let is_dec = dec.wrapping_sub(10) >> 63;
+    let is_hex = letter.wrapping_sub(6) >> 63;
+    let digit = (dec & is_dec.wrapping_neg()) | ((letter + 10) & is_hex.wrapping_neg());
+    (digit, is_dec, is_hex)
+}
+
+/// Computes `limbs = limbs * mul + add` and returns the carry out of the most significant limb.
+fn mul_add(limbs: &mut [u64], mul: u64, add: u64) -> u64 {
+    let mut carry = add;
+    for limb in limbs.iter_mut() {
+        let tmp = u128::from(*limb) * u128::from(mul) + u128::from(carry);
+        *limb = tmp as u64;
+        carry = (tmp >> 64) as u64;
+    }
+    carry
+}
+
+/// Computes `lhs = lhs - rhs` and returns the borrow, i.e., `1` if `lhs < rhs`.
+fn sub_assign(lhs: &mut [u64], rhs: &[u64]) -> u64 {
+    let mut borrow = 0;
+    for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+        let (diff, borrow1) = lhs.overflowing_sub(*rhs);
+        let (diff, borrow2) = diff.overflowing_sub(borrow);
+        *lhs = diff;
+        borrow = u64::from(borrow1 | borrow2);
+    }
+    borrow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{One, UniformRand, Zero};
+
+    type Fr = ark_bn254::Fr;
+
+    #[test]
+    fn parses_like_arkworks() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let val = Fr::rand(&mut rng);
+            let dec = val.to_string();
+            let hex = format!("0x{}", num_bigint_hex(val));
+            assert_eq!(parse_field_ct::<Fr>(&dec).unwrap(), val);
+            assert_eq!(parse_field_ct::<Fr>(&hex).unwrap(), val);
+            assert_eq!(parse_field_ct::<Fr>(&format!("-{dec}")).unwrap(), -val);
+            assert_eq!(parse_field_ct::<Fr>(&format!("-{hex}")).unwrap(), -val);
+        }
+        assert_eq!(parse_field_ct::<Fr>("0").unwrap(), Fr::zero());
+        assert_eq!(parse_field_ct::<Fr>("-0").unwrap(), Fr::zero());
+        assert_eq!(parse_field_ct::<Fr>("-1").unwrap(), -Fr::one());
+        assert_eq!(parse_field_ct::<Fr>("0x00FfaB").unwrap(), Fr::from(0xffab));
+        assert_eq!(parse_field_ct::<Fr>("007").unwrap(), Fr::from(7));
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        let modulus = num_bigint::BigUint::from(Fr::MODULUS);
+        let below = (&modulus - 1u64).to_string();
+        let modulus = modulus.to_string();
+        assert_eq!(parse_field_ct::<Fr>(&below).unwrap(), -Fr::one());
+        assert!(parse_field_ct::<Fr>(&modulus).is_err());
+        assert!(parse_field_ct::<Fr>(&format!("-{modulus}")).is_err());
+        assert!(parse_field_ct::<Fr>(&format!("{modulus}0")).is_err());
+        assert!(parse_field_ct::<Fr>(&format!("0x1{}", "0".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_strings() {
+        for s in [
+            "", "-", "0x", "-0x", "12a", "0x12g", " 1", "1 ", "--1", "+1", "0x-1", "1_0",
+        ] {
+            assert!(parse_field_ct::<Fr>(s).is_err(), "accepted {s:?}");
+        }
+    }
+
+    fn num_bigint_hex(val: Fr) -> String {
+        let big: num_bigint::BigUint = val.into();
+        big.to_str_radix(16)
+    }
+}
@@ -0,0 +1,25 @@
+use ark_bn254::Fr;
+use ark_ff::UniformRand;
+use co_circom_snarks::SerializeableSharedRep3Input;
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+
+type Input = SerializeableSharedRep3Input<Fr, StdRng>;
+
+fn share_array_input(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let values = (0..1 << 20).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("rep3 share 1M elements");
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter(|| Input::share_rep3(&values, &mut rng, false, false))
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| Input::share_rep3_par(&values, &mut rng, false, false))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, share_array_input);
+criterion_main!(benches);
@@ -1,9 +1,10 @@
 use ark_bls12_381::Bls12_381;
 use ark_bn254::Bn254;
-use ark_ec::pairing::Pairing;
-use ark_ff::PrimeField;
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_serialize::CanonicalSerialize;
 use circom_mpc_compiler::CoCircomCompiler;
-use circom_types::R1CS;
+use circom_types::{R1CSHeader, R1CS};
 use num_traits::Zero;
 use std::sync::Arc;
 
@@ -15,64 +16,128 @@ use circom_types::{
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
     Witness,
 };
+use circom_verifier as verify;
 use clap::{Parser, Subcommand};
+use co_circom::cbor;
+use co_circom::joint_seed;
+use co_circom::readiness::ReadinessProbe;
+use co_circom::AggregateCli;
+use co_circom::AggregateConfig;
+use co_circom::BenchSplitCli;
+use co_circom::BenchSplitConfig;
+use co_circom::BundleCli;
+use co_circom::BundleConfig;
+use co_circom::ByteOrder;
+use co_circom::ContributeSeedCli;
+use co_circom::ContributeSeedConfig;
+use co_circom::ConvertPublicInputsCli;
+use co_circom::ConvertPublicInputsConfig;
+use co_circom::DiffPublicInputsCli;
+use co_circom::DiffPublicInputsConfig;
+use co_circom::EstimateCli;
+use co_circom::EstimateConfig;
+use co_circom::ExportVkCli;
+use co_circom::ExportVkConfig;
+use co_circom::ExtractPublicInputsCli;
+use co_circom::ExtractPublicInputsConfig;
 use co_circom::GenerateProofCli;
 use co_circom::GenerateProofConfig;
 use co_circom::GenerateWitnessCli;
 use co_circom::GenerateWitnessConfig;
 use co_circom::MergeInputSharesCli;
 use co_circom::MergeInputSharesConfig;
+use co_circom::OutputFormat;
+use co_circom::ProofFormat;
+use co_circom::ProveFromInputCli;
+use co_circom::ProveFromInputConfig;
+use co_circom::PublicSignalsCli;
+use co_circom::PublicSignalsConfig;
+use co_circom::RerandomizeProofCli;
+use co_circom::RerandomizeProofConfig;
+use co_circom::ReshareCli;
+use co_circom::ReshareConfig;
 use co_circom::SplitInputCli;
 use co_circom::SplitInputConfig;
 use co_circom::SplitWitnessCli;
 use co_circom::SplitWitnessConfig;
 use co_circom::TranslateWitnessCli;
 use co_circom::TranslateWitnessConfig;
+use co_circom::VerifyAggregatedCli;
+use co_circom::VerifyAggregatedConfig;
 use co_circom::VerifyCli;
 use co_circom::VerifyConfig;
-use co_circom::{file_utils, MPCCurve, MPCProtocol, ProofSystem, SeedRng};
+use co_circom::VerifyResult;
+use co_circom::VkFingerprintCli;
+use co_circom::VkFingerprintConfig;
+use co_circom::{
+    file_utils, round_stats::RoundCountLayer, InputShareFile, MPCCurve, MPCProtocol, ProofBundle,
+    ProofSystem, Radix, SeedRng, ShareRng, SplitWitnessManifest, WitnessShareEntry,
+    PROOF_BUNDLE_FORMAT,
+};
 use co_circom_snarks::{
-    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedWitness,
+    parse::parse_field_ct, ProverPhase, SerializeableSharedRep3Input,
+    SerializeableSharedRep3Witness, SharedWitness,
 };
-use co_groth16::Groth16;
-use co_groth16::{Rep3CoGroth16, ShamirCoGroth16};
+use co_groth16::{AggregatedProof, Groth16, Rep3CoGroth16, ShamirCoGroth16};
+use co_plonk::Plonk;
 use co_plonk::Rep3CoPlonk;
-use co_plonk::{Plonk, ShamirCoPlonk};
+use co_plonk::ShamirCoPlonk;
 use color_eyre::eyre::{eyre, Context, ContextCompat};
 use mpc_core::protocols::{
     bridges::network::RepToShamirNetwork,
     rep3::network::Rep3MpcNet,
-    shamir::{ShamirPreprocessing, ShamirProtocol},
+    shamir::{
+        network::{ShamirMpcNet, ShamirNetwork},
+        ShamirPreprocessing, ShamirProtocol,
+    },
+};
+use mpc_core::protocols::{
+    rep3::{self, network::Rep3Network, Rep3PrimeFieldShare, Rep3ShareVecType},
+    shamir::ShamirPrimeFieldShare,
 };
-use mpc_core::protocols::{rep3::network::Rep3Network, shamir::ShamirPrimeFieldShare};
+use mpc_net::config::NetworkConfig;
 use num_bigint::BigUint;
 use num_traits::Num;
+use rand::{Rng, SeedableRng};
 use std::time::Instant;
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     fs::File,
-    io::{BufReader, BufWriter},
-    path::PathBuf,
+    io::{BufReader, BufWriter, Write},
+    net::ToSocketAddrs,
+    path::{Path, PathBuf},
     process::ExitCode,
 };
 use tracing::instrument;
 use tracing_subscriber::fmt::format::FmtSpan;
 
-fn install_tracing() {
+/// Installs the tracing subscriber. The log level is taken from `RUST_LOG` if set, otherwise from `--quiet` and `--verbose`.
+fn install_tracing(quiet: bool, verbose: u8) -> RoundCountLayer {
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
+    let (level, span_events) = match (quiet, verbose) {
+        (true, _) => ("error", FmtSpan::NONE),
+        (false, 0) => ("info", FmtSpan::CLOSE | FmtSpan::ENTER),
+        (false, 1) => ("debug", FmtSpan::CLOSE | FmtSpan::ENTER),
+        (false, _) => ("trace", FmtSpan::FULL),
+    };
+    // logs go to stderr, so that proofs and public inputs can be written to stdout
     let fmt_layer = fmt::layer()
+        .with_writer(std::io::stderr)
         .with_target(false)
         .with_line_number(false)
-        .with_span_events(FmtSpan::CLOSE | FmtSpan::ENTER);
+        .with_span_events(span_events);
     let filter_layer = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("info"))
+        .or_else(|_| EnvFilter::try_new(level))
         .unwrap();
+    let round_layer = RoundCountLayer::new();
 
     tracing_subscriber::registry()
-        .with(filter_layer)
-        .with(fmt_layer)
+        .with(fmt_layer.with_filter(filter_layer))
+        .with(round_layer.clone().with_filter(RoundCountLayer::filter()))
         .init();
+    round_layer
 }
 
 #[derive(Parser)]
@@ -80,31 +145,67 @@ fn install_tracing() {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Only log errors. Ignored if RUST_LOG is set
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Log debug messages, or trace messages and all span events if given twice. Ignored if RUST_LOG is set
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Splits an existing witness file generated by circom into secret shares for use in MPC
     SplitWitness(SplitWitnessCli),
+    /// INSECURE, lab use only: splits a witness file into trivial shares that reveal the witness, as fast inputs for benchmarks of the provers at various party counts
+    BenchSplit(BenchSplitCli),
     /// Splits a JSON input file into secret shares for use in MPC
     SplitInput(SplitInputCli),
+    /// Contributes to the seeds of our input share while party 0 splits an input with --joint-seed
+    ContributeSeed(ContributeSeedCli),
     /// Merge multiple shared inputs received from multiple parties into a single one
     MergeInputShares(MergeInputSharesCli),
     /// Evaluates the extended witness generation for the specified circuit and input share in MPC
     GenerateWitness(GenerateWitnessCli),
     /// Translates the witness generated with one MPC protocol to a witness for a different one
     TranslateWitness(TranslateWitnessCli),
+    /// Jointly reshares a witness under a different MPC protocol and number of parties, without reconstructing it
+    Reshare(ReshareCli),
     /// Evaluates the prover algorithm for the specified circuit and witness share in MPC
     GenerateProof(GenerateProofCli),
+    /// Evaluates the witness extension and then the prover in MPC over the same connections, without writing the witness share
+    ProveFromInput(ProveFromInputCli),
     /// Verification of a circom proof.
     Verify(VerifyCli),
+    /// Packs a proof, its verification key and its public inputs into a single JSON file
+    Bundle(BundleCli),
+    /// Aggregates Groth16 proofs for the same verification key, so they can be verified with a single multi-pairing
+    Aggregate(AggregateCli),
+    /// Verification of an aggregated Groth16 proof created with `aggregate`
+    VerifyAggregated(VerifyAggregatedCli),
+    /// Converts a public input JSON file between decimal and hexadecimal field elements
+    ConvertPublicInputs(ConvertPublicInputsCli),
+    /// Extracts the verification key from a zkey and writes it in the snarkjs JSON format
+    ExportVk(ExportVkCli),
+    /// Extracts the public inputs of a proof from a plain witness file and writes them in the JSON format of `verify`
+    ExtractPublicInputs(ExtractPublicInputsCli),
+    /// Prints the public signals of a circuit with their positions in the public inputs passed to the verifier
+    PublicSignals(PublicSignalsCli),
+    /// Prints a fingerprint of a verification key (or of the verification key of a zkey), which does not depend on the formatting of the JSON file
+    VkFingerprint(VkFingerprintCli),
+    /// Compares two public input JSON files element by element and reports the first difference
+    DiffPublicInputs(DiffPublicInputsCli),
+    /// Estimates the proof size and the verification cost for a zkey without generating a proof
+    Estimate(EstimateCli),
+    /// Rerandomizes a Groth16 proof into one or more proofs for the same statement that can not be linked to the original
+    RerandomizeProof(RerandomizeProofCli),
 }
 
 fn main() -> color_eyre::Result<ExitCode> {
-    install_tracing();
     let args = Cli::parse();
+    let round_layer = install_tracing(args.quiet, args.verbose);
 
-    match args.command {
+    let result = match args.command {
         Commands::SplitWitness(cli) => {
             let config = SplitWitnessConfig::parse(cli).context("while parsing config")?;
             match config.curve {
@@ -112,6 +213,13 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_split_witness::<Bls12_381>(config),
             }
         }
+        Commands::BenchSplit(cli) => {
+            let config = BenchSplitConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_bench_split::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_bench_split::<Bls12_381>(config),
+            }
+        }
         Commands::SplitInput(cli) => {
             let config = SplitInputConfig::parse(cli).context("while parsing config")?;
             match config.curve {
@@ -119,6 +227,10 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_split_input::<Bls12_381>(config),
             }
         }
+        Commands::ContributeSeed(cli) => {
+            let config = ContributeSeedConfig::parse(cli).context("while parsing config")?;
+            run_contribute_seed(config)
+        }
         Commands::MergeInputShares(cli) => {
             let config = MergeInputSharesConfig::parse(cli).context("while parsing config")?;
             match config.curve {
@@ -140,6 +252,13 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_translate_witness::<Bls12_381>(config),
             }
         }
+        Commands::Reshare(cli) => {
+            let config = ReshareConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_reshare::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_reshare::<Bls12_381>(config),
+            }
+        }
         Commands::GenerateProof(cli) => {
             let config = GenerateProofConfig::parse(cli).context("while parsing config")?;
             match config.curve {
@@ -147,14 +266,107 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_generate_proof::<Bls12_381>(config),
             }
         }
+        Commands::ProveFromInput(cli) => {
+            let config = ProveFromInputConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_prove_from_input::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_prove_from_input::<Bls12_381>(config),
+            }
+        }
         Commands::Verify(cli) => {
-            let config = VerifyConfig::parse(cli).context("while parsing config")?;
+            if let Some(bundle) = cli.bundle {
+                let structural_only = cli.structural_only;
+                skip_subgroup_checks_if(cli.skip_subgroup_check, || {
+                    run_verify_bundle(bundle, structural_only)
+                })
+            } else {
+                let config = VerifyConfig::parse(cli).context("while parsing config")?;
+                skip_subgroup_checks_if(config.skip_subgroup_check, || match config.curve {
+                    MPCCurve::BN254 => run_verify::<Bn254>(config),
+                    MPCCurve::BLS12_381 => run_verify::<Bls12_381>(config),
+                })
+            }
+        }
+        Commands::Bundle(cli) => {
+            let config = BundleConfig::parse(cli).context("while parsing config")?;
             match config.curve {
-                MPCCurve::BN254 => run_verify::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_verify::<Bls12_381>(config),
+                MPCCurve::BN254 => run_bundle::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_bundle::<Bls12_381>(config),
             }
         }
-    }
+        Commands::Aggregate(cli) => {
+            let config = AggregateConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_aggregate::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_aggregate::<Bls12_381>(config),
+            }
+        }
+        Commands::VerifyAggregated(cli) => {
+            let config = VerifyAggregatedConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_verify_aggregated::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_verify_aggregated::<Bls12_381>(config),
+            }
+        }
+        Commands::ConvertPublicInputs(cli) => {
+            let config = ConvertPublicInputsConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_convert_public_inputs::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_convert_public_inputs::<Bls12_381>(config),
+            }
+        }
+        Commands::ExportVk(cli) => {
+            let config = ExportVkConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_export_vk::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_export_vk::<Bls12_381>(config),
+            }
+        }
+        Commands::ExtractPublicInputs(cli) => {
+            let config = ExtractPublicInputsConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_extract_public_inputs::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_extract_public_inputs::<Bls12_381>(config),
+            }
+        }
+        Commands::PublicSignals(cli) => {
+            let config = PublicSignalsConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_public_signals::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_public_signals::<Bls12_381>(config),
+            }
+        }
+        Commands::VkFingerprint(cli) => {
+            let config = VkFingerprintConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_vk_fingerprint::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_vk_fingerprint::<Bls12_381>(config),
+            }
+        }
+        Commands::DiffPublicInputs(cli) => {
+            let config = DiffPublicInputsConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_diff_public_inputs::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_diff_public_inputs::<Bls12_381>(config),
+            }
+        }
+        Commands::Estimate(cli) => {
+            let config = EstimateConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_estimate::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_estimate::<Bls12_381>(config),
+            }
+        }
+        Commands::RerandomizeProof(cli) => {
+            let config = RerandomizeProofConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_rerandomize_proof::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_rerandomize_proof::<Bls12_381>(config),
+            }
+        }
+    };
+    round_layer.report();
+    result
 }
 
 #[instrument(level = "debug", skip(config))]
@@ -175,6 +387,9 @@ where
     file_utils::check_file_exists(&witness_path)?;
     file_utils::check_file_exists(&r1cs)?;
     file_utils::check_dir_exists(&out_dir)?;
+    if config.max_part_size == Some(0) {
+        return Err(eyre!("the maximum part size must be at least one byte"));
+    }
 
     // read the circom witness file
     let witness_file =
@@ -185,8 +400,10 @@ where
     // read the circom r1cs file
     let r1cs_file = BufReader::new(File::open(&r1cs).context("while opening r1cs file")?);
     let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+    check_witness_matches_r1cs(&witness, &r1cs)?;
 
-    let mut rng = rand::thread_rng();
+    let mut rng = ShareRng::new(config.rng, config.rng_seed)?;
+    let mut written = Vec::with_capacity(n);
 
     match protocol {
         MPCProtocol::REP3 => {
@@ -196,7 +413,12 @@ where
             if n != 3 {
                 return Err(eyre!("REP3 only allows the number of parties to be 3"));
             }
+            if config.party_ids.is_some() {
+                return Err(eyre!("REP3 does not support explicit party ids"));
+            }
+            let paths = share_paths(&out_dir, &witness_path, &[0, 1, 2], config.out_names)?;
             // create witness shares
+            let plain = config.verify_split.then(|| witness.clone());
             let start = Instant::now();
             let shares = SerializeableSharedRep3Witness::<_, SeedRng>::share_rep3(
                 witness,
@@ -207,56 +429,291 @@ where
             );
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!("Sharing took {} ms", duration_ms);
+            if let Some(plain) = plain {
+                verify_rep3_split(&shares, &plain)?;
+            }
 
             // write out the shares to the output directory
-            let base_name = witness_path
-                .file_name()
-                .context("we have a file name")?
-                .to_str()
-                .context("witness file name is not valid UTF-8")?;
-            for (i, share) in shares.iter().enumerate() {
-                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-                let out_file =
-                    BufWriter::new(File::create(&path).context("while creating output file")?);
-                bincode::serialize_into(out_file, share)
-                    .context("while serializing witness share")?;
-                tracing::info!("Wrote witness share {} to file {}", i, path.display());
+            for ((i, share), path) in shares.iter().enumerate().zip(paths) {
+                for path in write_witness_share(&path, share, config.curve, config.max_part_size)? {
+                    tracing::info!("Wrote witness share {} to file {}", i, path.display());
+                    written.push((i, path));
+                }
             }
         }
         MPCProtocol::SHAMIR => {
+            let party_ids = config.party_ids.unwrap_or_else(|| (0..n).collect());
+            if party_ids.len() != n {
+                return Err(eyre!(
+                    "Got {} party ids, but the number of parties is {}",
+                    party_ids.len(),
+                    n
+                ));
+            }
+            check_shamir_threshold(t, n)?;
+            if 2 * t + 1 > n {
+                tracing::warn!(
+                    "The shares can be reconstructed, but proving with threshold {t} needs at least {} parties",
+                    2 * t + 1
+                );
+            }
+            let paths = share_paths(&out_dir, &witness_path, &party_ids, config.out_names)?;
             // create witness shares
+            let plain = config.verify_split.then(|| witness.clone());
             let start = Instant::now();
-            let shares =
-                SharedWitness::<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>::share_shamir(
-                    witness,
-                    r1cs.num_inputs,
-                    t,
-                    n,
-                    &mut rng,
-                );
+            let shares = SharedWitness::<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>::share_shamir_for_parties(
+                witness,
+                r1cs.num_inputs,
+                t,
+                &party_ids,
+                &mut rng,
+            )
+            .context("while sharing witness")?;
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!("Sharing took {} ms", duration_ms);
+            if let Some(plain) = plain {
+                verify_shamir_split(&shares, &party_ids, t, &plain)?;
+            }
 
             // write out the shares to the output directory
-            let base_name = witness_path
-                .file_name()
-                .context("we have a file name")?
-                .to_str()
-                .context("witness file name is not valid UTF-8")?;
-            for (i, share) in shares.iter().enumerate() {
-                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-                let out_file =
-                    BufWriter::new(File::create(&path).context("while creating output file")?);
-                bincode::serialize_into(out_file, share)
-                    .context("while serializing witness share")?;
-                tracing::info!("Wrote witness share {} to file {}", i, path.display());
+            for ((&i, share), path) in party_ids.iter().zip(shares.iter()).zip(paths) {
+                for path in write_witness_share(&path, share, config.curve, config.max_part_size)? {
+                    tracing::info!("Wrote witness share {} to file {}", i, path.display());
+                    written.push((i, path));
+                }
             }
         }
     }
+
+    if let Some(manifest) = config.manifest {
+        let shares = written
+            .into_iter()
+            .map(|(party_id, path)| {
+                let size = std::fs::metadata(&path)
+                    .context("while reading size of witness share")?
+                    .len();
+                Ok(WitnessShareEntry {
+                    party_id,
+                    path,
+                    size,
+                })
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        let manifest_file =
+            BufWriter::new(File::create(&manifest).context("while creating manifest file")?);
+        serde_json::to_writer_pretty(
+            manifest_file,
+            &SplitWitnessManifest {
+                protocol,
+                curve: config.curve,
+                threshold: t,
+                shares,
+            },
+        )
+        .context("while writing manifest")?;
+        tracing::info!("Wrote manifest to file {}", manifest.display());
+    }
     tracing::info!("Split witness into shares successfully");
     Ok(ExitCode::SUCCESS)
 }
 
+#[instrument(level = "debug", skip(config))]
+fn run_bench_split<P: Pairing + CircomArkworksPairingBridge>(
+    config: BenchSplitConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let witness_path = config.witness;
+    let out_dir = config.out_dir;
+    let n = config.num_parties;
+
+    file_utils::check_file_exists(&witness_path)?;
+    file_utils::check_file_exists(&config.r1cs)?;
+    file_utils::check_dir_exists(&out_dir)?;
+    tracing::warn!(
+        "bench-split writes INSECURE shares that reveal the witness, only use them for benchmarks"
+    );
+
+    let witness_file =
+        BufReader::new(File::open(&witness_path).context("while opening witness file")?);
+    let witness = Witness::<P::ScalarField>::from_reader(witness_file)
+        .context("while parsing witness file")?;
+    let r1cs_file = BufReader::new(File::open(&config.r1cs).context("while opening r1cs file")?);
+    let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+    check_witness_matches_r1cs(&witness, &r1cs)?;
+
+    // trivial shares need no randomness, so the shares of all parties are only copies of the witness
+    let start = Instant::now();
+    let paths = share_paths(&out_dir, &witness_path, &(0..n).collect::<Vec<_>>(), None)?;
+    match config.protocol {
+        MPCProtocol::REP3 => {
+            if n != 3 {
+                return Err(eyre!("REP3 only allows the number of parties to be 3"));
+            }
+            let shares = SharedWitness::from_plaintext_rep3(witness, r1cs.num_inputs);
+            for (share, path) in shares.into_iter().zip(paths) {
+                let share =
+                    SerializeableSharedRep3Witness::<_, SeedRng>::from_shared_witness(share);
+                write_witness_share(&path, &share, config.curve, None)?;
+                tracing::info!("Wrote witness share to file {}", path.display());
+            }
+        }
+        MPCProtocol::SHAMIR => {
+            if n == 0 {
+                return Err(eyre!("the number of parties must be at least 1"));
+            }
+            // the same share is valid for every party and every threshold
+            let share = SharedWitness::<_, ShamirPrimeFieldShare<_>>::from_plaintext_shamir(
+                witness,
+                r1cs.num_inputs,
+                1,
+            )
+            .remove(0);
+            let mut bytes = vec![];
+            co_circom::write_witness_share_header(&mut bytes, config.curve)?;
+            bincode::serialize_into(&mut bytes, &share)
+                .context("while serializing witness share")?;
+            for path in paths {
+                std::fs::write(&path, &bytes).context("while writing witness share")?;
+                tracing::info!("Wrote witness share to file {}", path.display());
+            }
+        }
+    }
+    tracing::info!(
+        "Split witness into {n} trivial shares in {} ms",
+        start.elapsed().as_micros() as f64 / 1000.
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the paths of the share files of the parties `ids` in `out_dir`. The files are named `<source>.<id>.shared` after the file name of `source`, unless `out_names` gives one file name per party.
+fn share_paths(
+    out_dir: &Path,
+    source: &Path,
+    ids: &[usize],
+    out_names: Option<Vec<String>>,
+) -> color_eyre::Result<Vec<PathBuf>> {
+    let Some(out_names) = out_names else {
+        let base_name = source
+            .file_name()
+            .context("we have a file name")?
+            .to_str()
+            .context("file name is not valid UTF-8")?;
+        return Ok(ids
+            .iter()
+            .map(|i| out_dir.join(format!("{}.{}.shared", base_name, i)))
+            .collect());
+    };
+    if out_names.len() != ids.len() {
+        return Err(eyre!(
+            "Got {} output file names, but {} shares are written",
+            out_names.len(),
+            ids.len()
+        ));
+    }
+    let mut seen = HashSet::with_capacity(out_names.len());
+    for name in out_names.iter() {
+        if name.is_empty() {
+            return Err(eyre!("output file names must not be empty"));
+        }
+        if !seen.insert(name) {
+            return Err(eyre!("output file name {name} is given more than once"));
+        }
+    }
+    Ok(out_names.iter().map(|name| out_dir.join(name)).collect())
+}
+
+/// Writes a witness share for `curve` to `path`, or split into parts of at most `max_part_size` bytes. Returns the written files.
+fn write_witness_share<S: serde::Serialize>(
+    path: &Path,
+    share: &S,
+    curve: MPCCurve,
+    max_part_size: Option<usize>,
+) -> color_eyre::Result<Vec<PathBuf>> {
+    let mut bytes = vec![];
+    co_circom::write_witness_share_header(&mut bytes, curve)?;
+    bincode::serialize_into(&mut bytes, share).context("while serializing witness share")?;
+    file_utils::write_in_parts(path, &bytes, max_part_size).context("while writing witness share")
+}
+
+/// Reconstructs the witness from freshly created REP3 shares and checks that it matches the shared witness.
+fn verify_rep3_split<F: PrimeField>(
+    shares: &[SerializeableSharedRep3Witness<F, SeedRng>; 3],
+    witness: &Witness<F>,
+) -> color_eyre::Result<()> {
+    let opened = if shares.iter().all(|share| {
+        matches!(
+            share.witness,
+            Rep3ShareVecType::Replicated(_) | Rep3ShareVecType::SeededReplicated(_)
+        )
+    }) {
+        let shares = shares
+            .iter()
+            .map(|share| {
+                let witness = match share.witness.clone() {
+                    Rep3ShareVecType::Replicated(vec) => vec,
+                    Rep3ShareVecType::SeededReplicated(seeded) => seeded.expand_vec()?,
+                    _ => unreachable!("checked above"),
+                };
+                Ok(SharedWitness {
+                    public_inputs: share.public_inputs.clone(),
+                    witness,
+                })
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        // this also checks that the replicated shares are consistent
+        SharedWitness::<F, rep3::Rep3PrimeFieldShare<F>>::open_from_shares(&shares)
+            .context("while reconstructing the witness from the shares")?
+    } else {
+        if shares
+            .iter()
+            .any(|share| share.public_inputs != shares[0].public_inputs)
+        {
+            return Err(eyre!("the witness shares disagree on the public inputs"));
+        }
+        let additive = shares
+            .iter()
+            .map(|share| rep3::rep3_to_additive(share.witness.clone()))
+            .collect::<Vec<_>>();
+        let mut values = shares[0].public_inputs.clone();
+        values.extend(
+            additive[0]
+                .iter()
+                .zip(&additive[1])
+                .zip(&additive[2])
+                .map(|((a, b), c)| *a + b + c),
+        );
+        Witness { values }
+    };
+    if opened != *witness {
+        return Err(eyre!(
+            "the witness shares do not reconstruct the witness, no shares were written"
+        ));
+    }
+    tracing::info!("Verified that the shares reconstruct the witness");
+    Ok(())
+}
+
+/// Reconstructs the witness from freshly created Shamir shares for the parties `party_ids` and checks that it matches the shared witness.
+fn verify_shamir_split<F: PrimeField>(
+    shares: &[SharedWitness<F, ShamirPrimeFieldShare<F>>],
+    party_ids: &[usize],
+    threshold: usize,
+    witness: &Witness<F>,
+) -> color_eyre::Result<()> {
+    let opened = SharedWitness::open_from_party_shares(shares, party_ids, threshold)
+        .context("while reconstructing the witness from the shares")?;
+    if opened.values != witness.values {
+        return Err(eyre!(
+            "the witness shares do not reconstruct the witness, no shares were written"
+        ));
+    }
+    tracing::info!("Verified that the shares reconstruct the witness");
+    Ok(())
+}
+
 #[instrument(level = "debug", skip(config))]
 fn run_split_input<P: Pairing + CircomArkworksPairingBridge>(
     config: SplitInputConfig,
@@ -268,7 +725,7 @@ where
     let input = config.input;
     let circuit = config.circuit;
     let protocol = config.protocol;
-    let out_dir = config.out_dir;
+    let merge_into = config.merge_into.unwrap_or_default();
 
     if protocol != MPCProtocol::REP3 {
         return Err(eyre!(
@@ -278,17 +735,77 @@ where
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
-    file_utils::check_dir_exists(&out_dir)?;
+    let out_dir = if merge_into.is_empty() {
+        let out_dir = config
+            .out_dir
+            .ok_or_else(|| eyre!("pass --out-dir or --merge-into"))?;
+        file_utils::check_dir_exists(&out_dir)?;
+        out_dir
+    } else {
+        for path in merge_into.iter() {
+            file_utils::check_file_exists(path)?;
+        }
+        PathBuf::new()
+    };
+    let group = config.group.unwrap_or_default();
+    if !group.is_empty() && !merge_into.is_empty() {
+        return Err(eyre!("--group cannot be combined with --merge-into"));
+    }
+    if config.out_names.is_some() && (!group.is_empty() || !merge_into.is_empty()) {
+        return Err(eyre!(
+            "--out-names cannot be combined with --group or --merge-into"
+        ));
+    }
+    let paths = share_paths(&out_dir, &input, &[0, 1, 2], config.out_names)?;
+    if !group.is_empty() {
+        if group.len() != 2 || group[0] == group[1] {
+            return Err(eyre!(
+                "a group must consist of two different parties, got {group:?}"
+            ));
+        }
+        if let Some(id) = group.iter().find(|&&id| id >= 3) {
+            return Err(eyre!(
+                "invalid party id {id} in group, REP3 has parties 0, 1 and 2"
+            ));
+        }
+    }
+
+    let joint_seed_network = if config.joint_seed {
+        if !config.seeded || !config.additive {
+            return Err(eyre!("--joint-seed requires --seeded and --additive"));
+        }
+        let network = config.network.ok_or_else(|| {
+            eyre!("--joint-seed requires the network config of party 0 in the config file")
+        })?;
+        if network.my_id != 0 {
+            return Err(eyre!(
+                "with --joint-seed the input must be split by party 0, which keeps the explicit share, but the network config is for party {}",
+                network.my_id
+            ));
+        }
+        check_network_config(&network)?;
+        Some(network)
+    } else {
+        None
+    };
 
-    //get the public inputs if any from parser
-    let public_inputs = CoCircomCompiler::<P>::get_public_inputs(circuit, config.compiler)
-        .context("while reading public inputs from circuit")?;
+    // get the public inputs if any and the number of elements of every input signal, to catch wrongly sized arrays early
+    let (public_inputs, input_signals) =
+        CoCircomCompiler::<P>::get_inputs(circuit, config.compiler)
+            .context("while reading inputs from circuit")?;
+    let input_sizes = input_signals.into_iter().collect::<HashMap<_, _>>();
 
     // read the input file
     let input_file = BufReader::new(File::open(&input).context("while opening input file")?);
 
     let input_json: serde_json::Map<String, serde_json::Value> =
         serde_json::from_reader(input_file).context("while parsing input file")?;
+    let input_json = flatten_input_objects(input_json);
+    for name in config.scales.keys() {
+        if !input_json.contains_key(name) {
+            tracing::warn!("scale given for signal \"{name}\", which is not in the input");
+        }
+    }
 
     // create input shares
     let mut shares = [
@@ -297,14 +814,43 @@ where
         SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
     ];
 
-    let mut rng = rand::thread_rng();
+    let mut rng = ShareRng::new(config.rng, config.rng_seed)?;
+    // the seeds of the individual inputs are drawn from the tossed seeds, so that parties 1 and 2 can not be handed seeds of our choice
+    let mut mask_rngs = match joint_seed_network {
+        Some(network) => {
+            let mut net = Rep3MpcNet::new(network).context("while connecting to network")?;
+            let tosses = joint_seed::toss_mask_seeds(&mut net, &mut rng)
+                .context("while tossing the seeds with the other parties")?;
+            tracing::info!("Tossed the seeds of the input shares with parties 1 and 2");
+            for toss in tosses.iter() {
+                tracing::info!(
+                    "Seed fingerprint of party {}: {}",
+                    toss.party,
+                    toss.seed_fingerprint()
+                );
+            }
+            Some(tosses.map(|toss| SeedRng::from_seed(toss.seed)))
+        }
+        None => None,
+    };
     let start = Instant::now();
     for (name, val) in input_json {
-        let parsed_vals = if val.is_array() {
-            parse_array(&val)?
+        let parsed_vals = if let Some(scale) = config.scales.get(&name) {
+            parse_scaled(&val, *scale)
+                .with_context(|| format!("while applying scale {scale} to signal \"{name}\""))?
+        } else if val.is_array() {
+            parse_array(&val, config.input_montgomery)?
         } else {
-            vec![parse_field(&val)?]
+            vec![parse_field(&val, config.input_montgomery)?]
         };
+        if let Some(&size) = input_sizes.get(&name) {
+            if parsed_vals.len() != size {
+                return Err(eyre!(
+                    "signal `{name}` expects {size} elements, got {}",
+                    parsed_vals.len()
+                ));
+            }
+        }
         if public_inputs.contains(&name) {
             shares[0]
                 .public_inputs
@@ -314,12 +860,26 @@ where
                 .insert(name.clone(), parsed_vals.clone());
             shares[2].public_inputs.insert(name.clone(), parsed_vals);
         } else {
-            let [share0, share1, share2] = SerializeableSharedRep3Input::share_rep3(
-                &parsed_vals,
-                &mut rng,
-                config.seeded,
-                config.additive,
-            );
+            let [share0, share1, share2] = match mask_rngs.as_mut() {
+                Some([rng1, rng2]) => SerializeableSharedRep3Input::share_rep3_additive_from_seeds(
+                    &parsed_vals,
+                    rng1.gen(),
+                    rng2.gen(),
+                ),
+                // arrays can get large (e.g., images), so we share them in parallel
+                None if val.is_array() => SerializeableSharedRep3Input::share_rep3_par(
+                    &parsed_vals,
+                    &mut rng,
+                    config.seeded,
+                    config.additive,
+                ),
+                None => SerializeableSharedRep3Input::share_rep3(
+                    &parsed_vals,
+                    &mut rng,
+                    config.seeded,
+                    config.additive,
+                ),
+            };
             shares[0].shared_inputs.insert(name.clone(), share0);
             shares[1].shared_inputs.insert(name.clone(), share1);
             shares[2].shared_inputs.insert(name.clone(), share2);
@@ -328,22 +888,155 @@ where
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Sharing took {} ms", duration_ms);
 
+    if !merge_into.is_empty() {
+        merge_into_input_shares::<P>(shares, &merge_into)?;
+        tracing::info!("Split input and merged it into the existing shares successfully");
+        return Ok(ExitCode::SUCCESS);
+    }
+
     // write out the shares to the output directory
     let base_name = input
         .file_name()
         .context("we have a file name")?
         .to_str()
         .context("input file name is not valid UTF-8")?;
-    for (i, share) in shares.iter().enumerate() {
-        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+    for ((i, share), path) in shares.iter().enumerate().zip(paths) {
+        if group.contains(&i) {
+            continue;
+        }
         let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
         bincode::serialize_into(out_file, share).context("while serializing witness share")?;
         tracing::info!("Wrote input share {} to file {}", i, path.display());
     }
+    if !group.is_empty() {
+        let path = out_dir.join(format!("{}.{}_{}.shared", base_name, group[0], group[1]));
+        let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
+        let grouped = group
+            .iter()
+            .map(|&i| (i, &shares[i]))
+            .collect::<BTreeMap<_, _>>();
+        co_circom::write_grouped_input_shares(out_file, &grouped)?;
+        tracing::info!("Wrote input shares {:?} to file {}", group, path.display());
+        tracing::warn!(
+            "{} holds the shares of two parties, whoever holds it can reconstruct the private inputs",
+            path.display()
+        );
+    }
     tracing::info!("Split input into shares successfully");
     Ok(ExitCode::SUCCESS)
 }
 
+/// Merges freshly split input shares into the existing share files of the three parties, overwriting them.
+/// The files may also hold the grouped shares of several parties, see [assign_input_share_files].
+/// Nothing is written unless the new shares could be merged into all three files.
+fn merge_into_input_shares<P: Pairing>(
+    shares: [SerializeableSharedRep3Input<P::ScalarField, SeedRng>; 3],
+    paths: &[PathBuf],
+) -> color_eyre::Result<()> {
+    let mut files = paths
+        .iter()
+        .map(|path| {
+            let existing_file =
+                BufReader::new(File::open(path).context("while opening existing input share")?);
+            InputShareFile::<P::ScalarField>::from_reader(existing_file)
+                .with_context(|| format!("while deserializing input share {}", path.display()))
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    let owners = assign_input_share_files(&files, paths)?;
+    for (id, mut share) in shares.into_iter().enumerate() {
+        let path = &paths[owners[id]];
+        let existing = match &mut files[owners[id]] {
+            InputShareFile::Single(existing) => existing,
+            InputShareFile::Grouped(existing) => existing
+                .get_mut(&id)
+                .expect("assign_input_share_files only assigns contained parties"),
+        };
+        // the new input does not need to repeat the public inputs of the existing shares
+        for (name, value) in existing.public_inputs.iter() {
+            share
+                .public_inputs
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        *existing = std::mem::take(existing)
+            .merge(share)
+            .with_context(|| format!("while merging into input share {}", path.display()))?;
+    }
+    for (file, path) in files.iter().zip(paths) {
+        let out_file = BufWriter::new(File::create(path).context("while creating output file")?);
+        match file {
+            InputShareFile::Single(share) => {
+                bincode::serialize_into(out_file, share).context("while serializing input share")?
+            }
+            InputShareFile::Grouped(shares) => co_circom::write_grouped_input_shares(
+                out_file,
+                &shares.iter().map(|(id, share)| (*id, share)).collect(),
+            )?,
+        }
+        tracing::info!("Merged input share into file {}", path.display());
+    }
+    Ok(())
+}
+
+/// Returns for parties 0, 1 and 2 the index of the file in `--merge-into` that holds its input share.
+/// A grouped file holds the shares of the parties it contains, the single files hold the shares of the remaining parties in order.
+fn assign_input_share_files<F: PrimeField>(
+    files: &[InputShareFile<F>],
+    paths: &[PathBuf],
+) -> color_eyre::Result<[usize; 3]> {
+    let mut owners = [None; 3];
+    for (i, (file, path)) in files.iter().zip(paths).enumerate() {
+        if let InputShareFile::Grouped(shares) = file {
+            for &id in shares.keys() {
+                match owners.get_mut(id) {
+                    Some(owner @ None) => *owner = Some(i),
+                    Some(Some(_)) => {
+                        return Err(eyre!(
+                            "the input share of party {id} is in more than one file of --merge-into"
+                        ))
+                    }
+                    None => {
+                        return Err(eyre!(
+                            "{} contains the input share of party {id}, but REP3 has parties 0, 1 and 2",
+                            path.display()
+                        ))
+                    }
+                }
+            }
+        }
+    }
+    let mut singles = files
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| matches!(file, InputShareFile::Single(_)))
+        .map(|(i, _)| i);
+    for owner in owners.iter_mut().filter(|owner| owner.is_none()) {
+        *owner = singles.next();
+    }
+    match owners {
+        [Some(a), Some(b), Some(c)] if singles.next().is_none() => Ok([a, b, c]),
+        _ => Err(eyre!(
+            "--merge-into expects the input shares of parties 0, 1 and 2 exactly once, in single or grouped files"
+        )),
+    }
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_contribute_seed(config: ContributeSeedConfig) -> color_eyre::Result<ExitCode> {
+    if config.network.my_id == 0 {
+        return Err(eyre!(
+            "party 0 splits the input with --joint-seed and does not contribute a seed"
+        ));
+    }
+    check_network_config(&config.network)?;
+    let mut net = Rep3MpcNet::new(config.network).context("while connecting to network")?;
+    let toss = joint_seed::contribute_mask_seed(&mut net, &mut rand::thread_rng())
+        .context("while tossing the seed with party 0")?;
+    tracing::info!("Tossed the seed of our input share with party 0");
+    tracing::info!("Seed fingerprint: {}", toss.seed_fingerprint());
+    Ok(ExitCode::SUCCESS)
+}
+
 #[instrument(level = "debug", skip(config))]
 fn run_merge_input_shares<P: Pairing + CircomArkworksPairingBridge>(
     config: MergeInputSharesConfig,
@@ -368,8 +1061,19 @@ where
     for input in &inputs {
         file_utils::check_file_exists(input)?;
     }
+    let party = check_input_share_party(&inputs, config.party)?;
+    // the input signals of the circuit, if the merged share should be checked against them
+    let signals = if let Some(circuit) = config.circuit {
+        file_utils::check_file_exists(&PathBuf::from(&circuit))?;
+        let signals = CoCircomCompiler::<P>::get_input_signals(circuit, config.compiler)
+            .context("while reading input signals from circuit")?;
+        Some(signals)
+    } else {
+        None
+    };
+    tracing::info!("Merging {} input shares of party {party}", inputs.len());
 
-    merge_input_shares::<P::ScalarField>(inputs, out)?;
+    merge_input_shares::<P::ScalarField>(inputs, party, out, signals.as_deref(), config.additive)?;
 
     Ok(ExitCode::SUCCESS)
 }
@@ -385,6 +1089,7 @@ where
     let input = config.input.clone();
     let circuit = config.circuit.clone();
     let protocol = config.protocol;
+    let curve = config.curve;
     let out = config.out.clone();
 
     if protocol != MPCProtocol::REP3 {
@@ -392,31 +1097,70 @@ where
             "Only REP3 protocol is supported for merging input shares"
         ));
     }
-    file_utils::check_file_exists(&input)?;
+    if input.is_empty() {
+        return Err(eyre!("Need at least one input share"));
+    }
+    for input in &input {
+        file_utils::check_file_exists(input)?;
+    }
+    if input.len() > 1 {
+        tracing::info!("Merging {} input shares", input.len());
+    }
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
+    if config.plaintext_out.is_some() {
+        co_circom::check_single_host_network(&config.network)
+            .context("refusing to write the plaintext witness")?;
+        tracing::warn!(
+            "--plaintext-out opens the whole witness to all parties, this is INSECURE and only meant for debugging"
+        );
+    }
+
+    if config.dry_run {
+        return dry_run_generate_witness::<P>(config);
+    }
 
     // connect to network
     let mut mpc_net =
         Rep3MpcNet::new(config.network.to_owned()).context("while connecting to network")?;
 
     // parse input shares
-    let input_share_file =
-        BufReader::new(File::open(&input).context("while opening input share file")?);
-    let input_share = co_circom::parse_shared_input(input_share_file, &mut mpc_net)
-        .context("while parsing input")?;
+    let input_share =
+        co_circom::parse_shared_inputs(&input, &mut mpc_net).context("while parsing input")?;
 
     // Extend the witness
     let result_witness_share =
         co_circom::generate_witness_rep3::<P, SeedRng>(circuit, input_share, mpc_net, config)?;
 
     // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
+    let mut out_file = BufWriter::new(std::fs::File::create(&out)?);
+    co_circom::write_witness_share_header(&mut out_file, curve)?;
     bincode::serialize_into(out_file, &result_witness_share)?;
     tracing::info!("Witness successfully written to {}", out.display());
     Ok(ExitCode::SUCCESS)
 }
 
+#[instrument(level = "debug", skip(config))]
+fn dry_run_generate_witness<P: Pairing + CircomArkworksPairingBridge>(
+    config: GenerateWitnessConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    CoCircomCompiler::<P>::parse(config.circuit, config.compiler)
+        .context("while parsing circuit file")?;
+    co_circom::read_merged_input_shares::<P::ScalarField>(
+        &config.input,
+        config.network.my_id,
+        false,
+    )
+    .context("while parsing input")?;
+    check_network_config(&config.network)?;
+    tracing::info!("Dry run successful, config and files are valid");
+    Ok(ExitCode::SUCCESS)
+}
+
 #[instrument(level = "debug", skip(config))]
 fn run_translate_witness<P: Pairing + CircomArkworksPairingBridge>(
     config: TranslateWitnessConfig,
@@ -429,15 +1173,17 @@ where
     let src_protocol = config.src_protocol;
     let target_protocol = config.target_protocol;
     let out = config.out;
+    let self_check = config.self_check;
 
     if src_protocol != MPCProtocol::REP3 || target_protocol != MPCProtocol::SHAMIR {
         return Err(eyre!("Only REP3 to SHAMIR translation is supported"));
     }
-    file_utils::check_file_exists(&witness)?;
+    file_utils::check_file_or_parts_exist(&witness)?;
 
     // parse witness shares
     let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+        file_utils::open_file_or_parts(&witness).context("trying to open witness share file")?;
+    let witness_file = co_circom::WitnessShareReader::new(witness_file)?;
     let witness_share: SharedWitness<P::ScalarField, P::ScalarField> =
         co_circom::parse_witness_share_rep3_as_additive(witness_file)?;
 
@@ -447,15 +1193,28 @@ where
 
     // init MPC protocol
     let threshold = 1;
-    let num_pairs = witness_share.witness.len();
+    // the self-check needs one pair for the challenge
+    let num_pairs = witness_share.witness.len() + usize::from(self_check);
     let preprocessing = ShamirPreprocessing::new(threshold, net.to_shamir_net(), num_pairs)
         .context("while shamir preprocessing")?;
     let mut protocol = ShamirProtocol::from(preprocessing);
     // Translate witness to shamir shares
     let start = Instant::now();
+    let source_witness = self_check.then(|| witness_share.witness.clone());
     let translated_witness = protocol
         .translate_primefield_addshare_vec(witness_share.witness)
         .context("while translating witness")?;
+    if let Some(source_witness) = source_witness {
+        let consistent = protocol
+            .check_translated_primefield_addshare_vec(&source_witness, &translated_witness)
+            .context("while checking translated witness")?;
+        if !consistent {
+            return Err(eyre!(
+                "translated witness does not reconstruct to the source witness"
+            ));
+        }
+        tracing::info!("Party {}: Self-check of translated witness passed", id);
+    }
     let shamir_witness_share: SharedWitness<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>> =
         SharedWitness {
             public_inputs: witness_share.public_inputs,
@@ -465,67 +1224,272 @@ where
     tracing::info!("Party {}: Translating witness took {} ms", id, duration_ms);
 
     // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
+    let mut out_file = BufWriter::new(std::fs::File::create(&out)?);
+    co_circom::write_witness_share_header(&mut out_file, config.curve)?;
     bincode::serialize_into(out_file, &shamir_witness_share)?;
     tracing::info!("Witness successfully written to {}", out.display());
     Ok(ExitCode::SUCCESS)
 }
 
 #[instrument(level = "debug", skip(config))]
-fn run_generate_proof<P: Pairing + CircomArkworksPairingBridge>(
-    config: GenerateProofConfig,
+fn run_reshare<P: Pairing + CircomArkworksPairingBridge>(
+    config: ReshareConfig,
 ) -> color_eyre::Result<ExitCode>
 where
     P::ScalarField: CircomArkworksPrimeFieldBridge,
     P::BaseField: CircomArkworksPrimeFieldBridge,
 {
-    let proof_system = config.proof_system;
-    let witness = config.witness;
-    let zkey = config.zkey;
-    let protocol = config.protocol;
-    let out = config.out;
-    let public_input_filename = config.public_input;
-    let t = config.threshold;
-
-    file_utils::check_file_exists(&witness)?;
-    file_utils::check_file_exists(&zkey)?;
+    if config.src_protocol != MPCProtocol::REP3 {
+        return Err(eyre!("Only REP3 witness shares can be reshared"));
+    }
+    if config.target_protocol == MPCProtocol::REP3
+        && (config.threshold, config.num_parties) != (1, 3)
+    {
+        return Err(eyre!(
+            "REP3 only allows the threshold to be 1 and the number of parties to be 3"
+        ));
+    }
+    if config.target_protocol == MPCProtocol::SHAMIR {
+        check_shamir_threshold(config.threshold, config.num_parties)?;
+    }
 
-    // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+    // parse witness share, if we hold one
+    let witness_share = match &config.witness {
+        Some(witness) => {
+            file_utils::check_file_or_parts_exist(witness)?;
+            let witness_file = file_utils::open_file_or_parts(witness)
+                .context("trying to open witness share file")?;
+            let witness_file = co_circom::WitnessShareReader::new(witness_file)?;
+            Some(co_circom::parse_witness_share_rep3_as_additive::<
+                _,
+                P::ScalarField,
+            >(witness_file)?)
+        }
+        None => None,
+    };
 
-    // parse Circom zkey file
-    let zkey_file = File::open(zkey)?;
+    // connect to network
+    let mut net = ShamirMpcNet::new(config.network).context("while connecting to network")?;
+    let id = net.get_id();
+    let mut rng = ShareRng::new(config.rng, config.rng_seed)?;
 
-    let public_input = match proof_system {
-        ProofSystem::Groth16 => {
-            let zkey = Arc::new(Groth16ZKey::<P>::from_reader(zkey_file).context("reading zkey")?);
+    let start = Instant::now();
+    let bytes = match config.target_protocol {
+        MPCProtocol::REP3 => {
+            let witness_share =
+                witness_share.context("party holds a REP3 share and must provide its witness")?;
+            let reshared =
+                co_circom::reshare::reshare_rep3_to_rep3(&mut net, witness_share, &mut rng)
+                    .context("while resharing witness")?;
+            Some(bincode::serialize(&SerializeableSharedRep3Witness::<
+                _,
+                SeedRng,
+            >::from_shared_witness(
+                reshared
+            ))?)
+        }
+        MPCProtocol::SHAMIR => co_circom::reshare::reshare_rep3_to_shamir(
+            &mut net,
+            witness_share,
+            config.threshold,
+            config.num_parties,
+            &mut rng,
+        )
+        .context("while resharing witness")?
+        .map(|reshared| bincode::serialize(&reshared))
+        .transpose()?,
+    };
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Party {}: Resharing witness took {} ms", id, duration_ms);
+
+    // write result to output file
+    match (bytes, config.out) {
+        (Some(bytes), Some(out)) => {
+            let mut out_file =
+                BufWriter::new(File::create(&out).context("while creating output file")?);
+            co_circom::write_witness_share_header(&mut out_file, config.curve)?;
+            out_file
+                .write_all(&bytes)
+                .context("while writing witness share")?;
+            tracing::info!("Witness successfully written to {}", out.display());
+        }
+        (Some(_), None) => {
+            return Err(eyre!(
+                "party {id} got a share of the new sharing, but no output file was given"
+            ))
+        }
+        (None, _) => tracing::info!("Party {}: Not part of the new sharing", id),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Connects to the other parties of a REP3 network. The readiness endpoint reports the party as ready as soon as the other parties can connect to it.
+fn connect_rep3(
+    config: NetworkConfig,
+    readiness: Option<&ReadinessProbe>,
+) -> color_eyre::Result<Rep3MpcNet> {
+    Rep3MpcNet::new_with_listener(config, |_| {
+        if let Some(readiness) = readiness {
+            readiness.set_ready();
+        }
+    })
+    .context("while connecting to network")
+}
+
+/// Connects to the other parties of a Shamir network, see [connect_rep3].
+fn connect_shamir(
+    config: NetworkConfig,
+    readiness: Option<&ReadinessProbe>,
+) -> color_eyre::Result<ShamirMpcNet> {
+    ShamirMpcNet::new_with_listener(config, |_| {
+        if let Some(readiness) = readiness {
+            readiness.set_ready();
+        }
+    })
+    .context("while connecting to network")
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_generate_proof<P: Pairing + CircomArkworksPairingBridge>(
+    config: GenerateProofConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof_system = config.proof_system;
+    let witness = config.witness;
+    let zkey = config.zkey;
+    let protocol = config.protocol;
+    let out = config.out;
+    let public_input_filename = config.public_input;
+    let snarkjs_out = config.snarkjs_out;
+    let t = config.threshold;
+
+    file_utils::check_file_or_parts_exist(&witness)?;
+    if protocol == MPCProtocol::SHAMIR {
+        check_shamir_proving_threshold(t, config.network.parties.len())?;
+    }
+    let party_ids = match (protocol, config.party_ids) {
+        (MPCProtocol::REP3, Some(_)) => {
+            return Err(eyre!("REP3 does not support explicit party ids"))
+        }
+        (_, party_ids) => party_ids.unwrap_or_else(|| (0..config.network.parties.len()).collect()),
+    };
+    check_single_stdout_output(out.as_deref(), public_input_filename.as_deref())?;
+
+    set_prover_threads(config.threads)?;
+
+    if config.check_witness && protocol != MPCProtocol::REP3 {
+        return Err(eyre!("--check-witness is only supported for REP3"));
+    }
+    let check_witness_r1cs = match (config.check_witness, &config.r1cs) {
+        (true, None) => return Err(eyre!("--check-witness requires --r1cs")),
+        (true, Some(r1cs)) => Some(r1cs.clone()),
+        (false, _) => None,
+    };
+    let r1cs = match config.r1cs {
+        Some(r1cs) => {
+            file_utils::check_file_exists(&r1cs)?;
+            let r1cs_file = BufReader::new(File::open(&r1cs).context("while opening r1cs file")?);
+            let constraints =
+                R1CS::<P>::constraints_iter(r1cs_file).context("while parsing r1cs file")?;
+            Some(constraints.header().to_owned())
+        }
+        None => None,
+    };
+    // read the verification key before proving, so that a missing key does not waste a proof
+    let vk = match (config.verify, &config.vk) {
+        (true, None) => return Err(eyre!("--verify requires --vk")),
+        (true, Some(vk)) => Some(read_vk(vk)?),
+        (false, _) => None,
+    };
+
+    if config.dry_run {
+        return dry_run_generate_proof::<P>(
+            witness,
+            zkey,
+            r1cs,
+            config.curve,
+            protocol,
+            t,
+            proof_system,
+            config.network,
+        );
+    }
+
+    let readiness = config
+        .ready_addr
+        .map(ReadinessProbe::serve)
+        .transpose()
+        .context("while starting readiness endpoint")?;
+
+    // parse witness shares
+    let witness_file =
+        file_utils::open_file_or_parts(&witness).context("trying to open witness share file")?;
+    let witness_file = co_circom::WitnessShareReader::new(witness_file)?;
+    let witness_curve = witness_file.curve();
+    // fail before reading the zkey and connecting to the other parties if the share was created for another protocol
+    witness_file.expect_protocol(protocol)?;
+
+    // parse Circom zkey file
+    let zkey_file = file_utils::open_file_or_url(&zkey).context("while opening zkey")?;
 
+    let (public_input, verified) = match proof_system {
+        ProofSystem::Groth16 => {
+            let zkey = Arc::new(Groth16ZKey::<P>::from_reader(zkey_file).context("reading zkey")?);
+            check_witness_share_curve(witness_curve, config.curve)?;
+            if let Some(r1cs) = &r1cs {
+                check_groth16_zkey_matches_r1cs(&zkey, r1cs)?;
+            }
+            let zkey_vk = vk
+                .is_some()
+                .then(|| Groth16JsonVerificationKey::from_zkey(&zkey));
             let (proof, public_input) = match protocol {
                 MPCProtocol::REP3 => {
                     if t != 1 {
                         return Err(eyre!("REP3 only allows the threshold to be 1"));
                     }
 
-                    let mut mpc_net = Rep3MpcNet::new(config.network)?;
-                    let witness_share =
-                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
-                    let public_input = witness_share.public_inputs.clone();
+                    let mut mpc_net = connect_rep3(config.network, readiness.as_ref())?;
+                    let witness_share = if config.lazy_witness {
+                        co_circom::parse_witness_share_rep3_lazy(witness_file, &mut mpc_net)?
+                    } else {
+                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?
+                    };
+                    if let Some(r1cs) = &check_witness_r1cs {
+                        check_witness_rep3::<P>(&witness_share, r1cs, &mut mpc_net)?;
+                    }
+                    let public_input = witness_share
+                        .public_signals(zkey.num_public_inputs())?
+                        .to_vec();
                     // connect to network
-                    let prover =
-                        Rep3CoGroth16::with_network(mpc_net).context("while building prover")?;
+                    let prover = Rep3CoGroth16::with_network(mpc_net)
+                        .context("while building prover")?
+                        .with_progress(log_prover_progress);
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
                     (proof, public_input)
                 }
                 MPCProtocol::SHAMIR => {
-                    let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
-                    let public_input = witness_share.public_inputs.clone();
+                    let witness_share = if config.lazy_witness {
+                        co_circom::parse_witness_share_shamir_lazy(witness_file)?
+                    } else {
+                        co_circom::parse_witness_share_shamir(witness_file)?
+                    };
+                    let public_input = witness_share
+                        .public_signals(zkey.num_public_inputs())?
+                        .to_vec();
 
                     // connect to network
-                    let prover = ShamirCoGroth16::with_network_config(t, config.network)
-                        .context("while building prover")?;
+                    let prover = ShamirCoGroth16::with_network_for_parties(
+                        t,
+                        connect_shamir(config.network, readiness.as_ref())?,
+                        &party_ids,
+                    )
+                    .context("while building prover")?
+                    .with_progress(log_prover_progress);
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
@@ -534,48 +1498,82 @@ where
             };
 
             // write result to output file
-            if let Some(out) = out {
-                let out_file = BufWriter::new(
-                    std::fs::File::create(&out).context("while creating output file")?,
-                );
-
-                serde_json::to_writer(out_file, &proof)
-                    .context("while serializing proof to JSON file")?;
-                tracing::info!("Wrote proof to file {}", out.display());
+            if let Some(out) = &out {
+                write_proof(out, &proof, config.output_format, config.byte_order)?;
             }
-            public_input
+            if let Some(dir) = &snarkjs_out {
+                co_circom::snarkjs::write_dir(dir, &proof, &public_input)?;
+                tracing::info!("Wrote proof and public inputs to {}", dir.display());
+            }
+            let verified = vk.zip(zkey_vk).map(|(vk, zkey_vk)| {
+                verify_generated_proof::<P, _>(
+                    ProofSystem::Groth16,
+                    vk,
+                    &zkey_vk,
+                    |vk| vk.n_public,
+                    |vk| Ok(Groth16::<P>::verify(vk, &proof, &public_input)?),
+                )
+            });
+            (public_input, verified)
         }
         ProofSystem::Plonk => {
             let zkey =
                 Arc::new(PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?);
-
+            check_witness_share_curve(witness_curve, config.curve)?;
+            if let Some(r1cs) = &r1cs {
+                check_plonk_zkey_matches_r1cs(&zkey, r1cs)?;
+            }
+            let zkey_vk = vk
+                .is_some()
+                .then(|| PlonkJsonVerificationKey::from_zkey(&zkey));
             let (proof, public_input) = match protocol {
                 MPCProtocol::REP3 => {
                     if t != 1 {
                         return Err(eyre!("REP3 only allows the threshold to be 1"));
                     }
 
-                    let mut mpc_net = Rep3MpcNet::new(config.network)?;
-                    let witness_share =
-                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
+                    let mut mpc_net = connect_rep3(config.network, readiness.as_ref())?;
+                    let witness_share = if config.lazy_witness {
+                        co_circom::parse_witness_share_rep3_lazy(witness_file, &mut mpc_net)?
+                    } else {
+                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?
+                    };
+                    if let Some(r1cs) = &check_witness_r1cs {
+                        check_witness_rep3::<P>(&witness_share, r1cs, &mut mpc_net)?;
+                    }
 
-                    let public_input = witness_share.public_inputs.clone();
+                    let public_input = witness_share
+                        .public_signals(zkey.num_public_inputs())?
+                        .to_vec();
 
                     //init prover
-                    let prover =
-                        Rep3CoPlonk::with_network(mpc_net).context("while building prover")?;
+                    let prover = Rep3CoPlonk::with_network(mpc_net)
+                        .context("while building prover")?
+                        .with_progress(log_prover_progress);
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
                     (proof, public_input)
                 }
                 MPCProtocol::SHAMIR => {
-                    let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
-                    let public_input = witness_share.public_inputs.clone();
+                    let witness_share = if config.lazy_witness {
+                        co_circom::parse_witness_share_shamir_lazy(witness_file)?
+                    } else {
+                        co_circom::parse_witness_share_shamir(witness_file)?
+                    };
+                    let public_input = witness_share
+                        .public_signals(zkey.num_public_inputs())?
+                        .to_vec();
 
                     //init prover
-                    let prover = ShamirCoPlonk::with_network_config(t, config.network, &zkey)
-                        .context("while building prover")?;
+                    let prover = ShamirCoPlonk::with_network_for_parties(
+                        t,
+                        connect_shamir(config.network, readiness.as_ref())?,
+                        &zkey,
+                        &party_ids,
+                    )
+                    .context("while building prover")?
+                    .with_progress(log_prover_progress);
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
@@ -584,202 +1582,2261 @@ where
             };
 
             // write result to output file
-            if let Some(out) = out {
-                let out_file = BufWriter::new(
-                    std::fs::File::create(&out).context("while creating output file")?,
-                );
-
-                serde_json::to_writer(out_file, &proof)
-                    .context("while serializing proof to JSON file")?;
-                tracing::info!("Wrote proof to file {}", out.display());
+            if let Some(out) = &out {
+                write_proof(out, &proof, config.output_format, config.byte_order)?;
             }
-            public_input
+            if let Some(dir) = &snarkjs_out {
+                co_circom::snarkjs::write_dir(dir, &proof, &public_input)?;
+                tracing::info!("Wrote proof and public inputs to {}", dir.display());
+            }
+            let verified = vk.zip(zkey_vk).map(|(vk, zkey_vk)| {
+                verify_generated_proof::<P, _>(
+                    ProofSystem::Plonk,
+                    vk,
+                    &zkey_vk,
+                    |vk| vk.n_public,
+                    |vk| Ok(Plonk::<P>::verify(vk, &proof, &public_input)?),
+                )
+            });
+            (public_input, verified)
         }
     };
 
     // write public input to output file
     if let Some(public_input_filename) = public_input_filename {
-        let public_input_as_strings = public_input
-            .iter()
-            .skip(1) // we skip the constant 1 at position 0
-            .map(|f| {
-                if f.is_zero() {
-                    "0".to_string()
-                } else {
-                    f.to_string()
-                }
-            })
-            .collect::<Vec<String>>();
-        let public_input_file = BufWriter::new(
-            std::fs::File::create(&public_input_filename)
-                .context("while creating public input file")?,
-        );
-        serde_json::to_writer(public_input_file, &public_input_as_strings)
-            .context("while writing out public inputs to JSON file")?;
-        tracing::info!(
-            "Wrote public inputs to file {}",
-            public_input_filename.display()
-        );
+        write_public_inputs(
+            &public_input_filename,
+            &public_input,
+            config.output_format,
+            config.byte_order,
+        )?;
     }
     tracing::info!("Proof generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+
+    Ok(verified.unwrap_or(ExitCode::SUCCESS))
 }
 
-#[instrument(level = "debug", skip(config))]
-fn run_verify<P: Pairing + CircomArkworksPairingBridge>(
-    config: VerifyConfig,
-) -> color_eyre::Result<ExitCode>
+/// Verifies a proof right after proving, with the public inputs taken from the witness instead of a public inputs file, see `--verify`.
+/// Logs which check rejected the proof, if one did, and returns the exit code of `generate-proof`.
+fn verify_generated_proof<P, V>(
+    proof_system: ProofSystem,
+    vk: serde_json::Value,
+    zkey_vk: &V,
+    n_public: fn(&V) -> usize,
+    verify: impl FnOnce(&V) -> color_eyre::Result<bool>,
+) -> ExitCode
 where
+    P: Pairing + CircomArkworksPairingBridge,
     P::ScalarField: CircomArkworksPrimeFieldBridge,
     P::BaseField: CircomArkworksPrimeFieldBridge,
+    V: serde::de::DeserializeOwned + PartialEq,
 {
-    let proofsystem = config.proof_system;
-    let proof = config.proof;
-    let vk = config.vk;
-    let public_input = config.public_input;
-
-    file_utils::check_file_exists(&proof)?;
-    file_utils::check_file_exists(&vk)?;
-    file_utils::check_file_exists(&public_input)?;
+    let start = Instant::now();
+    match check_generated_proof::<P, V>(proof_system, vk, zkey_vk, n_public, verify) {
+        Ok(()) => {
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Proof verified successfully in {} ms", duration_ms);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            tracing::error!("Proof verification failed: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    // parse circom proof file
-    let proof_file = BufReader::new(File::open(&proof).context("while opening proof file")?);
+/// The checks of [verify_generated_proof]. `zkey_vk` is the verification key derived from the zkey the proof was generated with.
+///
+/// The verification key given with `--vk` is compared with `zkey_vk` before running the verifier, so that a key of another circuit is reported as such and not as an invalid proof.
+fn check_generated_proof<P, V>(
+    proof_system: ProofSystem,
+    vk: serde_json::Value,
+    zkey_vk: &V,
+    n_public: fn(&V) -> usize,
+    verify: impl FnOnce(&V) -> color_eyre::Result<bool>,
+) -> color_eyre::Result<()>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    V: serde::de::DeserializeOwned + PartialEq,
+{
+    let curve = P::get_circom_name();
+    match vk.get("curve").and_then(serde_json::Value::as_str) {
+        Some(found) if found != curve => {
+            return Err(eyre!(
+                "the verification key is for curve \"{found}\", but the proof for \"{curve}\""
+            ));
+        }
+        _ => {}
+    }
+    let vk = serde_json::from_value::<V>(vk).context("while parsing verification key")?;
+    if n_public(&vk) != n_public(zkey_vk) {
+        return Err(eyre!(
+            "the verification key has {} public inputs, but the zkey has {}",
+            n_public(&vk),
+            n_public(zkey_vk)
+        ));
+    }
+    if &vk != zkey_vk {
+        return Err(eyre!(
+            "the verification key does not belong to the zkey the proof was generated with"
+        ));
+    }
+    // like the verifier, the public signals of the witness share do not contain the constant 1
+    if !verify(&vk).context("while running verifier")? {
+        return Err(eyre!(
+            "the {proof_system} verifier rejected the proof, although the verification key belongs to the zkey"
+        ));
+    }
+    Ok(())
+}
 
-    // parse circom verification key file
-    let vk_file = BufReader::new(File::open(&vk).context("while opening verification key file")?);
+/// Reconstructs the witness from the REP3 shares of all parties and checks it against the constraints of the r1cs, see `--check-witness`.
+fn check_witness_rep3<P: Pairing + CircomArkworksPairingBridge>(
+    witness_share: &SharedWitness<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    r1cs: &Path,
+    mpc_net: &mut Rep3MpcNet,
+) -> color_eyre::Result<()>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    tracing::warn!(
+        "reconstructing the witness to check it against the r1cs, all parties learn the witness"
+    );
+    let start = Instant::now();
+    let witness = co_circom::open_witness_rep3(witness_share, mpc_net)?;
+    let r1cs_file = BufReader::new(File::open(r1cs).context("while opening r1cs file")?);
+    let constraints = R1CS::<P>::constraints_iter(r1cs_file).context("while parsing r1cs file")?;
+    co_circom::check_witness_satisfies_r1cs(&witness, constraints)
+        .context("aborting before proving")?;
+    tracing::info!(
+        "witness satisfies the r1cs, check took {} ms",
+        start.elapsed().as_millis()
+    );
+    Ok(())
+}
 
-    // parse public inputs
-    let public_inputs_file =
-        BufReader::new(File::open(&public_input).context("while opening public inputs file")?);
-    let public_inputs_as_strings: Vec<String> = serde_json::from_reader(public_inputs_file)
-        .context(
-            "while parsing public inputs, expect them to be array of stringified field elements",
-        )?;
-    // skip 1 atm
-    let public_inputs = public_inputs_as_strings
-        .into_iter()
-        .map(|s| {
-            s.parse::<P::ScalarField>()
-                .map_err(|_| eyre!("could not parse as field element: {}", s))
-        })
-        .collect::<Result<Vec<P::ScalarField>, _>>()
-        .context("while converting public input strings to field elements")?;
+/// Checks that at most one of the proof and the public inputs is written to stdout, as they could not be told apart otherwise.
+fn check_single_stdout_output(
+    out: Option<&Path>,
+    public_input: Option<&Path>,
+) -> color_eyre::Result<()> {
+    if out.is_some_and(file_utils::is_stdout) && public_input.is_some_and(file_utils::is_stdout) {
+        return Err(eyre!(
+            "only one of --out and --public-input can be written to stdout"
+        ));
+    }
+    Ok(())
+}
 
-    // verify proof
-    let res = match proofsystem {
-        ProofSystem::Groth16 => {
-            let proof: Groth16Proof<P> = serde_json::from_reader(proof_file)
-                .context("while deserializing proof from file")?;
+/// Checks the `--threads` of the proving commands. The prover blocks one thread of the pool while it waits for the MSMs it spawned
+/// on the same pool, so a single thread would deadlock.
+fn check_prover_threads(threads: usize) -> color_eyre::Result<()> {
+    if threads < 2 {
+        return Err(eyre!(
+            "the prover needs at least 2 threads, but --threads is {threads}"
+        ));
+    }
+    Ok(())
+}
 
-            let vk: Groth16JsonVerificationKey<P> = serde_json::from_reader(vk_file)
-                .context("while deserializing verification key from file")?;
+/// Sizes the global rayon pool the MSMs of the provers run on to `threads`, if given. The networking has its own runtime.
+fn set_prover_threads(threads: Option<usize>) -> color_eyre::Result<()> {
+    if let Some(threads) = threads {
+        check_prover_threads(threads)?;
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .context("while setting up the thread pool")?;
+    }
+    Ok(())
+}
 
-            // The actual verifier
-            let start = Instant::now();
-            let res = Groth16::<P>::verify(&vk, &proof, &public_inputs)
-                .context("while verifying proof")?;
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Proof verification took {} ms", duration_ms);
-            res
-        }
-        ProofSystem::Plonk => {
-            let proof: PlonkProof<P> = serde_json::from_reader(proof_file)
-                .context("while deserializing proof from file")?;
+/// A parsed zkey of either proof system.
+enum ProvingKey<P: Pairing + CircomArkworksPairingBridge>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    Groth16(Arc<Groth16ZKey<P>>),
+    Plonk(Arc<PlonkZKey<P>>),
+}
 
-            let vk: PlonkJsonVerificationKey<P> = serde_json::from_reader(vk_file)
-                .context("while deserializing verification key from file")?;
+#[instrument(level = "debug", skip(config))]
+fn run_prove_from_input<P: Pairing + CircomArkworksPairingBridge>(
+    config: ProveFromInputConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    if config.protocol != MPCProtocol::REP3 {
+        return Err(eyre!(
+            "Only REP3 protocol is supported for proving from input shares"
+        ));
+    }
+    if config.input.is_empty() {
+        return Err(eyre!("Need at least one input share"));
+    }
+    for input in &config.input {
+        file_utils::check_file_exists(input)?;
+    }
+    file_utils::check_file_exists(&PathBuf::from(&config.circuit))?;
+    check_single_stdout_output(config.out.as_deref(), config.public_input.as_deref())?;
+    set_prover_threads(config.threads)?;
+
+    // read the zkey before connecting, so the other parties do not wait for us
+    let zkey_file = file_utils::open_file_or_url(&config.zkey).context("while opening zkey")?;
+    let zkey = match config.proof_system {
+        ProofSystem::Groth16 => ProvingKey::Groth16(Arc::new(
+            Groth16ZKey::<P>::from_reader(zkey_file).context("reading zkey")?,
+        )),
+        ProofSystem::Plonk => ProvingKey::Plonk(Arc::new(
+            PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?,
+        )),
+    };
 
-            // The actual verifier
-            let start = Instant::now();
-            let res =
-                Plonk::<P>::verify(&vk, &proof, &public_inputs).context("while verifying proof")?;
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Proof verification took {} ms", duration_ms);
-            res
+    let mut mpc_net = Rep3MpcNet::new(config.network).context("while connecting to network")?;
+    let input_share = co_circom::parse_shared_inputs(&config.input, &mut mpc_net)
+        .context("while parsing input")?;
+    let (witness_share, mpc_net) = co_circom::generate_witness_rep3_keep_network::<P>(
+        config.circuit,
+        input_share,
+        mpc_net,
+        config.compiler,
+        config.vm,
+    )?;
+
+    let public_input = match zkey {
+        ProvingKey::Groth16(zkey) => {
+            let public_input = witness_share
+                .public_signals(zkey.num_public_inputs())?
+                .to_vec();
+            let prover = Rep3CoGroth16::with_network(mpc_net)
+                .context("while building prover")?
+                .with_progress(log_prover_progress);
+            let proof = prover.prove(zkey, witness_share)?;
+            if let Some(out) = &config.out {
+                write_proof(out, &proof, config.output_format, config.byte_order)?;
+            }
+            public_input
+        }
+        ProvingKey::Plonk(zkey) => {
+            let public_input = witness_share
+                .public_signals(zkey.num_public_inputs())?
+                .to_vec();
+            let prover = Rep3CoPlonk::with_network(mpc_net)
+                .context("while building prover")?
+                .with_progress(log_prover_progress);
+            let proof = prover.prove(zkey, witness_share)?;
+            if let Some(out) = &config.out {
+                write_proof(out, &proof, config.output_format, config.byte_order)?;
+            }
+            public_input
         }
     };
+    if let Some(public_input_filename) = &config.public_input {
+        write_public_inputs(
+            public_input_filename,
+            &public_input,
+            config.output_format,
+            config.byte_order,
+        )?;
+    }
+    tracing::info!("Proof generation finished successfully");
+    Ok(ExitCode::SUCCESS)
+}
 
-    if res {
-        tracing::info!("Proof verified successfully");
-        Ok(ExitCode::SUCCESS)
+/// Writes the public inputs as decimal strings to `out`, which is a file or `-` for stdout.
+fn write_public_inputs<F: PrimeField>(
+    out: &Path,
+    public_input: &[F],
+    format: OutputFormat,
+    byte_order: ByteOrder,
+) -> color_eyre::Result<()> {
+    let public_input_as_strings = co_circom::snarkjs::public_inputs_as_strings(public_input);
+    let public_input_file =
+        file_utils::create_file_or_stdout(out).context("while creating public input file")?;
+    write_output(
+        public_input_file,
+        &public_input_as_strings,
+        format,
+        byte_order,
+    )
+    .context("while writing out public inputs")?;
+    if file_utils::is_stdout(out) {
+        tracing::info!("Wrote public inputs to stdout");
     } else {
-        tracing::error!("Proof verification failed");
-        Ok(ExitCode::FAILURE)
+        tracing::info!("Wrote public inputs to file {}", out.display());
+    }
+    Ok(())
+}
+
+/// Writes the proof to `out`, which is a file or `-` for stdout.
+fn write_proof<T: serde::Serialize>(
+    out: &Path,
+    proof: &T,
+    format: OutputFormat,
+    byte_order: ByteOrder,
+) -> color_eyre::Result<()> {
+    let out_file = file_utils::create_file_or_stdout(out).context("while creating output file")?;
+    write_output(out_file, proof, format, byte_order).context("while serializing proof")?;
+    if file_utils::is_stdout(out) {
+        tracing::info!("Wrote proof to stdout");
+    } else {
+        tracing::info!("Wrote proof to file {}", out.display());
+    }
+    Ok(())
+}
+
+/// Writes a proof or public inputs in the given [OutputFormat]. The [ByteOrder] only applies to CBOR.
+fn write_output<T: serde::Serialize>(
+    writer: impl std::io::Write,
+    value: &T,
+    format: OutputFormat,
+    byte_order: ByteOrder,
+) -> color_eyre::Result<()> {
+    match format {
+        OutputFormat::Json => serde_json::to_writer(writer, value).context("while writing JSON"),
+        OutputFormat::Cbor => cbor::to_writer(value, writer, byte_order),
     }
 }
 
-fn parse_field<F>(val: &serde_json::Value) -> color_eyre::Result<F>
+#[instrument(level = "debug", skip(config))]
+fn run_verify<P: Pairing + CircomArkworksPairingBridge>(
+    config: VerifyConfig,
+) -> color_eyre::Result<ExitCode>
 where
-    F: std::str::FromStr + PrimeField,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
 {
-    let s = val.as_str().ok_or_else(|| {
-        eyre!(
-            "expected input to be a field element string, got \"{}\"",
-            val
+    let inputs = read_proof_and_public_inputs::<P>(&config);
+    let public_input_count = inputs
+        .as_ref()
+        .ok()
+        .map(|(_, public_inputs)| public_inputs.len());
+    let start = Instant::now();
+    let verified_under = inputs.and_then(|(proof, public_inputs)| {
+        verify_under_vks::<P>(
+            config.proof_system,
+            proof,
+            &config.vk,
+            public_inputs,
+            config.structural_only,
         )
-    })?;
-    let (is_negative, stripped) = if let Some(stripped) = s.strip_prefix('-') {
-        (true, stripped)
-    } else {
-        (false, s)
+    });
+    let verify_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    if let Some(result_json) = &config.result_json {
+        let reason = match &verified_under {
+            Ok(Some(_)) => None,
+            Ok(None) => Some("proof verification failed".to_owned()),
+            Err(err) => Some(format!("{err:#}")),
+        };
+        let result = VerifyResult {
+            valid: matches!(verified_under, Ok(Some(_))),
+            structural_only: config.structural_only,
+            proof_system: config.proof_system.to_string().to_lowercase(),
+            curve: config.curve.to_string().to_lowercase(),
+            verify_ms,
+            public_input_count,
+            vk: verified_under
+                .as_ref()
+                .ok()
+                .flatten()
+                .map(|vk| vk.to_path_buf()),
+            reason,
+        };
+        let result_file =
+            BufWriter::new(File::create(result_json).context("while creating result JSON file")?);
+        serde_json::to_writer_pretty(result_file, &result)
+            .context("while writing result JSON file")?;
+    }
+
+    match verified_under? {
+        Some(vk) => {
+            // the logs are hidden with --quiet, so the matching key goes to stdout
+            if config.vk.len() > 1 {
+                println!("{}", vk.display());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        None => Ok(ExitCode::FAILURE),
+    }
+}
+
+/// Reads the proof and the public inputs of `verify`, the latter in the order of the verifier.
+fn read_proof_and_public_inputs<P: Pairing + CircomArkworksPairingBridge>(
+    config: &VerifyConfig,
+) -> color_eyre::Result<(serde_json::Value, Vec<String>)>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proofsystem = config.proof_system;
+    let proof = &config.proof;
+    let public_input = &config.public_input;
+
+    file_utils::check_file_exists(proof)?;
+    file_utils::check_file_exists(public_input)?;
+
+    // parse proof file
+    let proof: serde_json::Value = match config.proof_format {
+        ProofFormat::Json => {
+            let proof_file = BufReader::new(File::open(proof).context("while opening proof file")?);
+            serde_json::from_reader(proof_file).context("while parsing proof file")?
+        }
+        ProofFormat::Cbor => {
+            let proof_file = BufReader::new(File::open(proof).context("while opening proof file")?);
+            cbor::from_reader(proof_file, config.byte_order).context("while parsing proof file")?
+        }
+        ProofFormat::Bin => {
+            if !matches!(proofsystem, ProofSystem::Groth16) {
+                return Err(eyre!(
+                    "--proof-format bin is only supported for Groth16 proofs"
+                ));
+            }
+            let proof = std::fs::read(proof).context("while reading proof file")?;
+            verify::groth16_proof_from_compressed::<P>(&proof)
+                .context("while parsing proof file")?
+        }
     };
-    let positive_value = if let Some(stripped) = stripped.strip_prefix("0x") {
-        let big_int = BigUint::from_str_radix(stripped, 16)
-            .map_err(|_| eyre!("could not parse field element: \"{}\"", val))
-            .context("while parsing field element")?;
-        let big_int: F::BigInt = big_int
-            .try_into()
-            .map_err(|_| eyre!("could not parse field element: \"{}\"", val))
-            .context("while parsing field element")?;
-        F::from(big_int)
-    } else {
-        stripped
-            .parse::<F>()
-            .map_err(|_| eyre!("could not parse field element: \"{}\"", val))
-            .context("while parsing field element")?
+
+    // parse public inputs
+    let public_inputs = std::fs::read(public_input).context("while reading public inputs file")?;
+    // CBOR arrays and maps start with a byte that can not start a JSON document
+    let public_inputs: serde_json::Value = match public_inputs.first() {
+        Some(0x80..=0xbf) => cbor::from_reader(public_inputs.as_slice(), config.byte_order)
+            .context("while parsing CBOR public inputs file")?,
+        _ => serde_json::from_slice(&public_inputs).context("while parsing public inputs file")?,
     };
-    if is_negative {
-        Ok(-positive_value)
-    } else {
-        Ok(positive_value)
+    let public_inputs_as_strings = match public_inputs {
+        serde_json::Value::Object(named) => {
+            let circuit = config.circuit.clone().ok_or_else(|| {
+                eyre!("public inputs are given as an object, pass --circuit to determine their order")
+            })?;
+            file_utils::check_file_exists(&PathBuf::from(&circuit))?;
+            let public_signals = CoCircomCompiler::<P>::get_public_signals(circuit, config.compiler.clone())
+                .context("while reading public signals from circuit")?;
+            order_named_public_inputs(named, &public_signals)?
+        }
+        public_inputs => serde_json::from_value(public_inputs).context(
+            "while parsing public inputs, expect them to be array of stringified field elements or an object",
+        )?,
+    };
+    Ok((proof, public_inputs_as_strings))
+}
+
+/// Verifies the proof under the first of `vks` that accepts it and returns that key, or `None` if none
+/// of them does.
+fn verify_under_vks<'a, P: Pairing + CircomArkworksPairingBridge>(
+    proofsystem: ProofSystem,
+    proof: serde_json::Value,
+    vks: &'a [PathBuf],
+    public_inputs_as_strings: Vec<String>,
+    structural_only: bool,
+) -> color_eyre::Result<Option<&'a Path>>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    match vks {
+        [] => Err(eyre!("no verification key given, pass at least one --vk")),
+        [vk_path] => {
+            let vk = read_vk(vk_path)?;
+            let valid = verify_proof::<P>(
+                proofsystem,
+                proof,
+                vk,
+                public_inputs_as_strings,
+                structural_only,
+            )?;
+            Ok(valid.then_some(vk_path.as_path()))
+        }
+        vks => {
+            for vk_path in vks {
+                // a key of another circuit version may not even fit the public inputs, so every failure just means no match
+                let res = read_vk(vk_path).and_then(|vk| {
+                    check_proof::<P>(
+                        proofsystem,
+                        proof.clone(),
+                        vk,
+                        public_inputs_as_strings.clone(),
+                        structural_only,
+                    )
+                });
+                match res {
+                    Ok(true) => {
+                        let outcome = if structural_only {
+                            "is well-formed"
+                        } else {
+                            "verified successfully"
+                        };
+                        tracing::info!("Proof {outcome} under {}", vk_path.display());
+                        return Ok(Some(vk_path));
+                    }
+                    Ok(false) => {
+                        tracing::debug!("Proof does not verify under {}", vk_path.display())
+                    }
+                    Err(err) => {
+                        tracing::warn!("Skipping {}: {err:#}", vk_path.display())
+                    }
+                }
+            }
+            tracing::error!(
+                "Proof verification failed under all {} verification keys",
+                vks.len()
+            );
+            Ok(None)
+        }
     }
 }
 
-fn parse_array<F: PrimeField>(val: &serde_json::Value) -> color_eyre::Result<Vec<F>> {
-    let json_arr = val.as_array().expect("is an array");
-    let mut field_elements = vec![];
-    for ele in json_arr {
-        if ele.is_array() {
-            field_elements.extend(parse_array::<F>(ele)?);
-        } else {
-            field_elements.push(parse_field(ele)?);
+/// Brings named public inputs into the order of the verifier given by `public_signals`, arrays are flattened in row-major order.
+/// Yields the same stringified field elements as the equivalent public inputs array.
+fn order_named_public_inputs(
+    named: serde_json::Map<String, serde_json::Value>,
+    public_signals: &[String],
+) -> color_eyre::Result<Vec<String>> {
+    let mut named = flatten_input_objects(named);
+    let mut public_inputs = vec![];
+    for name in public_signals {
+        let val = named
+            .remove(name)
+            .ok_or_else(|| eyre!("public signal \"{name}\" is missing in the public inputs"))?;
+        for leaf in flatten_array(&val) {
+            let s = leaf.as_str().ok_or_else(|| {
+                eyre!("expected public signal \"{name}\" to be a field element string, got \"{leaf}\"")
+            })?;
+            public_inputs.push(s.to_owned());
         }
     }
-    Ok(field_elements)
+    if let Some(name) = named.keys().next() {
+        return Err(eyre!("\"{name}\" is not a public signal of the circuit"));
+    }
+    Ok(public_inputs)
 }
 
-fn merge_input_shares<F: PrimeField>(inputs: Vec<PathBuf>, out: PathBuf) -> color_eyre::Result<()> {
-    let start = Instant::now();
-    let mut input_shares = inputs
-        .iter()
-        .map(|input| {
-            let input_share_file =
-                BufReader::new(File::open(input).context("while opening input share file")?);
-            let input_share: SerializeableSharedRep3Input<F, SeedRng> =
-                bincode::deserialize_from(input_share_file)
-                    .context("trying to parse input share file")?;
-            color_eyre::Result::<_>::Ok(input_share)
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-    let start_item = input_shares.pop().expect("we have at least two inputs");
-    let merged = input_shares.into_iter().try_fold(start_item, |a, b| {
-        a.merge(b).context("while merging input shares")
-    })?;
+/// Runs `f`, skipping the subgroup checks of the parsed points if `skip` is set.
+fn skip_subgroup_checks_if<R>(skip: bool, f: impl FnOnce() -> R) -> R {
+    if skip {
+        tracing::warn!("Skipping the subgroup checks, only do this for trusted inputs");
+        circom_types::traits::without_subgroup_checks(f)
+    } else {
+        f()
+    }
+}
+
+fn read_vk(vk: &Path) -> color_eyre::Result<serde_json::Value> {
+    let vk_file =
+        file_utils::open_file_or_url(vk).context("while opening verification key file")?;
+    serde_json::from_reader(vk_file).context("while parsing verification key file")
+}
+
+#[instrument(level = "debug")]
+fn run_verify_bundle(bundle: PathBuf, structural_only: bool) -> color_eyre::Result<ExitCode> {
+    file_utils::check_file_exists(&bundle)?;
+    let bundle_file = BufReader::new(File::open(&bundle).context("while opening bundle file")?);
+    let bundle: ProofBundle =
+        serde_json::from_reader(bundle_file).context("while parsing bundle file")?;
+    if bundle.format != PROOF_BUNDLE_FORMAT {
+        return Err(eyre!(
+            "unsupported bundle format \"{}\", expected \"{}\"",
+            bundle.format,
+            PROOF_BUNDLE_FORMAT
+        ));
+    }
+    tracing::info!(
+        "Verifying {:?} proof on {} from bundle",
+        bundle.proof_system,
+        bundle.curve
+    );
+    let valid = match bundle.curve {
+        MPCCurve::BN254 => verify_proof::<Bn254>(
+            bundle.proof_system,
+            bundle.proof,
+            bundle.vk,
+            bundle.public_inputs,
+            structural_only,
+        ),
+        MPCCurve::BLS12_381 => verify_proof::<Bls12_381>(
+            bundle.proof_system,
+            bundle.proof,
+            bundle.vk,
+            bundle.public_inputs,
+            structural_only,
+        ),
+    }?;
+    if valid {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn verify_proof<P: Pairing + CircomArkworksPairingBridge>(
+    proofsystem: ProofSystem,
+    proof: serde_json::Value,
+    vk: serde_json::Value,
+    public_inputs_as_strings: Vec<String>,
+    structural_only: bool,
+) -> color_eyre::Result<bool>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    if check_proof::<P>(
+        proofsystem,
+        proof,
+        vk,
+        public_inputs_as_strings,
+        structural_only,
+    )? {
+        if structural_only {
+            tracing::info!("Proof is well-formed, skipped the pairing check");
+        } else {
+            tracing::info!("Proof verified successfully");
+        }
+        Ok(true)
+    } else {
+        tracing::error!("Proof verification failed");
+        Ok(false)
+    }
+}
+
+fn check_proof<P: Pairing + CircomArkworksPairingBridge>(
+    proofsystem: ProofSystem,
+    proof: serde_json::Value,
+    vk: serde_json::Value,
+    public_inputs_as_strings: Vec<String>,
+    structural_only: bool,
+) -> color_eyre::Result<bool>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    // the public inputs do not contain the constant 1 of the witness, see circom_verifier
+    let public_inputs = verify::parse_public_inputs::<P::ScalarField>(&public_inputs_as_strings)
+        .context("while converting public input strings to field elements")?;
+
+    if structural_only {
+        verify::check_structure_json::<P>(proofsystem.into(), proof, vk, &public_inputs)
+            .context("while checking proof structure")?;
+        return Ok(true);
+    }
+
+    // verify proof
+    let start = Instant::now();
+    let res = verify::verify_json::<P>(proofsystem.into(), proof, vk, &public_inputs)
+        .context("while verifying proof")?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Proof verification took {} ms", duration_ms);
+    Ok(res)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_bundle<P: Pairing + CircomArkworksPairingBridge>(
+    config: BundleConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof = config.proof;
+    let vk = config.vk;
+    let public_input = config.public_input;
+    let out = config.out;
+
+    file_utils::check_file_exists(&proof)?;
+    file_utils::check_file_exists(&public_input)?;
+
+    let proof_file = BufReader::new(File::open(&proof).context("while opening proof file")?);
+    let proof: serde_json::Value =
+        serde_json::from_reader(proof_file).context("while parsing proof file")?;
+    let vk_file =
+        file_utils::open_file_or_url(&vk).context("while opening verification key file")?;
+    let vk: serde_json::Value =
+        serde_json::from_reader(vk_file).context("while parsing verification key file")?;
+    let public_inputs_file =
+        BufReader::new(File::open(&public_input).context("while opening public inputs file")?);
+    let public_inputs: Vec<String> = serde_json::from_reader(public_inputs_file).context(
+        "while parsing public inputs, expect them to be array of stringified field elements",
+    )?;
+
+    // make sure the bundle only contains well-formed data for the given proof system and curve
+    match config.proof_system {
+        ProofSystem::Groth16 => {
+            serde_json::from_value::<Groth16Proof<P>>(proof.clone())
+                .context("while deserializing proof")?;
+            serde_json::from_value::<Groth16JsonVerificationKey<P>>(vk.clone())
+                .context("while deserializing verification key")?;
+        }
+        ProofSystem::Plonk => {
+            serde_json::from_value::<PlonkProof<P>>(proof.clone())
+                .context("while deserializing proof")?;
+            serde_json::from_value::<PlonkJsonVerificationKey<P>>(vk.clone())
+                .context("while deserializing verification key")?;
+        }
+    }
+    for s in public_inputs.iter() {
+        s.parse::<P::ScalarField>()
+            .map_err(|_| eyre!("could not parse as field element: {}", s))
+            .context("while converting public input strings to field elements")?;
+    }
+
+    let bundle = ProofBundle {
+        format: PROOF_BUNDLE_FORMAT.to_owned(),
+        proof_system: config.proof_system,
+        curve: config.curve,
+        proof,
+        vk,
+        public_inputs,
+    };
+    let out_file =
+        BufWriter::new(std::fs::File::create(&out).context("while creating output file")?);
+    serde_json::to_writer(out_file, &bundle).context("while serializing bundle to JSON file")?;
+    tracing::info!("Wrote bundle to file {}", out.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_aggregate<P: Pairing + CircomArkworksPairingBridge>(
+    config: AggregateConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let out = config.out;
+    if config.proof.is_empty() {
+        return Err(eyre!("no proofs given, pass at least one --proof"));
+    }
+    if config.proof.len() != config.public_input.len() {
+        return Err(eyre!(
+            "got {} proofs but {} public input files, pass one --public-input for each --proof",
+            config.proof.len(),
+            config.public_input.len()
+        ));
+    }
+
+    let vk_file =
+        file_utils::open_file_or_url(&config.vk).context("while opening verification key file")?;
+    let vk: Groth16JsonVerificationKey<P> =
+        serde_json::from_reader(vk_file).context("while parsing verification key file")?;
+    let mut proofs = Vec::with_capacity(config.proof.len());
+    for (proof, public_input) in config.proof.iter().zip(config.public_input.iter()) {
+        file_utils::check_file_exists(proof)?;
+        file_utils::check_file_exists(public_input)?;
+        let proof_file = BufReader::new(File::open(proof).context("while opening proof file")?);
+        let proof: Groth16Proof<P> = serde_json::from_reader(proof_file)
+            .with_context(|| format!("while parsing proof file {}", proof.display()))?;
+        let public_inputs_file =
+            BufReader::new(File::open(public_input).context("while opening public inputs file")?);
+        let public_inputs: Vec<String> =
+            serde_json::from_reader(public_inputs_file).with_context(|| {
+                format!(
+                    "while parsing public inputs file {}",
+                    public_input.display()
+                )
+            })?;
+        let public_inputs = verify::parse_public_inputs::<P::ScalarField>(&public_inputs)
+            .context("while converting public input strings to field elements")?;
+        proofs.push((proof, public_inputs));
+    }
+
+    let start = Instant::now();
+    let aggregated = Groth16::<P>::aggregate(&vk, proofs).context("while aggregating proofs")?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!(
+        "Aggregating {} proofs took {} ms",
+        aggregated.proofs.len(),
+        duration_ms
+    );
+
+    let out_file =
+        BufWriter::new(std::fs::File::create(&out).context("while creating output file")?);
+    serde_json::to_writer(out_file, &aggregated)
+        .context("while serializing aggregated proof to JSON file")?;
+    tracing::info!("Wrote aggregated proof to file {}", out.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_verify_aggregated<P: Pairing + CircomArkworksPairingBridge>(
+    config: VerifyAggregatedConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof = config.proof;
+    file_utils::check_file_exists(&proof)?;
+
+    let proof_file = BufReader::new(File::open(&proof).context("while opening proof file")?);
+    let aggregated: AggregatedProof<P> =
+        serde_json::from_reader(proof_file).context("while parsing aggregated proof file")?;
+    let vk_file =
+        file_utils::open_file_or_url(&config.vk).context("while opening verification key file")?;
+    let vk: Groth16JsonVerificationKey<P> =
+        serde_json::from_reader(vk_file).context("while parsing verification key file")?;
+
+    let start = Instant::now();
+    let res = Groth16::<P>::verify_aggregated(&vk, &aggregated)
+        .context("while verifying aggregated proof")?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Proof verification took {} ms", duration_ms);
+    if res {
+        tracing::info!(
+            "All {} aggregated proofs verified successfully",
+            aggregated.proofs.len()
+        );
+        Ok(ExitCode::SUCCESS)
+    } else {
+        tracing::error!("Aggregated proof verification failed");
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_convert_public_inputs<P: Pairing + CircomArkworksPairingBridge>(
+    config: ConvertPublicInputsConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let input = config.input;
+    let out = config.out;
+
+    file_utils::check_file_exists(&input)?;
+
+    let input_file =
+        BufReader::new(File::open(&input).context("while opening public inputs file")?);
+    let public_inputs: Vec<serde_json::Value> = serde_json::from_reader(input_file).context(
+        "while parsing public inputs, expect them to be array of stringified field elements",
+    )?;
+
+    // hex strings are padded to the byte length of the field so all elements have the same width
+    let hex_width = (P::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(8) * 2;
+    let converted = public_inputs
+        .iter()
+        .map(|val| {
+            let value: BigUint = parse_field::<P::ScalarField>(val, false)?
+                .into_bigint()
+                .into();
+            Ok(match config.to {
+                Radix::Decimal => value.to_string(),
+                Radix::Hex => format!("0x{value:0hex_width$x}"),
+            })
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()
+        .context("while converting public inputs")?;
+
+    let out_file =
+        BufWriter::new(std::fs::File::create(&out).context("while creating output file")?);
+    serde_json::to_writer(out_file, &converted)
+        .context("while serializing public inputs to JSON file")?;
+    tracing::info!(
+        "Wrote {} public inputs as {:?} to file {}",
+        converted.len(),
+        config.to,
+        out.display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_export_vk<P: Pairing + CircomArkworksPairingBridge>(
+    config: ExportVkConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let out = config.out;
+
+    let zkey_file = file_utils::open_file_or_url(&config.zkey).context("while opening zkey")?;
+    let vk = match config.proof_system {
+        ProofSystem::Groth16 => {
+            let zkey = Groth16ZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?;
+            serde_json::to_vec_pretty(&Groth16JsonVerificationKey::from_zkey(&zkey))
+        }
+        ProofSystem::Plonk => {
+            // the verification key does not need the powers of tau
+            let zkey = PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?;
+            serde_json::to_vec_pretty(&PlonkJsonVerificationKey::from_zkey(&zkey))
+        }
+    }
+    .context("while serializing verification key")?;
+
+    std::fs::write(&out, vk).context("while writing verification key to JSON file")?;
+    tracing::info!("Wrote verification key to file {}", out.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_extract_public_inputs<P: Pairing + CircomArkworksPairingBridge>(
+    config: ExtractPublicInputsConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    file_utils::check_file_exists(&config.witness)?;
+
+    let zkey_file = file_utils::open_file_or_url(&config.zkey).context("while opening zkey")?;
+    let num_public_inputs = match config.proof_system {
+        ProofSystem::Groth16 => Groth16ZKey::<P>::from_reader(zkey_file)
+            .context("while parsing zkey")?
+            .num_public_inputs(),
+        // the number of public inputs does not need the powers of tau
+        ProofSystem::Plonk => PlonkZKey::<P>::from_reader(zkey_file)
+            .context("while parsing zkey")?
+            .num_public_inputs(),
+    };
+
+    let witness_file =
+        BufReader::new(File::open(&config.witness).context("while opening witness file")?);
+    let witness = Witness::<P::ScalarField>::from_reader(witness_file)
+        .context("while parsing witness file")?;
+    let public_input = co_circom::public_inputs_from_witness(witness, num_public_inputs)?;
+
+    write_public_inputs(
+        &config.out,
+        &public_input,
+        OutputFormat::Json,
+        ByteOrder::default(),
+    )?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_public_signals<P: Pairing + CircomArkworksPairingBridge>(
+    config: PublicSignalsConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    file_utils::check_file_exists(&PathBuf::from(&config.circuit))?;
+    let public_signals =
+        CoCircomCompiler::<P>::get_public_signals_with_sizes(config.circuit, config.compiler)
+            .context("while reading public signals from circuit")?;
+
+    // the indices are positions in the public inputs, which do not include the constant 1 of the witness
+    let mut index = 0;
+    for (name, size) in public_signals {
+        match size {
+            1 => println!("{index}\t{name}"),
+            size => println!("{index}..={}\t{name} ({size} elements)", index + size - 1),
+        }
+        index += size;
+    }
+    tracing::info!("The circuit has {} public inputs", index);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_vk_fingerprint<P: Pairing + CircomArkworksPairingBridge>(
+    config: VkFingerprintConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let vk = match (config.vk, config.zkey) {
+        (Some(vk), None) => read_vk(&vk)?,
+        (None, Some(zkey)) => {
+            let zkey_file = file_utils::open_file_or_url(&zkey).context("while opening zkey")?;
+            match config.proof_system {
+                ProofSystem::Groth16 => {
+                    let zkey =
+                        Groth16ZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?;
+                    serde_json::to_value(Groth16JsonVerificationKey::from_zkey(&zkey))
+                }
+                ProofSystem::Plonk => {
+                    let zkey =
+                        PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?;
+                    serde_json::to_value(PlonkJsonVerificationKey::from_zkey(&zkey))
+                }
+            }
+            .context("while serializing verification key")?
+        }
+        _ => return Err(eyre!("pass exactly one of --vk and --zkey")),
+    };
+    let fingerprint = verify::vk_fingerprint_json::<P>(config.proof_system.into(), vk)
+        .context("while computing the fingerprint")?;
+    println!("{fingerprint}");
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_diff_public_inputs<P: Pairing + CircomArkworksPairingBridge>(
+    config: DiffPublicInputsConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let read = |path: &PathBuf| -> color_eyre::Result<Vec<P::ScalarField>> {
+        file_utils::check_file_exists(path)?;
+        let file = BufReader::new(File::open(path).context("while opening public inputs file")?);
+        let public_inputs: Vec<serde_json::Value> = serde_json::from_reader(file).context(
+            "while parsing public inputs, expect them to be array of stringified field elements",
+        )?;
+        public_inputs
+            .iter()
+            .map(|val| parse_field(val, false))
+            .collect::<color_eyre::Result<Vec<_>>>()
+            .with_context(|| format!("while parsing public inputs from {}", path.display()))
+    };
+    let first = read(&config.first)?;
+    let second = read(&config.second)?;
+
+    // values are compared as field elements, so e.g. "0x0a" and "10" are equal
+    let diff = co_circom::diff_public_inputs(&first, &second);
+    if let Some((index, a, b)) = diff.first_mismatch {
+        // Display prints a zero field element as an empty string
+        let values = co_circom::snarkjs::public_inputs_as_strings(&[a, b]);
+        println!("first difference at index {index}:");
+        println!("  {}: {}", config.first.display(), values[0]);
+        println!("  {}: {}", config.second.display(), values[1]);
+    }
+    if diff.first_len != diff.second_len {
+        println!(
+            "length mismatch: {} has {} public inputs, {} has {}",
+            config.first.display(),
+            diff.first_len,
+            config.second.display(),
+            diff.second_len
+        );
+    }
+
+    if diff.is_equal() {
+        tracing::info!("The {} public inputs are equal", first.len());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+/// Gas costs of the BN254 precompiles since EIP-1108.
+const GAS_EC_ADD: usize = 150;
+const GAS_EC_MUL: usize = 6000;
+const GAS_PAIRING_BASE: usize = 45000;
+const GAS_PAIRING_PER_PAIR: usize = 34000;
+/// Gas per byte of calldata, assuming no zero bytes.
+const GAS_CALLDATA_BYTE: usize = 16;
+
+/// The G1 scalar multiplications of the Plonk verifier of snarkjs: 9 for the commitment D (Qm, Ql, Qr, Qo, Z, S3, T1, T2 and T3),
+/// 5 for F (A, B, C, S1 and S2), 1 for E (the generator) and 3 for the points of the final pairing check (Wxi and twice Wxiw).
+/// This does not depend on the number of public inputs, which only add field operations for the Lagrange evaluations.
+const PLONK_VERIFIER_G1_MULS: usize = 18;
+
+/// The size of a proof and the work of its verifier, see [run_estimate].
+struct ProofEstimate {
+    elements: &'static str,
+    json_size: usize,
+    cbor_size: usize,
+    bin_size: Option<usize>,
+    /// The number of 32 byte words passed to `verifyProof` of the Solidity verifier of snarkjs
+    calldata_words: usize,
+    pairings: usize,
+    pairings_note: &'static str,
+    g1_muls: usize,
+}
+
+/// Computes the [ProofEstimate] for a zkey of the given proof system, see [run_estimate]. Returns the number of public inputs along with it.
+fn estimate_proof<P: Pairing + CircomArkworksPairingBridge>(
+    proof_system: ProofSystem,
+    zkey: impl std::io::Read,
+) -> color_eyre::Result<(usize, ProofEstimate)>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    fn encoded_sizes<T: serde::Serialize>(proof: &T) -> color_eyre::Result<(usize, usize)> {
+        let json = serde_json::to_vec(proof).context("while serializing proof to JSON")?;
+        let mut cbor = vec![];
+        cbor::to_writer(proof, &mut cbor, ByteOrder::default())?;
+        Ok((json.len(), cbor.len()))
+    }
+
+    // only the size of the proof matters, so we encode one of random points. The size of
+    // the JSON encoding still varies by a few bytes with the number of digits of the coordinates
+    let mut rng = rand::thread_rng();
+    let estimate = match proof_system {
+        ProofSystem::Groth16 => {
+            let n_public =
+                Groth16ZKey::<P>::read_num_public_inputs(zkey).context("while parsing zkey")?;
+            let proof = Groth16Proof::<P> {
+                pi_a: P::G1::rand(&mut rng).into_affine(),
+                pi_b: P::G2::rand(&mut rng).into_affine(),
+                pi_c: P::G1::rand(&mut rng).into_affine(),
+                protocol: "groth16".to_owned(),
+                curve: P::get_circom_name(),
+            };
+            let (json_size, cbor_size) = encoded_sizes(&proof)?;
+            let estimate = ProofEstimate {
+                elements: "2 G1 points and 1 G2 point",
+                json_size,
+                cbor_size,
+                bin_size: Some(proof.compressed_size()),
+                // verifyProof(uint[2] a, uint[2][2] b, uint[2] c, uint[n] input)
+                calldata_words: 8 + n_public,
+                pairings: 4,
+                pairings_note:
+                    " (3 if e(alpha, beta) is precomputed, the Solidity verifier does not)",
+                // one per point of the IC of the verification key, except for the constant 1
+                g1_muls: n_public,
+            };
+            (n_public, estimate)
+        }
+        ProofSystem::Plonk => {
+            let zkey = PlonkZKey::<P>::from_reader(zkey).context("while parsing zkey")?;
+            let n_public = zkey.num_public_inputs();
+            let mut g1 = || P::G1::rand(&mut rng).into_affine();
+            let (a, b, c, z, t1, t2, t3, wxi, wxiw) =
+                (g1(), g1(), g1(), g1(), g1(), g1(), g1(), g1(), g1());
+            let mut fr = || P::ScalarField::rand(&mut rng);
+            let proof = PlonkProof::<P> {
+                a,
+                b,
+                c,
+                z,
+                t1,
+                t2,
+                t3,
+                wxi,
+                wxiw,
+                eval_a: fr(),
+                eval_b: fr(),
+                eval_c: fr(),
+                eval_s1: fr(),
+                eval_s2: fr(),
+                eval_zw: fr(),
+                protocol: "plonk".to_owned(),
+                curve: P::get_circom_name(),
+            };
+            let (json_size, cbor_size) = encoded_sizes(&proof)?;
+            let estimate = ProofEstimate {
+                elements: "9 G1 points and 6 evaluations",
+                json_size,
+                cbor_size,
+                bin_size: None,
+                // verifyProof(uint256[24] proof, uint256[n] pubSignals)
+                calldata_words: 24 + n_public,
+                pairings: 2,
+                pairings_note: "",
+                g1_muls: PLONK_VERIFIER_G1_MULS,
+            };
+            (n_public, estimate)
+        }
+    };
+    Ok(estimate)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_estimate<P: Pairing + CircomArkworksPairingBridge>(
+    config: EstimateConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let zkey_file = file_utils::open_file_or_url(&config.zkey).context("while opening zkey")?;
+    let (n_public, estimate) = estimate_proof::<P>(config.proof_system, zkey_file)?;
+
+    println!(
+        "{} proof on {} with {n_public} public inputs: {}",
+        config.proof_system, config.curve, estimate.elements
+    );
+    println!("proof size:");
+    println!("  json:     ~{} bytes", estimate.json_size);
+    match estimate.bin_size {
+        Some(size) => println!("  bin:      {size} bytes"),
+        None => println!("  bin:      not supported for {}", config.proof_system),
+    }
+    println!("  cbor:     ~{} bytes", estimate.cbor_size);
+    // the function selector followed by the arguments
+    let calldata = 4 + 32 * estimate.calldata_words;
+    if config.curve == MPCCurve::BN254 {
+        println!(
+            "  calldata: {calldata} bytes for verifyProof of the Solidity verifier of snarkjs"
+        );
+    } else {
+        println!("  calldata: not available, snarkjs only exports Solidity verifiers for BN254");
+    }
+    println!("verification:");
+    println!(
+        "  pairings:                  {}{}",
+        estimate.pairings, estimate.pairings_note
+    );
+    println!("  G1 scalar multiplications: {}", estimate.g1_muls);
+    if config.curve == MPCCurve::BN254 {
+        let gas = GAS_PAIRING_BASE
+            + GAS_PAIRING_PER_PAIR * estimate.pairings
+            + (GAS_EC_MUL + GAS_EC_ADD) * estimate.g1_muls
+            + GAS_CALLDATA_BYTE * calldata;
+        println!(
+            "  gas:                       ~{gas} for the precompiles and calldata, without the base cost of the transaction and the field arithmetic of the verifier"
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_rerandomize_proof<P: Pairing + CircomArkworksPairingBridge>(
+    config: RerandomizeProofConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    if config.count == 0 {
+        return Err(eyre!("the number of proofs must be at least 1"));
+    }
+    if config.count > 1 && file_utils::is_stdout(&config.out) {
+        return Err(eyre!("more than one proof can not be written to stdout"));
+    }
+    file_utils::check_file_exists(&config.proof)?;
+    let proof_file = BufReader::new(File::open(&config.proof).context("while opening proof file")?);
+    let proof: Groth16Proof<P> =
+        serde_json::from_reader(proof_file).context("while parsing proof file")?;
+    let vk_file =
+        file_utils::open_file_or_url(&config.vk).context("while opening verification key file")?;
+    let vk: Groth16JsonVerificationKey<P> =
+        serde_json::from_reader(vk_file).context("while parsing verification key file")?;
+
+    let mut rng = rand::thread_rng();
+    for i in 0..config.count {
+        let rerandomized = Groth16::<P>::rerandomize_proof(&vk, &proof, &mut rng);
+        let out = if config.count == 1 {
+            config.out.clone()
+        } else {
+            numbered_path(&config.out, i)
+        };
+        write_proof(
+            &out,
+            &rerandomized,
+            OutputFormat::Json,
+            ByteOrder::default(),
+        )?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Inserts `index` before the extension of `path`, e.g., `out.json` becomes `out.<index>.json`.
+fn numbered_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{stem}.{index}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{index}"),
+    };
+    path.with_file_name(file_name)
+}
+
+fn log_prover_progress(phase: ProverPhase, progress: f32) {
+    tracing::info!("prover: {} ({:.0}%)", phase, progress * 100.0);
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(level = "debug", skip(network))]
+fn dry_run_generate_proof<P: Pairing + CircomArkworksPairingBridge>(
+    witness: PathBuf,
+    zkey: PathBuf,
+    r1cs: Option<R1CSHeader>,
+    curve: MPCCurve,
+    protocol: MPCProtocol,
+    t: usize,
+    proof_system: ProofSystem,
+    network: NetworkConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let zkey_file = file_utils::open_file_or_url(&zkey).context("while opening zkey")?;
+    match proof_system {
+        ProofSystem::Groth16 => {
+            let zkey = Groth16ZKey::<P>::from_reader(zkey_file).context("reading zkey")?;
+            if let Some(r1cs) = &r1cs {
+                check_groth16_zkey_matches_r1cs(&zkey, r1cs)?;
+            }
+        }
+        ProofSystem::Plonk => {
+            let zkey = PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?;
+            if let Some(r1cs) = &r1cs {
+                check_plonk_zkey_matches_r1cs(&zkey, r1cs)?;
+            }
+        }
+    }
+
+    let witness_file =
+        file_utils::open_file_or_parts(&witness).context("trying to open witness share file")?;
+    let witness_file = co_circom::WitnessShareReader::new(witness_file)?;
+    check_witness_share_curve(witness_file.curve(), curve)?;
+    witness_file.expect_protocol(protocol)?;
+    match protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            let _: SerializeableSharedRep3Witness<P::ScalarField, SeedRng> =
+                bincode::deserialize_from(witness_file)
+                    .context("trying to parse witness share file")?;
+        }
+        MPCProtocol::SHAMIR => {
+            check_shamir_proving_threshold(t, network.parties.len())?;
+            co_circom::parse_witness_share_shamir::<_, P::ScalarField>(witness_file)?;
+        }
+    }
+
+    check_network_config(&network)?;
+    tracing::info!("Dry run successful, config and files are valid");
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Checks that a witness share was created for the curve of the zkey. As the zkey is parsed for the curve of the config, this is `zkey_curve` once the zkey could be parsed.
+/// Shares written without header, see [co_circom::WITNESS_SHARE_MAGIC], cannot be checked.
+fn check_witness_share_curve(
+    witness_curve: Option<MPCCurve>,
+    zkey_curve: MPCCurve,
+) -> color_eyre::Result<()> {
+    match witness_curve {
+        Some(witness_curve) if witness_curve != zkey_curve => Err(eyre!(
+            "witness is {witness_curve} but zkey is {zkey_curve}, was the witness share created with another --curve?"
+        )),
+        Some(_) => Ok(()),
+        None => {
+            tracing::debug!("witness share has no header, cannot check its curve");
+            Ok(())
+        }
+    }
+}
+
+/// Checks that a Groth16 zkey was created for the circuit described by the r1cs header, i.e., that they agree on the number of constraints, public inputs and variables.
+fn check_groth16_zkey_matches_r1cs<P: Pairing>(
+    zkey: &Groth16ZKey<P>,
+    r1cs: &R1CSHeader,
+) -> color_eyre::Result<()> {
+    // the constant 1 wire is counted as public input in the r1cs, but not in the zkey
+    if zkey.num_public_inputs() + 1 != r1cs.num_inputs {
+        return Err(eyre!(
+            "zkey has {} public inputs, but the r1cs has {}. Was the zkey created for another compilation of the circuit?",
+            zkey.num_public_inputs(),
+            r1cs.num_inputs - 1
+        ));
+    }
+    if zkey.num_constraints() != r1cs.n_constraints {
+        return Err(eyre!(
+            "zkey has {} constraints, but the r1cs has {}. Was the zkey created for another compilation of the circuit?",
+            zkey.num_constraints(),
+            r1cs.n_constraints
+        ));
+    }
+    let zkey_vars = zkey.n_public + zkey.matrices.num_witness_variables;
+    if zkey_vars != r1cs.num_variables {
+        return Err(eyre!(
+            "zkey has {} variables, but the r1cs has {}. Was the zkey created for another compilation of the circuit?",
+            zkey_vars,
+            r1cs.num_variables
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a Plonk zkey was created for the circuit described by the r1cs header.
+/// The Plonk constraints are derived from the r1cs constraints and their number differs, so only the public inputs and variables are compared.
+fn check_plonk_zkey_matches_r1cs<P: Pairing>(
+    zkey: &PlonkZKey<P>,
+    r1cs: &R1CSHeader,
+) -> color_eyre::Result<()> {
+    if zkey.num_public_inputs() + 1 != r1cs.num_inputs {
+        return Err(eyre!(
+            "zkey has {} public inputs, but the r1cs has {}. Was the zkey created for another compilation of the circuit?",
+            zkey.num_public_inputs(),
+            r1cs.num_inputs - 1
+        ));
+    }
+    // the linear combinations of the r1cs are split into additions, which get their own variables in the zkey
+    let zkey_vars = zkey.n_vars - zkey.n_additions;
+    if zkey_vars != r1cs.num_variables {
+        return Err(eyre!(
+            "zkey has {} variables, but the r1cs has {}. Was the zkey created for another compilation of the circuit?",
+            zkey_vars,
+            r1cs.num_variables
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a plain witness fits the circuit of the r1cs before it is shared, i.e., that it has one value per wire and that there is anything to prove at all.
+fn check_witness_matches_r1cs<P: Pairing>(
+    witness: &Witness<P::ScalarField>,
+    r1cs: &R1CS<P>,
+) -> color_eyre::Result<()> {
+    if r1cs.n_constraints == 0 {
+        return Err(eyre!(
+            "circuit has no constraints, there is nothing to prove"
+        ));
+    }
+    if witness.values.is_empty() {
+        return Err(eyre!("the witness is empty"));
+    }
+    if witness.values.len() != r1cs.num_variables {
+        return Err(eyre!(
+            "witness has {} values, but the circuit has {} wires. Was the witness generated for another circuit?",
+            witness.values.len(),
+            r1cs.num_variables
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a Shamir sharing of degree `t` among `n` parties hides the secret and can be reconstructed, i.e., that `0 < t < n`.
+fn check_shamir_threshold(t: usize, n: usize) -> color_eyre::Result<()> {
+    if t == 0 {
+        return Err(eyre!(
+            "the Shamir threshold must be at least 1, with threshold 0 every share reveals the secret"
+        ));
+    }
+    if t >= n {
+        return Err(eyre!(
+            "the Shamir threshold {t} must be less than the number of parties {n}, as reconstructing needs {} shares",
+            t + 1
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `n` parties can prove with Shamir shares of degree `t`. Multiplications double the degree of the shares, so `2t + 1` parties have to take part.
+fn check_shamir_proving_threshold(t: usize, n: usize) -> color_eyre::Result<()> {
+    check_shamir_threshold(t, n)?;
+    if 2 * t + 1 > n {
+        return Err(eyre!(
+            "proving with Shamir threshold {t} needs at least {} parties, but the network config has {n}",
+            2 * t + 1
+        ));
+    }
+    Ok(())
+}
+
+/// Checks the network config without connecting, i.e., that the config is consistent, all certificates and our key exist, and all party addresses resolve.
+fn check_network_config(network: &NetworkConfig) -> color_eyre::Result<()> {
+    network
+        .check_config()
+        .context("while checking network config")?;
+    file_utils::check_file_exists(&network.key_path)?;
+    for party in network.parties.iter() {
+        file_utils::check_file_exists(&party.cert_path)?;
+        // through a relay, the addresses of the parties are only used to check their certificates
+        if network.relay.is_some() {
+            continue;
+        }
+        party
+            .dns_name
+            .to_socket_addrs()
+            .with_context(|| format!("while resolving address of party {}", party.id))?
+            .next()
+            .with_context(|| format!("address of party {} did not resolve", party.id))?;
+    }
+    if let Some(relay) = &network.relay {
+        relay
+            .to_socket_addrs()
+            .context("while resolving address of relay")?
+            .next()
+            .context("address of relay did not resolve")?;
+    }
+    Ok(())
+}
+
+/// Parses a decimal or `0x`-prefixed hex field element, optionally with a leading `-`.
+/// With `montgomery`, only hex is accepted and the value is interpreted as the Montgomery form of the field element, see [from_montgomery].
+fn parse_field<F>(val: &serde_json::Value, montgomery: bool) -> color_eyre::Result<F>
+where
+    F: PrimeField,
+{
+    let s = val.as_str().ok_or_else(|| {
+        eyre!(
+            "expected input to be a field element string, got \"{}\"",
+            val
+        )
+    })?;
+    if montgomery && !s.strip_prefix('-').unwrap_or(s).starts_with("0x") {
+        return Err(eyre!(
+            "expected Montgomery form field element to be a hex string, got \"{}\"",
+            val
+        ));
+    }
+    let value = parse_field_ct::<F>(s)
+        .with_context(|| format!("could not parse field element: \"{}\"", val))
+        .context("while parsing field element")?;
+    if montgomery {
+        Ok(from_montgomery(value))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Converts the Montgomery form `x * R mod p` of a field element back to `x`, where `R = 2^(64 * #limbs)` as in arkworks.
+fn from_montgomery<F: PrimeField>(montgomery: F) -> F {
+    let r = F::from(2u64).pow([64 * <F::BigInt as BigInteger>::NUM_LIMBS as u64]);
+    montgomery * r.inverse().expect("R is invertible")
+}
+
+fn parse_array<F: PrimeField>(
+    val: &serde_json::Value,
+    montgomery: bool,
+) -> color_eyre::Result<Vec<F>> {
+    flatten_array(val)
+        .into_iter()
+        .map(|val| parse_field(val, montgomery))
+        .collect()
+}
+
+/// Flattens arbitrarily nested JSON arrays into their leaves in row-major order, e.g., `[[1, 2], [3]]` becomes `[1, 2, 3]`.
+/// Uses an explicit stack instead of recursion, so deeply nested inputs cannot overflow the call stack.
+/// A value that is not an array is returned as the only leaf.
+fn flatten_array(val: &serde_json::Value) -> Vec<&serde_json::Value> {
+    let Some(json_arr) = val.as_array() else {
+        return vec![val];
+    };
+    let mut leaves = vec![];
+    let mut stack = vec![json_arr.iter()];
+    while let Some(iter) = stack.last_mut() {
+        match iter.next() {
+            Some(serde_json::Value::Array(inner)) => stack.push(inner.iter()),
+            Some(leaf) => leaves.push(leaf),
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    leaves
+}
+
+/// Parses a (possibly nested array of) fixed-point decimal(s) and multiplies it with `scale`, e.g., `"-3.14"` with scale `100` becomes `-314`.
+/// Errors if a scaled value is not a whole number, as rounding would silently change the input.
+fn parse_scaled<F: PrimeField>(val: &serde_json::Value, scale: u64) -> color_eyre::Result<Vec<F>> {
+    if scale == 0 {
+        return Err(eyre!("scale must not be zero"));
+    }
+    flatten_array(val)
+        .into_iter()
+        .map(|val| parse_scaled_value(val, scale))
+        .collect()
+}
+
+fn parse_scaled_value<F: PrimeField>(val: &serde_json::Value, scale: u64) -> color_eyre::Result<F> {
+    // we also accept plain JSON numbers for scaled values, as decimals are common here
+    let s = match val {
+        serde_json::Value::String(s) => s.to_owned(),
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => return Err(eyre!("expected input to be a decimal, got \"{}\"", val)),
+    };
+    let (is_negative, stripped) = if let Some(stripped) = s.strip_prefix('-') {
+        (true, stripped)
+    } else {
+        (false, s.as_str())
+    };
+    let (int_part, frac_part) = stripped.split_once('.').unwrap_or((stripped, ""));
+    if int_part.is_empty() && frac_part.is_empty()
+        || !int_part
+            .chars()
+            .chain(frac_part.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(eyre!("could not parse decimal: \"{}\"", val));
+    }
+    // value = mantissa / 10^#frac_digits
+    let mantissa = BigUint::from_str_radix(&format!("{int_part}{frac_part}"), 10)
+        .map_err(|_| eyre!("could not parse decimal: \"{}\"", val))?;
+    let denominator = BigUint::from(10u32).pow(frac_part.len() as u32);
+    let scaled = mantissa * scale;
+    if !(&scaled % &denominator).is_zero() {
+        return Err(eyre!(
+            "{} multiplied with scale {} is not a whole number",
+            s,
+            scale
+        ));
+    }
+    let scaled = scaled / denominator;
+    if scaled >= F::MODULUS.into() {
+        return Err(eyre!(
+            "{} multiplied with scale {} does not fit into the field",
+            s,
+            scale
+        ));
+    }
+    let positive_value = F::from(scaled);
+    if is_negative {
+        Ok(-positive_value)
+    } else {
+        Ok(positive_value)
+    }
+}
+
+/// Flattens nested JSON objects into dotted signal names, e.g., `{"point": {"x": "1"}}` becomes `{"point.x": "1"}`.
+/// Arrays of objects are flattened into bracketed names (e.g., `points[0].x`), all other arrays are kept as they are and parsed with [parse_array].
+fn flatten_input_objects(
+    input: serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut flattened = serde_json::Map::with_capacity(input.len());
+    for (name, val) in input {
+        flatten_input_value(name, val, &mut flattened);
+    }
+    flattened
+}
+
+fn flatten_input_value(
+    name: String,
+    val: serde_json::Value,
+    flattened: &mut serde_json::Map<String, serde_json::Value>,
+) {
+    match val {
+        serde_json::Value::Object(obj) => {
+            for (key, val) in obj {
+                flatten_input_value(format!("{name}.{key}"), val, flattened);
+            }
+        }
+        serde_json::Value::Array(arr) if arr.iter().any(serde_json::Value::is_object) => {
+            for (i, val) in arr.into_iter().enumerate() {
+                flatten_input_value(format!("{name}[{i}]"), val, flattened);
+            }
+        }
+        val => {
+            flattened.insert(name, val);
+        }
+    }
+}
+
+/// Returns the party index in the name of an input share written by `split-input`, e.g., 1 for `input.json.1.shared`.
+fn input_share_party(path: &Path) -> Option<usize> {
+    let name = path.file_name()?.to_str()?.strip_suffix(".shared")?;
+    name.rsplit_once('.')?.1.parse().ok()
+}
+
+/// Input shares do not store the party they belong to, so we derive it from the file names and check that all shares belong to the same party.
+fn check_input_share_party(inputs: &[PathBuf], party: Option<usize>) -> color_eyre::Result<usize> {
+    let mut expected = party.map(|party| (party, format!("--party is {party}")));
+    for input in inputs {
+        let Some(found) = input_share_party(input) else {
+            continue;
+        };
+        match &expected {
+            Some((party, source)) if *party != found => {
+                return Err(eyre!(
+                    "input share {} belongs to party {found}, but {source}",
+                    input.display()
+                ));
+            }
+            Some(_) => {}
+            None => {
+                expected = Some((
+                    found,
+                    format!("{} belongs to party {found}", input.display()),
+                ))
+            }
+        }
+    }
+    let (party, _) = expected.ok_or_else(|| {
+        eyre!("could not derive the party index from the names of the input shares, please provide it with --party")
+    })?;
+    if party > 2 {
+        return Err(eyre!("party index must be 0, 1 or 2 for REP3, got {party}"));
+    }
+    Ok(party)
+}
+
+fn merge_input_shares<F: PrimeField>(
+    inputs: Vec<PathBuf>,
+    party: usize,
+    out: PathBuf,
+    signals: Option<&[(String, usize)]>,
+    additive: bool,
+) -> color_eyre::Result<()> {
+    let start = Instant::now();
+    // grouped input share files contribute the share of our party
+    let merged = co_circom::read_merged_input_shares::<F>(&inputs, party, additive)?;
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Merging took {} ms", duration_ms);
 
+    if let Some(signals) = signals {
+        merged
+            .check_signals(signals)
+            .context("while checking the merged input share against the circuit")?;
+        tracing::info!("Merged input share contains exactly the input signals of the circuit");
+    }
+
     let out_file = BufWriter::new(File::create(&out).context("while creating output file")?);
     bincode::serialize_into(out_file, &merged).context("while serializing witness share")?;
     tracing::info!("Wrote merged input share to file {}", out.display());
     Ok(())
 }
+
+#[cfg(test)]
+#[path = "../test_utils.rs"]
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::test_dir;
+    use super::*;
+    use ark_bn254::Fr;
+    use mpc_core::protocols::rep3::Rep3ShareVecType;
+    use serde_json::json;
+
+    type Input = SerializeableSharedRep3Input<Fr, SeedRng>;
+
+    fn input(names: &[&str]) -> Input {
+        let mut share = Input::default();
+        for name in names {
+            share.shared_inputs.insert(
+                name.to_string(),
+                Rep3ShareVecType::Additive(vec![Fr::from(1u64)]),
+            );
+        }
+        share
+    }
+
+    fn write_grouped(path: &Path, shares: &[(usize, &Input)]) {
+        let file = BufWriter::new(File::create(path).unwrap());
+        co_circom::write_grouped_input_shares(file, &shares.iter().copied().collect()).unwrap();
+    }
+
+    fn read(path: &Path, id: usize) -> Input {
+        co_circom::read_merged_input_shares::<Fr>(&[path.to_path_buf()], id, false).unwrap()
+    }
+
+    #[test]
+    fn merge_input_shares_reads_grouped_files() {
+        let dir = test_dir("merge-grouped");
+        let grouped = dir.join("a.json.0_1.shared");
+        write_grouped(&grouped, &[(0, &input(&["a"])), (1, &input(&["a"]))]);
+        let single = dir.join("b.json.1.shared");
+        std::fs::write(&single, bincode::serialize(&input(&["b"])).unwrap()).unwrap();
+        let out = dir.join("merged.1.shared");
+
+        merge_input_shares::<Fr>(vec![grouped, single], 1, out.clone(), None, false).unwrap();
+        let merged = read(&out, 1);
+        assert_eq!(
+            merged.shared_inputs.keys().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_into_grouped_files() {
+        let dir = test_dir("merge-into-grouped");
+        let grouped = dir.join("a.json.0_1.shared");
+        write_grouped(&grouped, &[(0, &input(&["a"])), (1, &input(&["a"]))]);
+        let single = dir.join("a.json.2.shared");
+        std::fs::write(&single, bincode::serialize(&input(&["a"])).unwrap()).unwrap();
+        let paths = [grouped.clone(), single.clone()];
+
+        let new = [input(&["b"]), input(&["b"]), input(&["b"])];
+        merge_into_input_shares::<Bn254>(new, &paths).unwrap();
+        for (path, id) in [(&grouped, 0), (&grouped, 1), (&single, 2)] {
+            assert_eq!(
+                read(path, id).shared_inputs.keys().collect::<Vec<_>>(),
+                vec!["a", "b"]
+            );
+        }
+
+        // every party must be covered exactly once
+        let new = [input(&["c"]), input(&["c"]), input(&["c"])];
+        let err = merge_into_input_shares::<Bn254>(new, &[grouped.clone(), grouped]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the input share of party 0 is in more than one file of --merge-into"
+        );
+        let new = [input(&["c"]), input(&["c"]), input(&["c"])];
+        assert!(merge_into_input_shares::<Bn254>(new, &[single.clone(), single]).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn prove_from_input_config_is_validated() {
+        let dir = "examples/groth16/test_vectors/multiplier2";
+        let parse = |args: &[&str]| -> color_eyre::Result<ProveFromInputConfig> {
+            let cli = Cli::try_parse_from(
+                ["co-circom", "prove-from-input", "groth16"]
+                    .iter()
+                    .chain(args)
+                    .chain(&["--config", "examples/configs/party1.toml"]),
+            )?;
+            let Commands::ProveFromInput(cli) = cli.command else {
+                unreachable!("parsed prove-from-input");
+            };
+            Ok(ProveFromInputConfig::parse(cli)?)
+        };
+        let (input, circuit, zkey) = (
+            format!("{dir}/input0.json"),
+            format!("{dir}/circuit.circom"),
+            format!("{dir}/multiplier2.zkey"),
+        );
+        let (input, circuit, zkey) = (input.as_str(), circuit.as_str(), zkey.as_str());
+        let args = [
+            "--input",
+            input,
+            "--circuit",
+            circuit,
+            "--zkey",
+            zkey,
+            "--curve",
+            "BN254",
+        ];
+
+        let config =
+            parse(&[&args[..], &["--protocol", "REP3", "--input", input]].concat()).unwrap();
+        assert_eq!(config.input, [PathBuf::from(input), PathBuf::from(input)]);
+        assert_eq!(config.network.my_id, 0);
+        assert_eq!(config.output_format, OutputFormat::Json);
+        // the zkey is required
+        assert!(parse(&["--input", input, "--circuit", circuit, "--protocol", "REP3"]).is_err());
+
+        let run = |extra: &[&str]| {
+            run_prove_from_input::<Bn254>(parse(&[&args[..], extra].concat()).unwrap())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            run(&["--protocol", "SHAMIR"]),
+            "Only REP3 protocol is supported for proving from input shares"
+        );
+        assert_eq!(
+            run(&["--protocol", "REP3", "--input", "missing.shared"]),
+            "File not found: missing.shared"
+        );
+        assert_eq!(
+            run(&["--protocol", "REP3", "--out", "-", "--public-input", "-"]),
+            "only one of --out and --public-input can be written to stdout"
+        );
+    }
+
+    #[test]
+    fn estimate_of_test_vector_zkeys() {
+        let zkey = |proof_system: &str| {
+            std::fs::File::open(format!(
+                "../../test_vectors/{proof_system}/bn254/multiplier2/circuit.zkey"
+            ))
+            .unwrap()
+        };
+        let (n_public, groth16) =
+            estimate_proof::<Bn254>(ProofSystem::Groth16, zkey("Groth16")).unwrap();
+        assert_eq!(n_public, 1);
+        // two compressed G1 points and one compressed G2 point
+        assert_eq!(groth16.bin_size, Some(32 + 64 + 32));
+        assert_eq!(groth16.calldata_words, 9);
+        assert_eq!(groth16.pairings, 4);
+        assert_eq!(groth16.g1_muls, 1);
+
+        let (n_public, plonk) = estimate_proof::<Bn254>(ProofSystem::Plonk, zkey("Plonk")).unwrap();
+        assert_eq!(n_public, 2);
+        assert_eq!(plonk.bin_size, None);
+        assert_eq!(plonk.calldata_words, 26);
+        assert_eq!(plonk.pairings, 2);
+        assert_eq!(plonk.g1_muls, PLONK_VERIFIER_G1_MULS);
+
+        // a zkey of the other proof system is rejected
+        assert!(estimate_proof::<Bn254>(ProofSystem::Plonk, zkey("Groth16")).is_err());
+    }
+
+    #[test]
+    fn split_witness_is_verified() {
+        let witness = Witness::<Fr>::from_reader(
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap(),
+        )
+        .unwrap();
+        // the constant 1 and the output
+        let num_inputs = 2;
+        let mut rng = rand::thread_rng();
+        let share_rep3 = |rng: &mut rand::rngs::ThreadRng, seeded, additive| {
+            SerializeableSharedRep3Witness::<_, SeedRng>::share_rep3(
+                witness.clone(),
+                num_inputs,
+                rng,
+                seeded,
+                additive,
+            )
+        };
+
+        for (seeded, additive) in [(false, false), (true, false), (false, true), (true, true)] {
+            verify_rep3_split(&share_rep3(&mut rng, seeded, additive), &witness).unwrap();
+        }
+
+        let mut replicated = share_rep3(&mut rng, false, false);
+        let Rep3ShareVecType::Replicated(shares) = &mut replicated[0].witness else {
+            panic!("expected replicated shares");
+        };
+        shares[0].a += Fr::from(1);
+        assert!(verify_rep3_split(&replicated, &witness).is_err());
+
+        let mut additive = share_rep3(&mut rng, false, true);
+        let Rep3ShareVecType::Additive(shares) = &mut additive[1].witness else {
+            panic!("expected additive shares");
+        };
+        shares[0] += Fr::from(1);
+        assert_eq!(
+            verify_rep3_split(&additive, &witness)
+                .unwrap_err()
+                .to_string(),
+            "the witness shares do not reconstruct the witness, no shares were written"
+        );
+
+        for party_ids in [[0, 1, 2], [0, 2, 4]] {
+            let mut shares =
+                SharedWitness::<_, ShamirPrimeFieldShare<_>>::share_shamir_for_parties(
+                    witness.clone(),
+                    num_inputs,
+                    1,
+                    &party_ids,
+                    &mut rng,
+                )
+                .unwrap();
+            verify_shamir_split(&shares, &party_ids, 1, &witness).unwrap();
+
+            let corrupted = shares[2].witness[0].inner() + Fr::from(1);
+            shares[2].witness[0] = ShamirPrimeFieldShare::new(corrupted);
+            assert!(verify_shamir_split(&shares, &party_ids, 1, &witness).is_err());
+        }
+    }
+
+    #[test]
+    fn zkey_is_checked_against_r1cs() {
+        let dir = |proof_system: &str, circuit: &str| {
+            format!("../../test_vectors/{proof_system}/bn254/{circuit}")
+        };
+        let r1cs = |dir: &str| {
+            let r1cs_file = BufReader::new(File::open(format!("{dir}/circuit.r1cs")).unwrap());
+            R1CS::<Bn254>::constraints_iter(r1cs_file)
+                .unwrap()
+                .header()
+                .to_owned()
+        };
+        let groth16_zkey = |dir: &str| {
+            Groth16ZKey::<Bn254>::from_reader(File::open(format!("{dir}/circuit.zkey")).unwrap())
+                .unwrap()
+        };
+        let plonk_zkey = |dir: &str| {
+            PlonkZKey::<Bn254>::from_reader(File::open(format!("{dir}/circuit.zkey")).unwrap())
+                .unwrap()
+        };
+        for circuit in ["multiplier2", "poseidon"] {
+            let groth16 = dir("Groth16", circuit);
+            check_groth16_zkey_matches_r1cs(&groth16_zkey(&groth16), &r1cs(&groth16)).unwrap();
+            let plonk = dir("Plonk", circuit);
+            check_plonk_zkey_matches_r1cs(&plonk_zkey(&plonk), &r1cs(&plonk)).unwrap();
+        }
+
+        // both circuits have one public input, so the number of constraints tells them apart
+        let err = check_groth16_zkey_matches_r1cs(
+            &groth16_zkey(&dir("Groth16", "multiplier2")),
+            &r1cs(&dir("Groth16", "poseidon")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "zkey has 1 constraints, but the r1cs has 213. Was the zkey created for another compilation of the circuit?"
+        );
+        let err = check_plonk_zkey_matches_r1cs(
+            &plonk_zkey(&dir("Plonk", "multiplier2")),
+            &r1cs(&dir("Plonk", "poseidon")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "zkey has 2 public inputs, but the r1cs has 1. Was the zkey created for another compilation of the circuit?"
+        );
+    }
+
+    #[test]
+    fn witness_is_checked_against_r1cs() {
+        let dir = "../../test_vectors/Groth16/bn254/multiplier2";
+        let r1cs =
+            R1CS::<Bn254>::from_reader(std::fs::File::open(format!("{dir}/circuit.r1cs")).unwrap())
+                .unwrap();
+        let witness =
+            Witness::<Fr>::from_reader(std::fs::File::open(format!("{dir}/witness.wtns")).unwrap())
+                .unwrap();
+        check_witness_matches_r1cs(&witness, &r1cs).unwrap();
+
+        let mut other = witness.clone();
+        other.values.push(Fr::from(1));
+        let err = check_witness_matches_r1cs(&other, &r1cs).unwrap_err();
+        assert!(err.to_string().starts_with(&format!(
+            "witness has {} values, but the circuit has {} wires",
+            witness.values.len() + 1,
+            witness.values.len()
+        )));
+
+        let empty = Witness { values: vec![] };
+        let err = check_witness_matches_r1cs(&empty, &r1cs).unwrap_err();
+        assert_eq!(err.to_string(), "the witness is empty");
+
+        let mut no_constraints = r1cs;
+        no_constraints.n_constraints = 0;
+        let err = check_witness_matches_r1cs(&witness, &no_constraints).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "circuit has no constraints, there is nothing to prove"
+        );
+    }
+
+    #[test]
+    fn shamir_threshold_is_checked() {
+        check_shamir_threshold(1, 3).unwrap();
+        check_shamir_threshold(2, 3).unwrap();
+        assert_eq!(
+            check_shamir_threshold(0, 3).unwrap_err().to_string(),
+            "the Shamir threshold must be at least 1, with threshold 0 every share reveals the secret"
+        );
+        assert_eq!(
+            check_shamir_threshold(3, 3).unwrap_err().to_string(),
+            "the Shamir threshold 3 must be less than the number of parties 3, as reconstructing needs 4 shares"
+        );
+        assert!(check_shamir_threshold(4, 3).is_err());
+
+        // proving additionally needs 2t + 1 parties
+        check_shamir_proving_threshold(1, 3).unwrap();
+        assert!(check_shamir_proving_threshold(2, 3).is_err());
+        assert!(check_shamir_proving_threshold(0, 3).is_err());
+    }
+
+    #[test]
+    fn prover_threads_are_checked() {
+        check_prover_threads(2).unwrap();
+        check_prover_threads(16).unwrap();
+        assert_eq!(
+            check_prover_threads(1).unwrap_err().to_string(),
+            "the prover needs at least 2 threads, but --threads is 1"
+        );
+        assert!(check_prover_threads(0).is_err());
+    }
+
+    #[test]
+    fn nested_arrays_are_flattened_in_row_major_order() {
+        let flat =
+            |val: serde_json::Value| flatten_array(&val).into_iter().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            flat(json!([[1, [2, 3]], [], [[4]], 5])),
+            [json!(1), json!(2), json!(3), json!(4), json!(5)]
+        );
+        assert_eq!(flat(json!("7")), [json!("7")]);
+        assert!(flat(json!([[], [[]]])).is_empty());
+    }
+
+    #[test]
+    fn scaled_decimals() {
+        let scaled = |val: serde_json::Value, scale| parse_scaled::<Fr>(&val, scale);
+        let fr = |x: i64| Fr::from(x);
+        assert_eq!(scaled(json!("3.14"), 100).unwrap(), [fr(314)]);
+        assert_eq!(scaled(json!("-3.14"), 100).unwrap(), [fr(-314)]);
+        assert_eq!(scaled(json!("3.140"), 100).unwrap(), [fr(314)]);
+        assert_eq!(scaled(json!("3."), 100).unwrap(), [fr(300)]);
+        assert_eq!(scaled(json!(".25"), 4).unwrap(), [fr(1)]);
+        assert_eq!(scaled(json!("-0"), 10).unwrap(), [fr(0)]);
+        assert_eq!(scaled(json!(2.5), 10).unwrap(), [fr(25)]);
+        assert_eq!(
+            scaled(json!([["1.5", "-0.25"], ["7"]]), 4).unwrap(),
+            [fr(6), fr(-1), fr(28)]
+        );
+
+        let err = |val: serde_json::Value, scale| scaled(val, scale).unwrap_err().to_string();
+        assert_eq!(
+            err(json!("3.145"), 100),
+            "3.145 multiplied with scale 100 is not a whole number"
+        );
+        assert_eq!(err(json!("1"), 0), "scale must not be zero");
+
+        // the scaled value has to be smaller than the modulus, negative values included
+        let modulus = Fr::MODULUS.to_string();
+        assert_eq!(
+            err(json!(modulus), 1),
+            format!("{modulus} multiplied with scale 1 does not fit into the field")
+        );
+        assert_eq!(
+            err(json!(format!("-{modulus}")), 1),
+            format!("-{modulus} multiplied with scale 1 does not fit into the field")
+        );
+        let below_modulus = (BigUint::from(Fr::MODULUS) - 1u32).to_string();
+        assert_eq!(scaled(json!(below_modulus), 1).unwrap(), [fr(-1)]);
+        assert!(err(json!(below_modulus), u64::MAX).ends_with("does not fit into the field"));
+
+        for invalid in [
+            json!(""),
+            json!("-"),
+            json!("."),
+            json!("abc"),
+            json!("1.2.3"),
+            json!("3.14kg"),
+            json!("1e2"),
+            json!("+1"),
+            json!("--1"),
+            json!(" 1"),
+        ] {
+            assert_eq!(
+                err(invalid.clone(), 100),
+                format!("could not parse decimal: \"{invalid}\"")
+            );
+        }
+        assert!(err(json!(true), 100).starts_with("expected input to be a decimal"));
+    }
+
+    #[test]
+    fn generated_proof_is_checked_against_vk_and_zkey() {
+        let dir = "../../test_vectors/Groth16/bn254";
+        let read_json = |path: &str| -> serde_json::Value {
+            serde_json::from_slice(&std::fs::read(format!("{dir}/{path}")).unwrap()).unwrap()
+        };
+        let zkey = Groth16ZKey::<Bn254>::from_reader(
+            std::fs::File::open(format!("{dir}/multiplier2/circuit.zkey")).unwrap(),
+        )
+        .unwrap();
+        let zkey_vk = Groth16JsonVerificationKey::from_zkey(&zkey);
+        let proof: Groth16Proof<Bn254> =
+            serde_json::from_value(read_json("multiplier2/circom.proof")).unwrap();
+        let public_input = verify::parse_public_inputs::<Fr>(
+            &serde_json::from_value::<Vec<String>>(read_json("multiplier2/public.json")).unwrap(),
+        )
+        .unwrap();
+        let check = |vk: serde_json::Value, public_input: &[Fr]| {
+            check_generated_proof::<Bn254, _>(
+                ProofSystem::Groth16,
+                vk,
+                &zkey_vk,
+                |vk| vk.n_public,
+                |vk| Ok(Groth16::<Bn254>::verify(vk, &proof, public_input)?),
+            )
+        };
+
+        check(
+            read_json("multiplier2/verification_key.json"),
+            &public_input,
+        )
+        .unwrap();
+
+        // both circuits have one public input
+        let err = check(read_json("poseidon/verification_key.json"), &public_input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the verification key does not belong to the zkey the proof was generated with"
+        );
+
+        let mut other_curve = read_json("multiplier2/verification_key.json");
+        other_curve["curve"] = json!("bls12381");
+        let err = check(other_curve, &public_input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the verification key is for curve \"bls12381\", but the proof for \"bn128\""
+        );
+
+        let mut more_inputs = read_json("multiplier2/verification_key.json");
+        more_inputs["nPublic"] = json!(2);
+        let err = check(more_inputs, &public_input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the verification key has 2 public inputs, but the zkey has 1"
+        );
+
+        let wrong_input = [public_input[0] + Fr::from(1)];
+        let err = check(read_json("multiplier2/verification_key.json"), &wrong_input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the Groth16 verifier rejected the proof, although the verification key belongs to the zkey"
+        );
+
+        // generate-proof exits with a failure if the proof does not verify
+        let exit_code = verify_generated_proof::<Bn254, _>(
+            ProofSystem::Groth16,
+            read_json("poseidon/verification_key.json"),
+            &zkey_vk,
+            |vk| vk.n_public,
+            |vk| Ok(Groth16::<Bn254>::verify(vk, &proof, &public_input)?),
+        );
+        assert_eq!(exit_code, ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn montgomery_form_is_converted_back() {
+        // 42 * 2^256 mod p
+        let montgomery = "0x903f860b6f71bd22a638bbbb1d55f3722fc912671306207220d09b5dfffff22";
+        assert_eq!(
+            parse_field::<Fr>(&json!(montgomery), true).unwrap(),
+            Fr::from(42)
+        );
+        // arkworks stores field elements in Montgomery form
+        assert_eq!(
+            Fr::from_bigint(Fr::from(42).0).unwrap(),
+            parse_field::<Fr>(&json!(montgomery), false).unwrap()
+        );
+        let mut rng = rand::thread_rng();
+        for _ in 0..10 {
+            let x = Fr::rand(&mut rng);
+            assert_eq!(from_montgomery(Fr::from_bigint(x.0).unwrap()), x);
+        }
+        assert!(parse_field::<Fr>(&json!("42"), true).is_err());
+    }
+
+    #[test]
+    fn witness_share_of_other_curve_is_rejected() {
+        let mut share = vec![];
+        co_circom::write_witness_share_header(&mut share, MPCCurve::BLS12_381).unwrap();
+        bincode::serialize_into(
+            &mut share,
+            &SharedWitness::<ark_bls12_381::Fr, ShamirPrimeFieldShare<ark_bls12_381::Fr>> {
+                public_inputs: vec![ark_bls12_381::Fr::from(1u64)],
+                witness: vec![],
+            },
+        )
+        .unwrap();
+        let reader = co_circom::WitnessShareReader::new(share.as_slice()).unwrap();
+        let err = check_witness_share_curve(reader.curve(), MPCCurve::BN254).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "witness is BLS12-381 but zkey is BN254, was the witness share created with another --curve?"
+        );
+        check_witness_share_curve(reader.curve(), MPCCurve::BLS12_381).unwrap();
+        // shares without header can not be checked
+        check_witness_share_curve(None, MPCCurve::BN254).unwrap();
+    }
+
+    #[test]
+    fn matching_vk_is_reported() {
+        let dir = "../../test_vectors/Groth16/bn254";
+        let read_json = |file: &str| -> serde_json::Value {
+            serde_json::from_reader(File::open(format!("{dir}/{file}")).unwrap()).unwrap()
+        };
+        let public_inputs: Vec<String> =
+            serde_json::from_value(read_json("multiplier2/public.json")).unwrap();
+        // both circuits have one public input, so the poseidon key fails the pairing check
+        let vks = [
+            PathBuf::from(format!("{dir}/poseidon/verification_key.json")),
+            PathBuf::from(format!("{dir}/multiplier2/verification_key.json")),
+        ];
+        let verified_under = verify_under_vks::<Bn254>(
+            ProofSystem::Groth16,
+            read_json("multiplier2/circom.proof"),
+            &vks,
+            public_inputs.clone(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(verified_under, Some(vks[1].as_path()));
+        let verified_under = verify_under_vks::<Bn254>(
+            ProofSystem::Groth16,
+            read_json("multiplier2/circom.proof"),
+            &vks[..1],
+            public_inputs,
+            false,
+        )
+        .unwrap();
+        assert_eq!(verified_under, None);
+    }
+}
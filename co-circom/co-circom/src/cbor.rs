@@ -0,0 +1,190 @@
+//! A compact CBOR encoding of proofs and public inputs.
+//!
+//! The encoding has the same structure as the JSON format of circom and snarkjs, as it uses the same [serde] implementations. Only the stringified field elements are replaced by their bytes, which roughly halves their size. By default ([ByteOrder::Le]), these are plain byte strings with the little-endian bytes of the value. Standard CBOR bignums (tag 2) are only written with [ByteOrder::Be].
+//!
+//! Which strings are field elements is fixed by the format instead of guessed from their content: the values of the keys in [TEXT_KEYS] stay text, all other strings have to be field elements in canonical decimal form. As the proofs and public inputs contain no other numbers, all integers, bignums and byte strings are read as field elements.
+//!
+//! The byte order of the field elements is chosen with [ByteOrder]. Big-endian field elements are written as bignums, little-endian ones as plain byte strings, as CBOR bignums are big-endian by definition.
+//! Bignums are therefore always read as big-endian, the [ByteOrder] passed to [from_reader] only applies to plain byte strings.
+use std::io::{Read, Write};
+
+use ciborium::value::Value;
+use color_eyre::eyre::{self, Context};
+use num_bigint::BigUint;
+use serde::Serialize;
+
+use crate::ByteOrder;
+
+/// The CBOR tag of an unsigned bignum.
+const BIGNUM_TAG: u64 = 2;
+
+/// The keys of the proofs whose values are text instead of field elements.
+pub const TEXT_KEYS: &[&str] = &["protocol", "curve"];
+
+/// Writes `value` as CBOR, encoding all stringified field elements as byte strings in the given [ByteOrder].
+pub fn to_writer<T: Serialize, W: Write>(
+    value: &T,
+    writer: W,
+    byte_order: ByteOrder,
+) -> eyre::Result<()> {
+    let value = Value::serialized(value).context("while encoding as CBOR")?;
+    ciborium::into_writer(&field_elements_to_bytes(value, byte_order)?, writer)
+        .context("while writing CBOR")
+}
+
+/// Reads a CBOR value written by [to_writer] and returns it in the JSON format of circom and snarkjs, i.e., with all field elements stringified.
+/// `byte_order` must be the one the value was written with.
+pub fn from_reader<R: Read>(reader: R, byte_order: ByteOrder) -> eyre::Result<serde_json::Value> {
+    let value: Value = ciborium::from_reader(reader).context("while reading CBOR")?;
+    to_json(value, byte_order)
+}
+
+/// Replaces every stringified field element by its bytes, as bignum for [ByteOrder::Be]. The values of the [TEXT_KEYS] are kept as text.
+fn field_elements_to_bytes(value: Value, byte_order: ByteOrder) -> eyre::Result<Value> {
+    Ok(match value {
+        Value::Text(text) => {
+            if !is_canonical_decimal(&text) {
+                eyre::bail!("expected a stringified field element, got {text:?}");
+            }
+            let number = text.parse::<BigUint>().expect("is a decimal number");
+            match byte_order {
+                ByteOrder::Le => Value::Bytes(number.to_bytes_le()),
+                ByteOrder::Be => {
+                    Value::Tag(BIGNUM_TAG, Box::new(Value::Bytes(number.to_bytes_be())))
+                }
+            }
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| field_elements_to_bytes(value, byte_order))
+                .collect::<eyre::Result<_>>()?,
+        ),
+        Value::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| match key.as_text() {
+                    Some(text) if TEXT_KEYS.contains(&text) => Ok((key, value)),
+                    _ => Ok((key, field_elements_to_bytes(value, byte_order)?)),
+                })
+                .collect::<eyre::Result<_>>()?,
+        ),
+        value => value,
+    })
+}
+
+/// Returns whether `text` is a decimal number without leading zeros, which is the only form that survives the round trip through bytes.
+fn is_canonical_decimal(text: &str) -> bool {
+    !text.is_empty()
+        && text.bytes().all(|c| c.is_ascii_digit())
+        && (text == "0" || !text.starts_with('0'))
+}
+
+fn to_json(value: Value, byte_order: ByteOrder) -> eyre::Result<serde_json::Value> {
+    Ok(match value {
+        Value::Tag(BIGNUM_TAG, bytes) => match *bytes {
+            Value::Bytes(bytes) => {
+                serde_json::Value::String(BigUint::from_bytes_be(&bytes).to_string())
+            }
+            _ => eyre::bail!("expected a byte string in a CBOR bignum"),
+        },
+        Value::Bytes(bytes) => {
+            let number = match byte_order {
+                ByteOrder::Le => BigUint::from_bytes_le(&bytes),
+                ByteOrder::Be => BigUint::from_bytes_be(&bytes),
+            };
+            serde_json::Value::String(number.to_string())
+        }
+        Value::Text(text) => serde_json::Value::String(text),
+        // small bignums are decoded as integers
+        Value::Integer(int) => serde_json::Value::String(
+            u128::try_from(int)
+                .map_err(|_| eyre::eyre!("expected a field element, got a negative integer"))?
+                .to_string(),
+        ),
+        Value::Bool(b) => serde_json::Value::Bool(b),
+        Value::Null => serde_json::Value::Null,
+        Value::Array(values) => serde_json::Value::Array(
+            values
+                .into_iter()
+                .map(|value| to_json(value, byte_order))
+                .collect::<eyre::Result<_>>()?,
+        ),
+        Value::Map(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Value::Text(key) => Ok((key, to_json(value, byte_order)?)),
+                    _ => eyre::bail!("expected text keys in CBOR maps"),
+                })
+                .collect::<eyre::Result<_>>()?,
+        ),
+        value => eyre::bail!("unsupported CBOR value {value:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROOF: &str = "../../test_vectors/Groth16/bn254/poseidon/circom.proof";
+
+    #[test]
+    fn roundtrip_in_both_byte_orders() {
+        let proof: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(PROOF).unwrap()).unwrap();
+        let mut encoded = vec![];
+        for byte_order in [ByteOrder::Le, ByteOrder::Be] {
+            let mut bytes = vec![];
+            to_writer(&proof, &mut bytes, byte_order).unwrap();
+            assert_eq!(from_reader(bytes.as_slice(), byte_order).unwrap(), proof);
+            encoded.push(bytes);
+        }
+        assert_ne!(encoded[0], encoded[1]);
+        // little-endian byte strings can not be read as big-endian
+        assert_ne!(
+            from_reader(encoded[0].as_slice(), ByteOrder::Be).unwrap(),
+            proof
+        );
+        // bignums are big-endian regardless of the byte order
+        assert_eq!(
+            from_reader(encoded[1].as_slice(), ByteOrder::Le).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn roundtrip_plonk_proof_and_public_inputs() {
+        for file in [
+            "../../test_vectors/Plonk/bn254/poseidon/circom.proof",
+            "../../test_vectors/Plonk/bn254/poseidon/public.json",
+        ] {
+            let value: serde_json::Value =
+                serde_json::from_reader(std::fs::File::open(file).unwrap()).unwrap();
+            for byte_order in [ByteOrder::Le, ByteOrder::Be] {
+                let mut bytes = vec![];
+                to_writer(&value, &mut bytes, byte_order).unwrap();
+                assert_eq!(from_reader(bytes.as_slice(), byte_order).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn text_keys_stay_text() {
+        let value = serde_json::json!({ "protocol": "123", "curve": "bn128", "A": ["0", "42"] });
+        let mut bytes = vec![];
+        to_writer(&value, &mut bytes, ByteOrder::Be).unwrap();
+        assert_eq!(from_reader(bytes.as_slice(), ByteOrder::Be).unwrap(), value);
+    }
+
+    #[test]
+    fn non_canonical_field_elements_are_rejected() {
+        for value in [
+            serde_json::json!(["007"]),
+            serde_json::json!(["groth16"]),
+            serde_json::json!({ "pi_a": [""] }),
+        ] {
+            assert!(to_writer(&value, vec![], ByteOrder::Be).is_err());
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Checkpointing of the REP3 witness extension.
+//!
+//! The state of the MPC-VM lives on the call stack of the running components and can not be written to disk. Instead, a checkpoint records everything that determines the execution of a party: the seed of its randomness (see [`IoContext::init_with_prf_seed`](mpc_core::protocols::rep3::network::IoContext::init_with_prf_seed)) and all messages it received from the other parties. As the witness extension is deterministic otherwise, a rerun with the same circuit and input share replays the recorded messages instead of waiting for them, until it catches up with the point where the checkpoint ends.
+//!
+//! The parties do not need to write their checkpoints at the same time. When resuming, every party tells the others how many of their messages it has recorded, and the others do not send these messages again. Messages that were received but not yet written to disk are simply sent again. All three parties have to resume from their checkpoints of the same run, which is checked when connecting.
+//!
+//! A checkpoint contains the seed of the party's randomness and has to be protected like its input share.
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use co_circom_snarks::SharedInput;
+use color_eyre::eyre::{self, Context};
+use mpc_core::protocols::rep3::{id::PartyID, network::Rep3Network};
+use rand::Rng;
+use sha3::{Digest, Sha3_256};
+
+/// A run id, seed or fingerprint stored in a checkpoint.
+pub type Seed = [u8; 32];
+
+/// The file storing the run id, the seed and the fingerprint of the computation.
+const RUN_FILE: &str = "run";
+
+/// Fingerprints the circuit file and the input share of a witness extension, so a checkpoint of another computation is detected.
+pub fn fingerprint<F: PrimeField, S>(
+    circuit: &Path,
+    input_share: &SharedInput<F, S>,
+) -> eyre::Result<Seed>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    let circuit = fs::read(circuit).context("while reading circuit file")?;
+    let input_share = bincode::serialize(input_share).context("while serializing input share")?;
+    Ok(Sha3_256::new()
+        .chain_update(circuit)
+        .chain_update(input_share)
+        .finalize()
+        .into())
+}
+
+/// A [Rep3Network] that records all received messages in a checkpoint directory and replays them when resuming from it, see the [module documentation](self).
+pub struct CheckpointedNet<N: Rep3Network> {
+    inner: N,
+    next: Transcript,
+    prev: Transcript,
+    checkpoint: Arc<Checkpoint>,
+}
+
+struct Checkpoint {
+    dir: PathBuf,
+    interval: Duration,
+    resume: bool,
+    /// The number of opened channels, every fork of the network has its own transcripts
+    channels: AtomicUsize,
+}
+
+/// The messages received from one of the other parties over one channel.
+struct Transcript {
+    /// The reader of the recorded messages and the number of messages that are left to replay
+    replay: Option<(BufReader<File>, u64)>,
+    log: BufWriter<File>,
+    interval: Duration,
+    last_flush: Instant,
+    /// The number of our messages the other party recorded, which are not sent again
+    skip_sends: u64,
+}
+
+impl<N: Rep3Network> CheckpointedNet<N> {
+    /// Wraps `net` and resumes from the checkpoint in `dir` if there is one, otherwise a new checkpoint is started. The received messages are written to disk at least every `interval`.
+    ///
+    /// The `fingerprint` of the computation (see [fingerprint]) has to match the one of the checkpoint. Returns the network together with the seed to pass to [`to_rep3_vm_with_network_and_prf_seed`](circom_mpc_vm::types::CoCircomCompilerParsed::to_rep3_vm_with_network_and_prf_seed).
+    pub fn open(
+        mut net: N,
+        dir: &Path,
+        interval: Duration,
+        fingerprint: Seed,
+    ) -> eyre::Result<(Self, Seed)> {
+        fs::create_dir_all(dir).context("while creating checkpoint directory")?;
+        let run_path = dir.join(RUN_FILE);
+        let stored = match fs::read(&run_path) {
+            Ok(bytes) if bytes.len() == 3 * 32 => {
+                let mut chunks = bytes
+                    .chunks_exact(32)
+                    .map(|chunk| Seed::try_from(chunk).expect("chunks have the length of a seed"));
+                let run_id = chunks.next().expect("has three chunks");
+                let prf_seed = chunks.next().expect("has three chunks");
+                if chunks.next() != Some(fingerprint) {
+                    eyre::bail!(
+                        "the checkpoint in {} belongs to another circuit or input share, delete it to start over",
+                        dir.display()
+                    );
+                }
+                Some((run_id, prf_seed))
+            }
+            Ok(_) => eyre::bail!("checkpoint file {} is corrupted", run_path.display()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err).context("while reading checkpoint"),
+        };
+
+        // all parties have to start a new run or resume the same one
+        let ours = stored.map(|(run_id, _)| run_id).unwrap_or_default();
+        let (prev, next) = net
+            .broadcast(ours)
+            .context("while comparing checkpoints with the other parties")?;
+        let (prf_seed, resume) = match stored {
+            Some((_, prf_seed)) if prev == ours && next == ours => {
+                tracing::info!("Resuming from checkpoint in {}", dir.display());
+                (prf_seed, true)
+            }
+            None if prev == Seed::default() && next == Seed::default() => {
+                let mut rng = rand::thread_rng();
+                let run_id: Seed = if net.get_id() == PartyID::ID0 {
+                    let run_id = rng.gen();
+                    net.send(PartyID::ID1, run_id)
+                        .context("while sending run id to party 1")?;
+                    net.send(PartyID::ID2, run_id)
+                        .context("while sending run id to party 2")?;
+                    run_id
+                } else {
+                    net.recv(PartyID::ID0)
+                        .context("while receiving run id of party 0")?
+                };
+                let prf_seed: Seed = rng.gen();
+                fs::write(&run_path, [run_id, prf_seed, fingerprint].concat())
+                    .context("while writing checkpoint")?;
+                tracing::info!("Writing checkpoint to {}", dir.display());
+                (prf_seed, false)
+            }
+            _ => eyre::bail!(
+                "the parties do not have checkpoints of the same run, delete the checkpoints of all parties to start over"
+            ),
+        };
+
+        let checkpoint = Arc::new(Checkpoint {
+            dir: dir.to_owned(),
+            interval,
+            resume,
+            channels: AtomicUsize::new(0),
+        });
+        let net = Self::open_channel(net, checkpoint).context("while opening checkpoint")?;
+        Ok((net, prf_seed))
+    }
+
+    fn open_channel(mut inner: N, checkpoint: Arc<Checkpoint>) -> io::Result<Self> {
+        let channel = checkpoint.channels.fetch_add(1, Ordering::SeqCst);
+        let id = inner.get_id();
+        let mut next = Transcript::open(&checkpoint, channel, "next")?;
+        let mut prev = Transcript::open(&checkpoint, channel, "prev")?;
+        // tell the others how many of their messages we have, so they do not send them again
+        inner.send(id.next_id(), next.recorded())?;
+        inner.send(id.prev_id(), prev.recorded())?;
+        next.skip_sends = inner.recv(id.next_id())?;
+        prev.skip_sends = inner.recv(id.prev_id())?;
+        Ok(Self {
+            inner,
+            next,
+            prev,
+            checkpoint,
+        })
+    }
+
+    fn transcript(&mut self, party: PartyID) -> io::Result<&mut Transcript> {
+        let id = self.inner.get_id();
+        if party == id.next_id() {
+            Ok(&mut self.next)
+        } else if party == id.prev_id() {
+            Ok(&mut self.prev)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot communicate with self",
+            ))
+        }
+    }
+}
+
+impl Transcript {
+    fn open(checkpoint: &Checkpoint, channel: usize, peer: &str) -> io::Result<Self> {
+        let path = checkpoint.dir.join(format!("channel{channel}.{peer}.log"));
+        let (replay, log) = if checkpoint.resume {
+            let mut log = OpenOptions::new()
+                .read(true)
+                .append(true)
+                .create(true)
+                .open(&path)?;
+            let (recorded, len) = count_records(&mut log)?;
+            // a record that was only partially written is received again
+            log.set_len(len)?;
+            let replay = (recorded > 0)
+                .then(|| File::open(&path).map(|file| (BufReader::new(file), recorded)))
+                .transpose()?;
+            (replay, log)
+        } else {
+            (None, File::create(&path)?)
+        };
+        Ok(Self {
+            replay,
+            log: BufWriter::new(log),
+            interval: checkpoint.interval,
+            last_flush: Instant::now(),
+            skip_sends: 0,
+        })
+    }
+
+    /// The number of messages that are replayed from the checkpoint.
+    fn recorded(&self) -> u64 {
+        self.replay.as_ref().map_or(0, |(_, left)| *left)
+    }
+
+    fn recv_many<F: CanonicalDeserialize, N: Rep3Network>(
+        &mut self,
+        inner: &mut N,
+        from: PartyID,
+    ) -> io::Result<Vec<F>> {
+        if let Some((reader, left)) = self.replay.as_mut() {
+            let data = read_record(reader)?.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::UnexpectedEof, "checkpoint ended early")
+            })?;
+            *left -= 1;
+            if *left == 0 {
+                self.replay = None;
+            }
+            return deserialize(&data);
+        }
+        let received = inner.recv_many::<Recorded<F>>(from)?;
+        // the uncompressed serialization of the vector, i.e., its length followed by the elements
+        let len = 8 + received.iter().map(|x| x.bytes.len()).sum::<usize>();
+        self.log.write_all(&(len as u64).to_le_bytes())?;
+        self.log.write_all(&(received.len() as u64).to_le_bytes())?;
+        for x in received.iter() {
+            self.log.write_all(&x.bytes)?;
+        }
+        if self.last_flush.elapsed() >= self.interval {
+            self.log.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(received.into_iter().map(|x| x.value).collect())
+    }
+}
+
+/// A received element together with the bytes it was deserialized from, so the messages can be recorded for any [Rep3Network].
+struct Recorded<F> {
+    value: F,
+    bytes: Vec<u8>,
+}
+
+impl<F: CanonicalDeserialize> Valid for Recorded<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.value.check()
+    }
+}
+
+impl<F: CanonicalDeserialize> CanonicalDeserialize for Recorded<F> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let mut reader = RecordingReader {
+            inner: reader,
+            bytes: Vec::new(),
+        };
+        let value = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            value,
+            bytes: reader.bytes,
+        })
+    }
+}
+
+struct RecordingReader<R> {
+    inner: R,
+    bytes: Vec<u8>,
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Counts the complete records of a transcript and returns their number and total length in bytes.
+fn count_records(log: &mut File) -> io::Result<(u64, u64)> {
+    log.seek(SeekFrom::Start(0))?;
+    let mut reader = BufReader::new(log);
+    let (mut count, mut len) = (0, 0);
+    while let Some(record) = read_record(&mut reader).or_else(|err| match err.kind() {
+        io::ErrorKind::UnexpectedEof => Ok(None),
+        _ => Err(err),
+    })? {
+        count += 1;
+        len += 8 + record.len() as u64;
+    }
+    Ok((count, len))
+}
+
+/// Reads a length-prefixed record, returns `None` at the end of the transcript.
+fn read_record<R: Read>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 8];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let mut record = vec![0; u64::from_le_bytes(len) as usize];
+    reader.read_exact(&mut record)?;
+    Ok(Some(record))
+}
+
+fn deserialize<F: CanonicalDeserialize>(data: &[u8]) -> io::Result<Vec<F>> {
+    Vec::<F>::deserialize_uncompressed(data)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+impl<N: Rep3Network> Rep3Network for CheckpointedNet<N> {
+    fn get_id(&self) -> PartyID {
+        self.inner.get_id()
+    }
+
+    fn reshare_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> io::Result<Vec<F>> {
+        let id = self.get_id();
+        self.send_many(id.next_id(), data)?;
+        self.recv_many(id.prev_id())
+    }
+
+    fn broadcast_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> io::Result<(Vec<F>, Vec<F>)> {
+        let id = self.get_id();
+        self.send_many(id.next_id(), data)?;
+        self.send_many(id.prev_id(), data)?;
+        let recv_next = self.recv_many(id.next_id())?;
+        let recv_prev = self.recv_many(id.prev_id())?;
+        Ok((recv_prev, recv_next))
+    }
+
+    fn send_many<F: CanonicalSerialize>(&mut self, target: PartyID, data: &[F]) -> io::Result<()> {
+        let transcript = self.transcript(target)?;
+        if transcript.skip_sends > 0 {
+            transcript.skip_sends -= 1;
+            return Ok(());
+        }
+        self.inner.send_many(target, data)
+    }
+
+    fn recv_many<F: CanonicalDeserialize>(&mut self, from: PartyID) -> io::Result<Vec<F>> {
+        let id = self.inner.get_id();
+        if from == id.next_id() {
+            self.next.recv_many(&mut self.inner, from)
+        } else if from == id.prev_id() {
+            self.prev.recv_many(&mut self.inner, from)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot recv from self",
+            ))
+        }
+    }
+
+    fn fork(&mut self) -> io::Result<Self> {
+        let inner = self.inner.fork()?;
+        Self::open_channel(inner, Arc::clone(&self.checkpoint))
+    }
+}
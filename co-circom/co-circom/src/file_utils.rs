@@ -1,4 +1,8 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
 
 /// An error type for file utility functions.
 #[derive(Debug, thiserror::Error)]
@@ -15,6 +19,12 @@ pub enum Error {
     /// The path was expected to be a file, but it is a directory.
     #[error("Expected {0} to be a file, but it is a directory.")]
     ExpectedFile(PathBuf),
+    /// Both a single file and the parts of a split file exist for the same path.
+    #[error("Both {0} and its parts exist, remove the stale one")]
+    AmbiguousParts(PathBuf),
+    /// The URL could not be fetched.
+    #[error("Could not fetch {0}: {1}")]
+    UrlNotReachable(String, Box<ureq::Error>),
     /// An I/O error occurred.
     #[error(transparent)]
     IoError(#[from] std::io::Error),
@@ -41,3 +51,216 @@ pub fn check_dir_exists(dir_path: &Path) -> Result<(), Error> {
     }
     Ok(())
 }
+
+/// Returns the path as URL if it is an `http://` or `https://` URL.
+pub fn as_url(path: &Path) -> Option<&str> {
+    path.to_str()
+        .filter(|s| s.starts_with("http://") || s.starts_with("https://"))
+}
+
+/// Opens a local file or an `http(s)://` URL for reading.
+///
+/// For local files, this checks that the file exists (see [check_file_exists]). For URLs, a GET request is sent and an error is returned if the server is not reachable or does not answer with a success status. The response body is read from the connection while it is consumed, it is not downloaded first. Note that parsing a zkey still reads all of it into memory, like for a local file.
+pub fn open_file_or_url(path: &Path) -> Result<Box<dyn Read + Send>, Error> {
+    match as_url(path) {
+        Some(url) => {
+            tracing::info!("Fetching {url}");
+            let response = ureq::get(url)
+                .call()
+                .map_err(|err| Error::UrlNotReachable(url.to_owned(), Box::new(err)))?;
+            Ok(Box::new(BufReader::new(response.into_reader())))
+        }
+        None => {
+            check_file_exists(path)?;
+            Ok(Box::new(BufReader::new(File::open(path)?)))
+        }
+    }
+}
+
+/// Returns true if the output path is `-`, which stands for stdout.
+pub fn is_stdout(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Creates the file at the given path for writing, or returns stdout if the path is `-` (see [is_stdout]).
+pub fn create_file_or_stdout(path: &Path) -> Result<Box<dyn Write>, Error> {
+    if is_stdout(path) {
+        Ok(Box::new(BufWriter::new(std::io::stdout().lock())))
+    } else {
+        Ok(Box::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+/// Returns the path of the `part`-th part of a file that is split into multiple parts, e.g., `witness.0.part1.shared` for `witness.0.shared`.
+pub fn part_path(path: &Path, part: usize) -> PathBuf {
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(ext)) => path.with_file_name(format!(
+            "{}.part{part}.{}",
+            stem.to_string_lossy(),
+            ext.to_string_lossy()
+        )),
+        _ => PathBuf::from(format!("{}.part{part}", path.display())),
+    }
+}
+
+/// Writes `bytes` to `path`, or, if `max_part_size` is given, to as many parts (see [part_path]) of at most `max_part_size` bytes as needed.
+///
+/// Returns the paths of the written files in order. A file or parts left at `path` by an earlier write in the other layout are removed, as [open_file_or_parts] refuses to choose between them.
+pub fn write_in_parts(
+    path: &Path,
+    bytes: &[u8],
+    max_part_size: Option<usize>,
+) -> Result<Vec<PathBuf>, Error> {
+    let Some(max_part_size) = max_part_size else {
+        remove_parts(path, 0)?;
+        std::fs::write(path, bytes)?;
+        return Ok(vec![path.to_path_buf()]);
+    };
+    if path.is_file() {
+        std::fs::remove_file(path)?;
+    }
+    let parts = bytes
+        .chunks(max_part_size.max(1))
+        .enumerate()
+        .map(|(part, chunk)| {
+            let part_path = part_path(path, part);
+            std::fs::write(&part_path, chunk)?;
+            Ok(part_path)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    // parts are read until the first missing one, so leftover parts of an earlier split must not follow ours
+    remove_parts(path, parts.len())?;
+    Ok(parts)
+}
+
+// Removes the parts of `path` starting at `first` until the first missing part.
+fn remove_parts(path: &Path, first: usize) -> Result<(), Error> {
+    let mut part = first;
+    while part_path(path, part).is_file() {
+        std::fs::remove_file(part_path(path, part))?;
+        part += 1;
+    }
+    Ok(())
+}
+
+/// Like [check_file_exists], but also accepts a file that was written in parts with [write_in_parts].
+pub fn check_file_or_parts_exist(path: &Path) -> Result<(), Error> {
+    if !path.exists() && part_path(path, 0).is_file() {
+        return Ok(());
+    }
+    check_file_exists(path)
+}
+
+/// Opens a file that was written with [write_in_parts]. If there is no file at `path`, the parts `part0`, `part1`, ... are read one after the other until the first missing part.
+///
+/// Returns [Error::AmbiguousParts] if both the file and its first part exist, as one of them is a stale leftover.
+pub fn open_file_or_parts(path: &Path) -> Result<Box<dyn Read + Send>, Error> {
+    if path.exists() && part_path(path, 0).is_file() {
+        return Err(Error::AmbiguousParts(path.to_path_buf()));
+    }
+    if path.exists() {
+        check_file_exists(path)?;
+        return Ok(Box::new(BufReader::new(File::open(path)?)));
+    }
+    let mut reader: Box<dyn Read + Send> = Box::new(std::io::empty());
+    let mut part = 0;
+    while part_path(path, part).is_file() {
+        let file = BufReader::new(File::open(part_path(path, part))?);
+        reader = Box::new(reader.chain(file));
+        part += 1;
+    }
+    if part == 0 {
+        return Err(Error::FileNotFound(path.to_path_buf()));
+    }
+    tracing::debug!("Reading {} from {part} parts", path.display());
+    Ok(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_dir;
+
+    fn read_all(path: &Path) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        open_file_or_parts(path)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[test]
+    fn parts_round_trip() {
+        let dir = test_dir("parts-round-trip");
+        let path = dir.join("witness.0.shared");
+        let bytes = (0..=255u8).collect::<Vec<_>>();
+
+        let parts = write_in_parts(&path, &bytes, Some(100)).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[1], dir.join("witness.0.part1.shared"));
+        assert!(!path.exists());
+        check_file_or_parts_exist(&path).unwrap();
+        assert_eq!(read_all(&path).unwrap(), bytes);
+
+        // a shorter split removes the parts of the longer one
+        let parts = write_in_parts(&path, &bytes[..150], Some(100)).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(!part_path(&path, 2).exists());
+        assert_eq!(read_all(&path).unwrap(), &bytes[..150]);
+
+        // writing a single file removes the parts
+        write_in_parts(&path, &bytes[..10], None).unwrap();
+        assert!(!part_path(&path, 0).exists());
+        assert_eq!(read_all(&path).unwrap(), &bytes[..10]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn url_body_is_streamed() {
+        use std::io::BufRead;
+        let listener = std::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let url = format!("http://{}/circuit.zkey", listener.local_addr().unwrap());
+        let (rest_tx, rest_rx) = std::sync::mpsc::channel();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                request.read_line(&mut line).unwrap();
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\nfirst")
+                .unwrap();
+            // the rest of the body is only sent once the client read the first part
+            rest_rx.recv().unwrap();
+            stream.write_all(b"end").unwrap();
+        });
+
+        let mut reader = open_file_or_url(Path::new(&url)).unwrap();
+        let mut first = [0; 5];
+        reader.read_exact(&mut first).unwrap();
+        assert_eq!(&first, b"first");
+        rest_tx.send(()).unwrap();
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"end");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn stale_single_file_does_not_shadow_parts() {
+        let dir = test_dir("stale-single-file");
+        let path = dir.join("witness.0.shared");
+        std::fs::write(&path, b"stale").unwrap();
+
+        write_in_parts(&path, b"fresh share", Some(4)).unwrap();
+        assert!(!path.exists());
+        assert_eq!(read_all(&path).unwrap(), b"fresh share");
+
+        // a stale file next to the parts, e.g., copied in by hand, is an error instead of silently winning
+        std::fs::write(&path, b"stale").unwrap();
+        assert!(matches!(read_all(&path), Err(Error::AmbiguousParts(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+//! Commit-reveal coin tossing for the seeds of additive input shares.
+//!
+//! Per default, `split-input` draws all randomness of the shares locally, so whoever splits the input fully controls it. With a joint seed, the input is split by party 0, which keeps the explicit additive share, while the seeds of the compressed shares of party 1 and party 2 are tossed together with the respective party:
+//!
+//! 1. Party 0 sends a commitment to its contribution to party `i`.
+//! 2. Party `i` answers with its own contribution.
+//! 3. Party 0 opens its commitment, and party `i` checks the opening.
+//!
+//! The seed is the hash of both contributions. Neither of the two can bias it, and party 1 and party 2 only learn the seed of their own share. Both parties have to be online while the input is split.
+//!
+//! Both parties get the whole exchange as a [SeedToss], so the seed can be audited afterwards.
+use color_eyre::eyre::{self, Context};
+use mpc_core::protocols::rep3::{id::PartyID, network::Rep3Network};
+use rand::{CryptoRng, Rng};
+use sha3::{Digest, Sha3_256};
+
+/// The seed of a compressed share, matching the seed of [SeedRng](crate::SeedRng).
+pub type Seed = [u8; 32];
+
+fn commit(contribution: &Seed) -> Seed {
+    Sha3_256::digest(contribution).into()
+}
+
+fn derive_seed(ours: &Seed, theirs: &Seed) -> Seed {
+    Sha3_256::new()
+        .chain_update(ours)
+        .chain_update(theirs)
+        .finalize()
+        .into()
+}
+
+/// The transcript of tossing the seed of the share of party 1 or party 2 with party 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedToss {
+    /// The party the share belongs to
+    pub party: PartyID,
+    /// The commitment of party 0 to its contribution
+    pub commitment: Seed,
+    /// The contribution of party 0
+    pub contribution0: Seed,
+    /// The contribution of the party the share belongs to
+    pub contribution: Seed,
+    /// The tossed seed
+    pub seed: Seed,
+}
+
+impl SeedToss {
+    /// Checks that the commitment opens to the contribution of party 0 and that the seed is derived from both contributions.
+    pub fn verify(&self) -> bool {
+        commit(&self.contribution0) == self.commitment
+            && derive_seed(&self.contribution0, &self.contribution) == self.seed
+    }
+
+    /// A hex encoded hash of the seed. It does not reveal the seed, so both parties can log it and compare it to check that they use the same seed.
+    pub fn seed_fingerprint(&self) -> String {
+        commit(&self.seed)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+/// Tosses the seeds of the shares of party 1 and party 2 (in this order), see the [module documentation](self).
+///
+/// Must be called by party 0, while party 1 and party 2 call [contribute_mask_seed] at the same time.
+pub fn toss_mask_seeds<N: Rep3Network, R: Rng + CryptoRng>(
+    net: &mut N,
+    rng: &mut R,
+) -> color_eyre::Result<[SeedToss; 2]> {
+    if net.get_id() != PartyID::ID0 {
+        eyre::bail!(
+            "the input must be split by party 0, which keeps the explicit share, but we are party {}",
+            net.get_id()
+        );
+    }
+    let parties = [PartyID::ID1, PartyID::ID2];
+    let contributions: [Seed; 2] = [rng.gen(), rng.gen()];
+    for (party, contribution) in parties.iter().zip(contributions.iter()) {
+        net.send(*party, commit(contribution))
+            .with_context(|| format!("while sending commitment to party {party}"))?;
+    }
+    let mut tosses = Vec::with_capacity(2);
+    for (party, contribution) in parties.into_iter().zip(contributions) {
+        let theirs: Seed = net
+            .recv(party)
+            .with_context(|| format!("while receiving contribution of party {party}"))?;
+        net.send(party, contribution)
+            .with_context(|| format!("while opening commitment to party {party}"))?;
+        tosses.push(SeedToss {
+            party,
+            commitment: commit(&contribution),
+            contribution0: contribution,
+            contribution: theirs,
+            seed: derive_seed(&contribution, &theirs),
+        });
+    }
+    Ok(tosses.try_into().expect("tossed two seeds"))
+}
+
+/// Contributes to the seed of our own share while party 0 splits the input with [toss_mask_seeds]. Returns the transcript of the toss.
+pub fn contribute_mask_seed<N: Rep3Network, R: Rng + CryptoRng>(
+    net: &mut N,
+    rng: &mut R,
+) -> color_eyre::Result<SeedToss> {
+    if net.get_id() == PartyID::ID0 {
+        eyre::bail!("party 0 splits the input and can not contribute to the seeds");
+    }
+    let commitment: Seed = net
+        .recv(PartyID::ID0)
+        .context("while receiving commitment of party 0")?;
+    let contribution: Seed = rng.gen();
+    net.send(PartyID::ID0, contribution)
+        .context("while sending contribution to party 0")?;
+    let theirs: Seed = net
+        .recv(PartyID::ID0)
+        .context("while receiving opening of party 0")?;
+    let toss = SeedToss {
+        party: net.get_id(),
+        commitment,
+        contribution0: theirs,
+        contribution,
+        seed: derive_seed(&theirs, &contribution),
+    };
+    if !toss.verify() {
+        eyre::bail!("party 0 opened its commitment to a different contribution");
+    }
+    Ok(toss)
+}
@@ -1,14 +1,23 @@
 #![warn(missing_docs)]
 //! This crate provides a binary and associated helper library for running collaborative SNARK proofs.
-use std::{collections::BTreeMap, io::Read, path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    collections::BTreeMap,
+    io::{BufWriter, Read, Write},
+    net::{SocketAddr, ToSocketAddrs},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, Compress, Validate};
 use circom_mpc_compiler::{CoCircomCompiler, CompilerConfig};
-use circom_mpc_vm::mpc_vm::VMConfig;
+use circom_mpc_vm::mpc_vm::{Rep3WitnessExtension, VMConfig};
 use circom_types::{
     groth16::{Groth16Proof, ZKey},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+    Witness,
 };
 use clap::Args;
 use clap::ValueEnum;
@@ -16,30 +25,43 @@ use co_circom_snarks::{
     SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedInput, SharedWitness,
 };
 use co_groth16::Rep3CoGroth16;
-use color_eyre::eyre::Context;
+use color_eyre::eyre::{eyre, Context};
 use figment::{
     providers::{Env, Format, Serialized, Toml},
     Figment,
 };
 use mpc_core::protocols::{
     rep3::{
+        self,
         network::{Rep3MpcNet, Rep3Network},
         Rep3PrimeFieldShare, Rep3ShareVecType,
     },
     shamir::ShamirPrimeFieldShare,
 };
-use mpc_net::config::NetworkConfig;
+use mpc_net::config::{Address, Compression, NetworkConfig, NetworkParty};
 use rand::{CryptoRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 /// The Rng used for expanding compressed Shares
 pub type SeedRng = rand_chacha::ChaCha12Rng;
 
+/// A module for the compact CBOR encoding of proofs and public inputs.
+pub mod cbor;
+/// A module for checkpointing the REP3 witness extension.
+pub mod checkpoint;
 /// A module for file utility functions.
 pub mod file_utils;
+pub mod joint_seed;
+pub mod readiness;
+pub mod reshare;
+pub mod round_stats;
+pub mod snarkjs;
+
+#[cfg(test)]
+mod test_utils;
 
 /// An enum representing the ZK proof system to use.
-#[derive(Debug, Clone, ValueEnum, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
 pub enum ProofSystem {
     /// The Groth16 proof system.
@@ -51,8 +73,17 @@ pub enum ProofSystem {
 impl std::fmt::Display for ProofSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ProofSystem::Groth16 => write!(f, "Plonk"),
-            ProofSystem::Plonk => write!(f, "Groth16"),
+            ProofSystem::Groth16 => write!(f, "Groth16"),
+            ProofSystem::Plonk => write!(f, "Plonk"),
+        }
+    }
+}
+
+impl From<ProofSystem> for circom_verifier::ProofSystem {
+    fn from(proof_system: ProofSystem) -> Self {
+        match proof_system {
+            ProofSystem::Groth16 => circom_verifier::ProofSystem::Groth16,
+            ProofSystem::Plonk => circom_verifier::ProofSystem::Plonk,
         }
     }
 }
@@ -109,6 +140,156 @@ impl std::fmt::Display for MPCProtocol {
     }
 }
 
+/// Cli arguments that override parts of the network config
+#[derive(Debug, Default, Serialize, Args)]
+pub struct NetworkCli {
+    /// The compression of the messages sent between the parties, all parties have to use the same
+    #[arg(long = "net-compression", value_enum)]
+    #[serde(
+        rename = "compression",
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub compression: Option<Compression>,
+    /// Our own id in the network, overrides `my_id` of the network config so all parties can share the same config file
+    #[arg(long, visible_alias = "my-id", env = "COCIRCOM_PARTY_ID")]
+    #[serde(
+        rename = "my_id",
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub party_id: Option<usize>,
+    /// Connect to the other parties through the relay at this address instead of directly, see `mpc_net::relay`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub relay: Option<Address>,
+    /// The local address we listen on, overrides `bind_addr` of the network config
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bind_addr: Option<SocketAddr>,
+    /// The path to our private key, overrides `key_path` of the network config
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub key_path: Option<PathBuf>,
+    /// The address of party 0 (`host:port`), overrides its address in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party0_addr: Option<Address>,
+    /// The address of party 1 (`host:port`), overrides its address in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party1_addr: Option<Address>,
+    /// The address of party 2 (`host:port`), overrides its address in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party2_addr: Option<Address>,
+    /// The path to the certificate of party 0, overrides its certificate in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party0_cert: Option<PathBuf>,
+    /// The path to the certificate of party 1, overrides its certificate in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party1_cert: Option<PathBuf>,
+    /// The path to the certificate of party 2, overrides its certificate in the network config
+    #[arg(long)]
+    #[serde(skip)]
+    pub party2_cert: Option<PathBuf>,
+    /// Read the overrides that are not given as flags from the environment variables `COCIRCOM_PARTY_ID`, `COCIRCOM_BIND_ADDR`, `COCIRCOM_KEY_PATH`, `COCIRCOM_RELAY`, `COCIRCOM_PARTY{i}_ADDR` and `COCIRCOM_PARTY{i}_CERT` for parties 0, 1 and 2.
+    /// Flags take precedence over the environment variables, which take precedence over the config file
+    #[arg(long, default_value_t = false)]
+    #[serde(skip)]
+    pub network_env: bool,
+}
+
+impl NetworkCli {
+    /// Fills the overrides that are not given as flags from the environment variables listed at `network_env`, where `var` looks up a variable.
+    ///
+    /// The overrides are merged over the config file afterwards, so the precedence is flags > environment variables > config file.
+    fn fill_from_env(
+        &mut self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), Box<figment::Error>> {
+        fn fill<T: std::str::FromStr>(
+            field: &mut Option<T>,
+            var: &impl Fn(&str) -> Option<String>,
+            name: &str,
+        ) -> Result<(), Box<figment::Error>>
+        where
+            T::Err: std::fmt::Display,
+        {
+            if field.is_none() {
+                if let Some(value) = var(name) {
+                    let value = value.parse().map_err(|err| {
+                        Box::new(figment::Error::from(format!(
+                            "invalid value \"{value}\" of {name}: {err}"
+                        )))
+                    })?;
+                    *field = Some(value);
+                }
+            }
+            Ok(())
+        }
+
+        fill(&mut self.party_id, &var, "COCIRCOM_PARTY_ID")?;
+        fill(&mut self.bind_addr, &var, "COCIRCOM_BIND_ADDR")?;
+        fill(&mut self.key_path, &var, "COCIRCOM_KEY_PATH")?;
+        fill(&mut self.relay, &var, "COCIRCOM_RELAY")?;
+        fill(&mut self.party0_addr, &var, "COCIRCOM_PARTY0_ADDR")?;
+        fill(&mut self.party1_addr, &var, "COCIRCOM_PARTY1_ADDR")?;
+        fill(&mut self.party2_addr, &var, "COCIRCOM_PARTY2_ADDR")?;
+        fill(&mut self.party0_cert, &var, "COCIRCOM_PARTY0_CERT")?;
+        fill(&mut self.party1_cert, &var, "COCIRCOM_PARTY1_CERT")?;
+        fill(&mut self.party2_cert, &var, "COCIRCOM_PARTY2_CERT")?;
+        Ok(())
+    }
+
+    /// Applies the `--party{i}-addr` and `--party{i}-cert` arguments to the parties of the network config in `figment`.
+    ///
+    /// As figment replaces lists as a whole, we merge the parties ourselves. A party that is not in the network config is added, which requires both its address and its certificate.
+    fn merge_parties(&self, figment: Figment) -> Result<Figment, Box<figment::Error>> {
+        let overrides = [
+            (&self.party0_addr, &self.party0_cert),
+            (&self.party1_addr, &self.party1_cert),
+            (&self.party2_addr, &self.party2_cert),
+        ];
+        if overrides
+            .iter()
+            .all(|(addr, cert)| addr.is_none() && cert.is_none())
+        {
+            return Ok(figment);
+        }
+        let mut parties = if figment.contains("network.parties") {
+            figment.extract_inner::<Vec<NetworkParty>>("network.parties")?
+        } else {
+            vec![]
+        };
+        for (id, (addr, cert)) in overrides.into_iter().enumerate() {
+            match (parties.iter_mut().find(|party| party.id == id), addr, cert) {
+                (_, None, None) => {}
+                (Some(party), addr, cert) => {
+                    if let Some(addr) = addr {
+                        party.dns_name = addr.clone();
+                    }
+                    if let Some(cert) = cert {
+                        party.cert_path = cert.clone();
+                    }
+                }
+                (None, Some(addr), Some(cert)) => parties.push(NetworkParty {
+                    id,
+                    dns_name: addr.clone(),
+                    cert_path: cert.clone(),
+                }),
+                (None, _, _) => {
+                    return Err(Box::new(figment::Error::from(format!(
+                        "party {id} is not in the network config, pass both --party{id}-addr and --party{id}-cert"
+                    ))))
+                }
+            }
+        }
+        parties.sort_by_key(|party| party.id);
+        Ok(figment.merge(Serialized::default("network.parties", parties)))
+    }
+}
+
 /// Cli arguments for `split_witness`
 #[derive(Debug, Default, Serialize, Args)]
 pub struct SplitWitnessCli {
@@ -148,6 +329,36 @@ pub struct SplitWitnessCli {
     /// Share compressed as additive shares
     #[arg(short, long, default_value_t = false)]
     pub additive: bool,
+    /// The ids of the parties to create Shamir shares for (comma separated). Defaults to 0..num_parties.
+    /// The share of party i is written to `<witness>.i.shared`.
+    #[arg(long, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub party_ids: Option<Vec<usize>>,
+    /// The output JSON file where a manifest of the written shares (party id, path and size of each share) is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub manifest: Option<PathBuf>,
+    /// Reconstruct the witness from the shares and compare it to the original before writing them
+    #[arg(long, default_value_t = false)]
+    pub verify_split: bool,
+    /// Split each share into files `<witness>.i.part0.shared`, `<witness>.i.part1.shared`, ... of at most this many bytes.
+    /// The commands reading the share are still given `<witness>.i.shared` and reassemble the parts.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub max_part_size: Option<usize>,
+    /// The file names of the shares in the output directory (comma separated), one per party in the order of the party ids.
+    /// Defaults to `<witness>.i.shared`.
+    #[arg(long, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out_names: Option<Vec<String>>,
+    /// The source of the randomness of the shares
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng: Option<RngSource>,
+    /// The seed of `--rng deterministic`. Anyone knowing the seed can reconstruct the secrets from a single share, so only use this for reproducible tests.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng_seed: Option<u64>,
 }
 
 /// Config for `split_witness`
@@ -171,6 +382,71 @@ pub struct SplitWitnessConfig {
     pub seeded: bool,
     /// Share compressed as additive shares
     pub additive: bool,
+    /// The ids of the parties to create Shamir shares for. Defaults to 0..num_parties.
+    pub party_ids: Option<Vec<usize>>,
+    /// The output JSON file where a manifest of the written shares (party id, path and size of each share) is written to
+    pub manifest: Option<PathBuf>,
+    /// Reconstruct the witness from the shares and compare it to the original before writing them
+    #[serde(default)]
+    pub verify_split: bool,
+    /// The maximum size in bytes of the files a share is split into, a share is written to a single file if not given
+    pub max_part_size: Option<usize>,
+    /// The file names of the shares in the output directory, one per party. Defaults to `<witness>.i.shared`.
+    pub out_names: Option<Vec<String>>,
+    /// The source of the randomness of the shares
+    #[serde(default)]
+    pub rng: RngSource,
+    /// The seed of the seeded rng
+    pub rng_seed: Option<u64>,
+}
+
+/// Cli arguments for `bench_split`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct BenchSplitCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input witness file generated by Circom
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The path to the r1cs file, generated by Circom compiler
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub r1cs: Option<PathBuf>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to the (existing) output directory
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out_dir: Option<PathBuf>,
+    /// The number of parties
+    #[arg(short, long, default_value_t = 3)]
+    pub num_parties: usize,
+}
+
+/// Config for `bench_split`
+#[derive(Debug, Deserialize)]
+pub struct BenchSplitConfig {
+    /// The path to the input witness file generated by Circom
+    pub witness: PathBuf,
+    /// The path to the r1cs file, generated by Circom compiler
+    pub r1cs: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to the (existing) output directory
+    pub out_dir: PathBuf,
+    /// The number of parties
+    pub num_parties: usize,
 }
 
 /// Cli arguments for `split_input`
@@ -206,6 +482,36 @@ pub struct SplitInputCli {
     /// Share compressed as additive shares
     #[arg(short, long, default_value_t = false)]
     pub additive: bool,
+    /// The ids of parties run by the same operator (comma separated), whose shares are written to a single file `<input>.<id>_<id>.shared`.
+    /// Whoever holds this file can reconstruct the private inputs.
+    #[arg(long, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub group: Option<Vec<usize>>,
+    /// The input shares of parties 0, 1 and 2 of an earlier split, the new shares are merged into these files in place instead of written to the output directory.
+    /// A file with the grouped shares of two parties (see `--group`) replaces their two single files.
+    #[arg(long, num_args = 2..=3, conflicts_with = "group")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub merge_into: Option<Vec<PathBuf>>,
+    /// Toss the seeds of the additive shares together with parties 1 and 2, which run `contribute-seed` at the same time, instead of drawing them locally.
+    /// Requires `--seeded --additive` and the network config of party 0 in the config file.
+    #[arg(long, default_value_t = false)]
+    pub joint_seed: bool,
+    /// Advanced: interpret the field elements of the input as hex encoded Montgomery form, as dumped by some low-level tooling, and convert them to canonical field elements.
+    /// All values except the ones of scaled signals must be `0x`-prefixed hex strings then. Montgomery form is not detectable, so a canonical input is silently turned into different values.
+    #[arg(long, default_value_t = false)]
+    pub input_montgomery: bool,
+    /// The file names of the shares of parties 0, 1 and 2 in the output directory (comma separated). Defaults to `<input>.i.shared`.
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["group", "merge_into"])]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out_names: Option<Vec<String>>,
+    /// The source of the randomness of the shares
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng: Option<RngSource>,
+    /// The seed of `--rng deterministic`. Anyone knowing the seed can reconstruct the secrets from a single share, so only use this for reproducible tests.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng_seed: Option<u64>,
 }
 
 /// Config for `split_input`
@@ -219,8 +525,8 @@ pub struct SplitInputConfig {
     pub protocol: MPCProtocol,
     /// The pairing friendly curve to be used
     pub curve: MPCCurve,
-    /// The path to the (existing) output directory
-    pub out_dir: PathBuf,
+    /// The path to the (existing) output directory, required unless `merge_into` is given
+    pub out_dir: Option<PathBuf>,
     /// MPC compiler config
     #[serde(default)]
     pub compiler: CompilerConfig,
@@ -228,6 +534,45 @@ pub struct SplitInputConfig {
     pub seeded: bool,
     /// Share compressed as additive shares
     pub additive: bool,
+    /// Fixed-point scale factors for input signals, e.g., `price = 100` turns `"3.14"` into `314`.
+    /// Values of scaled signals may be decimals with a fractional part, but multiplied with the scale they must be whole numbers.
+    #[serde(default)]
+    pub scales: BTreeMap<String, u64>,
+    /// The ids of parties run by the same operator, whose shares are written to a single file
+    pub group: Option<Vec<usize>>,
+    /// The input shares of parties 0, 1 and 2 to merge the new shares into
+    pub merge_into: Option<Vec<PathBuf>>,
+    /// Toss the seeds of the additive shares together with the other parties, see [joint_seed]
+    #[serde(default)]
+    pub joint_seed: bool,
+    /// Network config, only needed for `joint_seed`
+    pub network: Option<NetworkConfig>,
+    /// Interpret the hex field elements of the input as Montgomery form
+    #[serde(default)]
+    pub input_montgomery: bool,
+    /// The file names of the shares of parties 0, 1 and 2 in the output directory. Defaults to `<input>.i.shared`.
+    pub out_names: Option<Vec<String>>,
+    /// The source of the randomness of the shares
+    #[serde(default)]
+    pub rng: RngSource,
+    /// The seed of the seeded rng
+    pub rng_seed: Option<u64>,
+}
+
+/// Cli arguments for `contribute_seed`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct ContributeSeedCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+}
+
+/// Config for `contribute_seed`
+#[derive(Debug, Deserialize)]
+pub struct ContributeSeedConfig {
+    /// Network config
+    pub network: NetworkConfig,
 }
 
 /// Cli arguments for `merge_input_shares`
@@ -252,6 +597,17 @@ pub struct MergeInputSharesCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// The index of the party the input shares belong to. Only needed if it can not be derived from the file names (`<name>.<party>.shared`)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub party: Option<usize>,
+    /// The path to the circuit file. If given, the merged input share is checked to contain exactly the input signals of the circuit
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<String>,
+    /// Add up the shares of an input that is shared in more than one input share, e.g., to aggregate the contributions of several dealers, instead of rejecting it
+    #[arg(long, default_value_t = false)]
+    pub additive: bool,
 }
 
 /// Config for `merge_input_shares`
@@ -265,6 +621,18 @@ pub struct MergeInputSharesConfig {
     pub curve: MPCCurve,
     /// The output file where the merged input share is written to
     pub out: PathBuf,
+    /// The index of the party the input shares belong to
+    #[serde(default)]
+    pub party: Option<usize>,
+    /// The path to the circuit file the merged input share is checked against
+    #[serde(default)]
+    pub circuit: Option<String>,
+    /// Add up the shares of an input that is shared in more than one input share instead of rejecting it
+    #[serde(default)]
+    pub additive: bool,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
 }
 
 /// Cli arguments for `generate_witness`
@@ -274,10 +642,10 @@ pub struct GenerateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the input share file
+    /// The path to the input share file. Can be given multiple times, e.g., for the shares of several `split-input` runs, in which case the input shares are merged as in `merge-input-shares`
     #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub input: Option<PathBuf>,
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub input: Vec<PathBuf>,
     /// The path to the circuit file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
@@ -294,13 +662,35 @@ pub struct GenerateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// Only validate the config, the input files and the network config without connecting to the other parties
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Record the time spent in every template and write it to this file in the folded stack format, e.g., for `inferno-flamegraph`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub profile: Option<PathBuf>,
+    /// Record a checkpoint of the witness extension in this directory, or resume from the checkpoint in it. Either all parties or none must pass a checkpoint directory
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub checkpoint: Option<PathBuf>,
+    /// The interval in seconds in which the checkpoint is written to disk
+    #[arg(long, default_value_t = 10)]
+    pub checkpoint_interval: u64,
+    /// INSECURE, for debugging only: also open the extended witness and write it to this file as a circom witness file. All parties must pass this flag and all of them have to run on this host, otherwise the command is refused
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub plaintext_out: Option<PathBuf>,
+    /// Overrides for the network config
+    #[command(flatten)]
+    pub network: NetworkCli,
 }
 
 /// Config for `generate_witness`
 #[derive(Debug, Deserialize)]
 pub struct GenerateWitnessConfig {
-    /// The path to the input share file
-    pub input: PathBuf,
+    /// The path to the input share file. A list of input share files is merged as in `merge-input-shares`
+    #[serde(deserialize_with = "one_or_many")]
+    pub input: Vec<PathBuf>,
     /// The path to the circuit file
     pub circuit: String,
     /// The MPC protocol to be used
@@ -315,6 +705,17 @@ pub struct GenerateWitnessConfig {
     /// MPC VM config
     #[serde(default)]
     pub vm: VMConfig,
+    /// Only validate the config, the input files and the network config without connecting to the other parties
+    #[serde(default)]
+    pub dry_run: bool,
+    /// The output file where the time spent in every template is written to in the folded stack format
+    pub profile: Option<PathBuf>,
+    /// The directory where a checkpoint of the witness extension is recorded or resumed from
+    pub checkpoint: Option<PathBuf>,
+    /// The interval in seconds in which the checkpoint is written to disk
+    pub checkpoint_interval: u64,
+    /// The output file where the opened witness is written to as a circom witness file. INSECURE, only allowed if all parties run on the same host, see [check_single_host_network]
+    pub plaintext_out: Option<PathBuf>,
     /// Network config
     pub network: NetworkConfig,
 }
@@ -346,6 +747,12 @@ pub struct TranslateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// Jointly check that the translated shares hold the same values as the source shares, aborting otherwise
+    #[arg(long, default_value_t = false)]
+    pub self_check: bool,
+    /// Overrides for the network config
+    #[command(flatten)]
+    pub network: NetworkCli,
 }
 
 /// Config for `transalte_witness`
@@ -361,6 +768,81 @@ pub struct TranslateWitnessConfig {
     pub curve: MPCCurve,
     /// The output file where the final witness share is written to
     pub out: PathBuf,
+    /// Jointly check that the translated shares hold the same values as the source shares, aborting otherwise
+    #[serde(default)]
+    pub self_check: bool,
+    /// Network config
+    pub network: NetworkConfig,
+}
+
+/// Cli arguments for `reshare`
+#[derive(Debug, Serialize, Args)]
+pub struct ReshareCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the witness share file. Only given by the parties 0, 1 and 2, which hold the shares of the source protocol
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The MPC protocol of the witness shares
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub src_protocol: Option<MPCProtocol>,
+    /// The MPC protocol of the new witness shares
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub target_protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The threshold of tolerated colluding parties of the new sharing
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+    /// The number of parties of the new sharing. The network consists of the maximum of this number and 3 parties
+    #[arg(short, long, default_value_t = 3)]
+    pub num_parties: usize,
+    /// The output file where the new witness share is written to. Not needed by parties that do not get a new share
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The source of the randomness of the new shares
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng: Option<RngSource>,
+    /// The seed of `--rng deterministic`. Anyone knowing the seed can reconstruct the secrets from a single share, so only use this for reproducible tests.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub rng_seed: Option<u64>,
+    /// Overrides for the network config
+    #[command(flatten)]
+    pub network: NetworkCli,
+}
+
+/// Config for `reshare`
+#[derive(Debug, Deserialize)]
+pub struct ReshareConfig {
+    /// The path to the witness share file. Only given by the parties 0, 1 and 2, which hold the shares of the source protocol
+    pub witness: Option<PathBuf>,
+    /// The MPC protocol of the witness shares
+    pub src_protocol: MPCProtocol,
+    /// The MPC protocol of the new witness shares
+    pub target_protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The threshold of tolerated colluding parties of the new sharing
+    pub threshold: usize,
+    /// The number of parties of the new sharing. The network consists of the maximum of this number and 3 parties
+    pub num_parties: usize,
+    /// The output file where the new witness share is written to. Not needed by parties that do not get a new share
+    pub out: Option<PathBuf>,
+    /// The source of the randomness of the new shares
+    #[serde(default)]
+    pub rng: RngSource,
+    /// The seed of the seeded rng
+    pub rng_seed: Option<u64>,
     /// Network config
     pub network: NetworkConfig,
 }
@@ -379,7 +861,7 @@ pub struct GenerateProofCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub witness: Option<PathBuf>,
-    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, generated by snarkjs setup phase
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub zkey: Option<PathBuf>,
@@ -391,17 +873,67 @@ pub struct GenerateProofCli {
     #[arg(long, value_enum)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub curve: Option<MPCCurve>,
-    /// The output file where the final proof is written to. If not passed, this party will not write the proof to a file.
+    /// The output file where the final proof is written to, or `-` for stdout. If not passed, this party will not write the proof to a file.
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
-    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
+    /// The output JSON file where the public inputs are written to, or `-` for stdout. If not passed, this party will not write the public inputs to a file.
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub public_input: Option<PathBuf>,
+    /// The directory where `proof.json` and `public.json` are written to, with the names and the formatting of snarkjs. Can be combined with --out and --public-input.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub snarkjs_out: Option<PathBuf>,
     /// The threshold of tolerated colluding parties
     #[arg(short, long, default_value_t = 1)]
     pub threshold: usize,
+    /// Only validate the config, the input files and the network config without connecting to the other parties
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// The number of threads used for the local computations of the prover, e.g., the MSMs. Defaults to the number of available cores.
+    /// Must be at least 2, as the prover waits on one thread for the MSMs it runs on the others
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub threads: Option<usize>,
+    /// The path to the r1cs file the witness was generated for. If given, the zkey is checked to belong to the same compilation of the circuit before proving
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub r1cs: Option<PathBuf>,
+    /// Reconstruct the witness from the shares of all parties and abort before proving if it does not satisfy the constraints of --r1cs.
+    /// INSECURE: every party learns the whole witness, only use this in test environments. Only supported for REP3, all parties must pass this flag.
+    #[arg(long, default_value_t = false, requires = "r1cs")]
+    pub check_witness: bool,
+    /// The encoding of the proof and public input files
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+    /// The byte order of the field elements in the CBOR output. Only `be` writes standard CBOR bignums. Ignored for JSON
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub byte_order: Option<ByteOrder>,
+    /// Serve a readiness endpoint on this address, which answers HTTP requests with 503 until the zkey is parsed and the other parties can connect to this party, and with 200 afterwards
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub ready_addr: Option<SocketAddr>,
+    /// Read the witness share element by element instead of buffering the whole file, which roughly halves the peak memory for large witnesses.
+    /// All parties must pass this flag if the shares are additive.
+    #[arg(long, default_value_t = false)]
+    pub lazy_witness: bool,
+    /// Verify the proof under --vk after proving, with the public inputs taken from the witness. Exits with a non-zero code if the proof does not verify
+    #[arg(long, default_value_t = false, requires = "vk")]
+    pub verify: bool,
+    /// The path to (or http(s) URL of) the verification key file the proof is checked against with --verify
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The ids of the parties the Shamir witness shares were created for with `split_witness --party-ids` (comma separated), in the order of the parties in the network config. Defaults to 0..num_parties.
+    #[arg(long, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub party_ids: Option<Vec<usize>>,
+    /// Overrides for the network config
+    #[command(flatten)]
+    pub network: NetworkCli,
 }
 
 /// Config for `generate_proof`
@@ -411,7 +943,7 @@ pub struct GenerateProofConfig {
     pub proof_system: ProofSystem,
     /// The path to the witness share file
     pub witness: PathBuf,
-    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, generated by snarkjs setup phase
     pub zkey: PathBuf,
     /// The MPC protocol to be used
     pub protocol: MPCProtocol,
@@ -421,15 +953,45 @@ pub struct GenerateProofConfig {
     pub out: Option<PathBuf>,
     /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
     pub public_input: Option<PathBuf>,
+    /// The directory where `proof.json` and `public.json` are written to in the layout of snarkjs
+    pub snarkjs_out: Option<PathBuf>,
     /// The threshold of tolerated colluding parties
     pub threshold: usize,
+    /// Only validate the config, the input files and the network config without connecting to the other parties
+    #[serde(default)]
+    pub dry_run: bool,
+    /// The number of threads used for the local computations of the prover, e.g., the MSMs. Defaults to the number of available cores, must be at least 2
+    pub threads: Option<usize>,
+    /// The path to the r1cs file the witness was generated for. If given, the zkey is checked to belong to the same compilation of the circuit before proving
+    pub r1cs: Option<PathBuf>,
+    /// Reconstruct the witness and check it against the r1cs before proving, which reveals the witness to all parties
+    #[serde(default)]
+    pub check_witness: bool,
+    /// The encoding of the proof and public input files
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// The byte order of the field elements in the CBOR output
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// The address of the readiness endpoint, which reports whether the party is ready to prove
+    pub ready_addr: Option<SocketAddr>,
+    /// Read the witness share element by element instead of buffering the whole file
+    #[serde(default)]
+    pub lazy_witness: bool,
+    /// Verify the proof under the verification key `vk` after proving
+    #[serde(default)]
+    pub verify: bool,
+    /// The verification key for `verify`
+    pub vk: Option<PathBuf>,
+    /// The ids of the parties the Shamir witness shares were created for. Defaults to 0..num_parties.
+    pub party_ids: Option<Vec<usize>>,
     /// Network config
     pub network: NetworkConfig,
 }
 
-/// Cli arguments for `verify`
+/// Cli arguments for `prove_from_input`
 #[derive(Debug, Serialize, Args)]
-pub struct VerifyCli {
+pub struct ProveFromInputCli {
     /// The proof system to be used
     #[arg(value_enum)]
     pub proof_system: ProofSystem,
@@ -437,64 +999,850 @@ pub struct VerifyCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the proof file
+    /// The path to the input share file. Can be given multiple times, in which case the input shares are merged as in `merge-input-shares`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub input: Vec<PathBuf>,
+    /// The path to the circuit file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub proof: Option<PathBuf>,
+    pub circuit: Option<String>,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, generated by snarkjs setup phase
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
     /// The pairing friendly curve to be used
     #[arg(long, value_enum)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub curve: Option<MPCCurve>,
-    /// The path to the verification key file
+    /// The output file where the final proof is written to, or `-` for stdout. If not passed, this party will not write the proof to a file.
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub vk: Option<PathBuf>,
-    /// The path to the public input JSON file
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to, or `-` for stdout. If not passed, this party will not write the public inputs to a file.
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub public_input: Option<PathBuf>,
+    /// The encoding of the proof and public input files
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+    /// The byte order of the field elements in the CBOR output. Only `be` writes standard CBOR bignums. Ignored for JSON
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub byte_order: Option<ByteOrder>,
+    /// The number of threads used for the local computations of the prover, e.g., the MSMs. Defaults to the number of available cores.
+    /// Must be at least 2, as the prover waits on one thread for the MSMs it runs on the others
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub threads: Option<usize>,
+    /// Overrides for the network config
+    #[command(flatten)]
+    pub network: NetworkCli,
 }
 
-/// Config for `verify`
+/// Config for `prove_from_input`
 #[derive(Debug, Deserialize)]
-pub struct VerifyConfig {
+pub struct ProveFromInputConfig {
     /// The proof system to be used
     pub proof_system: ProofSystem,
-    /// The path to the proof file
-    pub proof: PathBuf,
+    /// The path to the input share file. A list of input share files is merged as in `merge-input-shares`
+    #[serde(deserialize_with = "one_or_many")]
+    pub input: Vec<PathBuf>,
+    /// The path to the circuit file
+    pub circuit: String,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file
+    pub zkey: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
     /// The pairing friendly curve to be used
     pub curve: MPCCurve,
-    /// The path to the verification key file
-    pub vk: PathBuf,
-    /// The path to the public input JSON file
-    pub public_input: PathBuf,
-}
-
-/// Prefix for config env variables
-pub const CONFIG_ENV_PREFIX: &str = "COCIRCOM_";
-
-/// Error type for config parsing and merging
+    /// The output file where the final proof is written to
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to
+    pub public_input: Option<PathBuf>,
+    /// The encoding of the proof and public input files
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// The byte order of the field elements in the CBOR output
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// The number of threads used for the local computations of the prover, e.g., the MSMs. Defaults to the number of available cores, must be at least 2
+    pub threads: Option<usize>,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// MPC VM config
+    #[serde(default)]
+    pub vm: VMConfig,
+    /// Network config
+    pub network: NetworkConfig,
+}
+
+/// Cli arguments for `verify`
+#[derive(Debug, Serialize, Args)]
+pub struct VerifyCli {
+    /// The proof system to be used. Not needed when verifying a bundle.
+    #[arg(value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof_system: Option<ProofSystem>,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to a proof bundle created with `bundle`. If given, the proof system, curve, proof, verification key and public inputs are taken from the bundle.
+    #[arg(long, conflicts_with_all = ["proof_system", "config", "proof", "curve", "vk", "public_input", "circuit", "result_json"])]
+    #[serde(skip)]
+    pub bundle: Option<PathBuf>,
+    /// The path to the proof file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The encoding of the proof file
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof_format: Option<ProofFormat>,
+    /// The byte order of the field elements in CBOR proof and public input files, must match the one they were written with
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub byte_order: Option<ByteOrder>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to (or http(s) URL of) the verification key file. Can be given multiple times, in which case the proof is checked against each key in turn until one of them accepts it, and the path of that key is printed
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub vk: Vec<PathBuf>,
+    /// The path to the public input JSON file. Either an array of stringified field elements or an object mapping the public signals to their values. The CBOR encoding of `generate-proof --output-format cbor` is detected automatically
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The path to the circuit file. Only needed if the public inputs are given as an object, to bring them into the order of the verifier
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<String>,
+    /// Only check that the proof is well-formed, i.e., that its points are on the curve and in the prime order subgroup and that the number of public inputs matches the verification key, but skip the pairing check. A proof that passes this check may still be invalid
+    #[arg(long, default_value_t = false)]
+    pub structural_only: bool,
+    /// Skip checking that the points of the proof and the verification key are in the prime order subgroup. They are still checked to be on the curve. Only use this for trusted inputs, points outside of the subgroup can make invalid proofs verify
+    #[arg(long, default_value_t = false)]
+    pub skip_subgroup_check: bool,
+    /// The output JSON file where the outcome of the verification is written to, see [VerifyResult]. Written whether the proof verifies or not
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub result_json: Option<PathBuf>,
+}
+
+/// Config for `verify`
+#[derive(Debug, Deserialize)]
+pub struct VerifyConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the proof file
+    pub proof: PathBuf,
+    /// The encoding of the proof file
+    #[serde(default)]
+    pub proof_format: ProofFormat,
+    /// The byte order of the field elements in CBOR proof and public input files
+    #[serde(default)]
+    pub byte_order: ByteOrder,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to (or http(s) URL of) the verification key file. A list of keys is tried in order until one of them accepts the proof
+    #[serde(deserialize_with = "one_or_many")]
+    pub vk: Vec<PathBuf>,
+    /// The path to the public input JSON file. Either an array of stringified field elements or an object mapping the public signals to their values. The CBOR encoding of `generate-proof --output-format cbor` is detected automatically
+    pub public_input: PathBuf,
+    /// The path to the circuit file. Only needed if the public inputs are given as an object, to bring them into the order of the verifier
+    pub circuit: Option<String>,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// Only check that the proof is well-formed and skip the pairing check
+    #[serde(default)]
+    pub structural_only: bool,
+    /// Skip checking that the points of the proof and the verification key are in the prime order subgroup
+    #[serde(default)]
+    pub skip_subgroup_check: bool,
+    /// The output JSON file where the outcome of the verification is written to
+    pub result_json: Option<PathBuf>,
+}
+
+/// Deserializes either a single value or a list of values, so config files can keep using a single path.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+/// Cli arguments for `bundle`
+#[derive(Debug, Serialize, Args)]
+pub struct BundleCli {
+    /// The proof system to be used
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the proof file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to (or http(s) URL of) the verification key file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The path to the public input JSON file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The output file where the bundle is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `bundle`
+#[derive(Debug, Deserialize)]
+pub struct BundleConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the proof file
+    pub proof: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to (or http(s) URL of) the verification key file
+    pub vk: PathBuf,
+    /// The path to the public input JSON file
+    pub public_input: PathBuf,
+    /// The output file where the bundle is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `aggregate`
+#[derive(Debug, Serialize, Args)]
+pub struct AggregateCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to a Groth16 proof file. Can be given multiple times, once for each proof
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub proof: Vec<PathBuf>,
+    /// The path to the public input JSON file of a proof. Must be given once for each proof, in the same order as the proofs
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub public_input: Vec<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to (or http(s) URL of) the verification key file shared by all proofs
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The output file where the aggregated proof is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `aggregate`
+#[derive(Debug, Deserialize)]
+pub struct AggregateConfig {
+    /// The paths to the Groth16 proof files
+    pub proof: Vec<PathBuf>,
+    /// The paths to the public input JSON files, one for each proof
+    pub public_input: Vec<PathBuf>,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to (or http(s) URL of) the verification key file shared by all proofs
+    pub vk: PathBuf,
+    /// The output file where the aggregated proof is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `verify-aggregated`
+#[derive(Debug, Serialize, Args)]
+pub struct VerifyAggregatedCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the aggregated proof file created with `aggregate`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to (or http(s) URL of) the verification key file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+}
+
+/// Config for `verify-aggregated`
+#[derive(Debug, Deserialize)]
+pub struct VerifyAggregatedConfig {
+    /// The path to the aggregated proof file
+    pub proof: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to (or http(s) URL of) the verification key file
+    pub vk: PathBuf,
+}
+
+/// The encoding of a proof file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[clap(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ProofFormat {
+    /// The JSON format of circom and snarkjs.
+    #[default]
+    Json,
+    /// The compressed byte form of arkworks' `CanonicalSerialize`. Only supported for Groth16.
+    Bin,
+    /// The CBOR encoding written by `generate-proof --output-format cbor`, see [cbor].
+    Cbor,
+}
+
+/// The encoding of the proof and public input files written by `generate-proof`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[clap(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// The JSON format of circom and snarkjs.
+    #[default]
+    Json,
+    /// A compact CBOR encoding with the same structure as the JSON format, but field elements as byte strings, see [cbor].
+    /// With the default `--byte-order le`, these are little-endian byte strings and not standard CBOR bignums, which need `--byte-order be`.
+    Cbor,
+}
+
+/// The byte order of the field elements in the CBOR encoding, see [cbor].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[clap(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ByteOrder {
+    /// Little-endian, the native order of arkworks.
+    #[default]
+    Le,
+    /// Big-endian, as expected by EVM tooling.
+    Be,
+}
+
+/// The source of the randomness used for creating shares.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[clap(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RngSource {
+    /// The random number generator of the operating system.
+    Os,
+    /// The thread-local generator of `rand`, which is periodically reseeded from the operating system.
+    #[default]
+    Thread,
+    /// A [SeedRng] seeded with `rng_seed`. Only meant for reproducible tests, as anyone knowing the seed can reconstruct the secrets from a single share.
+    /// Not to be confused with `--seeded`, which selects the compressed share format. `seeded` is still accepted as its old name.
+    #[value(alias = "seeded")]
+    #[serde(alias = "seeded")]
+    Deterministic,
+}
+
+/// The random number generator selected with an [RngSource], so that the sharing functions can be called with a single type.
+pub enum ShareRng {
+    /// See [RngSource::Os]
+    Os(rand::rngs::OsRng),
+    /// See [RngSource::Thread]
+    Thread(rand::rngs::ThreadRng),
+    /// See [RngSource::Deterministic]
+    Deterministic(Box<SeedRng>),
+}
+
+impl ShareRng {
+    /// Creates the random number generator for `source`. The `seed` is required for [RngSource::Deterministic] and rejected for the other sources.
+    pub fn new(source: RngSource, seed: Option<u64>) -> color_eyre::Result<Self> {
+        match (source, seed) {
+            (RngSource::Os, None) => Ok(Self::Os(rand::rngs::OsRng)),
+            (RngSource::Thread, None) => Ok(Self::Thread(rand::thread_rng())),
+            (RngSource::Deterministic, Some(seed)) => {
+                Ok(Self::Deterministic(Box::new(SeedRng::seed_from_u64(seed))))
+            }
+            (RngSource::Deterministic, None) => {
+                Err(eyre!("--rng deterministic requires --rng-seed"))
+            }
+            (_, Some(_)) => Err(eyre!("--rng-seed is only used with --rng deterministic")),
+        }
+    }
+}
+
+impl rand::RngCore for ShareRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::Os(rng) => rng.next_u32(),
+            Self::Thread(rng) => rng.next_u32(),
+            Self::Deterministic(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::Os(rng) => rng.next_u64(),
+            Self::Thread(rng) => rng.next_u64(),
+            Self::Deterministic(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::Os(rng) => rng.fill_bytes(dest),
+            Self::Thread(rng) => rng.fill_bytes(dest),
+            Self::Deterministic(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            Self::Os(rng) => rng.try_fill_bytes(dest),
+            Self::Thread(rng) => rng.try_fill_bytes(dest),
+            Self::Deterministic(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+impl CryptoRng for ShareRng {}
+
+/// The radix in which field elements are written.
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize)]
+#[clap(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Radix {
+    /// Decimal strings, as written by circom and snarkjs.
+    Decimal,
+    /// `0x`-prefixed hexadecimal strings, zero-padded to the byte length of the field.
+    Hex,
+}
+
+/// Cli arguments for `convert_public_inputs`
+#[derive(Debug, Serialize, Args)]
+pub struct ConvertPublicInputsCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the public input JSON file to convert
+    #[arg(long = "in")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub input: Option<PathBuf>,
+    /// The output file where the converted public inputs are written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The radix the field elements are converted to
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub to: Option<Radix>,
+}
+
+/// Config for `convert_public_inputs`
+#[derive(Debug, Deserialize)]
+pub struct ConvertPublicInputsConfig {
+    /// The path to the public input JSON file to convert
+    pub input: PathBuf,
+    /// The output file where the converted public inputs are written to
+    pub out: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The radix the field elements are converted to
+    pub to: Radix,
+}
+
+/// Cli arguments for `public_signals`
+#[derive(Debug, Serialize, Args)]
+pub struct PublicSignalsCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<String>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `public_signals`
+#[derive(Debug, Deserialize)]
+pub struct PublicSignalsConfig {
+    /// The path to the circuit file
+    pub circuit: String,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+}
+
+/// Cli arguments for `export_vk`
+#[derive(Debug, Serialize, Args)]
+pub struct ExportVkCli {
+    /// The proof system of the zkey
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, generated by snarkjs setup phase
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the verification key is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `export_vk`
+#[derive(Debug, Deserialize)]
+pub struct ExportVkConfig {
+    /// The proof system of the zkey
+    pub proof_system: ProofSystem,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, generated by snarkjs setup phase
+    pub zkey: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the verification key is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `extract_public_inputs`
+#[derive(Debug, Serialize, Args)]
+pub struct ExtractPublicInputsCli {
+    /// The proof system of the zkey
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the plain witness file generated by circom
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file, which determines the number of public inputs
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output JSON file where the public inputs are written to, or `-` for stdout
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `extract_public_inputs`
+#[derive(Debug, Deserialize)]
+pub struct ExtractPublicInputsConfig {
+    /// The proof system of the zkey
+    pub proof_system: ProofSystem,
+    /// The path to the plain witness file
+    pub witness: PathBuf,
+    /// The path to (or http(s) URL of) the proving key (.zkey) file
+    pub zkey: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output JSON file where the public inputs are written to, or `-` for stdout
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `vk_fingerprint`
+#[derive(Debug, Serialize, Args)]
+pub struct VkFingerprintCli {
+    /// The proof system of the verification key
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to (or http(s) URL of) the verification key JSON file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The path to (or http(s) URL of) a zkey file. The fingerprint is computed for the verification key of the zkey, so it matches the fingerprint of the verification key exported from it
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `vk_fingerprint`
+#[derive(Debug, Deserialize)]
+pub struct VkFingerprintConfig {
+    /// The proof system of the verification key
+    pub proof_system: ProofSystem,
+    /// The path to the verification key JSON file
+    #[serde(default)]
+    pub vk: Option<PathBuf>,
+    /// The path to (or http(s) URL of) a zkey file
+    #[serde(default)]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+}
+
+/// Cli arguments for `diff_public_inputs`
+#[derive(Debug, Serialize, Args)]
+pub struct DiffPublicInputsCli {
+    /// The first public input JSON file
+    pub first: PathBuf,
+    /// The second public input JSON file
+    pub second: PathBuf,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `diff_public_inputs`
+#[derive(Debug, Deserialize)]
+pub struct DiffPublicInputsConfig {
+    /// The first public input JSON file
+    pub first: PathBuf,
+    /// The second public input JSON file
+    pub second: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+}
+
+/// Cli arguments for `estimate`
+#[derive(Debug, Serialize, Args)]
+pub struct EstimateCli {
+    /// The proof system of the zkey
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to (or http(s) URL of) the zkey file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `estimate`
+#[derive(Debug, Deserialize)]
+pub struct EstimateConfig {
+    /// The proof system of the zkey
+    pub proof_system: ProofSystem,
+    /// The path to (or http(s) URL of) the zkey file
+    pub zkey: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+}
+
+/// Cli arguments for `rerandomize_proof`
+#[derive(Debug, Serialize, Args)]
+pub struct RerandomizeProofCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the Groth16 proof file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The path to (or http(s) URL of) the verification key file of the proof
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the rerandomized proof is written to. For more than one proof, the index is inserted before the extension, e.g., `out.0.json`, `out.1.json`, ...
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The number of independently rerandomized proofs to create
+    #[arg(long, default_value_t = 1)]
+    pub count: usize,
+}
+
+/// Config for `rerandomize_proof`
+#[derive(Debug, Deserialize)]
+pub struct RerandomizeProofConfig {
+    /// The path to the Groth16 proof file
+    pub proof: PathBuf,
+    /// The path to (or http(s) URL of) the verification key file of the proof
+    pub vk: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file, or the template for the numbered output files if `count` is larger than one
+    pub out: PathBuf,
+    /// The number of independently rerandomized proofs to create
+    pub count: usize,
+}
+
+/// The format tag of a [ProofBundle].
+pub const PROOF_BUNDLE_FORMAT: &str = "co-circom-proof-bundle-v1";
+
+/// The outcome of `verify`, written to `--result-json` for consumption by other tools.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyResult {
+    /// Whether the proof verified, or was well-formed for `structural_only`
+    pub valid: bool,
+    /// Whether only the structure of the proof was checked
+    pub structural_only: bool,
+    /// The proof system in lowercase, e.g. `groth16`
+    pub proof_system: String,
+    /// The curve in lowercase, e.g. `bn254`
+    pub curve: String,
+    /// The time spent on reading the verification keys and verifying the proof in milliseconds
+    pub verify_ms: f64,
+    /// The number of public inputs, missing if they could not be read
+    pub public_input_count: Option<usize>,
+    /// The verification key the proof verified under, missing if it did not verify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// Why the proof did not verify, missing if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// A self-describing JSON bundle of a proof, its verification key and its public inputs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofBundle {
+    /// The format tag, always [PROOF_BUNDLE_FORMAT].
+    pub format: String,
+    /// The proof system the proof was generated with
+    pub proof_system: ProofSystem,
+    /// The pairing friendly curve the proof was generated with
+    pub curve: MPCCurve,
+    /// The proof in the circom JSON format
+    pub proof: serde_json::Value,
+    /// The verification key in the circom JSON format
+    pub vk: serde_json::Value,
+    /// The public inputs as stringified field elements
+    pub public_inputs: Vec<String>,
+}
+
+/// A JSON manifest of the witness shares written by `split_witness`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitWitnessManifest {
+    /// The MPC protocol the shares were created for
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve the shares were created for
+    pub curve: MPCCurve,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The written shares, in the order they were written
+    pub shares: Vec<WitnessShareEntry>,
+}
+
+/// A single witness share in a [SplitWitnessManifest].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WitnessShareEntry {
+    /// The id of the party the share belongs to
+    pub party_id: usize,
+    /// The path the share was written to
+    pub path: PathBuf,
+    /// The size of the share file in bytes
+    pub size: u64,
+}
+
+/// Prefix for config env variables
+pub const CONFIG_ENV_PREFIX: &str = "COCIRCOM_";
+
+/// Error type for config parsing and merging
 #[derive(thiserror::Error, Debug)]
 #[error(transparent)]
 pub struct ConfigError(#[from] figment::error::Error);
 
+/// The config file (if any) merged with the env variables, the cli arguments are merged on top.
+fn file_and_env_figment(config: Option<&Path>) -> Figment {
+    let figment = match config {
+        Some(path) => Figment::new().merge(Toml::file(path)),
+        None => Figment::new(),
+    };
+    figment.merge(Env::prefixed(CONFIG_ENV_PREFIX))
+}
+
 macro_rules! impl_config {
     ($cli: ty, $config: ty) => {
         impl $config {
             /// Parse config from file, env, cli
             pub fn parse(cli: $cli) -> Result<Self, ConfigError> {
-                if let Some(path) = &cli.config {
-                    Ok(Figment::new()
-                        .merge(Toml::file(path))
-                        .merge(Env::prefixed(CONFIG_ENV_PREFIX))
-                        .merge(Serialized::defaults(cli))
-                        .extract()?)
-                } else {
-                    Ok(Figment::new()
-                        .merge(Env::prefixed(CONFIG_ENV_PREFIX))
-                        .merge(Serialized::defaults(cli))
-                        .extract()?)
+                Ok(file_and_env_figment(cli.config.as_deref())
+                    .merge(Serialized::defaults(cli))
+                    .extract()?)
+            }
+        }
+    };
+    ($cli: ty, $config: ty, network) => {
+        impl $config {
+            /// Parse config from file, env, cli
+            pub fn parse(mut cli: $cli) -> Result<Self, ConfigError> {
+                if cli.network.network_env {
+                    cli.network
+                        .fill_from_env(|name| std::env::var(name).ok())
+                        .map_err(|err| ConfigError::from(*err))?;
                 }
+                let figment = cli
+                    .network
+                    .merge_parties(file_and_env_figment(cli.config.as_deref()))
+                    .map_err(|err| ConfigError::from(*err))?;
+                Ok(figment.merge(Serialized::defaults(cli)).extract()?)
             }
         }
     };
@@ -502,45 +1850,79 @@ macro_rules! impl_config {
 
 impl_config!(SplitInputCli, SplitInputConfig);
 impl_config!(SplitWitnessCli, SplitWitnessConfig);
+impl_config!(BenchSplitCli, BenchSplitConfig);
+impl_config!(ContributeSeedCli, ContributeSeedConfig);
 impl_config!(MergeInputSharesCli, MergeInputSharesConfig);
-impl_config!(GenerateWitnessCli, GenerateWitnessConfig);
-impl_config!(TranslateWitnessCli, TranslateWitnessConfig);
-impl_config!(GenerateProofCli, GenerateProofConfig);
+impl_config!(GenerateWitnessCli, GenerateWitnessConfig, network);
+impl_config!(TranslateWitnessCli, TranslateWitnessConfig, network);
+impl_config!(ReshareCli, ReshareConfig, network);
+impl_config!(GenerateProofCli, GenerateProofConfig, network);
+impl_config!(ProveFromInputCli, ProveFromInputConfig, network);
 impl_config!(VerifyCli, VerifyConfig);
+impl_config!(BundleCli, BundleConfig);
+impl_config!(AggregateCli, AggregateConfig);
+impl_config!(VerifyAggregatedCli, VerifyAggregatedConfig);
+impl_config!(ConvertPublicInputsCli, ConvertPublicInputsConfig);
+impl_config!(ExportVkCli, ExportVkConfig);
+impl_config!(ExtractPublicInputsCli, ExtractPublicInputsConfig);
+impl_config!(PublicSignalsCli, PublicSignalsConfig);
+impl_config!(VkFingerprintCli, VkFingerprintConfig);
+impl_config!(DiffPublicInputsCli, DiffPublicInputsConfig);
+impl_config!(EstimateCli, EstimateConfig);
+impl_config!(RerandomizeProofCli, RerandomizeProofConfig);
+
+/// The number of additive shares that are reshared in one message, see [reshare_vec].
+const RESHARE_CHUNK_SIZE: usize = 1 << 16;
 
-fn reshare_vec<F: PrimeField>(
+/// Reshares additive shares into replicated shares.
+///
+/// The shares are sent in chunks of [RESHARE_CHUNK_SIZE] elements, so the messages only depend on the number of shares and not on how the caller obtained them. This allows [parse_witness_share_rep3_lazy] to reshare while reading and still talk to parties that use [parse_witness_share_rep3].
+fn reshare_vec<F: PrimeField, N: Rep3Network>(
     vec: Vec<F>,
-    mpc_net: &mut Rep3MpcNet,
+    mpc_net: &mut N,
 ) -> color_eyre::Result<Vec<Rep3PrimeFieldShare<F>>> {
-    mpc_net.send_next_many(&vec)?;
-    let b: Vec<F> = mpc_net.recv_prev_many()?;
+    let mut shares = Vec::with_capacity(vec.len());
+    for chunk in vec.chunks(RESHARE_CHUNK_SIZE) {
+        mpc_net.send_next_many(chunk)?;
+        let b: Vec<F> = mpc_net.recv_prev_many()?;
 
-    if vec.len() != b.len() {
-        return Err(color_eyre::eyre::eyre!(
-            "reshare_vec: vec and b have different lengths"
-        ));
-    }
+        if chunk.len() != b.len() {
+            return Err(color_eyre::eyre::eyre!(
+                "reshare_vec: vec and b have different lengths"
+            ));
+        }
 
-    let shares = vec
-        .into_iter()
-        .zip(b)
-        .map(|(a, b)| Rep3PrimeFieldShare { a, b })
-        .collect();
+        shares.extend(
+            chunk
+                .iter()
+                .zip(b)
+                .map(|(a, b)| Rep3PrimeFieldShare { a: *a, b }),
+        );
+    }
 
     Ok(shares)
 }
 
-/// Try to parse a [SharedWitness] from a [Read]er.
-pub fn parse_witness_share_rep3<R: Read, F: PrimeField>(
-    reader: R,
-    mpc_net: &mut Rep3MpcNet,
+/// Try to parse a [SharedWitness] from a [WitnessShareReader].
+pub fn parse_witness_share_rep3<R: Read, F: PrimeField, N: Rep3Network>(
+    reader: WitnessShareReader<R>,
+    mpc_net: &mut N,
 ) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    reader.expect_protocol(MPCProtocol::REP3)?;
     let deserialized: SerializeableSharedRep3Witness<F, SeedRng> =
         bincode::deserialize_from(reader).context("trying to parse witness share file")?;
+    shared_witness_from_rep3_share(deserialized, mpc_net)
+}
 
-    let public_inputs = deserialized.public_inputs;
-    let witness = deserialized.witness;
-    let witness = match witness {
+/// Converts an already deserialized REP3 witness share into a [SharedWitness] that can be passed to [`Rep3CoGroth16::prove`] or [`co_plonk::Rep3CoPlonk::prove`].
+///
+/// Seeded shares are expanded and additive shares are reshared into replicated shares, which needs one round of communication with the other parties.
+pub fn shared_witness_from_rep3_share<F: PrimeField, N: Rep3Network>(
+    share: SerializeableSharedRep3Witness<F, SeedRng>,
+    mpc_net: &mut N,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    let public_inputs = share.public_inputs;
+    let witness = match share.witness {
         Rep3ShareVecType::Replicated(vec) => vec,
         Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
             replicated_seed_type.expand_vec()?
@@ -557,45 +1939,558 @@ pub fn parse_witness_share_rep3<R: Read, F: PrimeField>(
     })
 }
 
-/// Try to parse a [SharedWitness] from a [Read]er, returning only the additive shares
+/// Reconstructs the plain witness, i.e., the public inputs followed by the opened private witness, from the REP3 shares of all parties.
+///
+/// INSECURE: all parties learn the whole witness. This is only meant for checking a witness in test environments, see [check_witness_satisfies_r1cs].
+pub fn open_witness_rep3<F: PrimeField, N: Rep3Network>(
+    witness: &SharedWitness<F, Rep3PrimeFieldShare<F>>,
+    mpc_net: &mut N,
+) -> color_eyre::Result<Vec<F>> {
+    let b = witness
+        .witness
+        .iter()
+        .map(|share| share.b)
+        .collect::<Vec<_>>();
+    let c = mpc_net
+        .reshare_many(&b)
+        .context("while opening the witness")?;
+    let mut values = witness.public_inputs.clone();
+    values.extend(
+        witness
+            .witness
+            .iter()
+            .zip(c)
+            .map(|(share, c)| share.a + share.b + c),
+    );
+    Ok(values)
+}
+
+/// Checks that all parties of the network run on this host, i.e., that the addresses of all parties resolve to loopback addresses.
+/// Used to refuse insecure modes like the `--plaintext-out` of `generate-witness` in a genuine multi-party setting.
+pub fn check_single_host_network(network: &NetworkConfig) -> color_eyre::Result<()> {
+    for party in network.parties.iter() {
+        let addrs = party
+            .dns_name
+            .to_socket_addrs()
+            .with_context(|| format!("while resolving address of party {}", party.id))?
+            .collect::<Vec<_>>();
+        if addrs.is_empty() || addrs.iter().any(|addr| !addr.ip().is_loopback()) {
+            return Err(eyre!(
+                "party {} at {} does not run on this host",
+                party.id,
+                party.dns_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Opens the witness and writes it to `path` as a circom witness file, see [open_witness_rep3].
+fn write_plaintext_witness<F: CircomArkworksPrimeFieldBridge, N: Rep3Network>(
+    witness: &SharedWitness<F, Rep3PrimeFieldShare<F>>,
+    mpc_net: &mut N,
+    path: &Path,
+) -> color_eyre::Result<()> {
+    let values = open_witness_rep3(witness, mpc_net)?;
+    let out_file =
+        BufWriter::new(std::fs::File::create(path).context("while creating plaintext witness")?);
+    Witness { values }
+        .to_writer(out_file)
+        .context("while writing plaintext witness")?;
+    tracing::warn!("Wrote the INSECURE plaintext witness to {}", path.display());
+    Ok(())
+}
+
+/// Returns the public inputs of a proof for the plain `witness`, i.e., the `num_public_inputs` values after the constant 1, as `generate-proof` takes them from a witness share.
+/// The number of public inputs is the one of the zkey, e.g., [`circom_types::groth16::ZKey::num_public_inputs`].
+pub fn public_inputs_from_witness<F: PrimeField>(
+    witness: Witness<F>,
+    num_public_inputs: usize,
+) -> color_eyre::Result<Vec<F>> {
+    if witness.values.len() <= num_public_inputs {
+        return Err(eyre!(
+            "the witness has only {} values, but the zkey expects {} public inputs after the constant 1",
+            witness.values.len(),
+            num_public_inputs
+        ));
+    }
+    let witness = SharedWitness::<F, F>::from_plaintext(witness, num_public_inputs + 1);
+    Ok(witness.public_signals(num_public_inputs)?.to_vec())
+}
+
+/// The result of [diff_public_inputs].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputsDiff<F> {
+    /// The index and the two values of the first public input that differs, if any
+    pub first_mismatch: Option<(usize, F, F)>,
+    /// The number of public inputs of the first list
+    pub first_len: usize,
+    /// The number of public inputs of the second list
+    pub second_len: usize,
+}
+
+impl<F> PublicInputsDiff<F> {
+    /// Returns `true` if both lists have the same length and no public input differs.
+    pub fn is_equal(&self) -> bool {
+        self.first_mismatch.is_none() && self.first_len == self.second_len
+    }
+}
+
+/// Compares two lists of public inputs field-by-field, as `diff-public-inputs` does.
+///
+/// Values are compared as field elements, so e.g. `"0x0a"` and `"10"` are equal after parsing. Only the common prefix is searched for a mismatch, a difference in length is reported by the lengths.
+pub fn diff_public_inputs<F: PrimeField>(first: &[F], second: &[F]) -> PublicInputsDiff<F> {
+    let first_mismatch = first
+        .iter()
+        .zip(second.iter())
+        .enumerate()
+        .find(|(_, (a, b))| a != b)
+        .map(|(index, (a, b))| (index, *a, *b));
+    PublicInputsDiff {
+        first_mismatch,
+        first_len: first.len(),
+        second_len: second.len(),
+    }
+}
+
+/// Checks that a plain witness satisfies the constraints `A * B = C` of an R1CS, e.g., the constraints obtained from [`circom_types::R1CS::constraints_iter`].
+/// Returns an error naming the first unsatisfied constraint.
+pub fn check_witness_satisfies_r1cs<F, E, I>(
+    witness: &[F],
+    constraints: I,
+) -> color_eyre::Result<()>
+where
+    F: PrimeField,
+    E: std::error::Error + Send + Sync + 'static,
+    I: IntoIterator<Item = Result<(Vec<(usize, F)>, Vec<(usize, F)>, Vec<(usize, F)>), E>>,
+{
+    let evaluate = |lc: &[(usize, F)]| -> color_eyre::Result<F> {
+        lc.iter().try_fold(F::zero(), |acc, (wire, coeff)| {
+            let value = witness.get(*wire).ok_or_else(|| {
+                eyre!(
+                    "constraint refers to wire {wire}, but the witness has only {} values",
+                    witness.len()
+                )
+            })?;
+            Ok(acc + *coeff * value)
+        })
+    };
+    for (i, constraint) in constraints.into_iter().enumerate() {
+        let (a, b, c) = constraint.context("while reading r1cs constraints")?;
+        let (a, b, c) = (evaluate(&a)?, evaluate(&b)?, evaluate(&c)?);
+        if a * b != c {
+            return Err(eyre!(
+                "witness does not satisfy constraint {i} of the r1cs: A = {}, B = {}, C = {}",
+                a.into_bigint(),
+                b.into_bigint(),
+                c.into_bigint()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Try to parse a [SharedWitness] from a [WitnessShareReader], returning only the additive shares
 pub fn parse_witness_share_rep3_as_additive<R: Read, F: PrimeField>(
-    reader: R,
+    reader: WitnessShareReader<R>,
 ) -> color_eyre::Result<SharedWitness<F, F>> {
+    reader.expect_protocol(MPCProtocol::REP3)?;
     let deserialized: SerializeableSharedRep3Witness<F, SeedRng> =
         bincode::deserialize_from(reader).context("trying to parse witness share file")?;
 
-    let public_inputs = deserialized.public_inputs;
-    let witness = deserialized.witness;
-    let witness = match witness {
-        Rep3ShareVecType::Replicated(vec) => vec.into_iter().map(|x| x.a).collect::<Vec<_>>(),
-        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
-            replicated_seed_type.a.expand_vec()
+    Ok(SharedWitness {
+        public_inputs: deserialized.public_inputs,
+        witness: rep3::rep3_to_additive(deserialized.witness),
+    })
+}
+
+/// Try to parse a [SharedWitness] from a [WitnessShareReader].
+///
+/// The result can be passed to [`co_groth16::ShamirCoGroth16::prove`] or [`co_plonk::ShamirCoPlonk::prove`].
+pub fn parse_witness_share_shamir<R: Read, F: PrimeField>(
+    reader: WitnessShareReader<R>,
+) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
+    reader.expect_protocol(MPCProtocol::SHAMIR)?;
+    bincode::deserialize_from(reader).context("trying to parse witness share file")
+}
+
+/// Like [parse_witness_share_rep3], but reads the shares directly into the vector passed to the prover.
+///
+/// The eager parser buffers the serialized shares before deserializing them and holds the additive and the reshared replicated shares at the same time, so its peak memory is about twice the size of the witness. Here, the shares are read one by one and additive shares are reshared chunk by chunk while reading, so the peak memory stays close to the size of the parsed witness. The prover needs random access to the witness, so the witness itself is still held in memory. The result and the messages sent to the other parties are the same as the ones of [parse_witness_share_rep3], so parties can choose the parser independently.
+pub fn parse_witness_share_rep3_lazy<R: Read, F: PrimeField, N: Rep3Network>(
+    mut reader: WitnessShareReader<R>,
+    mpc_net: &mut N,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    reader.expect_protocol(MPCProtocol::REP3)?;
+    let public_inputs = read_ark_vec(&mut reader).context("trying to parse public inputs")?;
+    let mut tag = [0u8; 4];
+    reader
+        .read_exact(&mut tag)
+        .context("trying to parse witness share file")?;
+    let witness = match u32::from_le_bytes(tag) {
+        // Rep3ShareVecType::Replicated
+        0 => read_ark_vec(&mut reader).context("trying to parse witness share file")?,
+        // Rep3ShareVecType::Additive
+        2 => {
+            let (len, mut reader) = read_ark_vec_header(&mut reader)?;
+            let mut witness = Vec::with_capacity(len);
+            let mut chunk = Vec::with_capacity(len.min(RESHARE_CHUNK_SIZE));
+            for i in 0..len {
+                chunk.push(
+                    F::deserialize_with_mode(&mut reader, Compress::Yes, Validate::Yes)
+                        .context("trying to parse witness share file")?,
+                );
+                if chunk.len() == RESHARE_CHUNK_SIZE || i + 1 == len {
+                    witness.extend(reshare_vec(std::mem::take(&mut chunk), mpc_net)?);
+                }
+            }
+            check_ark_vec_consumed(&reader)?;
+            witness
+        }
+        // the compressed shares are small, they are parsed eagerly
+        tag @ (1 | 3) => {
+            let mut header = std::io::Cursor::new(tag.to_le_bytes());
+            let witness = bincode::deserialize_from::<_, Rep3ShareVecType<F, SeedRng>>(
+                (&mut header).chain(reader),
+            )
+            .context("trying to parse witness share file")?;
+            return shared_witness_from_rep3_share(
+                SerializeableSharedRep3Witness {
+                    public_inputs,
+                    witness,
+                },
+                mpc_net,
+            );
+        }
+        tag => {
+            return Err(color_eyre::eyre::eyre!(
+                "witness share file is malformed, unknown share type {tag}"
+            ))
         }
-        Rep3ShareVecType::Additive(vec) => vec,
-        Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.expand_vec(),
     };
+    Ok(SharedWitness {
+        public_inputs,
+        witness,
+    })
+}
 
+/// Like [parse_witness_share_shamir], but reads the shares directly into the vector passed to the prover, see [parse_witness_share_rep3_lazy].
+pub fn parse_witness_share_shamir_lazy<R: Read, F: PrimeField>(
+    mut reader: WitnessShareReader<R>,
+) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
+    reader.expect_protocol(MPCProtocol::SHAMIR)?;
+    let public_inputs = read_ark_vec(&mut reader).context("trying to parse public inputs")?;
+    let witness = read_ark_vec(&mut reader).context("trying to parse witness share file")?;
     Ok(SharedWitness {
         public_inputs,
         witness,
     })
 }
 
-/// Try to parse a [SharedWitness] from a [Read]er.
-pub fn parse_witness_share_shamir<R: Read, F: PrimeField>(
+/// Reads the header of a vector that was serialized with [mpc_core::ark_se] and bincode, i.e., the length of the serialized bytes followed by the number of elements.
+/// Returns the number of elements and a reader limited to the serialized elements.
+fn read_ark_vec_header<R: Read>(mut reader: R) -> color_eyre::Result<(usize, std::io::Take<R>)> {
+    let mut header = [0u8; 16];
+    reader
+        .read_exact(&mut header)
+        .context("trying to read vector header")?;
+    let bytes = u64::from_le_bytes(header[..8].try_into().expect("8 bytes"));
+    let len = u64::from_le_bytes(header[8..].try_into().expect("8 bytes"));
+    // every element takes at least one byte, so a length that does not fit the bytes is malformed
+    let elements_bytes = bytes
+        .checked_sub(8)
+        .filter(|&elements_bytes| len <= elements_bytes)
+        .ok_or_else(|| color_eyre::eyre::eyre!("witness share file is malformed"))?;
+    let len = usize::try_from(len).context("vector does not fit into memory")?;
+    Ok((len, reader.take(elements_bytes)))
+}
+
+fn check_ark_vec_consumed<R: Read>(reader: &std::io::Take<R>) -> color_eyre::Result<()> {
+    if reader.limit() != 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "witness share file is malformed, {} trailing bytes after the elements of a vector",
+            reader.limit()
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a vector that was serialized with [mpc_core::ark_se] and bincode element by element, without buffering the serialized bytes.
+fn read_ark_vec<T: CanonicalDeserialize, R: Read>(reader: R) -> color_eyre::Result<Vec<T>> {
+    let (len, mut reader) = read_ark_vec_header(reader)?;
+    let mut vec = Vec::with_capacity(len);
+    for _ in 0..len {
+        vec.push(T::deserialize_with_mode(
+            &mut reader,
+            Compress::Yes,
+            Validate::Yes,
+        )?);
+    }
+    check_ark_vec_consumed(&reader)?;
+    Ok(vec)
+}
+
+/// The magic bytes at the start of a witness share, followed by a single byte identifying the [MPCCurve] the share was created for.
+///
+/// Witness shares without this header, as written by older versions, start with the length of the serialized public inputs as
+/// a little endian `u64`, which is never as large as these bytes. Such shares are still accepted, but their curve is unknown.
+pub const WITNESS_SHARE_MAGIC: [u8; 8] = *b"cocircws";
+
+/// Writes the header of a witness share created for `curve`, see [WITNESS_SHARE_MAGIC].
+pub fn write_witness_share_header<W: Write>(mut writer: W, curve: MPCCurve) -> std::io::Result<()> {
+    let tag = match curve {
+        MPCCurve::BN254 => 0u8,
+        MPCCurve::BLS12_381 => 1u8,
+    };
+    writer.write_all(&WITNESS_SHARE_MAGIC)?;
+    writer.write_all(&[tag])
+}
+
+/// The largest accepted size of the serialized public inputs of a witness share. They are buffered while the header of the share is read, see [WitnessShareReader::new].
+const MAX_PUBLIC_INPUTS_BYTES: u64 = 1 << 28;
+
+/// A witness share whose header was read by [WitnessShareReader::new].
+///
+/// It reads the share without the header described in [WITNESS_SHARE_MAGIC] and is passed to the `parse_witness_share_*` functions, so the header is only parsed once.
+pub struct WitnessShareReader<R> {
+    curve: Option<MPCCurve>,
+    protocol: MPCProtocol,
+    reader: std::io::Chain<std::io::Cursor<Vec<u8>>, R>,
+}
+
+impl<R: Read> WitnessShareReader<R> {
+    /// Reads the header of a witness share and determines the curve and the [MPCProtocol] it was created for.
+    ///
+    /// The reader does not need to be a file, a share that is already held in memory can be read from its bytes, as `&[u8]` implements [Read].
+    ///
+    /// Both share types start with the serialized public inputs. They are followed by the enum tag of
+    /// [Rep3ShareVecType] (a little endian `u32` in `0..=3`) for REP3 shares and by the length of the
+    /// serialized shares for Shamir shares. The latter is never in `0..=3`, as it is the length of a
+    /// vector header plus a multiple of the field element size.
+    pub fn new(mut reader: R) -> color_eyre::Result<Self> {
+        let mut prefix = [0u8; 8];
+        reader
+            .read_exact(&mut prefix)
+            .context("trying to read witness share header")?;
+        let curve = if prefix == WITNESS_SHARE_MAGIC {
+            let mut tag = [0u8];
+            reader
+                .read_exact(&mut tag)
+                .context("trying to read witness share header")?;
+            let curve = match tag[0] {
+                0 => MPCCurve::BN254,
+                1 => MPCCurve::BLS12_381,
+                tag => {
+                    color_eyre::eyre::bail!("witness share file is malformed, unknown curve {tag}")
+                }
+            };
+            reader
+                .read_exact(&mut prefix)
+                .context("trying to read witness share header")?;
+            Some(curve)
+        } else {
+            None
+        };
+        // the length of the serialized public inputs, followed by the public inputs and the tag or length described above
+        let public_inputs_len = u64::from_le_bytes(prefix);
+        if public_inputs_len > MAX_PUBLIC_INPUTS_BYTES {
+            color_eyre::eyre::bail!(
+                "witness share file is malformed, {public_inputs_len} bytes of public inputs"
+            );
+        }
+        let mut header = prefix.to_vec();
+        reader
+            .by_ref()
+            .take(public_inputs_len + 4)
+            .read_to_end(&mut header)
+            .context("trying to read witness share header")?;
+        if header.len() as u64 != 8 + public_inputs_len + 4 {
+            color_eyre::eyre::bail!("witness share file is malformed, it ends in the header");
+        }
+        let tag = u32::from_le_bytes(header[header.len() - 4..].try_into().expect("4 bytes"));
+        let protocol = if tag <= 3 {
+            MPCProtocol::REP3
+        } else {
+            MPCProtocol::SHAMIR
+        };
+        Ok(Self {
+            curve,
+            protocol,
+            reader: std::io::Cursor::new(header).chain(reader),
+        })
+    }
+
+    /// Returns the curve the share was created for, or `None` for a share without header, as written by older versions.
+    pub fn curve(&self) -> Option<MPCCurve> {
+        self.curve
+    }
+
+    /// Returns the [MPCProtocol] the share was created for.
+    pub fn protocol(&self) -> MPCProtocol {
+        self.protocol
+    }
+
+    /// Checks that the share was created for the `expected` [MPCProtocol].
+    pub fn expect_protocol(&self, expected: MPCProtocol) -> color_eyre::Result<()> {
+        let protocol = self.protocol;
+        if protocol != expected {
+            color_eyre::eyre::bail!(
+                "this is a {protocol} witness share but {expected} was requested, use --protocol {protocol} or provide a {expected} share"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for WitnessShareReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+/// The magic bytes at the start of a file with the input shares of multiple parties, as written by `split_input --group`.
+///
+/// The rest of the file is a map from party id to [SerializeableSharedRep3Input]. A single input share starts with the
+/// number of its public inputs as a little endian `u64`, which is never as large as these bytes, so both can be told apart.
+pub const GROUPED_INPUT_SHARES_MAGIC: [u8; 8] = *b"cocircgr";
+
+/// The content of an input share file, which holds the input share of a single party or, if written by `split_input --group`, the input shares of several parties.
+pub enum InputShareFile<F: PrimeField> {
+    /// The input share of a single party
+    Single(SerializeableSharedRep3Input<F, SeedRng>),
+    /// The input shares of several parties by party id, see [GROUPED_INPUT_SHARES_MAGIC]
+    Grouped(BTreeMap<usize, SerializeableSharedRep3Input<F, SeedRng>>),
+}
+
+impl<F: PrimeField> InputShareFile<F> {
+    /// Reads a single or grouped input share file, telling them apart by [GROUPED_INPUT_SHARES_MAGIC].
+    pub fn from_reader<R: Read>(mut reader: R) -> color_eyre::Result<Self> {
+        let mut prefix = [0u8; 8];
+        reader
+            .read_exact(&mut prefix)
+            .context("trying to read input share header")?;
+        if prefix == GROUPED_INPUT_SHARES_MAGIC {
+            let shares = bincode::deserialize_from(reader)
+                .context("trying to parse grouped input share file")?;
+            Ok(Self::Grouped(shares))
+        } else {
+            let share = bincode::deserialize_from(std::io::Cursor::new(prefix).chain(reader))
+                .context("trying to parse input share file")?;
+            Ok(Self::Single(share))
+        }
+    }
+}
+
+/// Writes the input shares of several parties by party id into a single file, which can be read with [InputShareFile::from_reader].
+pub fn write_grouped_input_shares<W: Write, F: PrimeField>(
+    mut writer: W,
+    shares: &BTreeMap<usize, &SerializeableSharedRep3Input<F, SeedRng>>,
+) -> color_eyre::Result<()> {
+    writer
+        .write_all(&GROUPED_INPUT_SHARES_MAGIC)
+        .context("while writing grouped input shares")?;
+    bincode::serialize_into(writer, shares).context("while serializing grouped input shares")
+}
+
+/// Reads the input share of party `id`, either from a single input share or from a file with grouped input shares, see [GROUPED_INPUT_SHARES_MAGIC].
+pub fn read_input_share<R: Read, F: PrimeField>(
     reader: R,
-) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
-    bincode::deserialize_from(reader).context("trying to parse witness share file")
+    id: usize,
+) -> color_eyre::Result<SerializeableSharedRep3Input<F, SeedRng>> {
+    match InputShareFile::from_reader(reader)? {
+        InputShareFile::Single(share) => Ok(share),
+        InputShareFile::Grouped(mut shares) => shares.remove(&id).ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "grouped input share file contains the shares of parties {:?}, but not of party {id}",
+                shares.keys().collect::<Vec<_>>()
+            )
+        }),
+    }
 }
 
 /// Try to parse a [SharedInput] from a [Read]er.
+///
+/// The reader may also yield the grouped input shares of multiple parties, in which case the share of our own party is used.
 pub fn parse_shared_input<R: Read, F: PrimeField>(
     reader: R,
     mpc_net: &mut Rep3MpcNet,
 ) -> color_eyre::Result<SharedInput<F, Rep3PrimeFieldShare<F>>> {
-    let deserialized: SerializeableSharedRep3Input<F, SeedRng> =
-        bincode::deserialize_from(reader).context("trying to parse input share file")?;
+    let deserialized = read_input_share::<_, F>(reader, usize::from(mpc_net.get_id()))?;
+    reshare_input_share(deserialized, mpc_net)
+}
+
+/// Try to parse a [SharedInput] from several input share files, e.g., of different `split-input` runs.
+///
+/// The input shares are merged as in `merge-input-shares`, so an input that is shared in more than one file and conflicting public inputs are rejected. Like in [parse_shared_input], the files may also contain the grouped input shares of multiple parties.
+pub fn parse_shared_inputs<F: PrimeField>(
+    paths: &[PathBuf],
+    mpc_net: &mut Rep3MpcNet,
+) -> color_eyre::Result<SharedInput<F, Rep3PrimeFieldShare<F>>> {
+    let merged = read_merged_input_shares::<F>(paths, usize::from(mpc_net.get_id()), false)?;
+    reshare_input_share(merged, mpc_net)
+}
+
+/// Reads the input shares of party `id` from all `paths` and merges them as in `merge-input-shares`.
+///
+/// An input that is shared in more than one file is rejected with an error that names both files, unless `additive` is set.
+/// With `additive`, the shares of such an input are added up with [SerializeableSharedRep3Input::merge_additive], e.g., to aggregate the contributions of several dealers.
+pub fn read_merged_input_shares<F: PrimeField>(
+    paths: &[PathBuf],
+    id: usize,
+    additive: bool,
+) -> color_eyre::Result<SerializeableSharedRep3Input<F, SeedRng>> {
+    let mut merged: Option<SerializeableSharedRep3Input<F, SeedRng>> = None;
+    // the first file every input name was found in
+    let mut origins = BTreeMap::<String, &PathBuf>::new();
+    for path in paths {
+        let reader = std::io::BufReader::new(
+            std::fs::File::open(path)
+                .with_context(|| format!("while opening input share file {}", path.display()))?,
+        );
+        let input_share = read_input_share::<_, F>(reader, id)
+            .with_context(|| format!("while reading input share file {}", path.display()))?;
+        if !additive {
+            for name in input_share.shared_inputs.keys() {
+                if let Some(origin) = origins.get(name) {
+                    return Err(eyre!(
+                        "input \"{name}\" is in both {} and {}",
+                        origin.display(),
+                        path.display()
+                    ));
+                }
+            }
+        }
+        for name in input_share
+            .shared_inputs
+            .keys()
+            .chain(input_share.public_inputs.keys())
+        {
+            origins.entry(name.to_owned()).or_insert(path);
+        }
+        merged = Some(match merged {
+            None => input_share,
+            Some(merged) => {
+                let merged = if additive {
+                    merged.merge_additive(input_share)
+                } else {
+                    merged.merge(input_share)
+                };
+                merged.with_context(|| {
+                    format!(
+                        "while merging input share {} into the others",
+                        path.display()
+                    )
+                })?
+            }
+        });
+    }
+    merged.ok_or_else(|| color_eyre::eyre::eyre!("no input share file given"))
+}
 
+fn reshare_input_share<F: PrimeField>(
+    deserialized: SerializeableSharedRep3Input<F, SeedRng>,
+    mpc_net: &mut Rep3MpcNet,
+) -> color_eyre::Result<SharedInput<F, Rep3PrimeFieldShare<F>>> {
     let public_inputs = deserialized.public_inputs;
     let shared_inputs_ = deserialized.shared_inputs;
 
@@ -667,10 +2562,78 @@ where
     let id = usize::from(net.get_id());
 
     // init MPC protocol
+    let mut vm_config = config.vm;
+    vm_config.profile |= config.profile.is_some();
+    let result_witness_share = if let Some(dir) = &config.checkpoint {
+        let fingerprint = checkpoint::fingerprint(&circuit_path, &input_share)?;
+        let (net, prf_seed) = checkpoint::CheckpointedNet::open(
+            net,
+            dir,
+            Duration::from_secs(config.checkpoint_interval),
+            fingerprint,
+        )?;
+        let mut rep3_vm = parsed_circom_circuit
+            .to_rep3_vm_with_network_and_prf_seed(net, prf_seed, vm_config)
+            .context("while constructing MPC VM")?;
+        let witness =
+            run_witness_extension(&mut rep3_vm, input_share, id, config.profile.as_deref())?;
+        if let Some(path) = &config.plaintext_out {
+            write_plaintext_witness(&witness, &mut rep3_vm.into_network(), path)?;
+        }
+        witness
+    } else {
+        let mut rep3_vm = parsed_circom_circuit
+            .to_rep3_vm_with_network(net, vm_config)
+            .context("while constructing MPC VM")?;
+        let witness =
+            run_witness_extension(&mut rep3_vm, input_share, id, config.profile.as_deref())?;
+        if let Some(path) = &config.plaintext_out {
+            write_plaintext_witness(&witness, &mut rep3_vm.into_network(), path)?;
+        }
+        witness
+    };
+
+    let res = SerializeableSharedRep3Witness::from_shared_witness(result_witness_share);
+
+    Ok(res)
+}
+
+/// Same as [generate_witness_rep3], but also returns the network the witness was extended over, so that a prover can reuse the connections instead of connecting again. Neither checkpoints nor profiles are written.
+#[allow(clippy::type_complexity)]
+pub fn generate_witness_rep3_keep_network<P>(
+    circuit: String,
+    input_share: SharedInput<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    net: Rep3MpcNet,
+    compiler: CompilerConfig,
+    vm_config: VMConfig,
+) -> color_eyre::Result<(
+    SharedWitness<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    Rep3MpcNet,
+)>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    file_utils::check_file_exists(&PathBuf::from(&circuit))?;
+    let parsed_circom_circuit =
+        CoCircomCompiler::<P>::parse(circuit, compiler).context("while parsing circuit file")?;
+
+    let id = usize::from(net.get_id());
     let mut rep3_vm = parsed_circom_circuit
-        .to_rep3_vm_with_network(net, config.vm)
+        .to_rep3_vm_with_network(net, vm_config)
         .context("while constructing MPC VM")?;
+    let witness_share = run_witness_extension(&mut rep3_vm, input_share, id, None)?;
+    Ok((witness_share, rep3_vm.into_network()))
+}
 
+/// Executes the witness extension in MPC and reports the profile, if it was recorded.
+fn run_witness_extension<F: PrimeField, N: Rep3Network>(
+    rep3_vm: &mut Rep3WitnessExtension<F, N>,
+    input_share: SharedInput<F, Rep3PrimeFieldShare<F>>,
+    id: usize,
+    profile_path: Option<&Path>,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
     // execute witness generation in MPC
     let start = Instant::now();
     let result_witness_share = rep3_vm
@@ -680,11 +2643,22 @@ where
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
 
-    let res = SerializeableSharedRep3Witness::from_shared_witness(
-        result_witness_share.into_shared_witness(),
-    );
+    if let Some(profile) = result_witness_share.profile() {
+        for (template, time) in profile.per_template().into_iter().take(5) {
+            tracing::info!(
+                "Party {}: {} ms spent in template {}",
+                id,
+                time.as_micros() as f64 / 1000.,
+                template
+            );
+        }
+        if let Some(path) = profile_path {
+            std::fs::write(path, profile.to_folded()).context("while writing profile")?;
+            tracing::info!("Party {}: Wrote profile to {}", id, path.display());
+        }
+    }
 
-    Ok(res)
+    Ok(result_witness_share.into_shared_witness())
 }
 
 /// Invoke the MPC proof generation process. It will return a [`Groth16Proof`] if successful.
@@ -710,3 +2684,353 @@ where
     let zkey = Arc::new(zkey);
     prover.prove(zkey, witness_share)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETWORK_CONFIG: &str = r#"
+        [network]
+        my_id = 0
+        bind_addr = "0.0.0.0:10000"
+        key_path = "key0.der"
+        [[network.parties]]
+        id = 0
+        dns_name = "file0:10000"
+        cert_path = "cert0.der"
+        [[network.parties]]
+        id = 1
+        dns_name = "file1:10001"
+        cert_path = "cert1.der"
+        [[network.parties]]
+        id = 2
+        dns_name = "file2:10002"
+        cert_path = "cert2.der"
+    "#;
+
+    fn parse_network(
+        mut cli: NetworkCli,
+        env: &[(&str, &str)],
+    ) -> Result<NetworkConfig, Box<figment::Error>> {
+        cli.fill_from_env(|name| {
+            env.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })?;
+        Ok(cli
+            .merge_parties(Figment::from(Toml::string(NETWORK_CONFIG)))?
+            .merge(Serialized::default("network", &cli))
+            .extract_inner("network")?)
+    }
+
+    #[test]
+    fn network_env_precedence() {
+        let env = [
+            ("COCIRCOM_PARTY_ID", "1"),
+            ("COCIRCOM_BIND_ADDR", "127.0.0.1:9000"),
+            ("COCIRCOM_PARTY1_ADDR", "env1:20001"),
+            ("COCIRCOM_PARTY2_CERT", "env_cert2.der"),
+        ];
+
+        // the environment variables take precedence over the config file
+        let config = parse_network(NetworkCli::default(), &env).unwrap();
+        assert_eq!(config.my_id, 1);
+        assert_eq!(config.bind_addr, "127.0.0.1:9000".parse().unwrap());
+        assert_eq!(config.key_path, PathBuf::from("key0.der"));
+        assert_eq!(config.parties[0].dns_name.to_string(), "file0:10000");
+        assert_eq!(config.parties[1].dns_name.to_string(), "env1:20001");
+        assert_eq!(config.parties[2].dns_name.to_string(), "file2:10002");
+        assert_eq!(config.parties[2].cert_path, PathBuf::from("env_cert2.der"));
+
+        // the flags take precedence over the environment variables
+        let cli = NetworkCli {
+            party_id: Some(2),
+            party1_addr: Some("flag1:30001".parse().unwrap()),
+            ..Default::default()
+        };
+        let config = parse_network(cli, &env).unwrap();
+        assert_eq!(config.my_id, 2);
+        assert_eq!(config.bind_addr, "127.0.0.1:9000".parse().unwrap());
+        assert_eq!(config.parties[1].dns_name.to_string(), "flag1:30001");
+
+        assert!(parse_network(NetworkCli::default(), &[("COCIRCOM_PARTY0_ADDR", "nope")]).is_err());
+    }
+
+    #[test]
+    fn plaintext_witness_needs_single_host() {
+        let local = [
+            ("COCIRCOM_PARTY0_ADDR", "127.0.0.1:10000"),
+            ("COCIRCOM_PARTY1_ADDR", "localhost:10001"),
+            ("COCIRCOM_PARTY2_ADDR", "127.0.0.1:10002"),
+        ];
+        let config = parse_network(NetworkCli::default(), &local).unwrap();
+        check_single_host_network(&config).unwrap();
+
+        let remote = [
+            ("COCIRCOM_PARTY0_ADDR", "127.0.0.1:10000"),
+            ("COCIRCOM_PARTY1_ADDR", "localhost:10001"),
+            ("COCIRCOM_PARTY2_ADDR", "10.0.0.2:10002"),
+        ];
+        let config = parse_network(NetworkCli::default(), &remote).unwrap();
+        let err = check_single_host_network(&config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "party 2 at 10.0.0.2:10002 does not run on this host"
+        );
+    }
+
+    #[test]
+    fn public_inputs_are_extracted_from_witness() {
+        use ark_bn254::{Bn254, Fr};
+        use circom_types::groth16::ZKey;
+        use std::fs::File;
+
+        let dir = "../../test_vectors/Groth16/bn254/multiplier2";
+        let witness =
+            Witness::<Fr>::from_reader(File::open(format!("{dir}/witness.wtns")).unwrap()).unwrap();
+        let zkey =
+            ZKey::<Bn254>::from_reader(File::open(format!("{dir}/circuit.zkey")).unwrap()).unwrap();
+        let public_json: Vec<String> =
+            serde_json::from_reader(File::open(format!("{dir}/public.json")).unwrap()).unwrap();
+
+        let public_inputs =
+            public_inputs_from_witness(witness.clone(), zkey.num_public_inputs()).unwrap();
+        assert_eq!(
+            snarkjs::public_inputs_as_strings(&public_inputs),
+            public_json
+        );
+
+        let len = witness.values.len();
+        let err = public_inputs_from_witness(witness, len).unwrap_err();
+        assert!(err.to_string().starts_with("the witness has only 4 values"));
+    }
+
+    #[test]
+    fn diff_public_inputs_reports_first_mismatch_and_lengths() {
+        use ark_bn254::Fr;
+
+        let values = |values: &[u64]| values.iter().map(|v| Fr::from(*v)).collect::<Vec<_>>();
+        let diff = diff_public_inputs(&values(&[1, 0, 3]), &values(&[1, 0, 3]));
+        assert!(diff.is_equal());
+        assert_eq!(diff.first_mismatch, None);
+
+        let diff = diff_public_inputs(&values(&[1, 0, 3, 0]), &values(&[1, 2, 3]));
+        assert!(!diff.is_equal());
+        assert_eq!(
+            diff.first_mismatch,
+            Some((1, Fr::from(0u64), Fr::from(2u64)))
+        );
+        assert_eq!((diff.first_len, diff.second_len), (4, 3));
+        // a zero is printed as "0" and not as an empty string
+        let (_, a, b) = diff.first_mismatch.unwrap();
+        assert_eq!(snarkjs::public_inputs_as_strings(&[a, b]), ["0", "2"]);
+
+        let diff = diff_public_inputs(&values(&[1, 2]), &values(&[1, 2, 3]));
+        assert!(!diff.is_equal());
+        assert_eq!(diff.first_mismatch, None);
+        assert_eq!((diff.first_len, diff.second_len), (2, 3));
+    }
+
+    #[test]
+    fn merged_input_shares_reject_duplicate_names() {
+        use ark_bn254::Fr;
+
+        let dir = test_utils::test_dir("merge");
+        let write = |name: &str, inputs: &[&str]| {
+            let mut share = SerializeableSharedRep3Input::<Fr, SeedRng>::default();
+            for input in inputs {
+                share.shared_inputs.insert(
+                    input.to_string(),
+                    Rep3ShareVecType::Additive(vec![Fr::from(1u64)]),
+                );
+            }
+            let path = dir.join(name);
+            std::fs::write(&path, bincode::serialize(&share).unwrap()).unwrap();
+            path
+        };
+        let a = write("a.json.0.shared", &["a", "x"]);
+        let b = write("b.json.0.shared", &["b"]);
+        let c = write("c.json.0.shared", &["c", "x"]);
+
+        let merged = read_merged_input_shares::<Fr>(&[a.clone(), b.clone()], 0, false).unwrap();
+        assert_eq!(merged.shared_inputs.len(), 3);
+
+        let err = read_merged_input_shares::<Fr>(&[a.clone(), b.clone(), c.clone()], 0, false)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("input \"x\" is in both {} and {}", a.display(), c.display())
+        );
+
+        // with additive merging, the shares of "x" are added up instead
+        let merged = read_merged_input_shares::<Fr>(&[a, b, c], 0, true).unwrap();
+        assert_eq!(merged.shared_inputs.len(), 4);
+        match &merged.shared_inputs["x"] {
+            Rep3ShareVecType::Additive(x) => assert_eq!(x, &[Fr::from(2u64)]),
+            _ => panic!("additive shares stay additive"),
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn witness_is_checked_against_r1cs() {
+        use ark_bn254::Bn254;
+        use circom_types::{Witness, R1CS};
+        use std::fs::File;
+
+        let dir = "../../test_vectors/Groth16/bn254/multiplier2";
+        let witness = Witness::<ark_bn254::Fr>::from_reader(
+            File::open(format!("{dir}/witness.wtns")).unwrap(),
+        )
+        .unwrap();
+        let constraints = || {
+            R1CS::<Bn254>::constraints_iter(File::open(format!("{dir}/circuit.r1cs")).unwrap())
+                .unwrap()
+        };
+        check_witness_satisfies_r1cs(&witness.values, constraints()).unwrap();
+
+        let mut bad = witness.values.clone();
+        *bad.last_mut().unwrap() += ark_bn254::Fr::from(1u64);
+        let err = check_witness_satisfies_r1cs(&bad, constraints()).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("witness does not satisfy constraint 0"));
+
+        let err = check_witness_satisfies_r1cs(&witness.values[..1], constraints()).unwrap_err();
+        assert!(err.to_string().contains("the witness has only 1 values"));
+    }
+
+    fn multiplier2_witness() -> circom_types::Witness<ark_bn254::Fr> {
+        circom_types::Witness::from_reader(
+            std::fs::File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns")
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn witness_share_protocol_is_detected() {
+        use ark_bn254::Fr;
+
+        let [rep3, _, _] = SerializeableSharedRep3Witness::<Fr, SeedRng>::share_rep3(
+            multiplier2_witness(),
+            2,
+            &mut rand::thread_rng(),
+            false,
+            true,
+        );
+        let shamir = SharedWitness::<Fr, ShamirPrimeFieldShare<Fr>>::from_plaintext_shamir(
+            multiplier2_witness(),
+            2,
+            1,
+        )
+        .remove(0);
+        let rep3 = bincode::serialize(&rep3).unwrap();
+        let shamir = bincode::serialize(&shamir).unwrap();
+
+        let reader = WitnessShareReader::new(rep3.as_slice()).unwrap();
+        assert_eq!(reader.protocol(), MPCProtocol::REP3);
+        let err = reader.expect_protocol(MPCProtocol::SHAMIR).unwrap_err();
+        assert!(err.to_string().starts_with("this is a REP3 witness share"));
+        // the reader yields the full share again
+        let parsed = parse_witness_share_rep3_as_additive::<_, Fr>(reader).unwrap();
+        assert_eq!(parsed.public_inputs.len(), 2);
+
+        let reader = WitnessShareReader::new(shamir.as_slice()).unwrap();
+        assert_eq!(reader.protocol(), MPCProtocol::SHAMIR);
+        parse_witness_share_shamir::<_, Fr>(reader).unwrap();
+    }
+
+    #[test]
+    fn witness_share_header_is_bounded() {
+        // a share claiming huge public inputs is rejected before they are buffered
+        let mut share = u64::MAX.to_le_bytes().to_vec();
+        share.extend([0; 64]);
+        let err = WitnessShareReader::new(share.as_slice()).err().unwrap();
+        assert!(err.to_string().contains("bytes of public inputs"));
+
+        let mut share = 40u64.to_le_bytes().to_vec();
+        share.extend([0; 20]);
+        let err = WitnessShareReader::new(share.as_slice()).err().unwrap();
+        assert!(err.to_string().contains("it ends in the header"));
+    }
+
+    #[test]
+    fn witness_share_header_round_trip() {
+        use ark_bn254::Fr;
+
+        let [share, _, _] = SerializeableSharedRep3Witness::<Fr, SeedRng>::share_rep3(
+            multiplier2_witness(),
+            2,
+            &mut rand::thread_rng(),
+            true,
+            false,
+        );
+        let serialized = bincode::serialize(&share).unwrap();
+        for curve in [MPCCurve::BN254, MPCCurve::BLS12_381] {
+            let mut bytes = vec![];
+            write_witness_share_header(&mut bytes, curve).unwrap();
+            assert_eq!(bytes[..8], WITNESS_SHARE_MAGIC);
+            bytes.extend(&serialized);
+            let mut reader = WitnessShareReader::new(bytes.as_slice()).unwrap();
+            assert_eq!(reader.curve(), Some(curve));
+            assert_eq!(reader.protocol(), MPCProtocol::REP3);
+            let mut rest = vec![];
+            reader.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, serialized);
+        }
+
+        let mut bytes = WITNESS_SHARE_MAGIC.to_vec();
+        bytes.push(2);
+        bytes.extend(&serialized);
+        let err = WitnessShareReader::new(bytes.as_slice()).err().unwrap();
+        assert!(err.to_string().contains("unknown curve 2"));
+    }
+
+    #[test]
+    fn legacy_witness_shares_without_header_load() {
+        use ark_bn254::Fr;
+
+        let witness = multiplier2_witness();
+        let [share, _, _] = SerializeableSharedRep3Witness::<Fr, SeedRng>::share_rep3(
+            witness.clone(),
+            2,
+            &mut rand::thread_rng(),
+            false,
+            true,
+        );
+        // shares of older versions are the plain bincode serialization
+        let legacy = bincode::serialize(&share).unwrap();
+        let reader = WitnessShareReader::new(legacy.as_slice()).unwrap();
+        assert_eq!(reader.curve(), None);
+        let parsed = parse_witness_share_rep3_as_additive::<_, Fr>(reader).unwrap();
+        assert_eq!(parsed.public_inputs, witness.values[..2]);
+    }
+
+    #[test]
+    fn deterministic_rng_reproduces_shares() {
+        use ark_bn254::Fr;
+
+        let share = |seed| {
+            let mut rng = ShareRng::new(RngSource::Deterministic, Some(seed)).unwrap();
+            let shares = SerializeableSharedRep3Witness::<Fr, SeedRng>::share_rep3(
+                multiplier2_witness(),
+                2,
+                &mut rng,
+                false,
+                false,
+            );
+            shares.map(|share| bincode::serialize(&share).unwrap())
+        };
+        assert_eq!(share(42), share(42));
+        assert_ne!(share(42), share(43));
+
+        let err = ShareRng::new(RngSource::Deterministic, None).err().unwrap();
+        assert!(err.to_string().contains("requires --rng-seed"));
+        let err = ShareRng::new(RngSource::Os, Some(42)).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("only used with --rng deterministic"));
+    }
+}
@@ -0,0 +1,94 @@
+//! A minimal HTTP readiness endpoint for orchestrated deployments.
+//!
+//! Loading a large zkey can take a while, and connecting to the other parties blocks until all of them are up. The endpoint lets load balancers and orchestrators see when a party has finished its local setup without sending it a proving request: every HTTP request (or plain TCP connection) is answered with `503 Service Unavailable` until the party is ready, and with `200 OK` afterwards.
+//!
+//! A party is ready once it has loaded its inputs and the other parties can connect to it, so a party that is reported as ready never refuses the connections of the others.
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// How long we wait for a client to send its request before answering anyway.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A readiness endpoint served on a background thread, see the [module documentation](self).
+///
+/// The endpoint keeps running until the process exits.
+#[derive(Debug)]
+pub struct ReadinessProbe {
+    ready: Arc<AtomicBool>,
+    local_addr: SocketAddr,
+}
+
+impl ReadinessProbe {
+    /// Binds the endpoint to `addr` and starts answering requests as not ready.
+    pub fn serve(addr: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let ready = Arc::new(AtomicBool::new(false));
+        let ready_ = Arc::clone(&ready);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| respond(stream, &ready_));
+                if let Err(err) = result {
+                    tracing::debug!("readiness endpoint: {err}");
+                }
+            }
+        });
+        tracing::info!("Serving readiness endpoint on {local_addr}");
+        Ok(Self { ready, local_addr })
+    }
+
+    /// Returns the address the endpoint is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Marks the party as ready, all following requests are answered with `200 OK`.
+    pub fn set_ready(&self) {
+        if !self.ready.swap(true, Ordering::SeqCst) {
+            tracing::info!("Party is ready");
+        }
+    }
+}
+
+fn respond(stream: TcpStream, ready: &AtomicBool) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    // consume the request head, we answer every request the same way
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) if line == "\r\n" || line == "\n" => break,
+            Ok(_) => {}
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                break
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let (status, body) = if ready.load(Ordering::SeqCst) {
+        ("200 OK", "OK\n")
+    } else {
+        ("503 Service Unavailable", "NOT READY\n")
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,177 @@
+//! Resharing of a REP3-shared witness under a new protocol and number of parties.
+//!
+//! All parties are connected in one network of `max(3, num_parties)` parties. Parties 0, 1 and 2 hold the REP3 shares of the witness, and parties `0..num_parties` receive the new shares, so a party can take part in both the old and the new sharing. Every holder of a REP3 share
+//!
+//! 1. converts its share to an additive share of the witness,
+//! 2. shares the additive share under the new protocol and sends one of the resulting shares to every new party,
+//!
+//! and every new party adds up the three shares it received. As the sum of sharings of the additive shares is a sharing of their sum, the new parties end up with a fresh sharing of the witness, while no party sees more than its own additive share. Party 0 sends the public inputs along to the new parties that do not hold a REP3 share.
+//!
+//! As the MPC protocols themselves, the resharing is only secure against semi-honest parties.
+use ark_ff::PrimeField;
+use co_circom_snarks::SharedWitness;
+use color_eyre::eyre::{self, Context};
+use mpc_core::protocols::{
+    rep3::{self, Rep3PrimeFieldShare},
+    shamir::{self, network::ShamirNetwork, ShamirPrimeFieldShare},
+};
+use rand::{CryptoRng, Rng};
+
+/// The number of parties holding the REP3 shares that are reshared.
+const REP3_PARTIES: usize = 3;
+
+/// Reshares a REP3-shared witness as Shamir shares of degree `threshold` for `num_parties` parties, see the [module documentation](self).
+///
+/// Parties 0, 1 and 2 pass their witness share as additive share (see [parse_witness_share_rep3_as_additive](crate::parse_witness_share_rep3_as_additive)), all other parties pass `None`. Returns the new share for parties `0..num_parties` and `None` for the remaining parties.
+pub fn reshare_rep3_to_shamir<F: PrimeField, N: ShamirNetwork, R: Rng + CryptoRng>(
+    net: &mut N,
+    witness: Option<SharedWitness<F, F>>,
+    threshold: usize,
+    num_parties: usize,
+    rng: &mut R,
+) -> color_eyre::Result<Option<SharedWitness<F, ShamirPrimeFieldShare<F>>>> {
+    if threshold == 0 || threshold >= num_parties {
+        eyre::bail!(
+            "the threshold must be at least 1 and less than the number of parties, but got threshold {threshold} for {num_parties} parties"
+        );
+    }
+    let reshared = distribute(net, witness, num_parties, |additive| {
+        shamir::share_field_elements(additive, threshold, num_parties, rng)
+            .into_iter()
+            .map(ShamirPrimeFieldShare::convert_vec)
+            .collect()
+    })?;
+    Ok(reshared.map(|reshared| SharedWitness {
+        public_inputs: reshared.public_inputs,
+        witness: ShamirPrimeFieldShare::convert_vec_rev(reshared.witness),
+    }))
+}
+
+/// Reshares a REP3-shared witness as fresh REP3 shares, see the [module documentation](self). The new shares are independent of the old ones.
+///
+/// All three parties pass their witness share as additive share (see [parse_witness_share_rep3_as_additive](crate::parse_witness_share_rep3_as_additive)).
+pub fn reshare_rep3_to_rep3<F: PrimeField, N: ShamirNetwork, R: Rng + CryptoRng>(
+    net: &mut N,
+    witness: SharedWitness<F, F>,
+    rng: &mut R,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    let reshared = distribute(net, Some(witness), REP3_PARTIES, |additive| {
+        let mut shares = (0..REP3_PARTIES)
+            .map(|_| Vec::with_capacity(additive.len()))
+            .collect::<Vec<_>>();
+        for val in additive {
+            for (share, val) in shares
+                .iter_mut()
+                .zip(rep3::share_field_element_additive(*val, rng))
+            {
+                share.push(val);
+            }
+        }
+        shares
+    })?
+    .expect("all REP3 parties receive a share");
+
+    // every party holds its own additive share and the one of the previous party
+    let id = net.get_id();
+    net.send_many((id + 1) % REP3_PARTIES, &reshared.witness)
+        .context("while sending share to next party")?;
+    let prev = net
+        .recv_many::<F>((id + REP3_PARTIES - 1) % REP3_PARTIES)
+        .context("while receiving share of previous party")?;
+    if prev.len() != reshared.witness.len() {
+        eyre::bail!(
+            "previous party sent {} shares, but expected {}",
+            prev.len(),
+            reshared.witness.len()
+        );
+    }
+    Ok(SharedWitness {
+        public_inputs: reshared.public_inputs,
+        witness: reshared
+            .witness
+            .into_iter()
+            .zip(prev)
+            .map(|(a, b)| Rep3PrimeFieldShare::new(a, b))
+            .collect(),
+    })
+}
+
+/// Sends the shares of the additive witness share of parties 0, 1 and 2 to the new parties and adds up the received shares. `share` splits an additive share into one share per new party.
+fn distribute<F: PrimeField, N: ShamirNetwork>(
+    net: &mut N,
+    witness: Option<SharedWitness<F, F>>,
+    num_parties: usize,
+    share: impl FnOnce(&[F]) -> Vec<Vec<F>>,
+) -> color_eyre::Result<Option<SharedWitness<F, F>>> {
+    let id = net.get_id();
+    let expected_parties = num_parties.max(REP3_PARTIES);
+    if net.get_num_parties() != expected_parties {
+        eyre::bail!(
+            "resharing to {num_parties} parties needs a network of {expected_parties} parties, but the network has {}",
+            net.get_num_parties()
+        );
+    }
+
+    let mut own = None;
+    let mut public_inputs = None;
+    match (id < REP3_PARTIES, witness) {
+        (true, Some(witness)) => {
+            let shares = share(&witness.witness);
+            for (party, shares) in shares.into_iter().enumerate() {
+                if party == id {
+                    own = Some(shares);
+                } else {
+                    net.send_many(party, &shares)
+                        .with_context(|| format!("while sending shares to party {party}"))?;
+                }
+            }
+            public_inputs = Some(witness.public_inputs);
+        }
+        (true, None) => eyre::bail!("party {id} holds a REP3 share and must provide its witness"),
+        (false, Some(_)) => {
+            eyre::bail!("only parties 0, 1 and 2 hold REP3 shares, but party {id} got a witness")
+        }
+        (false, None) => {}
+    }
+    if id >= num_parties {
+        return Ok(None);
+    }
+
+    let mut sum = own;
+    for party in (0..REP3_PARTIES).filter(|party| *party != id) {
+        let received = net
+            .recv_many::<F>(party)
+            .with_context(|| format!("while receiving shares of party {party}"))?;
+        match sum.as_mut() {
+            Some(sum) if sum.len() != received.len() => eyre::bail!(
+                "party {party} sent {} shares, but expected {}",
+                received.len(),
+                sum.len()
+            ),
+            Some(sum) => sum
+                .iter_mut()
+                .zip(received)
+                .for_each(|(sum, received)| *sum += received),
+            None => sum = Some(received),
+        }
+    }
+
+    let public_inputs = if id == 0 {
+        let public_inputs = public_inputs.expect("party 0 holds a REP3 share");
+        for party in REP3_PARTIES..num_parties {
+            net.send_many(party, &public_inputs)
+                .with_context(|| format!("while sending public inputs to party {party}"))?;
+        }
+        public_inputs
+    } else if let Some(public_inputs) = public_inputs {
+        public_inputs
+    } else {
+        net.recv_many(0)
+            .context("while receiving public inputs of party 0")?
+    };
+
+    Ok(Some(SharedWitness {
+        public_inputs,
+        witness: sum.expect("received shares of the other REP3 parties"),
+    }))
+}
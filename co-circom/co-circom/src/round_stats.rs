@@ -0,0 +1,70 @@
+//! A [`tracing_subscriber`] layer that counts the communication rounds of the MPC networks and attributes them to the spans that are active at that time.
+
+use std::sync::{Arc, Mutex};
+
+use mpc_net::rounds::ROUND_TRACE_TARGET;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::{
+    filter::{filter_fn, FilterFn},
+    layer::Context,
+    registry::LookupSpan,
+    Layer,
+};
+
+type Counts = Arc<Mutex<Vec<(&'static str, usize)>>>;
+
+/// Counts the round events emitted by the networks (see [`mpc_net::rounds`]) per span name. A round is attributed to every span in its scope, so nested phases are reported individually as well as part of their parents.
+#[derive(Clone, Debug, Default)]
+pub struct RoundCountLayer {
+    counts: Counts,
+}
+
+impl RoundCountLayer {
+    /// Creates a new layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The filter that should be used for this layer. It enables all spans up to level DEBUG (the level used for the phases of the protocols) and the round events, independent of the filter used for logging.
+    pub fn filter() -> FilterFn<impl Fn(&Metadata<'_>) -> bool> {
+        filter_fn(|metadata| {
+            (metadata.is_span() && *metadata.level() <= Level::DEBUG)
+                || metadata.target() == ROUND_TRACE_TARGET
+        })
+    }
+
+    /// Returns the number of rounds per span name, in the order the spans were first seen.
+    pub fn counts(&self) -> Vec<(&'static str, usize)> {
+        self.counts.lock().expect("not poisoned").clone()
+    }
+
+    /// Logs the number of rounds per span name, e.g., "Groth16 - Proof: 42 rounds".
+    pub fn report(&self) {
+        for (name, rounds) in self.counts() {
+            tracing::info!("{name}: {rounds} rounds");
+        }
+    }
+}
+
+impl<S> Layer<S> for RoundCountLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != ROUND_TRACE_TARGET {
+            return;
+        }
+        let mut counts = self.counts.lock().expect("not poisoned");
+        let mut count = |name: &'static str| {
+            if let Some((_, rounds)) = counts.iter_mut().find(|(n, _)| *n == name) {
+                *rounds += 1;
+            } else {
+                counts.push((name, 1));
+            }
+        };
+        match ctx.event_scope(event) {
+            Some(scope) => scope.from_root().for_each(|span| count(span.name())),
+            None => count("unattributed"),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! The directory layout and JSON formatting of the proofs written by snarkjs.
+//!
+//! snarkjs writes the proof to `proof.json` and the public inputs, without the leading constant 1, to `public.json`. Both files are indented by a single space and have no trailing newline.
+//! Writing them the same way makes the files of co-circom interchangeable with the ones of snarkjs, even for tools that compare or hash them.
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use ark_ff::PrimeField;
+use color_eyre::eyre::{self, Context};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+
+/// The name of the proof file written by snarkjs.
+pub const PROOF_FILE: &str = "proof.json";
+/// The name of the public input file written by snarkjs.
+pub const PUBLIC_FILE: &str = "public.json";
+
+/// Writes `value` as JSON formatted like snarkjs.
+pub fn to_writer<T: Serialize, W: Write>(value: &T, writer: W) -> eyre::Result<()> {
+    let mut ser =
+        serde_json::Serializer::with_formatter(writer, PrettyFormatter::with_indent(b" "));
+    value.serialize(&mut ser).context("while writing JSON")
+}
+
+/// Returns the public inputs as the decimal strings used by circom and snarkjs.
+pub fn public_inputs_as_strings<F: PrimeField>(public_inputs: &[F]) -> Vec<String> {
+    public_inputs
+        .iter()
+        .map(|f| {
+            if f.is_zero() {
+                "0".to_string()
+            } else {
+                f.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Writes the proof and the public inputs to [PROOF_FILE] and [PUBLIC_FILE] in `dir`, which is created if it does not exist.
+/// The public inputs must not contain the leading constant 1.
+pub fn write_dir<T: Serialize, F: PrimeField>(
+    dir: &Path,
+    proof: &T,
+    public_inputs: &[F],
+) -> eyre::Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("while creating {}", dir.display()))?;
+    let proof_file = File::create(dir.join(PROOF_FILE)).context("while creating proof file")?;
+    let mut proof_file = BufWriter::new(proof_file);
+    to_writer(proof, &mut proof_file).context("while writing proof")?;
+    proof_file.flush().context("while writing proof")?;
+
+    let public_file =
+        File::create(dir.join(PUBLIC_FILE)).context("while creating public input file")?;
+    let mut public_file = BufWriter::new(public_file);
+    to_writer(&public_inputs_as_strings(public_inputs), &mut public_file)
+        .context("while writing public inputs")?;
+    public_file.flush().context("while writing public inputs")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Bn254;
+    use circom_types::{groth16::Groth16Proof, plonk::PlonkProof};
+
+    use super::*;
+
+    const GROTH16: &str = "../../test_vectors/Groth16/bn254/poseidon";
+    const PLONK: &str = "../../test_vectors/Plonk/bn254/poseidon";
+
+    fn rewrite<T: Serialize + serde::de::DeserializeOwned>(path: &str) -> (Vec<u8>, Vec<u8>) {
+        let original = fs::read(path).unwrap();
+        let value: T = serde_json::from_slice(&original).unwrap();
+        let mut rewritten = vec![];
+        to_writer(&value, &mut rewritten).unwrap();
+        (original, rewritten)
+    }
+
+    #[test]
+    fn matches_snarkjs_byte_for_byte() {
+        for dir in [GROTH16, PLONK] {
+            let public = fs::read(format!("{dir}/public.json")).unwrap();
+            let strings: Vec<String> = serde_json::from_slice(&public).unwrap();
+            let values = strings
+                .iter()
+                .map(|s| s.parse::<ark_bn254::Fr>().unwrap())
+                .collect::<Vec<_>>();
+            let mut rewritten = vec![];
+            to_writer(&public_inputs_as_strings(&values), &mut rewritten).unwrap();
+            assert_eq!(public, rewritten);
+        }
+
+        let (original, rewritten) =
+            rewrite::<Groth16Proof<Bn254>>(&format!("{GROTH16}/circom.proof"));
+        assert_eq!(original, rewritten);
+        let (original, rewritten) = rewrite::<PlonkProof<Bn254>>(&format!("{PLONK}/circom.proof"));
+        assert_eq!(original, rewritten);
+    }
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by the unit tests of the library and the binary.
+use std::path::PathBuf;
+
+/// Creates an empty temporary directory for the test `name`, removing what a previous run left behind.
+pub(crate) fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("co-circom-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,136 @@
+//! Benchmarks the plain Groth16 prover on rayon thread pools of different sizes, which is what
+//! `--threads` of the proving commands of `co-circom` bounds.
+//!
+//! Runs on the poseidon test vector and on a synthetic circuit with 2^18 constraints. Set
+//! `CO_GROTH16_BENCH_CIRCUIT` to a directory containing a bn254 `circuit.zkey` and `witness.wtns`
+//! to benchmark another circuit instead of the poseidon test vector.
+//!
+//! The prover blocks a thread of the pool while it waits for the MSMs it spawned on the same pool,
+//! so the smallest pool has two threads. A pool with a single thread would deadlock.
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, UniformRand};
+use ark_relations::r1cs::ConstraintMatrices;
+use circom_types::{groth16::ZKey, Witness};
+use co_circom_snarks::SharedWitness;
+use co_groth16::Groth16;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{fs::File, path::PathBuf, sync::Arc};
+
+/// The number of distinct random points in the synthetic zkey. The queries repeat them, which does not change the cost of the MSMs.
+const SYNTHETIC_POINTS: usize = 256;
+
+fn open(file: &str) -> File {
+    let mut d = match std::env::var_os("CO_GROTH16_BENCH_CIRCUIT") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            d.push("../../test_vectors/Groth16/bn254/poseidon");
+            d
+        }
+    };
+    d.push(file);
+    File::open(d).unwrap()
+}
+
+fn test_vector() -> (ZKey<Bn254>, SharedWitness<Fr, Fr>) {
+    let zkey = ZKey::<Bn254>::from_reader(open("circuit.zkey")).unwrap();
+    let witness = Witness::<Fr>::from_reader(open("witness.wtns")).unwrap();
+    let witness = SharedWitness {
+        public_inputs: witness.values[..=zkey.n_public].to_vec(),
+        witness: witness.values[zkey.n_public + 1..].to_vec(),
+    };
+    (zkey, witness)
+}
+
+/// A zkey with random points for a circuit with one public input, in which the `i`-th constraint squares the `i`-th private value.
+/// The resulting proof is not valid, but the prover does the same work as for a real circuit of this size. Only the type of the verifying
+/// key is taken from `template`, its points are replaced.
+fn synthetic(template: &ZKey<Bn254>, pow: usize) -> (ZKey<Bn254>, SharedWitness<Fr, Fr>) {
+    let mut rng = rand::thread_rng();
+    let num_inputs = 2;
+    let num_constraints = (1 << pow) - num_inputs;
+    let g1 = (0..SYNTHETIC_POINTS)
+        .map(|_| (G1Affine::generator() * Fr::rand(&mut rng)).into_affine())
+        .collect::<Vec<_>>();
+    let g2 = (0..SYNTHETIC_POINTS)
+        .map(|_| (G2Affine::generator() * Fr::rand(&mut rng)).into_affine())
+        .collect::<Vec<_>>();
+    let g1_query = |len: usize| g1.iter().copied().cycle().take(len).collect::<Vec<_>>();
+    let square = (0..num_constraints)
+        .map(|i| vec![(Fr::one(), num_inputs + i)])
+        .collect::<Vec<_>>();
+    let mut zkey = ZKey {
+        n_public: num_inputs - 1,
+        pow,
+        vk: template.vk.clone(),
+        beta_g1: g1[1],
+        delta_g1: g1[2],
+        a_query: g1_query(num_inputs + num_constraints),
+        b_g1_query: g1_query(num_inputs + num_constraints),
+        b_g2_query: g2
+            .iter()
+            .copied()
+            .cycle()
+            .take(num_inputs + num_constraints)
+            .collect(),
+        h_query: g1_query(1 << pow),
+        l_query: g1_query(num_constraints),
+        matrices: ConstraintMatrices {
+            num_instance_variables: num_inputs,
+            num_witness_variables: num_constraints,
+            num_constraints,
+            a_num_non_zero: num_constraints,
+            b_num_non_zero: num_constraints,
+            c_num_non_zero: 0,
+            a: square.clone(),
+            b: square,
+            c: vec![],
+        },
+    };
+    zkey.vk.alpha_g1 = g1[0];
+    zkey.vk.beta_g2 = g2[0];
+    zkey.vk.gamma_g2 = g2[1];
+    zkey.vk.delta_g2 = g2[2];
+    zkey.vk.gamma_abc_g1 = g1_query(num_inputs);
+    let witness = SharedWitness {
+        public_inputs: vec![Fr::one(), Fr::rand(&mut rng)],
+        witness: (0..num_constraints).map(|_| Fr::rand(&mut rng)).collect(),
+    };
+    (zkey, witness)
+}
+
+fn bench_threads(c: &mut Criterion, name: &str, zkey: ZKey<Bn254>, witness: SharedWitness<Fr, Fr>) {
+    let zkey = Arc::new(zkey);
+    let cores = std::thread::available_parallelism().map_or(2, |n| n.get().max(2));
+    let mut threads = vec![2, 4, 8, cores];
+    threads.retain(|t| *t <= cores);
+    threads.sort();
+    threads.dedup();
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for t in threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(t)
+            .build()
+            .unwrap();
+        group.bench_with_input(BenchmarkId::new("threads", t), &t, |b, _| {
+            b.iter(|| {
+                pool.install(|| Groth16::<Bn254>::plain_prove(zkey.clone(), witness.clone()))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn groth16_prove_threads(c: &mut Criterion) {
+    let (zkey, witness) = test_vector();
+    let (large_zkey, large_witness) = synthetic(&zkey, 18);
+    bench_threads(c, "groth16 prove", zkey, witness);
+    bench_threads(c, "groth16 prove 2^18", large_zkey, large_witness);
+}
+
+criterion_group!(benches, groth16_prove_threads);
+criterion_main!(benches);
@@ -0,0 +1,127 @@
+//! This module implements the aggregation of many Groth16 proofs for the same verification key.
+//!
+//! An [`AggregatedProof`] contains the proofs and their public inputs. Instead of checking one pairing equation per proof, [`Groth16::verify_aggregated`] combines the equations of all proofs with random weights and checks them with a single multi-pairing of `n + 3` pairings with one final exponentiation, instead of three pairings and a final exponentiation per proof. The size of the aggregated proof is still linear in the number of proofs, as a succinct aggregation requires an additional trusted setup.
+
+use crate::groth16::Groth16;
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::{UniformRand, Zero};
+use circom_types::groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey};
+use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use serde::{Deserialize, Serialize};
+
+/// Many Groth16 proofs for the same verification key, created with [`Groth16::aggregate`] and checked with [`Groth16::verify_aggregated`].
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct AggregatedProof<P: Pairing + CircomArkworksPairingBridge>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// The aggregated proofs
+    pub proofs: Vec<Groth16Proof<P>>,
+    /// The public inputs of each proof, without the constant 1 of the witness
+    pub public_inputs: Vec<JsonPublicInput<P::ScalarField>>,
+    /// The protocol of the aggregated proofs (always `"groth16"`)
+    pub protocol: String,
+    /// The curve of the aggregated proofs
+    pub curve: String,
+}
+
+impl<P: Pairing> Groth16<P>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    /// Aggregates proofs for the verification key `vk`, given with their public inputs.
+    ///
+    /// Fails if no proofs are given, if the number of public inputs of a proof does not match `vk`, or if a proof is invalid. In the latter case, the error names the first invalid proof.
+    pub fn aggregate(
+        vk: &JsonVerificationKey<P>,
+        proofs: Vec<(Groth16Proof<P>, Vec<P::ScalarField>)>,
+    ) -> Result<AggregatedProof<P>, eyre::Report> {
+        let (proofs, public_inputs): (Vec<_>, Vec<_>) = proofs
+            .into_iter()
+            .map(|(proof, values)| (proof, JsonPublicInput { values }))
+            .unzip();
+        let aggregated = AggregatedProof {
+            proofs,
+            public_inputs,
+            protocol: "groth16".to_owned(),
+            curve: P::get_circom_name(),
+        };
+        if Self::verify_aggregated(vk, &aggregated)? {
+            return Ok(aggregated);
+        }
+        for (i, (proof, public_inputs)) in aggregated
+            .proofs
+            .iter()
+            .zip(aggregated.public_inputs.iter())
+            .enumerate()
+        {
+            if !Self::verify(vk, proof, &public_inputs.values)? {
+                eyre::bail!("proof {i} is invalid");
+            }
+        }
+        // every proof is valid on its own, so the combined check must not fail
+        eyre::bail!("the aggregated check failed although every proof is valid")
+    }
+
+    /// Verifies an [`AggregatedProof`] against the verification key `vk`, see the [module documentation](self). Returns `Ok(true)` if all aggregated proofs are valid.
+    ///
+    /// Fails if the aggregated proof contains no proofs or if the number of public inputs of a proof does not match `vk`.
+    pub fn verify_aggregated(
+        vk: &JsonVerificationKey<P>,
+        aggregated: &AggregatedProof<P>,
+    ) -> Result<bool, eyre::Report> {
+        if aggregated.proofs.is_empty() {
+            eyre::bail!("the aggregated proof contains no proofs");
+        }
+        if aggregated.proofs.len() != aggregated.public_inputs.len() {
+            eyre::bail!(
+                "the aggregated proof contains {} proofs, but public inputs for {}",
+                aggregated.proofs.len(),
+                aggregated.public_inputs.len()
+            );
+        }
+        if vk.ic.len() != vk.n_public + 1 {
+            eyre::bail!("malformed verification key");
+        }
+        for (i, public_inputs) in aggregated.public_inputs.iter().enumerate() {
+            if public_inputs.values.len() != vk.n_public {
+                eyre::bail!(
+                    "proof {i} has {} public inputs, but the verification key expects {}",
+                    public_inputs.values.len(),
+                    vk.n_public
+                );
+            }
+        }
+
+        // for every proof e(A, B) = e(α, β) e(IC · (1, inputs), γ) e(C, δ), we check the weighted product
+        let mut rng = rand::thread_rng();
+        let mut g1 = Vec::with_capacity(aggregated.proofs.len() + 3);
+        let mut g2 = Vec::with_capacity(aggregated.proofs.len() + 3);
+        // the first scalar is the sum of the weights, as the witness starts with the constant 1
+        let mut ic_scalars = vec![P::ScalarField::zero(); vk.ic.len()];
+        let mut c = P::G1::zero();
+        for (proof, public_inputs) in aggregated.proofs.iter().zip(&aggregated.public_inputs) {
+            let r = P::ScalarField::rand(&mut rng);
+            ic_scalars[0] += r;
+            for (scalar, input) in ic_scalars[1..].iter_mut().zip(&public_inputs.values) {
+                *scalar += r * input;
+            }
+            c += proof.pi_c * r;
+            g1.push((proof.pi_a * r).into_affine());
+            g2.push(proof.pi_b);
+        }
+        let ic = P::G1::msm_unchecked(&vk.ic, &ic_scalars);
+        g1.push((vk.alpha_1 * -ic_scalars[0]).into_affine());
+        g2.push(vk.beta_2);
+        g1.push((-ic).into_affine());
+        g2.push(vk.gamma_2);
+        g1.push((-c).into_affine());
+        g2.push(vk.delta_2);
+        Ok(P::multi_pairing(g1, g2).is_zero())
+    }
+}
@@ -7,7 +7,7 @@ use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_relations::r1cs::{ConstraintMatrices, Matrix, SynthesisError};
 use circom_types::groth16::{Groth16Proof, ZKey};
 use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{ProgressCallback, ProverPhase, SharedWitness};
 use eyre::Result;
 use mpc_core::protocols::rep3::network::{IoContext, Rep3MpcNet};
 use mpc_core::protocols::shamir::network::ShamirMpcNet;
@@ -90,6 +90,7 @@ fn root_of_unity_for_groth16<F: PrimeField + FftField>(
 pub struct CoGroth16<P: Pairing, T: CircomGroth16Prover<P>> {
     pub(crate) driver: T,
     phantom_data: PhantomData<P>,
+    progress: Option<ProgressCallback>,
 }
 
 impl<P: Pairing + CircomArkworksPairingBridge, T: CircomGroth16Prover<P>> CoGroth16<P, T>
@@ -102,11 +103,29 @@ where
         Self {
             driver,
             phantom_data: PhantomData,
+            progress: None,
+        }
+    }
+
+    /// Registers a callback that is invoked as the prover moves through the [ProverPhase]s of a proof, e.g., to drive a progress bar.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(ProverPhase, f32) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    fn report_progress(&self, phase: ProverPhase, progress: f32) {
+        if let Some(callback) = &self.progress {
+            callback(phase, progress);
         }
     }
 
     /// Execute the Groth16 prover using the internal MPC driver.
     /// This version takes the Circom-generated constraint matrices as input and does not re-calculate them.
+    ///
+    /// The local MSMs for `A`, `B`, `C` and `H` run concurrently on the current rayon thread pool, its size bounds the number of threads used by the prover.
     #[instrument(level = "debug", name = "Groth16 - Proof", skip_all)]
     pub fn prove(
         mut self,
@@ -116,11 +135,34 @@ where
         let id = self.driver.get_party_id();
         tracing::info!("Party {}: starting proof generation..", id);
         let start = Instant::now();
+        self.report_progress(ProverPhase::Witness, 0.0);
         let matrices = &zkey.matrices;
         let num_inputs = matrices.num_instance_variables;
         let num_constraints = matrices.num_constraints;
-        let public_inputs = Arc::new(private_witness.public_inputs);
-        let private_witness = Arc::new(private_witness.witness);
+        if num_constraints == 0 {
+            eyre::bail!("circuit has no constraints");
+        }
+        if private_witness.public_inputs.len() < num_inputs
+            || private_witness.witness.len() < zkey.l_query.len()
+        {
+            eyre::bail!(
+                "witness is too short for the zkey: expected at least {} public and {} private values, got {} and {}",
+                num_inputs,
+                zkey.l_query.len(),
+                private_witness.public_inputs.len(),
+                private_witness.witness.len()
+            );
+        }
+        // like the Plonk prover, we ignore additional values at the end of the witness. The queries are split
+        // by the number of public inputs, so they have to be cut to the lengths of the zkey
+        let SharedWitness {
+            mut public_inputs,
+            witness: mut private_witness,
+        } = private_witness;
+        public_inputs.truncate(num_inputs);
+        private_witness.truncate(zkey.l_query.len());
+        let public_inputs = Arc::new(public_inputs);
+        let private_witness = Arc::new(private_witness);
         let h = self.witness_map_from_matrices(
             zkey.pow,
             matrices,
@@ -131,6 +173,7 @@ where
         )?;
         let (r, s) = (self.driver.rand()?, self.driver.rand()?);
 
+        self.report_progress(ProverPhase::Msm, 1.0 / 3.0);
         let proof = self.create_proof_with_assignment(
             Arc::clone(&zkey),
             r,
@@ -145,6 +188,18 @@ where
         Ok(proof)
     }
 
+    /// Execute the Groth16 prover with a zkey that stays resident at the caller.
+    ///
+    /// Parsing a zkey is expensive, so long-lived services should parse it once with [`ZKey::from_reader`], keep the [`Arc`] around and pass it to this method for every witness.
+    /// Only the reference count is increased, the zkey itself is never copied or re-parsed. As the MPC driver is consumed by a proof, every call needs a fresh prover instance.
+    pub fn prove_with_key(
+        self,
+        zkey: &Arc<ZKey<P>>,
+        private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<Groth16Proof<P>> {
+        self.prove(Arc::clone(zkey), private_witness)
+    }
+
     fn evaluate_constraint(
         party_id: T::PartyID,
         domain_size: usize,
@@ -434,6 +489,7 @@ where
         let g_a = r_g1_rx.blocking_recv()?;
         let g1_b = s_g1_rx.blocking_recv()?;
 
+        self.report_progress(ProverPhase::Finalize, 2.0 / 3.0);
         let network_round = tracing::debug_span!("network round after calc coeff").entered();
         let (g_a_opened, r_g1_b) = self.driver.open_point_and_scalar_mul(&g_a, &g1_b, r)?;
         network_round.exit();
@@ -453,6 +509,7 @@ where
         let g2_b = s_g2_rx.blocking_recv()?;
         let (g_c_opened, g2_b_opened) = self.driver.open_two_points(g_c, g2_b)?;
         last_round.exit();
+        self.report_progress(ProverPhase::Finalize, 1.0);
 
         Ok(Groth16Proof {
             pi_a: g_a_opened.into_affine(),
@@ -478,6 +535,7 @@ where
         Ok(CoGroth16 {
             driver,
             phantom_data: PhantomData,
+            progress: None,
         })
     }
 
@@ -496,11 +554,21 @@ where
 {
     /// Create a new [ShamirCoGroth16] protocol with a given network configuration.
     pub fn with_network_config(threshold: usize, config: NetworkConfig) -> Result<Self> {
+        let party_ids = (0..config.parties.len()).collect::<Vec<_>>();
+        Self::with_network_for_parties(threshold, ShamirMpcNet::new(config)?, &party_ids)
+    }
+
+    /// Create a new [ShamirCoGroth16] protocol with a given network for witness shares created for an explicit set of parties. The party with id `i` in the network holds the shares of party `party_ids[i]`.
+    pub fn with_network_for_parties(
+        threshold: usize,
+        mpc_net: ShamirMpcNet,
+        party_ids: &[usize],
+    ) -> Result<Self> {
         // we need 2 + 1 number of corr rand pairs. We need the values r/s (1 pair) and 2 muls (2
         // pairs)
         let num_pairs = 3;
-        let mpc_net = ShamirMpcNet::new(config)?;
-        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
+        let preprocessing =
+            ShamirPreprocessing::new_for_parties(threshold, mpc_net, num_pairs, party_ids)?;
         let mut protocol0 = ShamirProtocol::from(preprocessing);
         // the protocol1 is only used for scalar_mul and a field_mul which need 1 pair each (ergo 2
         // pairs)
@@ -509,6 +577,7 @@ where
         Ok(CoGroth16 {
             driver,
             phantom_data: PhantomData,
+            progress: None,
         })
     }
 }
@@ -528,8 +597,27 @@ where
         private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
     ) -> Result<Groth16Proof<P>> {
         let prover = Self {
-            driver: PlainGroth16Driver,
+            driver: PlainGroth16Driver::default(),
+            phantom_data: PhantomData,
+            progress: None,
+        };
+        prover.prove(zkey, private_witness)
+    }
+
+    /// *Locally* create a reproducible `Groth16` proof, i.e., the randomness `r` and `s` are derived from `seed`.
+    /// The same seed, zkey and witness always result in the same proof, which allows for golden-file tests.
+    ///
+    /// Only meant for tests, a proof with a known seed is not zero-knowledge.
+    #[cfg(any(test, feature = "deterministic-rng"))]
+    pub fn plain_prove_with_seed(
+        seed: [u8; 32],
+        zkey: Arc<ZKey<P>>,
+        private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
+    ) -> Result<Groth16Proof<P>> {
+        let prover = Self {
+            driver: PlainGroth16Driver::with_seed(seed),
             phantom_data: PhantomData,
+            progress: None,
         };
         prover.prove(zkey, private_witness)
     }
@@ -1,11 +1,15 @@
 //! A library for creating and verifying Groth16 proofs in a collaborative fashion using MPC.
 #![warn(missing_docs)]
+#[cfg(feature = "verifier")]
+mod aggregate;
 mod groth16;
 /// This module contains the Groth16 prover trait
 pub mod mpc;
 #[cfg(feature = "verifier")]
 mod verifier;
 
+#[cfg(feature = "verifier")]
+pub use aggregate::AggregatedProof;
 pub use groth16::CoGroth16;
 pub use groth16::Groth16;
 pub use groth16::Rep3CoGroth16;
@@ -18,15 +22,17 @@ mod tests {
     use ark_bn254::Bn254;
     use circom_types::{
         groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey, ZKey},
-        Witness,
+        Witness, R1CS,
     };
-    use co_circom_snarks::SharedWitness;
+    use co_circom_snarks::{ProverPhase, SharedWitness};
     use std::{
         fs::{self, File},
-        sync::Arc,
+        sync::{Arc, Mutex},
     };
 
     use crate::groth16::Groth16;
+    use crate::mpc::plain::PlainGroth16Driver;
+    use crate::AggregatedProof;
 
     #[test]
     fn create_proof_and_verify_bn254() {
@@ -53,6 +59,278 @@ mod tests {
         assert!(verified);
     }
 
+    #[test]
+    fn public_signals_include_public_output_bn254() {
+        // multiplier2 has the private inputs a and b and the public output c = a * b
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let r1cs_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let public_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/public.json").unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file).unwrap());
+        let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let public_input =
+            serde_json::from_str::<JsonPublicInput<ark_bn254::Fr>>(&public_string).unwrap();
+
+        let witness = SharedWitness {
+            public_inputs: witness.values[..r1cs.num_inputs].to_vec(),
+            witness: witness.values[r1cs.num_inputs..].to_vec(),
+        };
+        let public_signals = witness
+            .public_signals(zkey.num_public_inputs())
+            .unwrap()
+            .to_vec();
+        assert_eq!(public_signals, public_input.values);
+        assert_eq!(public_signals, [ark_bn254::Fr::from(33)]);
+
+        // a share without the output does not match the zkey
+        let without_output = SharedWitness {
+            public_inputs: witness.public_inputs[..1].to_vec(),
+            witness: witness.witness.clone(),
+        };
+        assert!(without_output
+            .public_signals(zkey.num_public_inputs())
+            .is_err());
+
+        let proof = Groth16::<Bn254>::plain_prove(zkey, witness).expect("proof generation works");
+        let verified = Groth16::verify(&vk, &proof, &public_signals).expect("can verify");
+        assert!(verified);
+    }
+
+    #[test]
+    fn aggregate_and_verify_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file).unwrap());
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let public_input = witness.values[1..=zkey.n_public].to_vec();
+        let proofs = || {
+            let mut proofs = (0..3)
+                .map(|_| {
+                    let witness = SharedWitness {
+                        public_inputs: witness.values[..=zkey.n_public].to_vec(),
+                        witness: witness.values[zkey.n_public + 1..].to_vec(),
+                    };
+                    let proof = Groth16::<Bn254>::plain_prove(zkey.clone(), witness)
+                        .expect("proof generation works");
+                    (proof, public_input.clone())
+                })
+                .collect::<Vec<_>>();
+            let circom_proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+            proofs.push((circom_proof, public_input.clone()));
+            proofs
+        };
+
+        let aggregated = Groth16::<Bn254>::aggregate(&vk, proofs()).unwrap();
+        assert!(Groth16::<Bn254>::verify_aggregated(&vk, &aggregated).unwrap());
+        let ser_aggregated = serde_json::to_string(&aggregated).unwrap();
+        let mut der_aggregated =
+            serde_json::from_str::<AggregatedProof<Bn254>>(&ser_aggregated).unwrap();
+        assert_eq!(der_aggregated, aggregated);
+
+        der_aggregated.public_inputs[2].values[0] += ark_bn254::Fr::from(1);
+        assert!(!Groth16::<Bn254>::verify_aggregated(&vk, &der_aggregated).unwrap());
+        der_aggregated.public_inputs[2]
+            .values
+            .push(ark_bn254::Fr::from(1));
+        assert!(Groth16::<Bn254>::verify_aggregated(&vk, &der_aggregated).is_err());
+
+        let mut invalid = proofs();
+        invalid[1].1[0] += ark_bn254::Fr::from(1);
+        invalid[3].1[0] += ark_bn254::Fr::from(1);
+        let err = Groth16::<Bn254>::aggregate(&vk, invalid).unwrap_err();
+        assert_eq!(err.to_string(), "proof 1 is invalid");
+        assert!(Groth16::<Bn254>::aggregate(&vk, vec![]).is_err());
+    }
+
+    #[test]
+    fn prove_twice_with_resident_key_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file).unwrap());
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let public_input = witness.values[..=zkey.n_public].to_vec();
+        for _ in 0..2 {
+            let witness = SharedWitness {
+                public_inputs: public_input.clone(),
+                witness: witness.values[zkey.n_public + 1..].to_vec(),
+            };
+            let proof = Groth16::<Bn254>::new(PlainGroth16Driver::default())
+                .prove_with_key(&zkey, witness)
+                .expect("proof generation works");
+            let verified = Groth16::verify(&vk, &proof, &public_input[1..]).expect("can verify");
+            assert!(verified);
+        }
+        assert_eq!(Arc::strong_count(&zkey), 1);
+    }
+
+    #[test]
+    fn progress_is_reported_in_order_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file).unwrap());
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reported_clone = Arc::clone(&reported);
+        Groth16::<Bn254>::new(PlainGroth16Driver::default())
+            .with_progress(move |phase, progress| {
+                reported_clone.lock().unwrap().push((phase, progress))
+            })
+            .prove(zkey, witness)
+            .expect("proof generation works");
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.first(), Some(&(ProverPhase::Witness, 0.0)));
+        assert_eq!(reported.last(), Some(&(ProverPhase::Finalize, 1.0)));
+        assert!(reported.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[test]
+    fn seeded_proof_is_reproducible_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file).unwrap());
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+        let proof1 =
+            Groth16::<Bn254>::plain_prove_with_seed([42; 32], zkey.clone(), witness.clone())
+                .expect("proof generation works");
+        let proof2 =
+            Groth16::<Bn254>::plain_prove_with_seed([42; 32], zkey.clone(), witness.clone())
+                .expect("proof generation works");
+        let proof3 = Groth16::<Bn254>::plain_prove_with_seed([43; 32], zkey, witness)
+            .expect("proof generation works");
+        assert_eq!(
+            serde_json::to_string(&proof1).unwrap(),
+            serde_json::to_string(&proof2).unwrap()
+        );
+        assert_ne!(proof1, proof3);
+    }
+
+    #[test]
+    fn rerandomized_proofs_verify_and_are_distinct_bn254() {
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        let proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+        let public_input = [ark_bn254::Fr::from(33)];
+
+        let mut rng = rand::thread_rng();
+        let proofs = (0..3)
+            .map(|_| Groth16::<Bn254>::rerandomize_proof(&vk, &proof, &mut rng))
+            .collect::<Vec<_>>();
+        for (i, rerandomized) in proofs.iter().enumerate() {
+            assert!(Groth16::<Bn254>::verify(&vk, rerandomized, &public_input).unwrap());
+            assert_ne!(rerandomized, &proof);
+            assert!(proofs[i + 1..].iter().all(|other| other != rerandomized));
+        }
+    }
+
+    #[test]
+    fn verify_with_rerandomized_vk_bn254() {
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        let proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+        let public_input = [ark_bn254::Fr::from(33)];
+        let gamma_scalar = ark_bn254::Fr::from(7);
+        let delta_scalar = ark_bn254::Fr::from(11);
+
+        assert!(Groth16::<Bn254>::verify(&vk, &proof, &public_input).unwrap());
+        let rerandomized =
+            Groth16::<Bn254>::rerandomize_proof_for_vk(&proof, delta_scalar).unwrap();
+        assert!(Groth16::<Bn254>::verify_with_rerandomized_vk(
+            &vk,
+            &rerandomized,
+            &public_input,
+            gamma_scalar,
+            delta_scalar
+        )
+        .unwrap());
+        // the rerandomization of γ is absorbed by the key alone
+        assert!(Groth16::<Bn254>::verify_with_rerandomized_vk(
+            &vk,
+            &proof,
+            &public_input,
+            gamma_scalar,
+            ark_bn254::Fr::from(1)
+        )
+        .unwrap());
+        // the original proof does not verify under a rerandomized δ
+        assert!(!Groth16::<Bn254>::verify_with_rerandomized_vk(
+            &vk,
+            &proof,
+            &public_input,
+            gamma_scalar,
+            delta_scalar
+        )
+        .unwrap());
+        assert!(!Groth16::<Bn254>::verify_with_rerandomized_vk(
+            &vk,
+            &rerandomized,
+            &[ark_bn254::Fr::from(34)],
+            gamma_scalar,
+            delta_scalar
+        )
+        .unwrap());
+        assert!(Groth16::<Bn254>::verify_with_rerandomized_vk(
+            &vk,
+            &proof,
+            &public_input,
+            ark_bn254::Fr::from(0),
+            delta_scalar
+        )
+        .is_err());
+    }
+
     #[test]
     fn verify_circom_proof_bn254() {
         let vk_string = fs::read_to_string(
@@ -194,4 +472,45 @@ mod tests {
             Groth16::<Bn254>::verify(&vk, &der_proof, &public_input[1..]).expect("can verify");
         assert!(verified)
     }
+
+    #[test]
+    fn degenerate_inputs_are_rejected_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let mut zkey = ZKey::<Bn254>::from_reader(zkey_file).unwrap();
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let empty = SharedWitness {
+            public_inputs: vec![],
+            witness: vec![],
+        };
+        let err = Groth16::<Bn254>::plain_prove(Arc::new(zkey.clone()), empty).unwrap_err();
+        assert!(err.to_string().starts_with("witness is too short"));
+
+        let mut truncated = witness.clone();
+        truncated.witness.pop();
+        let err = Groth16::<Bn254>::plain_prove(Arc::new(zkey.clone()), truncated).unwrap_err();
+        assert!(err.to_string().starts_with("witness is too short"));
+
+        // additional values at the end are ignored, like by the Plonk prover
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let mut extended = witness.clone();
+        extended.public_inputs.push(ark_bn254::Fr::from(7));
+        extended.witness.push(ark_bn254::Fr::from(7));
+        let proof = Groth16::<Bn254>::plain_prove(Arc::new(zkey.clone()), extended).unwrap();
+        assert!(Groth16::<Bn254>::verify(&vk, &proof, &witness.public_inputs[1..]).unwrap());
+
+        zkey.matrices.num_constraints = 0;
+        let err = Groth16::<Bn254>::plain_prove(Arc::new(zkey), witness).unwrap_err();
+        assert_eq!(err.to_string(), "circuit has no constraints");
+    }
 }
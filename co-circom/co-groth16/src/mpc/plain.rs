@@ -1,14 +1,41 @@
 use ark_ec::pairing::Pairing;
 use ark_ec::CurveGroup;
 use ark_ff::UniformRand;
-use rand::thread_rng;
+use rand::{rngs::StdRng, SeedableRng};
 
 use super::CircomGroth16Prover;
 
 type IoResult<T> = std::io::Result<T>;
 
 /// A plain Groth16 driver
-pub struct PlainGroth16Driver;
+pub struct PlainGroth16Driver {
+    rng: StdRng,
+}
+
+impl Default for PlainGroth16Driver {
+    fn default() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl PlainGroth16Driver {
+    /// Creates a new [`PlainGroth16Driver`] with fresh randomness.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`PlainGroth16Driver`] whose randomness is derived from `seed`, which makes the generated proofs reproducible.
+    ///
+    /// Only meant for tests, a proof with a known seed is not zero-knowledge.
+    #[cfg(any(test, feature = "deterministic-rng"))]
+    pub fn with_seed(seed: [u8; 32]) -> Self {
+        Self {
+            rng: StdRng::from_seed(seed),
+        }
+    }
+}
 
 impl<P: Pairing> CircomGroth16Prover<P> for PlainGroth16Driver {
     type ArithmeticShare = P::ScalarField;
@@ -18,8 +45,7 @@ impl<P: Pairing> CircomGroth16Prover<P> for PlainGroth16Driver {
     type PartyID = usize;
 
     fn rand(&mut self) -> IoResult<Self::ArithmeticShare> {
-        let mut rng = thread_rng();
-        Ok(Self::ArithmeticShare::rand(&mut rng))
+        Ok(Self::ArithmeticShare::rand(&mut self.rng))
     }
 
     fn get_party_id(&self) -> Self::PartyID {
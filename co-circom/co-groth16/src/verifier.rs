@@ -5,10 +5,13 @@
 
 use crate::groth16::Groth16;
 use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::{Field, Zero};
 use ark_groth16::Proof;
 use ark_groth16::VerifyingKey;
 use circom_types::groth16::{Groth16Proof, JsonVerificationKey};
 use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use rand::Rng;
 
 use ark_groth16::Groth16 as ArkworksGroth16;
 
@@ -25,13 +28,92 @@ where
         proof: &Groth16Proof<P>,
         public_inputs: &[P::ScalarField],
     ) -> Result<bool, ark_relations::r1cs::SynthesisError> {
-        let vk = VerifyingKey::<P> {
+        Self::verify_ark(Self::ark_vk(vk), proof, public_inputs)
+    }
+
+    /// Verify a Groth16 proof against the verification key `vk` rerandomized with the scalars `gamma_scalar` and `delta_scalar`.
+    ///
+    /// The rerandomized key consists of `γ * gamma_scalar`, `δ * delta_scalar` and the points `IC` scaled by `gamma_scalar^-1`, while `α` and `β` are the ones of `vk`. As the pairing check only depends on the products of the scaled points, a proof for `vk` is turned into a proof for the rerandomized key with [rerandomize_proof_for_vk](Self::rerandomize_proof_for_vk). Fails with [SynthesisError::MalformedVerifyingKey](ark_relations::r1cs::SynthesisError::MalformedVerifyingKey) if one of the scalars is zero.
+    pub fn verify_with_rerandomized_vk(
+        vk: &JsonVerificationKey<P>,
+        proof: &Groth16Proof<P>,
+        public_inputs: &[P::ScalarField],
+        gamma_scalar: P::ScalarField,
+        delta_scalar: P::ScalarField,
+    ) -> Result<bool, ark_relations::r1cs::SynthesisError> {
+        let gamma_inv = gamma_scalar
+            .inverse()
+            .ok_or(ark_relations::r1cs::SynthesisError::MalformedVerifyingKey)?;
+        if delta_scalar.is_zero() {
+            return Err(ark_relations::r1cs::SynthesisError::MalformedVerifyingKey);
+        }
+        let mut vk = Self::ark_vk(vk);
+        vk.gamma_g2 = (vk.gamma_g2 * gamma_scalar).into_affine();
+        vk.delta_g2 = (vk.delta_g2 * delta_scalar).into_affine();
+        vk.gamma_abc_g1 = P::G1::normalize_batch(
+            &vk.gamma_abc_g1
+                .iter()
+                .map(|ic| *ic * gamma_inv)
+                .collect::<Vec<_>>(),
+        );
+        Self::verify_ark(vk, proof, public_inputs)
+    }
+
+    /// Turns a proof for a verification key into a proof for the key rerandomized with `delta_scalar`, see [verify_with_rerandomized_vk](Self::verify_with_rerandomized_vk). Only the element C is scaled by `delta_scalar^-1`, the rerandomization of γ does not change the proof.
+    pub fn rerandomize_proof_for_vk(
+        proof: &Groth16Proof<P>,
+        delta_scalar: P::ScalarField,
+    ) -> Result<Groth16Proof<P>, ark_relations::r1cs::SynthesisError> {
+        let delta_inv = delta_scalar
+            .inverse()
+            .ok_or(ark_relations::r1cs::SynthesisError::MalformedVerifyingKey)?;
+        Ok(Groth16Proof {
+            pi_a: proof.pi_a,
+            pi_b: proof.pi_b,
+            pi_c: (proof.pi_c * delta_inv).into_affine(),
+            protocol: proof.protocol.clone(),
+            curve: proof.curve.clone(),
+        })
+    }
+
+    /// Rerandomizes a proof for `vk`, see [arkworks](https://docs.rs/ark-groth16/latest/ark_groth16/struct.Groth16.html#method.rerandomize_proof).
+    /// The result verifies for the same key and public inputs, but can not be linked to `proof` or to other rerandomizations of it without knowing the witness.
+    pub fn rerandomize_proof(
+        vk: &JsonVerificationKey<P>,
+        proof: &Groth16Proof<P>,
+        rng: &mut impl Rng,
+    ) -> Groth16Proof<P> {
+        let ark_proof = Proof {
+            a: proof.pi_a,
+            b: proof.pi_b,
+            c: proof.pi_c,
+        };
+        let rerandomized =
+            ArkworksGroth16::<P>::rerandomize_proof(&Self::ark_vk(vk), &ark_proof, rng);
+        Groth16Proof {
+            pi_a: rerandomized.a,
+            pi_b: rerandomized.b,
+            pi_c: rerandomized.c,
+            protocol: proof.protocol.clone(),
+            curve: proof.curve.clone(),
+        }
+    }
+
+    fn ark_vk(vk: &JsonVerificationKey<P>) -> VerifyingKey<P> {
+        VerifyingKey::<P> {
             alpha_g1: vk.alpha_1,
             beta_g2: vk.beta_2,
             gamma_g2: vk.gamma_2,
             delta_g2: vk.delta_2,
             gamma_abc_g1: vk.ic.clone(),
-        };
+        }
+    }
+
+    fn verify_ark(
+        vk: VerifyingKey<P>,
+        proof: &Groth16Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<bool, ark_relations::r1cs::SynthesisError> {
         let proof = Proof {
             a: proof.pi_a,
             b: proof.pi_b,
@@ -0,0 +1,47 @@
+use ark_bn254::Bn254;
+use circom_types::{
+    groth16::JsonPublicInput,
+    plonk::{JsonVerificationKey, PlonkProof},
+};
+use co_plonk::Plonk;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{fs::File, path::PathBuf};
+
+fn open(file: &str) -> File {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("../../test_vectors/Plonk/bn254/poseidon");
+    d.push(file);
+    File::open(d).unwrap()
+}
+
+fn plonk_verify_batch(c: &mut Criterion) {
+    let vk: JsonVerificationKey<Bn254> =
+        serde_json::from_reader(open("verification_key.json")).unwrap();
+    let public_inputs: JsonPublicInput<ark_bn254::Fr> =
+        serde_json::from_reader(open("public.json")).unwrap();
+
+    let mut group = c.benchmark_group("plonk verify");
+    for n in [1, 4, 16, 64] {
+        let proofs = (0..n)
+            .map(|_| {
+                let proof: PlonkProof<Bn254> =
+                    serde_json::from_reader(open("circom.proof")).unwrap();
+                (proof, public_inputs.values.clone())
+            })
+            .collect::<Vec<_>>();
+        group.bench_with_input(BenchmarkId::new("single", n), &proofs, |b, proofs| {
+            b.iter(|| {
+                for (proof, public_inputs) in proofs {
+                    assert!(Plonk::verify(&vk, proof, public_inputs).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("batch", n), &proofs, |b, proofs| {
+            b.iter(|| assert_eq!(Plonk::verify_batch(&vk, proofs).unwrap(), None))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, plonk_verify_batch);
+criterion_main!(benches);
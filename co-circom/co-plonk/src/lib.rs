@@ -6,7 +6,7 @@ use circom_types::plonk::PlonkProof;
 use circom_types::plonk::ZKey;
 use circom_types::traits::CircomArkworksPairingBridge;
 use circom_types::traits::CircomArkworksPrimeFieldBridge;
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{ProgressCallback, ProverPhase, SharedWitness};
 use mpc::rep3::Rep3PlonkDriver;
 use mpc::shamir::ShamirPlonkDriver;
 use mpc::CircomPlonkProver;
@@ -41,7 +41,7 @@ pub type Rep3CoPlonk<P> = CoPlonk<P, Rep3PlonkDriver<Rep3MpcNet>>;
 pub type ShamirCoPlonk<P> =
     CoPlonk<P, ShamirPlonkDriver<<P as Pairing>::ScalarField, ShamirMpcNet>>;
 
-/// The errors that may arise during the computation of a co-PLONK proof.
+/// The errors that may arise during the computation of a co-PLONK proof or the verification of a PLONK proof.
 #[derive(Debug, thiserror::Error)]
 pub enum PlonkProofError {
     /// Invalid domain size
@@ -53,6 +53,29 @@ pub enum PlonkProofError {
     /// Indicates that the domain size from the zkey is corrupted.
     #[error("Cannot create domain, Polynomial degree too large")]
     PolynomialDegreeTooLarge,
+    /// The zkey describes a circuit without any constraints.
+    #[error("circuit has no constraints")]
+    NoConstraints,
+    /// The witness has fewer values than the zkey requires.
+    #[error("witness too short: expected at least {expected_public} public and {expected_private} private values, got {public} and {private}")]
+    WitnessTooShort {
+        /// The number of public inputs required by the zkey, including the leading constant.
+        expected_public: usize,
+        /// The number of private values required by the zkey.
+        expected_private: usize,
+        /// The number of public inputs in the witness.
+        public: usize,
+        /// The number of private values in the witness.
+        private: usize,
+    },
+    /// The number of public inputs does not match the verification key.
+    #[error("expected {expected} public inputs, got {got}")]
+    PublicInputCount {
+        /// The number of public inputs of the verification key.
+        expected: usize,
+        /// The number of given public inputs.
+        got: usize,
+    },
     /// An [io::Error]. Communication to another party failed.
     #[error(transparent)]
     IOError(#[from] io::Error),
@@ -62,6 +85,7 @@ pub enum PlonkProofError {
 pub struct CoPlonk<P: Pairing, T: CircomPlonkProver<P>> {
     pub(crate) driver: T,
     phantom_data: PhantomData<P>,
+    progress: Option<ProgressCallback>,
 }
 
 impl<P, T> CoPlonk<P, T>
@@ -76,9 +100,21 @@ where
         Self {
             driver,
             phantom_data: PhantomData,
+            progress: None,
         }
     }
 
+    /// Registers a callback that is invoked as the prover moves through the [ProverPhase]s of a proof, e.g., to drive a progress bar.
+    ///
+    /// Rounds one to three are reported as [ProverPhase::Msm], rounds four and five as [ProverPhase::Finalize].
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(ProverPhase, f32) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
     /// Execute the PLONK prover using the internal MPC driver.
     pub fn prove(
         self,
@@ -100,21 +136,59 @@ where
             zkey.n_vars,
             zkey.n_public
         );
+        let report_progress = |phase, progress| {
+            if let Some(callback) = &self.progress {
+                callback(phase, progress);
+            }
+        };
+        if zkey.n_constraints == 0 {
+            return Err(PlonkProofError::NoConstraints);
+        }
+        let expected_private = (zkey.n_vars - zkey.n_additions).saturating_sub(zkey.n_public + 1);
+        if witness.public_inputs.len() < zkey.n_public + 1
+            || witness.witness.len() < expected_private
+        {
+            return Err(PlonkProofError::WitnessTooShort {
+                expected_public: zkey.n_public + 1,
+                expected_private,
+                public: witness.public_inputs.len(),
+                private: witness.witness.len(),
+            });
+        }
+        report_progress(ProverPhase::Witness, 0.0);
         let state = Round1::init_round(self.driver, zkey.as_ref(), witness)?;
         tracing::debug!("init round done..");
+        report_progress(ProverPhase::Msm, 1.0 / 6.0);
         let state = state.round1()?;
         tracing::debug!("round 1 done..");
+        report_progress(ProverPhase::Msm, 2.0 / 6.0);
         let state = state.round2()?;
         tracing::debug!("round 2 done..");
+        report_progress(ProverPhase::Msm, 3.0 / 6.0);
         let state = state.round3()?;
         tracing::debug!("round 3 done..");
+        report_progress(ProverPhase::Finalize, 4.0 / 6.0);
         let state = state.round4()?;
         tracing::debug!("round 4 done..");
-        let result = state.round5();
+        report_progress(ProverPhase::Finalize, 5.0 / 6.0);
+        let result = state.round5()?;
         tracing::debug!("round 5 done! We are done!");
+        report_progress(ProverPhase::Finalize, 1.0);
         let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
         tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
-        result
+        Ok(result)
+    }
+
+    /// Execute the PLONK prover with a zkey that stays resident at the caller.
+    ///
+    /// Parsing a zkey is expensive, so long-lived services should parse it once with [`ZKey::from_reader`], keep the [`Arc`] around and pass it to this method for every witness.
+    /// Only the reference count is increased, the zkey itself is never copied or re-parsed. As the MPC driver is consumed by a proof, every call needs a fresh prover instance.
+    pub fn prove_with_key(
+        self,
+        zkey: &Arc<ZKey<P>>,
+        witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> PlonkProofResult<PlonkProof<P>> {
+        self.prove(Arc::clone(zkey), witness)
     }
 }
 
@@ -228,6 +302,7 @@ impl<P: Pairing> Rep3CoPlonk<P> {
         Ok(CoPlonk {
             driver,
             phantom_data: PhantomData,
+            progress: None,
         })
     }
 
@@ -244,12 +319,23 @@ impl<P: Pairing> ShamirCoPlonk<P> {
         threshold: usize,
         config: NetworkConfig,
         zkey: &ZKey<P>,
+    ) -> eyre::Result<Self> {
+        let party_ids = (0..config.parties.len()).collect::<Vec<_>>();
+        Self::with_network_for_parties(threshold, ShamirMpcNet::new(config)?, zkey, &party_ids)
+    }
+
+    /// Create a new [ShamirCoPlonk] protocol with a given network for witness shares created for an explicit set of parties. The party with id `i` in the network holds the shares of party `party_ids[i]`.
+    pub fn with_network_for_parties(
+        threshold: usize,
+        mpc_net: ShamirMpcNet,
+        zkey: &ZKey<P>,
+        party_ids: &[usize],
     ) -> eyre::Result<Self> {
         let domain_size = zkey.domain_size;
         // TODO check and explain numbers
         let num_pairs = domain_size * 222 + 15;
-        let mpc_net = ShamirMpcNet::new(config)?;
-        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
+        let preprocessing =
+            ShamirPreprocessing::new_for_parties(threshold, mpc_net, num_pairs, party_ids)?;
         let mut protocol0 = ShamirProtocol::from(preprocessing);
         // TODO check and explain numbers
         let protocol1 = protocol0.fork_with_pairs(domain_size * 7 + 2)?;
@@ -257,6 +343,7 @@ impl<P: Pairing> ShamirCoPlonk<P> {
         Ok(CoPlonk {
             driver,
             phantom_data: PhantomData,
+            progress: None,
         })
     }
 }
@@ -267,10 +354,11 @@ pub mod tests {
     use circom_types::groth16::JsonPublicInput;
     use circom_types::plonk::{JsonVerificationKey, ZKey};
     use circom_types::Witness;
-    use co_circom_snarks::SharedWitness;
-    use std::sync::Arc;
+    use co_circom_snarks::{ProverPhase, SharedWitness};
+    use std::sync::{Arc, Mutex};
     use std::{fs::File, io::BufReader};
 
+    use crate::mpc::plain::PlainPlonkDriver;
     use crate::plonk::Plonk;
 
     #[test]
@@ -301,6 +389,88 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn progress_is_reported_in_order_bn254() -> eyre::Result<()> {
+        let zkey_file = "../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey";
+        let witness_file = "../../test_vectors/Plonk/bn254/multiplier2/witness.wtns";
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(File::open(zkey_file)?)?);
+        let witness = Witness::<ark_bn254::Fr>::from_reader(File::open(witness_file)?)?;
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reported_clone = Arc::clone(&reported);
+        Plonk::<Bn254>::new(PlainPlonkDriver::default())
+            .with_progress(move |phase, progress| {
+                reported_clone.lock().unwrap().push((phase, progress))
+            })
+            .prove(zkey, witness)?;
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.first(), Some(&(ProverPhase::Witness, 0.0)));
+        assert_eq!(reported.last(), Some(&(ProverPhase::Finalize, 1.0)));
+        assert!(reported.windows(2).all(|w| w[0].1 < w[1].1));
+        Ok(())
+    }
+
+    #[test]
+    fn seeded_proof_is_reproducible_bn254() -> eyre::Result<()> {
+        let zkey_file = "../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey";
+        let witness_file = "../../test_vectors/Plonk/bn254/multiplier2/witness.wtns";
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(File::open(zkey_file)?)?);
+        let witness = Witness::<ark_bn254::Fr>::from_reader(File::open(witness_file)?)?;
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let proof1 =
+            Plonk::<Bn254>::plain_prove_with_seed([42; 32], zkey.clone(), witness.clone())?;
+        let proof2 =
+            Plonk::<Bn254>::plain_prove_with_seed([42; 32], zkey.clone(), witness.clone())?;
+        let proof3 = Plonk::<Bn254>::plain_prove_with_seed([43; 32], zkey, witness)?;
+        assert_eq!(
+            serde_json::to_string(&proof1)?,
+            serde_json::to_string(&proof2)?
+        );
+        assert_ne!(
+            serde_json::to_string(&proof1)?,
+            serde_json::to_string(&proof3)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn degenerate_inputs_are_rejected_bn254() -> eyre::Result<()> {
+        let zkey_file = "../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey";
+        let witness_file = "../../test_vectors/Plonk/bn254/multiplier2/witness.wtns";
+        let mut zkey = ZKey::<Bn254>::from_reader(File::open(zkey_file)?)?;
+        let witness = Witness::<ark_bn254::Fr>::from_reader(File::open(witness_file)?)?;
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let empty = SharedWitness {
+            public_inputs: vec![],
+            witness: vec![],
+        };
+        let err = Plonk::<Bn254>::plain_prove(Arc::new(zkey.clone()), empty).unwrap_err();
+        assert!(err.to_string().starts_with("witness too short"), "{err}");
+
+        let mut truncated = witness.clone();
+        truncated.witness.pop();
+        let err = Plonk::<Bn254>::plain_prove(Arc::new(zkey.clone()), truncated).unwrap_err();
+        assert!(err.to_string().starts_with("witness too short"), "{err}");
+
+        zkey.n_constraints = 0;
+        let err = Plonk::<Bn254>::plain_prove(Arc::new(zkey), witness).unwrap_err();
+        assert_eq!(err.to_string(), "circuit has no constraints");
+        Ok(())
+    }
+
     #[test]
     pub fn test_poseidon_bn254() {
         let mut reader = BufReader::new(
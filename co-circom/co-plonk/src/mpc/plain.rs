@@ -9,10 +9,37 @@ use itertools::izip;
 use num_traits::Zero;
 
 use super::CircomPlonkProver;
-use rand::thread_rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 /// A plain Plonk driver
-pub struct PlainPlonkDriver;
+pub struct PlainPlonkDriver {
+    rng: StdRng,
+}
+
+impl Default for PlainPlonkDriver {
+    fn default() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl PlainPlonkDriver {
+    /// Creates a new [`PlainPlonkDriver`] with fresh randomness.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`PlainPlonkDriver`] whose randomness is derived from `seed`, which makes the generated proofs reproducible.
+    ///
+    /// Only meant for tests, a proof with a known seed is not zero-knowledge.
+    #[cfg(any(test, feature = "deterministic-rng"))]
+    pub fn with_seed(seed: [u8; 32]) -> Self {
+        Self {
+            rng: StdRng::from_seed(seed),
+        }
+    }
+}
 
 impl<P: Pairing> CircomPlonkProver<P> for PlainPlonkDriver {
     type ArithmeticShare = P::ScalarField;
@@ -24,12 +51,11 @@ impl<P: Pairing> CircomPlonkProver<P> for PlainPlonkDriver {
     //doesn't matter
     type PartyID = usize;
 
-    //doesn't matter
-    type IoContext = ();
+    // there is no network, the context only holds the randomness of array_prod_mul
+    type IoContext = StdRng;
 
     fn rand(&mut self) -> IoResult<Self::ArithmeticShare> {
-        let mut rng = thread_rng();
-        Ok(Self::ArithmeticShare::rand(&mut rng))
+        Ok(Self::ArithmeticShare::rand(&mut self.rng))
     }
 
     fn get_party_id(&self) -> Self::PartyID {
@@ -177,7 +203,7 @@ impl<P: Pairing> CircomPlonkProver<P> for PlainPlonkDriver {
     }
 
     fn array_prod_mul(
-        _: &mut Self::IoContext,
+        rng: &mut Self::IoContext,
         inv: bool,
         arr1: &[Self::ArithmeticShare],
         arr2: &[Self::ArithmeticShare],
@@ -203,10 +229,9 @@ impl<P: Pairing> CircomPlonkProver<P> for PlainPlonkDriver {
         // Do the multiplications of inp[i] * inp[i-1] in constant rounds
         let len = arr.len();
 
-        let mut rng = thread_rng();
         let mut r = Vec::with_capacity(len + 1);
         for _ in 0..=len {
-            r.push(Self::ArithmeticShare::rand(&mut rng));
+            r.push(Self::ArithmeticShare::rand(rng));
         }
 
         let r_inv = inv_vec(&r).unwrap();
@@ -245,8 +270,8 @@ impl<P: Pairing> CircomPlonkProver<P> for PlainPlonkDriver {
         d2: &[Self::ArithmeticShare],
         d3: &[Self::ArithmeticShare],
     ) -> IoResult<(Vec<Self::ArithmeticShare>, Vec<Self::ArithmeticShare>)> {
-        let mut io_context0 = ();
-        let mut io_context1 = ();
+        let mut io_context0 = StdRng::from_seed(self.rng.gen());
+        let mut io_context1 = StdRng::from_seed(self.rng.gen());
         let num =
             <Self as CircomPlonkProver<P>>::array_prod_mul(&mut io_context0, false, n1, n2, n3)?;
         let den =
@@ -6,9 +6,12 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use crate::{mpc::plain::PlainPlonkDriver, plonk_utils, types::Domains, CoPlonk};
+use crate::{
+    mpc::plain::PlainPlonkDriver, plonk_utils, types::Domains, CoPlonk, PlonkProofError,
+    PlonkProofResult,
+};
 use ark_ec::{pairing::Pairing, Group};
-use ark_ff::Field;
+use ark_ff::{Field, UniformRand};
 use circom_types::{
     plonk::{JsonVerificationKey, PlonkProof, ZKey},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
@@ -136,25 +139,72 @@ where
         vk: &JsonVerificationKey<P>,
         proof: &PlonkProof<P>,
         public_inputs: &[P::ScalarField],
-    ) -> Result<bool, eyre::Report>
+    ) -> PlonkProofResult<bool>
     where
         P: Pairing,
         P: CircomArkworksPairingBridge,
         P::BaseField: CircomArkworksPrimeFieldBridge,
         P::ScalarField: CircomArkworksPrimeFieldBridge,
     {
+        let domains = Domains::<P::ScalarField>::new(1 << vk.power)?;
+        let (a1, b1) = Plonk::<P>::pairing_points(vk, proof, public_inputs, &domains)?;
+        Ok(Plonk::<P>::valid_pairing(vk, a1, b1))
+    }
+
+    /// Verifies many circom PLONK proofs for the same verification key. Returns the index of the first invalid proof, or `None` if all proofs are valid.
+    ///
+    /// The domains are only computed once and the pairing checks of all proofs are combined into a single one with random weights, so the batch costs two pairings instead of two per proof. Only if the combined check fails, the proofs are checked one by one to find the invalid one.
+    pub fn verify_batch(
+        vk: &JsonVerificationKey<P>,
+        proofs: &[(PlonkProof<P>, Vec<P::ScalarField>)],
+    ) -> Result<Option<usize>, eyre::Report> {
+        let domains = Domains::<P::ScalarField>::new(1 << vk.power)?;
+        let points = proofs
+            .iter()
+            .enumerate()
+            .map(|(i, (proof, public_inputs))| {
+                Plonk::<P>::pairing_points(vk, proof, public_inputs, &domains).map_err(|err| {
+                    eyre::Report::from(err).wrap_err(format!("while verifying proof {i}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rng = rand::thread_rng();
+        let (mut a, mut b) = (P::G1::zero(), P::G1::zero());
+        for (a1, b1) in points.iter() {
+            let r = P::ScalarField::rand(&mut rng);
+            a += *a1 * r;
+            b += *b1 * r;
+        }
+        if Plonk::<P>::valid_pairing(vk, a, b) {
+            return Ok(None);
+        }
+        Ok(points
+            .into_iter()
+            .position(|(a1, b1)| !Plonk::<P>::valid_pairing(vk, a1, b1)))
+    }
+
+    /// Computes the points `A1` and `B1` of the final pairing check `e(A1, [x]_2) = e(B1, [1]_2)` of a proof.
+    fn pairing_points(
+        vk: &JsonVerificationKey<P>,
+        proof: &PlonkProof<P>,
+        public_inputs: &[P::ScalarField],
+        domains: &Domains<P::ScalarField>,
+    ) -> PlonkProofResult<(P::G1, P::G1)> {
         if vk.n_public != public_inputs.len() {
-            return Err(eyre::eyre!("Invalid number of public inputs"));
+            return Err(PlonkProofError::PublicInputCount {
+                expected: vk.n_public,
+                got: public_inputs.len(),
+            });
         }
 
         let challenges = VerifierChallenges::<P>::new(vk, proof, public_inputs);
-        let domains = Domains::<P::ScalarField>::new(1 << vk.power)?;
 
         let (l, xin) = plonk_utils::calculate_lagrange_evaluations::<P>(
             vk.power,
             vk.n_public,
             &challenges.xi,
-            &domains,
+            domains,
         );
         let pi = plonk_utils::calculate_pi::<P>(public_inputs, &l);
         let (r0, d) = Plonk::<P>::calculate_r0_d(vk, proof, &challenges, pi, &l[0], xin);
@@ -162,14 +212,10 @@ where
         let e = Plonk::<P>::calculate_e(proof, &challenges, r0);
         let f = Plonk::<P>::calculate_f(vk, proof, &challenges, d);
 
-        Ok(Plonk::<P>::valid_pairing(
-            vk,
-            proof,
-            &challenges,
-            e,
-            f,
-            &domains,
-        ))
+        let s = challenges.u * challenges.xi * domains.root_of_unity_pow;
+        let a1 = proof.wxi + proof.wxiw * challenges.u;
+        let b1 = proof.wxi * challenges.xi + proof.wxiw * s - e + f;
+        Ok((a1, b1))
     }
 
     pub(crate) fn calculate_r0_d(
@@ -253,19 +299,7 @@ where
             + vk.s2 * challenges.v[4]
     }
 
-    fn valid_pairing(
-        vk: &JsonVerificationKey<P>,
-        proof: &PlonkProof<P>,
-        challenges: &VerifierChallenges<P>,
-        e: P::G1,
-        f: P::G1,
-        domains: &Domains<P::ScalarField>,
-    ) -> bool {
-        let s = challenges.u * challenges.xi * domains.root_of_unity_pow;
-
-        let a1 = proof.wxi + proof.wxiw * challenges.u;
-        let b1 = proof.wxi * challenges.xi + proof.wxiw * s - e + f;
-
+    fn valid_pairing(vk: &JsonVerificationKey<P>, a1: P::G1, b1: P::G1) -> bool {
         let lhs = P::pairing(a1, vk.x2);
         let rhs = P::pairing(b1, P::G2::generator());
 
@@ -288,8 +322,27 @@ where
         private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
     ) -> eyre::Result<PlonkProof<P>> {
         let prover = Self {
-            driver: PlainPlonkDriver,
+            driver: PlainPlonkDriver::default(),
             phantom_data: PhantomData,
+            progress: None,
+        };
+        Ok(prover.prove(zkey, private_witness)?)
+    }
+
+    /// *Locally* create a reproducible `Plonk` proof, i.e., all blinding factors are derived from `seed`.
+    /// The same seed, zkey and witness always result in the same proof, which allows for golden-file tests.
+    ///
+    /// Only meant for tests, a proof with a known seed is not zero-knowledge.
+    #[cfg(any(test, feature = "deterministic-rng"))]
+    pub fn plain_prove_with_seed(
+        seed: [u8; 32],
+        zkey: Arc<ZKey<P>>,
+        private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
+    ) -> eyre::Result<PlonkProof<P>> {
+        let prover = Self {
+            driver: PlainPlonkDriver::with_seed(seed),
+            phantom_data: PhantomData,
+            progress: None,
         };
         Ok(prover.prove(zkey, private_witness)?)
     }
@@ -389,6 +442,36 @@ pub mod tests {
         assert!(Plonk::verify(&vk, &proof, &public_inputs.values).unwrap());
     }
 
+    #[test]
+    pub fn verify_batch_multiplier2() {
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(
+            File::open("../../test_vectors/Plonk/bn254/multiplier2/verification_key.json").unwrap(),
+        )
+        .unwrap();
+        let public_inputs: JsonPublicInput<ark_bn254::Fr> = serde_json::from_reader(
+            File::open("../../test_vectors/Plonk/bn254/multiplier2/public.json").unwrap(),
+        )
+        .unwrap();
+        let mut batch = (0..5)
+            .map(|_| {
+                let proof: PlonkProof<Bn254> = serde_json::from_reader(
+                    File::open("../../test_vectors/Plonk/bn254/multiplier2/circom.proof").unwrap(),
+                )
+                .unwrap();
+                (proof, public_inputs.values.clone())
+            })
+            .collect_vec();
+        assert_eq!(Plonk::verify_batch(&vk, &batch).unwrap(), None);
+        assert_eq!(Plonk::verify_batch(&vk, &[]).unwrap(), None);
+
+        batch[3].1[0] += ark_bn254::Fr::from(1);
+        batch[4].1[0] += ark_bn254::Fr::from(1);
+        assert_eq!(Plonk::verify_batch(&vk, &batch).unwrap(), Some(3));
+
+        batch[1].1.push(ark_bn254::Fr::from(1));
+        assert!(Plonk::verify_batch(&vk, &batch).is_err());
+    }
+
     #[test]
     pub fn verify_poseidon_from_circom() {
         let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(
@@ -317,7 +317,7 @@ pub mod tests {
 
     #[test]
     fn test_round1_multiplier2() {
-        let mut driver = PlainPlonkDriver;
+        let mut driver = PlainPlonkDriver::default();
         let mut reader = BufReader::new(
             File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap(),
         );
@@ -358,7 +358,7 @@ pub mod tests {
 
     #[test]
     fn test_round1_poseidon_bls12_381() {
-        let mut driver = PlainPlonkDriver;
+        let mut driver = PlainPlonkDriver::default();
         let mut reader = BufReader::new(
             File::open("../../test_vectors/Plonk/bls12_381/poseidon/circuit.zkey").unwrap(),
         );
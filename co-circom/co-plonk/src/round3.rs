@@ -509,7 +509,7 @@ pub mod tests {
 
     #[test]
     fn test_round3_multiplier2() {
-        let mut driver = PlainPlonkDriver;
+        let mut driver = PlainPlonkDriver::default();
         let mut reader = BufReader::new(
             File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap(),
         );
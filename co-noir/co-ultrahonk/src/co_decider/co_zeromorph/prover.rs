@@ -475,7 +475,7 @@ impl<
         // Compute and send commitments C_{q_k} = [q_k], k = 0,...,d-1
         let mut commitments = Vec::with_capacity(log_n as usize);
         for q in quotients.iter() {
-            let commitment = CoUtils::commit::<T, P>(q.as_ref(), commitment_key);
+            let commitment = CoUtils::commit::<T, P>(q.as_ref(), commitment_key)?;
             commitments.push(commitment);
         }
         let commitments = self.driver.open_point_many(&commitments)?;
@@ -502,7 +502,7 @@ impl<
         );
 
         // Compute and send the commitment C_q = [\hat{q}]
-        let q_commitment = CoUtils::commit::<T, P>(&batched_quotient.coefficients, commitment_key);
+        let q_commitment = CoUtils::commit::<T, P>(&batched_quotient.coefficients, commitment_key)?;
         let q_commitment = self.driver.open_point(q_commitment)?;
         transcript.send_point_to_verifier::<P>("ZM:C_q".to_string(), q_commitment.into());
 
@@ -46,7 +46,7 @@ impl<
         quotient[0] = driver.add_with_public(-pair.evaluation, quotient[0]);
         // Computes the coefficients for the quotient polynomial q(X) = (p(X) - v) / (X - r) through an FFT
         quotient.factor_roots(driver, &pair.challenge);
-        let quotient_commitment = CoUtils::commit::<T, P>(&quotient.coefficients, crs);
+        let quotient_commitment = CoUtils::commit::<T, P>(&quotient.coefficients, crs)?;
         // AZTEC TODO(#479): for now we compute the KZG commitment directly to unify the KZG and IPA interfaces but in the
         // future we might need to adjust this to use the incoming alternative to work queue (i.e. variation of
         // pthreads) or even the work queue itself
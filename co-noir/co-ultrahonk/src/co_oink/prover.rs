@@ -465,15 +465,15 @@ impl<
         let w_l = CoUtils::commit::<T, P>(
             proving_key.polynomials.witness.w_l().as_ref(),
             &proving_key.crs,
-        );
+        )?;
         let w_r = CoUtils::commit::<T, P>(
             proving_key.polynomials.witness.w_r().as_ref(),
             &proving_key.crs,
-        );
+        )?;
         let w_o = CoUtils::commit::<T, P>(
             proving_key.polynomials.witness.w_o().as_ref(),
             &proving_key.crs,
-        );
+        )?;
 
         let open = self.driver.open_point_many(&[w_l, w_r, w_o])?;
 
@@ -516,7 +516,7 @@ impl<
             proving_key.polynomials.witness.lookup_read_tags().as_ref(),
             &proving_key.crs,
         )?;
-        let w_4 = CoUtils::commit::<T, P>(self.memory.w_4.as_ref(), &proving_key.crs);
+        let w_4 = CoUtils::commit::<T, P>(self.memory.w_4.as_ref(), &proving_key.crs)?;
         let w_4 = self.driver.open_point(w_4)?;
 
         transcript.send_point_to_verifier::<P>(
@@ -563,9 +563,9 @@ impl<
 
         // This is from the previous round, but we open it here with z_perm
         let lookup_inverses =
-            CoUtils::commit::<T, P>(self.memory.lookup_inverses.as_ref(), &proving_key.crs);
+            CoUtils::commit::<T, P>(self.memory.lookup_inverses.as_ref(), &proving_key.crs)?;
 
-        let z_perm = CoUtils::commit::<T, P>(self.memory.z_perm.as_ref(), &proving_key.crs);
+        let z_perm = CoUtils::commit::<T, P>(self.memory.z_perm.as_ref(), &proving_key.crs)?;
 
         let open = self.driver.open_point_many(&[lookup_inverses, z_perm])?;
 
@@ -9,7 +9,10 @@ pub(crate) mod types;
 use ark_ec::pairing::Pairing;
 use mpc::{plain::PlainUltraHonkDriver, NoirUltraHonkProver};
 use parse::builder_variable::SharedBuilderVariable;
-use ultrahonk::prelude::ProverCrs;
+use ultrahonk::{
+    prelude::{HonkProofResult, ProverCrs},
+    Utils,
+};
 
 impl<P: Pairing> SharedBuilderVariable<PlainUltraHonkDriver, P> {
     pub fn promote_public_witness_vector(
@@ -39,9 +42,11 @@ impl CoUtils {
     pub(crate) fn commit<T: NoirUltraHonkProver<P>, P: Pairing>(
         poly: &[T::ArithmeticShare],
         crs: &ProverCrs<P>,
-    ) -> T::PointShare {
+    ) -> HonkProofResult<T::PointShare> {
         let len = poly.len();
-        T::msm_public_points(&crs.monomials[..len], poly)
+        // the coefficients are shared, so trailing zeros can not be skipped
+        Utils::check_degree(len, crs.monomials.len())?;
+        Ok(T::msm_public_points(&crs.monomials[..len], poly))
     }
 
     pub(crate) fn batch_invert<T: NoirUltraHonkProver<P>, P: Pairing>(
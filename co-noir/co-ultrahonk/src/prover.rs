@@ -51,12 +51,24 @@ impl<
     }
 
     pub fn prove(
+        self,
+        proving_key: ProvingKey<T, P>,
+    ) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
+        self.prove_with_domain_separator(proving_key, &[])
+    }
+
+    /// Like [`CoUltraHonk::prove`], but binds the proof to `domain_separator`. The verifier has to use the same tag.
+    ///
+    /// The proof always opens with KZG, there is no MPC counterpart of `UltraHonk::prove_with_opening_scheme`.
+    pub fn prove_with_domain_separator(
         mut self,
         proving_key: ProvingKey<T, P>,
+        domain_separator: &[u8],
     ) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
         tracing::trace!("CoUltraHonk prove");
 
-        let mut transcript = Transcript::<TranscriptFieldType, H>::new();
+        let mut transcript =
+            Transcript::<TranscriptFieldType, H>::new_with_domain_separator(domain_separator);
 
         let oink = CoOink::new(&mut self.driver);
         let oink_result = oink.prove(&proving_key, &mut transcript)?;
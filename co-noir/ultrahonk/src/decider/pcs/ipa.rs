@@ -0,0 +1,267 @@
+use super::OpeningScheme;
+use crate::{
+    decider::polynomial::Polynomial,
+    prelude::{HonkCurve, TranscriptFieldType},
+    prover::HonkProofResult,
+    transcript::{Transcript, TranscriptHasher},
+    types::ProverCrs,
+    verifier::HonkVerifyResult,
+    Utils,
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, PrimeField, Zero};
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+
+const CRS_DOMAIN_SEPARATOR: &[u8] = b"ULTRAHONK_IPA_CRS";
+const AUX_GENERATOR_DOMAIN_SEPARATOR: &[u8] = b"ULTRAHONK_IPA_AUX_GENERATOR";
+
+/// The inner product argument of Bulletproofs as an opening scheme without pairings.
+///
+/// The crs has to be a set of points without known discrete logarithm relations, as created by [Ipa::crs], and the verifier needs all of them. Opening proofs consist of `2 log n` points and verification is linear in the size `n` of the polynomial.
+///
+/// Only the group operations of `P::G1` are used, but a [HonkCurve] is still a [Pairing], so curves without a pairing like Grumpkin cannot be plugged in yet.
+pub struct Ipa;
+
+impl Ipa {
+    /// Creates an IPA crs with `size` points. The first point is the generator, which the ZeroMorph verifier uses as the commitment to the constant polynomial 1, all others are derived by hashing to the curve.
+    ///
+    /// The crs is deterministic, so the prover and the verifier can both create it. The verifier uses it as its [OpeningScheme::VerifierKey].
+    pub fn crs<P: Pairing>(size: usize) -> ProverCrs<P> {
+        let monomials = (0..size)
+            .into_par_iter()
+            .map(|i| {
+                if i == 0 {
+                    P::G1Affine::generator()
+                } else {
+                    Self::hash_to_curve::<P>(CRS_DOMAIN_SEPARATOR, i as u64)
+                }
+            })
+            .collect();
+        ProverCrs { monomials }
+    }
+
+    /// Try-and-increment: hashes the domain separator, the index and a counter until the hash is the encoding of a point.
+    fn hash_to_curve<P: Pairing>(domain_separator: &[u8], index: u64) -> P::G1Affine {
+        (0u64..)
+            .find_map(|counter| {
+                let mut hasher = Keccak256::new();
+                hasher.update(domain_separator);
+                hasher.update(index.to_be_bytes());
+                hasher.update(counter.to_be_bytes());
+                let point = P::G1Affine::from_random_bytes(&hasher.finalize())?.clear_cofactor();
+                (!point.is_zero()).then_some(point)
+            })
+            .expect("half of all x-coordinates are on the curve")
+    }
+
+    /// The point the claimed inner products are committed to, which is independent of the crs.
+    fn aux_generator<P: Pairing>() -> P::G1Affine {
+        Self::hash_to_curve::<P>(AUX_GENERATOR_DOMAIN_SEPARATOR, 0)
+    }
+
+    fn inner_product<F: PrimeField>(a: &[F], b: &[F]) -> F {
+        a.par_iter().zip(b.par_iter()).map(|(a, b)| *a * b).sum()
+    }
+
+    fn powers<F: PrimeField>(x: F, n: usize) -> Vec<F> {
+        let mut powers = Vec::with_capacity(n);
+        let mut current = F::one();
+        for _ in 0..n {
+            powers.push(current);
+            current *= x;
+        }
+        powers
+    }
+
+    /// Returns for every index `i` the product of the round challenges `u_j` for which the `j`-th most significant bit of `i` is set and their inverses for all others.
+    /// These are the coefficients of the folded generator and of the folded powers of the evaluation point.
+    fn round_challenge_products<F: PrimeField>(round_challenges: &[(F, F)]) -> Vec<F> {
+        let mut products = vec![F::one()];
+        for (u, u_inv) in round_challenges {
+            products = products.iter().flat_map(|s| [*s * u_inv, *s * u]).collect();
+        }
+        products
+    }
+}
+
+impl<P: HonkCurve<TranscriptFieldType>> OpeningScheme<P> for Ipa {
+    /// The crs created by [Ipa::crs].
+    type VerifierKey = ProverCrs<P>;
+
+    fn compute_opening_proof<H: TranscriptHasher<TranscriptFieldType>>(
+        polynomial: Polynomial<P::ScalarField>,
+        challenge: P::ScalarField,
+        // v enters the proof through the commitment C + v * U of the verifier
+        _evaluation: P::ScalarField,
+        crs: &ProverCrs<P>,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkProofResult<()> {
+        tracing::trace!("IPA opening proof");
+
+        let n = polynomial.len().next_power_of_two();
+        Utils::check_degree(n, crs.monomials.len())?;
+        let log_n = Utils::get_msb64(n as u64) as usize;
+        transcript.send_u64_to_verifier("IPA:poly_degree_plus_1".to_string(), n as u64);
+        let generator_challenge =
+            transcript.get_challenge::<P>("IPA:generator_challenge".to_string());
+        let aux_generator = Self::aux_generator::<P>() * generator_challenge;
+
+        let mut a = polynomial.into_vec();
+        a.resize(n, P::ScalarField::zero());
+        let mut b = Self::powers(challenge, n);
+        let mut g = crs.monomials[..n].to_vec();
+
+        // In every round, the vectors are halved by folding their upper halves onto their lower halves with the round challenge u:
+        // a' = u * a_lo + u^-1 * a_hi, b' = u^-1 * b_lo + u * b_hi and G' = u^-1 * G_lo + u * G_hi,
+        // for which the commitment <a', G'> + <a', b'> * U is C + u^2 * L + u^-2 * R
+        for round in 0..log_n {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+
+            let l =
+                P::G1::msm_unchecked(g_hi, a_lo) + aux_generator * Self::inner_product(a_lo, b_hi);
+            let r =
+                P::G1::msm_unchecked(g_lo, a_hi) + aux_generator * Self::inner_product(a_hi, b_lo);
+            transcript.send_point_to_verifier::<P>(format!("IPA:L_{}", round), l.into());
+            transcript.send_point_to_verifier::<P>(format!("IPA:R_{}", round), r.into());
+
+            let u = transcript.get_challenge::<P>(format!("IPA:round_challenge_{}", round));
+            let u_inv = u.inverse().expect("round challenge is not zero");
+
+            let a_folded = a_lo
+                .par_iter()
+                .zip(a_hi.par_iter())
+                .map(|(lo, hi)| *lo * u + *hi * u_inv)
+                .collect();
+            let b_folded = b_lo
+                .par_iter()
+                .zip(b_hi.par_iter())
+                .map(|(lo, hi)| *lo * u_inv + *hi * u)
+                .collect();
+            let g_folded = g_lo
+                .par_iter()
+                .zip(g_hi.par_iter())
+                .map(|(lo, hi)| *lo * u_inv + *hi * u)
+                .collect::<Vec<P::G1>>();
+            a = a_folded;
+            b = b_folded;
+            g = P::G1::normalize_batch(&g_folded);
+        }
+
+        transcript.send_fr_to_verifier::<P>("IPA:a_0".to_string(), a[0]);
+        Ok(())
+    }
+
+    fn verify_opening<H: TranscriptHasher<TranscriptFieldType>>(
+        commitment: P::G1,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        key: &Self::VerifierKey,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkVerifyResult<bool> {
+        tracing::trace!("IPA verify");
+
+        let n = transcript.receive_u64_from_prover("IPA:poly_degree_plus_1".to_string())? as usize;
+        if !n.is_power_of_two() || n > key.monomials.len() {
+            eyre::bail!(
+                "IPA opening of {} coefficients does not fit a crs of {} points",
+                n,
+                key.monomials.len()
+            );
+        }
+        let log_n = Utils::get_msb64(n as u64) as usize;
+        let generator_challenge =
+            transcript.get_challenge::<P>("IPA:generator_challenge".to_string());
+        let aux_generator = Self::aux_generator::<P>() * generator_challenge;
+
+        let mut folded_commitment = commitment + aux_generator * evaluation;
+        let mut round_challenges = Vec::with_capacity(log_n);
+        for round in 0..log_n {
+            let l = transcript.receive_point_from_prover::<P>(format!("IPA:L_{}", round))?;
+            let r = transcript.receive_point_from_prover::<P>(format!("IPA:R_{}", round))?;
+            let u = transcript.get_challenge::<P>(format!("IPA:round_challenge_{}", round));
+            let Some(u_inv) = u.inverse() else {
+                eyre::bail!("IPA round challenge {} is zero", round);
+            };
+            folded_commitment += l * u.square() + r * u_inv.square();
+            round_challenges.push((u, u_inv));
+        }
+        let a_0 = transcript.receive_fr_from_prover::<P>("IPA:a_0".to_string())?;
+
+        let products = Self::round_challenge_products(&round_challenges);
+        let g_0 = P::G1::msm_unchecked(&key.monomials[..n], &products);
+        let b_0 = Self::inner_product(&products, &Self::powers(challenge, n));
+
+        Ok(folded_commitment == g_0 * a_0 + aux_generator * (a_0 * b_0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ipa;
+    use crate::{
+        decider::{pcs::OpeningScheme, polynomial::Polynomial},
+        prelude::Poseidon2Sponge,
+        transcript::{Transcript, TranscriptFieldType},
+        Utils,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{UniformRand, Zero};
+
+    type T = Transcript<TranscriptFieldType, Poseidon2Sponge>;
+
+    fn evaluate(poly: &Polynomial<Fr>, x: Fr) -> Fr {
+        poly.coefficients
+            .iter()
+            .rev()
+            .fold(Fr::zero(), |acc, c| acc * x + c)
+    }
+
+    #[test]
+    fn ipa_opening_verifies() {
+        let mut rng = rand::thread_rng();
+        let crs = Ipa::crs::<Bn254>(17);
+        // 13 coefficients are padded to 16 for the opening
+        let poly = Polynomial::new((0..13).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>());
+        let challenge = Fr::rand(&mut rng);
+        let evaluation = evaluate(&poly, challenge);
+        let commitment = Utils::commit(&poly.coefficients, &crs).unwrap();
+
+        let mut transcript = T::new();
+        Ipa::compute_opening_proof(poly, challenge, evaluation, &crs, &mut transcript).unwrap();
+        let proof = transcript.get_proof();
+
+        let mut transcript = T::new_verifier(proof.clone());
+        assert!(<Ipa as OpeningScheme<Bn254>>::verify_opening(
+            commitment,
+            challenge,
+            evaluation,
+            &crs,
+            &mut transcript
+        )
+        .unwrap());
+
+        let mut transcript = T::new_verifier(proof.clone());
+        assert!(!<Ipa as OpeningScheme<Bn254>>::verify_opening(
+            commitment,
+            challenge,
+            evaluation + Fr::from(1u64),
+            &crs,
+            &mut transcript
+        )
+        .unwrap());
+
+        let mut transcript = T::new_verifier(proof);
+        assert!(<Ipa as OpeningScheme<Bn254>>::verify_opening(
+            commitment,
+            challenge,
+            evaluation,
+            &Ipa::crs::<Bn254>(8),
+            &mut transcript
+        )
+        .is_err());
+    }
+}
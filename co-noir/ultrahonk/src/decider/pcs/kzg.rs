@@ -0,0 +1,161 @@
+use super::OpeningScheme;
+use crate::{
+    decider::polynomial::Polynomial,
+    prelude::{HonkCurve, TranscriptFieldType},
+    prover::HonkProofResult,
+    transcript::{Transcript, TranscriptHasher},
+    types::ProverCrs,
+    verifier::HonkVerifyResult,
+    Utils,
+};
+use ark_ec::AffineRepr;
+use ark_ff::One;
+
+/// The KZG opening of barretenberg. It needs a pairing and a crs of the powers of a secret `τ`, of which the verifier holds `[τ]_2`.
+pub struct Kzg;
+
+impl Kzg {
+    // Note: The pairing check can be expressed naturally as
+    // e(C - v * [1]_1, [1]_2) = e([W]_1, [X - r]_2) where C =[p(X)]_1. This can be rearranged (e.g. see the plonk
+    // paper) as e(C + r*[W]_1 - v*[1]_1, [1]_2) * e(-[W]_1, [X]_2) = 1, or e(P_0, [1]_2) * e(P_1, [X]_2) = 1
+    fn reduce_verify<
+        P: HonkCurve<TranscriptFieldType>,
+        H: TranscriptHasher<TranscriptFieldType>,
+    >(
+        commitment: P::G1,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkVerifyResult<(P::G1Affine, P::G1Affine)> {
+        tracing::trace!("Reduce and verify opening pair");
+
+        let g1_affine = P::G1Affine::generator();
+        let g1_projective: P::G1 = g1_affine.into_group();
+
+        let quotient_commitment = transcript.receive_point_from_prover::<P>("KZG:W".to_string())?;
+
+        let p_1 = -P::G1::from(quotient_commitment);
+        let p_0 = commitment;
+        let first = quotient_commitment.into_group() * challenge;
+        let second = g1_projective * evaluation;
+        let p_0 = p_0 + first;
+        let p_0 = p_0 - second;
+        Ok((p_0.into(), p_1.into()))
+    }
+
+    fn pairing_check<P: HonkCurve<TranscriptFieldType>>(
+        p0: P::G1Affine,
+        p1: P::G1Affine,
+        g2_x: P::G2Affine,
+        g2_gen: P::G2Affine,
+    ) -> bool {
+        tracing::trace!("Pairing check");
+        let p = [g2_gen, g2_x];
+        let g1_prepared = [P::G1Prepared::from(p0), P::G1Prepared::from(p1)];
+        P::multi_pairing(g1_prepared, p).0 == P::TargetField::one()
+    }
+}
+
+impl<P: HonkCurve<TranscriptFieldType>> OpeningScheme<P> for Kzg {
+    /// The point `[τ]_2` of the crs.
+    type VerifierKey = P::G2Affine;
+
+    fn compute_opening_proof<H: TranscriptHasher<TranscriptFieldType>>(
+        polynomial: Polynomial<P::ScalarField>,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        crs: &ProverCrs<P>,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkProofResult<()> {
+        let mut quotient = polynomial;
+        quotient[0] -= evaluation;
+        // Computes the coefficients for the quotient polynomial q(X) = (p(X) - v) / (X - r) through an FFT
+        quotient.factor_roots(&challenge);
+        let quotient_commitment = Utils::commit(&quotient.coefficients, crs)?;
+        // AZTEC TODO(#479): for now we compute the KZG commitment directly to unify the KZG and IPA interfaces but in the
+        // future we might need to adjust this to use the incoming alternative to work queue (i.e. variation of
+        // pthreads) or even the work queue itself
+        transcript.send_point_to_verifier::<P>("KZG:W".to_string(), quotient_commitment.into());
+        Ok(())
+    }
+
+    fn verify_opening<H: TranscriptHasher<TranscriptFieldType>>(
+        commitment: P::G1,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        key: &Self::VerifierKey,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkVerifyResult<bool> {
+        let pairing_points =
+            Self::reduce_verify::<P, H>(commitment, challenge, evaluation, transcript)?;
+        Ok(Self::pairing_check::<P>(
+            pairing_points.0,
+            pairing_points.1,
+            *key,
+            P::G2Affine::generator(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kzg;
+    use crate::{
+        decider::{pcs::OpeningScheme, polynomial::Polynomial},
+        prelude::Poseidon2Sponge,
+        transcript::{Transcript, TranscriptFieldType},
+        types::ProverCrs,
+        Utils,
+    };
+    use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{Field, Zero};
+
+    type T = Transcript<TranscriptFieldType, Poseidon2Sponge>;
+
+    fn evaluate(poly: &Polynomial<Fr>, x: Fr) -> Fr {
+        poly.coefficients
+            .iter()
+            .rev()
+            .fold(Fr::zero(), |acc, c| acc * x + c)
+    }
+
+    #[test]
+    fn kzg_opening_verifies() {
+        let tau = Fr::from(7u64);
+        let monomials = (0..8)
+            .map(|i| (G1Affine::generator() * tau.pow([i as u64])).into_affine())
+            .collect();
+        let crs = ProverCrs::<Bn254> { monomials };
+        let g2_x = (G2Affine::generator() * tau).into_affine();
+
+        let poly = Polynomial::new((1..=8u64).map(Fr::from).collect::<Vec<_>>());
+        let challenge = Fr::from(3u64);
+        let evaluation = evaluate(&poly, challenge);
+        let commitment = Utils::commit(&poly.coefficients, &crs).unwrap();
+
+        let mut transcript = T::new();
+        Kzg::compute_opening_proof(poly, challenge, evaluation, &crs, &mut transcript).unwrap();
+        let proof = transcript.get_proof();
+
+        let mut transcript = T::new_verifier(proof.clone());
+        assert!(<Kzg as OpeningScheme<Bn254>>::verify_opening(
+            commitment,
+            challenge,
+            evaluation,
+            &g2_x,
+            &mut transcript
+        )
+        .unwrap());
+
+        let mut transcript = T::new_verifier(proof);
+        assert!(!<Kzg as OpeningScheme<Bn254>>::verify_opening(
+            commitment,
+            challenge,
+            evaluation + Fr::from(1u64),
+            &g2_x,
+            &mut transcript
+        )
+        .unwrap());
+    }
+}
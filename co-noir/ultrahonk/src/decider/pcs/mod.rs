@@ -0,0 +1,43 @@
+pub(crate) mod ipa;
+pub(crate) mod kzg;
+
+pub use ipa::Ipa;
+pub use kzg::Kzg;
+
+use crate::{
+    decider::polynomial::Polynomial,
+    prelude::{HonkCurve, TranscriptFieldType},
+    prover::HonkProofResult,
+    transcript::{Transcript, TranscriptHasher},
+    types::ProverCrs,
+    verifier::HonkVerifyResult,
+};
+
+/// The univariate polynomial commitment scheme that opens the claim ZeroMorph reduces the multilinear evaluations to.
+///
+/// All commitments of a proof are MSMs over the monomials of the [ProverCrs], so the scheme also determines which points the crs has to consist of.
+///
+/// Only the plain prover ([`UltraHonk::prove_with_opening_scheme`](crate::prelude::UltraHonk::prove_with_opening_scheme)) can choose the scheme.
+/// The MPC prover in co-ultrahonk always produces KZG openings, so its proofs have to be verified with [Kzg].
+pub trait OpeningScheme<P: HonkCurve<TranscriptFieldType>> {
+    /// What the verifier needs in addition to the transcript to check an opening proof.
+    type VerifierKey;
+
+    /// Proves that `polynomial` evaluates to `evaluation` at `challenge` and sends the proof to the verifier.
+    fn compute_opening_proof<H: TranscriptHasher<TranscriptFieldType>>(
+        polynomial: Polynomial<P::ScalarField>,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        crs: &ProverCrs<P>,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkProofResult<()>;
+
+    /// Checks the opening proof in the transcript for the claim that the polynomial committed to in `commitment` evaluates to `evaluation` at `challenge`.
+    fn verify_opening<H: TranscriptHasher<TranscriptFieldType>>(
+        commitment: P::G1,
+        challenge: P::ScalarField,
+        evaluation: P::ScalarField,
+        key: &Self::VerifierKey,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkVerifyResult<bool>;
+}
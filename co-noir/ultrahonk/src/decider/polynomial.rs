@@ -76,6 +76,11 @@ impl<F: Clone> Polynomial<F> {
         self.coefficients.resize(size, value);
     }
 
+    /// Drops all coefficients above the given degree. Does nothing if the polynomial has at most `degree + 1` coefficients.
+    pub fn truncate(&mut self, degree: usize) {
+        self.coefficients.truncate(degree.saturating_add(1));
+    }
+
     pub fn into_vec(self) -> Vec<F> {
         self.coefficients
     }
@@ -88,16 +93,9 @@ impl<F: Zero + Clone> Polynomial<F> {
         }
     }
 
-    pub fn degree(&self) -> usize {
-        let mut len = self.coefficients.len() - 1;
-        for c in self.coefficients.iter().rev() {
-            if c.is_zero() {
-                len -= 1;
-            } else {
-                break;
-            }
-        }
-        len
+    /// Returns the degree of the polynomial, ignoring trailing zero coefficients. Returns `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.iter().rposition(|c| !c.is_zero())
     }
 }
 
@@ -208,3 +206,42 @@ impl<F: PrimeField> AddAssign<&[F]> for Polynomial<F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+    use ark_bn254::Fr;
+    use num_traits::Zero;
+
+    #[test]
+    fn truncate_drops_high_coefficients() {
+        let mut poly = Polynomial::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)]);
+        poly.truncate(1);
+        assert_eq!(poly.coefficients, vec![Fr::from(1u64), Fr::from(2u64)]);
+        assert_eq!(poly.degree(), Some(1));
+    }
+
+    #[test]
+    fn truncate_small_polynomial_is_noop() {
+        let coefficients = vec![Fr::from(1u64), Fr::from(2u64)];
+        let mut poly = Polynomial::new(coefficients.clone());
+        poly.truncate(5);
+        assert_eq!(poly.coefficients, coefficients);
+        poly.truncate(1);
+        assert_eq!(poly.coefficients, coefficients);
+    }
+
+    #[test]
+    fn degree_ignores_trailing_zeros() {
+        let poly = Polynomial::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::zero(), Fr::zero()]);
+        assert_eq!(poly.degree(), Some(1));
+        let poly = Polynomial::new(vec![Fr::from(5u64)]);
+        assert_eq!(poly.degree(), Some(0));
+    }
+
+    #[test]
+    fn degree_of_zero_polynomial_is_none() {
+        assert_eq!(Polynomial::<Fr>::new_zero(4).degree(), None);
+        assert_eq!(Polynomial::<Fr>::new(vec![]).degree(), None);
+    }
+}
@@ -1,10 +1,9 @@
-use super::{sumcheck::SumcheckOutput, types::ProverMemory, zeromorph::ZeroMorphOpeningClaim};
+use super::{pcs::OpeningScheme, sumcheck::SumcheckOutput, types::ProverMemory};
 use crate::{
     honk_curve::HonkCurve,
     prover::HonkProofResult,
     transcript::{Transcript, TranscriptFieldType, TranscriptHasher},
     types::{HonkProof, ProverCrs},
-    Utils,
 };
 use std::marker::PhantomData;
 
@@ -26,24 +25,6 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         }
     }
 
-    fn compute_opening_proof(
-        opening_claim: ZeroMorphOpeningClaim<P::ScalarField>,
-        transcript: &mut Transcript<TranscriptFieldType, H>,
-        crs: &ProverCrs<P>,
-    ) -> HonkProofResult<()> {
-        let mut quotient = opening_claim.polynomial;
-        let pair = opening_claim.opening_pair;
-        quotient[0] -= pair.evaluation;
-        // Computes the coefficients for the quotient polynomial q(X) = (p(X) - v) / (X - r) through an FFT
-        quotient.factor_roots(&pair.challenge);
-        let quotient_commitment = Utils::commit(&quotient.coefficients, crs)?;
-        // AZTEC TODO(#479): for now we compute the KZG commitment directly to unify the KZG and IPA interfaces but in the
-        // future we might need to adjust this to use the incoming alternative to work queue (i.e. variation of
-        // pthreads) or even the work queue itself
-        transcript.send_point_to_verifier::<P>("KZG:W".to_string(), quotient_commitment.into());
-        Ok(())
-    }
-
     /**
      * @brief Run Sumcheck to establish that ∑_i pow(\vec{β*})f_i(ω) = e*. This results in u = (u_1,...,u_d) sumcheck round
      * challenges and all evaluations at u being calculated.
@@ -64,7 +45,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
      * @details See https://hackmd.io/dlf9xEwhTQyE3hiGbq4FsA?view for a complete description of the unrolled protocol.
      *
      * */
-    fn execute_pcs_rounds(
+    fn execute_pcs_rounds<S: OpeningScheme<P>>(
         &mut self,
         transcript: &mut Transcript<TranscriptFieldType, H>,
         circuit_size: u32,
@@ -73,10 +54,17 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
     ) -> HonkProofResult<()> {
         let prover_opening_claim =
             self.zeromorph_prove(transcript, circuit_size, crs, sumcheck_output)?;
-        Self::compute_opening_proof(prover_opening_claim, transcript, crs)
+        let pair = prover_opening_claim.opening_pair;
+        S::compute_opening_proof(
+            prover_opening_claim.polynomial,
+            pair.challenge,
+            pair.evaluation,
+            crs,
+            transcript,
+        )
     }
 
-    pub(crate) fn prove(
+    pub(crate) fn prove<S: OpeningScheme<P>>(
         mut self,
         circuit_size: u32,
         crs: &ProverCrs<P>,
@@ -89,7 +77,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
 
         // Fiat-Shamir: rho, y, x, z
         // Execute Zeromorph multilinear PCS
-        self.execute_pcs_rounds(&mut transcript, circuit_size, crs, sumcheck_output)?;
+        self.execute_pcs_rounds::<S>(&mut transcript, circuit_size, crs, sumcheck_output)?;
 
         Ok(transcript.get_proof())
     }
@@ -2,7 +2,16 @@ pub(crate) mod prover;
 pub(crate) mod types;
 pub(crate) mod verifier;
 
-use super::polynomial::Polynomial;
+use super::{
+    pcs::OpeningScheme, polynomial::Polynomial, prover::Decider, verifier::DeciderVerifier,
+};
+use crate::{
+    honk_curve::HonkCurve,
+    prover::HonkProofResult,
+    transcript::{Transcript, TranscriptFieldType, TranscriptHasher},
+    types::ProverCrs,
+    verifier::HonkVerifyResult,
+};
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
 
@@ -21,3 +30,200 @@ pub(crate) struct ZeroMorphVerifierOpeningClaim<P: Pairing> {
     pub(crate) evaluation: P::ScalarField,
     pub(crate) commitment: P::G1,
 }
+
+/// A claim that a multilinear polynomial evaluates to the given values at several points.
+///
+/// The claim is reduced to a single [ZeroMorphOpeningClaim] by [`Decider::zeromorph_prove_multi_point`](super::prover::Decider::zeromorph_prove_multi_point).
+pub(crate) struct ZeroMorphMultiPointOpeningClaim<F: PrimeField> {
+    pub(crate) polynomial: Polynomial<F>,
+    pub(crate) opening_pairs: Vec<MultilinearOpeningPair<F>>,
+}
+
+/// A point `challenge` of the boolean hypercube's variables together with the claimed evaluation of a multilinear polynomial at it.
+#[derive(Clone)]
+pub struct MultilinearOpeningPair<F: PrimeField> {
+    pub challenge: Vec<F>,
+    pub evaluation: F,
+}
+
+/// Opens a single multilinear polynomial at several points with one univariate opening of the [OpeningScheme].
+///
+/// The polynomial is given by its evaluations over the boolean hypercube, where bit `k` of the index corresponds to variable `k` of a challenge.
+pub struct ZeroMorph;
+
+impl ZeroMorph {
+    /// Proves that `polynomial` evaluates to the claimed values at all points of `opening_pairs` and sends the proof to the verifier.
+    pub fn prove_multi_point<
+        P: HonkCurve<TranscriptFieldType>,
+        H: TranscriptHasher<TranscriptFieldType>,
+        S: OpeningScheme<P>,
+    >(
+        polynomial: Polynomial<P::ScalarField>,
+        opening_pairs: Vec<MultilinearOpeningPair<P::ScalarField>>,
+        crs: &ProverCrs<P>,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkProofResult<()> {
+        let claim = ZeroMorphMultiPointOpeningClaim {
+            polynomial,
+            opening_pairs,
+        };
+        let prover_opening_claim =
+            Decider::<P, H>::zeromorph_prove_multi_point(transcript, crs, claim)?;
+        S::compute_opening_proof(
+            prover_opening_claim.polynomial,
+            prover_opening_claim.opening_pair.challenge,
+            prover_opening_claim.opening_pair.evaluation,
+            crs,
+            transcript,
+        )
+    }
+
+    /// Checks the proof in the transcript that the polynomial of size `circuit_size` committed to in `commitment` evaluates to the claimed values at all points of `opening_pairs`.
+    pub fn verify_multi_point<
+        P: HonkCurve<TranscriptFieldType>,
+        H: TranscriptHasher<TranscriptFieldType>,
+        S: OpeningScheme<P>,
+    >(
+        circuit_size: u32,
+        commitment: P::G1Affine,
+        opening_pairs: &[MultilinearOpeningPair<P::ScalarField>],
+        key: &S::VerifierKey,
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+    ) -> HonkVerifyResult<bool> {
+        let opening_claim = DeciderVerifier::<P, H>::zeromorph_verify_multi_point(
+            transcript,
+            circuit_size,
+            commitment,
+            opening_pairs,
+        )?;
+        S::verify_opening(
+            opening_claim.commitment,
+            opening_claim.challenge,
+            opening_claim.evaluation,
+            key,
+            transcript,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultilinearOpeningPair, ZeroMorph};
+    use crate::{
+        decider::{pcs::Kzg, polynomial::Polynomial},
+        prelude::Poseidon2Sponge,
+        transcript::{Transcript, TranscriptFieldType},
+        types::ProverCrs,
+        Utils,
+    };
+    use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{Field, One};
+
+    type T = Transcript<TranscriptFieldType, Poseidon2Sponge>;
+
+    const LOG_N: usize = 3;
+
+    // Evaluates the multilinear extension of the hypercube evaluations `poly`, folding the highest variable first
+    fn evaluate_mle(poly: &Polynomial<Fr>, u: &[Fr]) -> Fr {
+        let mut evals = poly.coefficients.clone();
+        for u_k in u.iter().rev() {
+            let (lo, hi) = evals.split_at(evals.len() / 2);
+            evals = lo
+                .iter()
+                .zip(hi)
+                .map(|(a, b)| *a + *u_k * (*b - a))
+                .collect();
+        }
+        evals[0]
+    }
+
+    fn setup() -> (
+        ProverCrs<Bn254>,
+        G2Affine,
+        Polynomial<Fr>,
+        Vec<MultilinearOpeningPair<Fr>>,
+    ) {
+        let tau = Fr::from(11u64);
+        let monomials = (0..1 << LOG_N)
+            .map(|i| (G1Affine::generator() * tau.pow([i as u64])).into_affine())
+            .collect();
+        let crs = ProverCrs::<Bn254> { monomials };
+        let g2_x = (G2Affine::generator() * tau).into_affine();
+
+        let poly = Polynomial::new((1..=1u64 << LOG_N).map(|i| Fr::from(i * i + 3)).collect());
+        let opening_pairs = (0..3u64)
+            .map(|j| {
+                let challenge = (0..LOG_N as u64)
+                    .map(|k| Fr::from(5 + 7 * j + k))
+                    .collect::<Vec<_>>();
+                let evaluation = evaluate_mle(&poly, &challenge);
+                MultilinearOpeningPair {
+                    challenge,
+                    evaluation,
+                }
+            })
+            .collect();
+        (crs, g2_x, poly, opening_pairs)
+    }
+
+    #[test]
+    fn evaluate_mle_matches_hypercube() {
+        let (_, _, poly, _) = setup();
+        let point = [Fr::one(), Fr::from(0u64), Fr::one()];
+        assert_eq!(evaluate_mle(&poly, &point), poly[0b101]);
+    }
+
+    #[test]
+    fn multi_point_opening_verifies() {
+        let (crs, g2_x, poly, opening_pairs) = setup();
+        let commitment = Utils::commit(&poly.coefficients, &crs).unwrap();
+
+        let mut transcript = T::new();
+        ZeroMorph::prove_multi_point::<Bn254, _, Kzg>(
+            poly,
+            opening_pairs.clone(),
+            &crs,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.get_proof();
+
+        let mut transcript = T::new_verifier(proof);
+        assert!(ZeroMorph::verify_multi_point::<Bn254, _, Kzg>(
+            1 << LOG_N,
+            commitment.into(),
+            &opening_pairs,
+            &g2_x,
+            &mut transcript
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn multi_point_opening_rejects_tampered_evaluation() {
+        let (crs, g2_x, poly, mut opening_pairs) = setup();
+        let commitment = Utils::commit(&poly.coefficients, &crs).unwrap();
+
+        let mut transcript = T::new();
+        ZeroMorph::prove_multi_point::<Bn254, _, Kzg>(
+            poly,
+            opening_pairs.clone(),
+            &crs,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.get_proof();
+
+        opening_pairs[1].evaluation += Fr::one();
+        let mut transcript = T::new_verifier(proof);
+        assert!(!ZeroMorph::verify_multi_point::<Bn254, _, Kzg>(
+            1 << LOG_N,
+            commitment.into(),
+            &opening_pairs,
+            &g2_x,
+            &mut transcript
+        )
+        .unwrap());
+    }
+}
@@ -1,7 +1,7 @@
 use super::{
     super::{prover::Decider, sumcheck::SumcheckOutput},
     types::{PolyF, PolyG, PolyGShift},
-    ZeroMorphOpeningClaim,
+    ZeroMorphMultiPointOpeningClaim, ZeroMorphOpeningClaim,
 };
 use crate::{
     decider::{polynomial::Polynomial, types::ClaimedEvaluations, zeromorph::OpeningPair},
@@ -452,4 +452,103 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         };
         Ok(res)
     }
+
+    /**
+     * @brief Returns a univariate opening claim equivalent to evaluation claims f(u_j) = v_j of a single multilinear
+     * polynomial f at several points u_j
+     * @details For every point, the quotients q_k^j and the batched lifted-degree quotient \hat{q}^j are committed to as
+     * in the single point case. All points share the challenges y, x and z, such that every point yields a polynomial
+     * pi_j with pi_j(x) = 0. The pi_j are then batched with powers of a challenge r into pi = \sum_j r^j * pi_j, which is
+     * opened at x with a single univariate PCS opening.
+     *
+     * @param claim Polynomial f together with the points u_j and claimed evaluations v_j
+     * @param crs
+     * @param transcript
+     */
+    pub(crate) fn zeromorph_prove_multi_point(
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+        crs: &ProverCrs<P>,
+        claim: ZeroMorphMultiPointOpeningClaim<P::ScalarField>,
+    ) -> HonkProofResult<ZeroMorphOpeningClaim<P::ScalarField>> {
+        tracing::trace!("Zeromorph multi-point prove");
+
+        let commitment_key = crs;
+        let f_polynomial = claim.polynomial;
+        let n = f_polynomial.len();
+        let log_n = Utils::get_msb64(n as u64) as usize;
+        debug_assert!(n.is_power_of_two());
+
+        // Compute and send the commitments C_{q_k^j} = [q_k^j] of the multilinear quotients for every point u_j
+        let mut quotients = Vec::with_capacity(claim.opening_pairs.len());
+        for (j, pair) in claim.opening_pairs.iter().enumerate() {
+            debug_assert_eq!(pair.challenge.len(), log_n);
+            let quotients_j = Self::compute_multilinear_quotients(&f_polynomial, &pair.challenge);
+            for (k, val) in quotients_j.iter().enumerate() {
+                let res = Utils::commit(&val.coefficients, commitment_key)?;
+                let label = format!("ZM:MP:C_q_{}_{}", j, k);
+                transcript.send_point_to_verifier::<P>(label, res.into());
+            }
+            quotients.push(quotients_j);
+        }
+
+        // Get challenge y
+        let y_challenge = transcript.get_challenge::<P>("ZM:MP:y".to_string());
+
+        // Compute and send the commitments C_q^j = [\hat{q}^j] of the batched, lifted-degree quotients
+        let mut batched_quotients = Vec::with_capacity(quotients.len());
+        for (j, quotients_j) in quotients.iter().enumerate() {
+            let batched_quotient =
+                Self::compute_batched_lifted_degree_quotient(quotients_j, &y_challenge, n);
+            let q_commitment = Utils::commit(&batched_quotient.coefficients, commitment_key)?;
+            transcript.send_point_to_verifier::<P>(format!("ZM:MP:C_q_{}", j), q_commitment.into());
+            batched_quotients.push(batched_quotient);
+        }
+
+        // Get challenges x, z and the batching challenge r for the points
+        let challs = transcript.get_challenges::<P>(&[
+            "ZM:MP:x".to_string(),
+            "ZM:MP:z".to_string(),
+            "ZM:MP:r".to_string(),
+        ]);
+        let x_challenge = challs[0];
+        let z_challenge = challs[1];
+        let r_challenge = challs[2];
+
+        // Compute pi = \sum_j r^j * pi_j
+        let mut pi_polynomial = Polynomial::new_zero(n);
+        let mut batching_scalar = P::ScalarField::one();
+        for (pair, quotients_j, batched_quotient) in
+            izip!(claim.opening_pairs, quotients, batched_quotients)
+        {
+            let zeta_x = Self::compute_partially_evaluated_degree_check_polynomial(
+                &batched_quotient,
+                &quotients_j,
+                &y_challenge,
+                &x_challenge,
+            );
+            let z_x = Self::compute_partially_evaluated_zeromorph_identity_polynomial(
+                f_polynomial.to_owned(),
+                Polynomial::new_zero(n),
+                quotients_j,
+                pair.evaluation,
+                &pair.challenge,
+                x_challenge,
+            );
+            let pi_j = Self::compute_batched_evaluation_and_degree_check_polynomial(
+                zeta_x,
+                z_x,
+                z_challenge,
+            );
+            pi_polynomial.add_scaled(&pi_j, &batching_scalar);
+            batching_scalar *= r_challenge;
+        }
+
+        Ok(ZeroMorphOpeningClaim {
+            polynomial: pi_polynomial,
+            opening_pair: OpeningPair {
+                challenge: x_challenge,
+                evaluation: P::ScalarField::zero(),
+            },
+        })
+    }
 }
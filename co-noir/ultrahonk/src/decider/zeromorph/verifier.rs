@@ -3,7 +3,7 @@ use crate::{
     decider::{
         types::{ClaimedEvaluations, VerifierCommitments},
         verifier::DeciderVerifier,
-        zeromorph::ZeroMorphVerifierOpeningClaim,
+        zeromorph::{MultilinearOpeningPair, ZeroMorphVerifierOpeningClaim},
     },
     prelude::{HonkCurve, TranscriptFieldType},
     transcript::{Transcript, TranscriptHasher},
@@ -12,6 +12,7 @@ use crate::{
 };
 use ark_ec::AffineRepr;
 use ark_ff::{Field, One, Zero};
+use itertools::izip;
 
 impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>>
     DeciderVerifier<P, H>
@@ -112,6 +113,76 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         })
     }
 
+    /// Verifier counterpart of [`Decider::zeromorph_prove_multi_point`](crate::decider::prover::Decider::zeromorph_prove_multi_point).
+    /// Reduces the claims f(u_j) = v_j for the commitment C_f = [f] to a single opening claim pi(x) = 0, where the commitment to pi is the combination \sum_j r^j * (C_{\zeta_x}^j + z * C_{Z_x}^j).
+    pub(crate) fn zeromorph_verify_multi_point(
+        transcript: &mut Transcript<TranscriptFieldType, H>,
+        circuit_size: u32,
+        commitment: P::G1Affine,
+        opening_pairs: &[MultilinearOpeningPair<P::ScalarField>],
+    ) -> HonkVerifyResult<ZeroMorphVerifierOpeningClaim<P>> {
+        tracing::trace!("Zeromorph multi-point verify");
+
+        let log_circuit_size = Utils::get_msb32(circuit_size) as usize;
+
+        let mut c_q_k = Vec::with_capacity(opening_pairs.len());
+        for j in 0..opening_pairs.len() {
+            let mut c_q_k_j = Vec::with_capacity(log_circuit_size);
+            for k in 0..log_circuit_size {
+                c_q_k_j.push(
+                    transcript.receive_point_from_prover::<P>(format!("ZM:MP:C_q_{}_{}", j, k))?,
+                );
+            }
+            c_q_k.push(c_q_k_j);
+        }
+
+        let y_challenge = transcript.get_challenge::<P>("ZM:MP:y".to_string());
+
+        let mut c_q = Vec::with_capacity(opening_pairs.len());
+        for j in 0..opening_pairs.len() {
+            c_q.push(transcript.receive_point_from_prover::<P>(format!("ZM:MP:C_q_{}", j))?);
+        }
+
+        let challs = transcript.get_challenges::<P>(&[
+            "ZM:MP:x".to_string(),
+            "ZM:MP:z".to_string(),
+            "ZM:MP:r".to_string(),
+        ]);
+        let x_challenge = challs[0];
+        let z_challenge = challs[1];
+        let r_challenge = challs[2];
+
+        let mut c_pi = P::G1::zero();
+        let mut batching_scalar = P::ScalarField::one();
+        for (pair, c_q_k_j, c_q_j) in izip!(opening_pairs, c_q_k, c_q) {
+            if pair.challenge.len() != log_circuit_size {
+                eyre::bail!(
+                    "multilinear challenge has {} variables, expected {}",
+                    pair.challenge.len(),
+                    log_circuit_size
+                );
+            }
+            let c_zeta_x =
+                Self::compute_c_zeta_x(c_q_j, &c_q_k_j, y_challenge, x_challenge, circuit_size)?;
+            let c_z_x = Self::compute_c_z_x_single(
+                commitment,
+                &c_q_k_j,
+                pair.evaluation,
+                x_challenge,
+                &pair.challenge,
+                circuit_size,
+            )?;
+            c_pi += (c_zeta_x + c_z_x * z_challenge) * batching_scalar;
+            batching_scalar *= r_challenge;
+        }
+
+        Ok(ZeroMorphVerifierOpeningClaim {
+            challenge: x_challenge,
+            evaluation: P::ScalarField::ZERO,
+            commitment: c_pi,
+        })
+    }
+
     // (compare cpp/src/barretenberg/commitment_schemes/zeromorph/zeromorph.hpp or https://hackmd.io/dlf9xEwhTQyE3hiGbq4FsA?view)
 
     fn compute_c_zeta_x(
@@ -220,4 +291,46 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
 
         Ok(Utils::msm::<P>(&scalars, &commitments)?)
     }
+
+    // Computes C_{Z_x} = x * C_f - v * x * \Phi_n(x) * [1]_1 - x * \sum_k (x^{2^k}\Phi_{n-k-1}(x^{2^{k+1}}) - u_k\Phi_{n-k}(x^{2^k})) * C_{q_k}
+    // for a single commitment C_f, i.e., without batching and shifts
+    fn compute_c_z_x_single(
+        commitment: P::G1Affine,
+        c_q_k: &[P::G1Affine],
+        evaluation: P::ScalarField,
+        x_challenge: P::ScalarField,
+        u_challenge: &[P::ScalarField],
+        circuit_size: u32,
+    ) -> HonkVerifyResult<P::G1> {
+        let mut scalars = Vec::with_capacity(2 + c_q_k.len());
+        let mut commitments = Vec::with_capacity(2 + c_q_k.len());
+
+        let phi_numerator = x_challenge.pow([circuit_size as u64]) - P::ScalarField::ONE;
+        let minus_one = -P::ScalarField::ONE;
+        let phi_n_x = phi_numerator / (x_challenge - P::ScalarField::ONE);
+
+        scalars.push(evaluation * x_challenge * phi_n_x * minus_one);
+        commitments.push(P::G1Affine::generator());
+        scalars.push(x_challenge);
+        commitments.push(commitment);
+
+        let mut x_pow_2k = x_challenge; // x^{2^k}
+        let mut x_pow_2kp1 = x_challenge * x_challenge;
+
+        for (&c_q_k_item, u) in c_q_k.iter().zip(u_challenge.iter()) {
+            let phi_term_1 = phi_numerator / (x_pow_2kp1 - P::ScalarField::ONE); // \Phi_{n-k-1}(x^{2^{k + 1}})
+            let phi_term_2 = phi_numerator / (x_pow_2k - P::ScalarField::ONE); // \Phi_{n-k}(x^{2^k})
+
+            let scalar = ((x_pow_2k * phi_term_1) - (*u * phi_term_2)) * x_challenge * minus_one;
+
+            scalars.push(scalar);
+            commitments.push(c_q_k_item);
+
+            // Update powers of challenge x
+            x_pow_2k = x_pow_2kp1;
+            x_pow_2kp1 *= x_pow_2kp1;
+        }
+
+        Ok(Utils::msm::<P>(&scalars, &commitments)?)
+    }
 }
@@ -0,0 +1,85 @@
+use crate::{decider::polynomial::Polynomial, prover::HonkProofResult, types::ProverCrs, Utils};
+use ark_ec::pairing::Pairing;
+use num_traits::Zero;
+
+/// Computes the KZG commitment `[p(x)]_1` to the polynomial `p` as an MSM over the monomial powers of the CRS.
+///
+/// Trailing zero coefficients do not count towards the degree, so the polynomial may be stored with more coefficients than the CRS has points as long as its actual degree is smaller than the CRS size. Returns [crate::prover::HonkProofError::DegreeExceedsCrs] otherwise.
+pub fn commit<P: Pairing>(
+    crs: &ProverCrs<P>,
+    poly: &Polynomial<P::ScalarField>,
+) -> HonkProofResult<P::G1> {
+    let len = checked_len(crs, poly)?;
+    Utils::commit(&poly.coefficients[..len], crs)
+}
+
+/// Returns the number of coefficients of `poly` without trailing zeros, or [crate::prover::HonkProofError::DegreeExceedsCrs] if the CRS has fewer points.
+fn checked_len<P: Pairing>(
+    crs: &ProverCrs<P>,
+    poly: &Polynomial<P::ScalarField>,
+) -> HonkProofResult<usize> {
+    let len = poly
+        .coefficients
+        .iter()
+        .rposition(|c| !c.is_zero())
+        .map_or(0, |degree| degree + 1);
+    Utils::check_degree(len, crs.monomials.len())?;
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::commit;
+    use crate::{decider::polynomial::Polynomial, prover::HonkProofError, types::ProverCrs};
+    use ark_bn254::{Bn254, Fr, G1Affine, G1Projective};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{Field, Zero};
+
+    fn crs_for_tau(tau: Fr, size: usize) -> ProverCrs<Bn254> {
+        let g = G1Affine::generator();
+        let monomials = (0..size)
+            .map(|i| (g * tau.pow([i as u64])).into_affine())
+            .collect();
+        ProverCrs { monomials }
+    }
+
+    #[test]
+    fn commit_matches_evaluation_at_tau() {
+        let tau = Fr::from(7u64);
+        let crs = crs_for_tau(tau, 4);
+        // p(X) = 3 + 2X + 5X^3, so p(7) = 3 + 14 + 1715 = 1732
+        let poly = Polynomial::new(vec![
+            Fr::from(3u64),
+            Fr::from(2u64),
+            Fr::zero(),
+            Fr::from(5u64),
+        ]);
+        let expected: G1Projective = G1Affine::generator() * Fr::from(1732u64);
+        assert_eq!(commit(&crs, &poly).unwrap(), expected);
+    }
+
+    #[test]
+    fn commit_ignores_trailing_zeros() {
+        let tau = Fr::from(7u64);
+        let crs = crs_for_tau(tau, 2);
+        // p(X) = 1 + X, padded beyond the size of the crs
+        let poly = Polynomial::new(vec![Fr::from(1u64), Fr::from(1u64), Fr::zero(), Fr::zero()]);
+        let expected: G1Projective = G1Affine::generator() * Fr::from(8u64);
+        assert_eq!(commit(&crs, &poly).unwrap(), expected);
+    }
+
+    #[test]
+    fn commit_rejects_degree_above_crs() {
+        let crs = crs_for_tau(Fr::from(7u64), 2);
+        let poly = Polynomial::new(vec![Fr::from(1u64), Fr::zero(), Fr::from(1u64)]);
+        let err = commit(&crs, &poly).unwrap_err();
+        assert!(matches!(
+            err,
+            HonkProofError::DegreeExceedsCrs {
+                degree: 2,
+                srs_size: 2
+            }
+        ));
+        assert_eq!(err.to_string(), "polynomial degree 2 exceeds SRS size 2");
+    }
+}
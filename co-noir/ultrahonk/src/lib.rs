@@ -1,6 +1,7 @@
 pub(crate) mod decider;
 pub(crate) mod honk_curve;
 pub(crate) mod keccak_hash;
+pub(crate) mod kzg;
 pub(crate) mod oink;
 #[allow(unused)] // TACEO TODO remove this at a later point
 pub(crate) mod parse;
@@ -109,9 +110,22 @@ impl Utils {
         poly: &[P::ScalarField],
         crs: &ProverCrs<P>,
     ) -> HonkProofResult<P::G1> {
+        Self::check_degree(poly.len(), crs.monomials.len())?;
         Self::msm::<P>(poly, crs.monomials.as_slice())
     }
 
+    /// Checks that a polynomial with `len` coefficients can be committed to with a crs of `srs_size` points, i.e., that its degree is smaller than the size of the crs.
+    /// Committing to a larger polynomial would drop its high coefficients and result in an unsound commitment.
+    pub fn check_degree(len: usize, srs_size: usize) -> HonkProofResult<()> {
+        if len > srs_size {
+            return Err(HonkProofError::DegreeExceedsCrs {
+                degree: len - 1,
+                srs_size,
+            });
+        }
+        Ok(())
+    }
+
     pub fn msm<P: Pairing>(poly: &[P::ScalarField], crs: &[P::G1Affine]) -> HonkProofResult<P::G1> {
         if poly.len() > crs.len() {
             return Err(HonkProofError::CrsTooSmall);
@@ -235,3 +249,31 @@ impl<F: PrimeField> Serialize<F> {
         F::from(bigint)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Utils;
+    use crate::prover::HonkProofError;
+
+    #[test]
+    fn check_degree_rejects_more_coefficients_than_srs_points() {
+        assert!(Utils::check_degree(0, 0).is_ok());
+        assert!(Utils::check_degree(4, 4).is_ok());
+        let err = Utils::check_degree(5, 4).unwrap_err();
+        assert!(matches!(
+            err,
+            HonkProofError::DegreeExceedsCrs {
+                degree: 4,
+                srs_size: 4
+            }
+        ));
+        assert_eq!(err.to_string(), "polynomial degree 4 exceeds SRS size 4");
+        assert!(matches!(
+            Utils::check_degree(1048578, 1048576),
+            Err(HonkProofError::DegreeExceedsCrs {
+                degree: 1048577,
+                srs_size: 1048576
+            })
+        ));
+    }
+}
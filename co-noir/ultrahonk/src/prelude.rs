@@ -1,8 +1,11 @@
 pub use crate::decider::barycentric::Barycentric;
+pub use crate::decider::pcs::{Ipa, Kzg, OpeningScheme};
 pub use crate::decider::polynomial::Polynomial;
 pub use crate::decider::types::GateSeparatorPolynomial;
 pub use crate::decider::univariate::Univariate;
+pub use crate::decider::zeromorph::{MultilinearOpeningPair, ZeroMorph};
 pub use crate::honk_curve::HonkCurve;
+pub use crate::kzg::commit;
 pub use crate::parse::crs::CrsParser;
 pub use crate::parse::{
     acir_format::AcirFormat,
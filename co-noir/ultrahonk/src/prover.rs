@@ -1,5 +1,9 @@
 use crate::{
-    decider::{prover::Decider, types::ProverMemory},
+    decider::{
+        pcs::{Kzg, OpeningScheme},
+        prover::Decider,
+        types::ProverMemory,
+    },
     honk_curve::HonkCurve,
     oink::prover::Oink,
     transcript::{Transcript, TranscriptFieldType, TranscriptHasher},
@@ -20,6 +24,9 @@ pub enum HonkProofError {
     /// Indicates that the crs is too small
     #[error("CRS too small")]
     CrsTooSmall,
+    /// Indicates that the degree of a polynomial to commit to is not smaller than the size of the crs
+    #[error("polynomial degree {degree} exceeds SRS size {srs_size}")]
+    DegreeExceedsCrs { degree: usize, srs_size: usize },
     /// The proof has too few elements
     #[error("Proof too small")]
     ProofTooSmall,
@@ -61,9 +68,29 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
     }
 
     pub fn prove(proving_key: ProvingKey<P>) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
+        Self::prove_with_domain_separator(proving_key, &[])
+    }
+
+    /// Like [`UltraHonk::prove`], but binds the proof to `domain_separator`. The verifier has to use the same tag.
+    pub fn prove_with_domain_separator(
+        proving_key: ProvingKey<P>,
+        domain_separator: &[u8],
+    ) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
+        Self::prove_with_opening_scheme::<Kzg>(proving_key, domain_separator)
+    }
+
+    /// Like [`UltraHonk::prove_with_domain_separator`], but opens the polynomials with the opening scheme `S` instead of KZG.
+    /// The crs of the proving key has to be one for `S`, e.g., created by [`Ipa::crs`](crate::prelude::Ipa::crs).
+    ///
+    /// Only this plain prover supports other opening schemes. The MPC prover in co-ultrahonk always opens with KZG.
+    pub fn prove_with_opening_scheme<S: OpeningScheme<P>>(
+        proving_key: ProvingKey<P>,
+        domain_separator: &[u8],
+    ) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
         tracing::trace!("UltraHonk prove");
 
-        let mut transcript = Transcript::<TranscriptFieldType, H>::new();
+        let mut transcript =
+            Transcript::<TranscriptFieldType, H>::new_with_domain_separator(domain_separator);
 
         let oink = Oink::default();
         let oink_result = oink.prove(&proving_key, &mut transcript)?;
@@ -77,6 +104,6 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
             Self::generate_gate_challenges(&mut transcript);
 
         let decider = Decider::new(memory);
-        decider.prove(cicruit_size, &crs, transcript)
+        decider.prove::<S>(cicruit_size, &crs, transcript)
     }
 }
@@ -58,7 +58,16 @@ where
     H: TranscriptHasher<F>,
 {
     pub fn new() -> Self {
-        Self {
+        Self::new_with_domain_separator(&[])
+    }
+
+    pub fn new_verifier(proof: HonkProof<F>) -> Self {
+        Self::new_verifier_with_domain_separator(proof, &[])
+    }
+
+    /// Creates a prover transcript which absorbs `domain_separator` before any other data. An empty tag yields the same transcript as [`Transcript::new`].
+    pub fn new_with_domain_separator(domain_separator: &[u8]) -> Self {
+        let mut transcript = Self {
             proof_data: Default::default(),
             manifest: Default::default(),
             num_frs_written: 0,
@@ -68,11 +77,17 @@ where
             current_round_data: Default::default(),
             previous_challenge: Default::default(),
             phantom_data: Default::default(),
-        }
+        };
+        transcript.absorb_domain_separator(domain_separator);
+        transcript
     }
 
-    pub fn new_verifier(proof: HonkProof<F>) -> Self {
-        Self {
+    /// Creates a verifier transcript for `proof` which absorbs `domain_separator` before any other data. Has to match the tag used by the prover.
+    pub fn new_verifier_with_domain_separator(
+        proof: HonkProof<F>,
+        domain_separator: &[u8],
+    ) -> Self {
+        let mut transcript = Self {
             proof_data: proof.inner(),
             manifest: Default::default(),
             num_frs_written: 0,
@@ -82,7 +97,24 @@ where
             current_round_data: Default::default(),
             previous_challenge: Default::default(),
             phantom_data: Default::default(),
+        };
+        transcript.absorb_domain_separator(domain_separator);
+        transcript
+    }
+
+    fn absorb_domain_separator(&mut self, domain_separator: &[u8]) {
+        if domain_separator.is_empty() {
+            return;
         }
+        // The tag only enters the hash buffer of the first round, it is not part of the proof.
+        // We prefix the length so that tags differing only in leading zero bytes of a chunk are separated.
+        let mut elements = Vec::with_capacity(1 + domain_separator.len().div_ceil(31));
+        elements.push(F::from(domain_separator.len() as u64));
+        elements.extend(domain_separator.chunks(31).map(F::from_be_bytes_mod_order));
+        let len = elements.len();
+        self.manifest
+            .add_entry(self.round_number, "domain_separator".to_string(), len);
+        self.current_round_data.extend(elements);
     }
 
     pub fn get_proof(self) -> HonkProof<F> {
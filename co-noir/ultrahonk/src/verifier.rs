@@ -1,5 +1,9 @@
 use crate::{
-    decider::{types::VerifierMemory, verifier::DeciderVerifier},
+    decider::{
+        pcs::{Kzg, OpeningScheme},
+        types::VerifierMemory,
+        verifier::DeciderVerifier,
+    },
     oink::verifier::OinkVerifier,
     prelude::{HonkCurve, TranscriptFieldType},
     prover::UltraHonk,
@@ -13,22 +17,46 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
     pub fn verify(
         honk_proof: HonkProof<TranscriptFieldType>,
         verifying_key: VerifyingKey<P>,
+    ) -> HonkVerifyResult<bool> {
+        Self::verify_with_domain_separator(honk_proof, verifying_key, &[])
+    }
+
+    /// Like [`UltraHonk::verify`], but for proofs bound to `domain_separator`. Fails to verify if the prover used a different tag.
+    pub fn verify_with_domain_separator(
+        honk_proof: HonkProof<TranscriptFieldType>,
+        verifying_key: VerifyingKey<P>,
+        domain_separator: &[u8],
+    ) -> HonkVerifyResult<bool> {
+        let crs = verifying_key.crs;
+        Self::verify_with_opening_scheme::<Kzg>(honk_proof, verifying_key, &crs, domain_separator)
+    }
+
+    /// Like [`UltraHonk::verify_with_domain_separator`], but for proofs created by [`UltraHonk::prove_with_opening_scheme`] with the opening scheme `S`.
+    /// The opening proof is checked against `opening_key` instead of the crs of the verifying key.
+    pub fn verify_with_opening_scheme<S: OpeningScheme<P>>(
+        honk_proof: HonkProof<TranscriptFieldType>,
+        verifying_key: VerifyingKey<P>,
+        opening_key: &S::VerifierKey,
+        domain_separator: &[u8],
     ) -> HonkVerifyResult<bool> {
         tracing::trace!("UltraHonk verification");
 
-        let mut transcript = Transcript::<TranscriptFieldType, H>::new_verifier(honk_proof);
+        let mut transcript =
+            Transcript::<TranscriptFieldType, H>::new_verifier_with_domain_separator(
+                honk_proof,
+                domain_separator,
+            );
 
         let oink_verifier = OinkVerifier::default();
         let oink_result = oink_verifier.verify(&verifying_key, &mut transcript)?;
 
         let cicruit_size = verifying_key.circuit_size;
-        let crs = verifying_key.crs;
 
         let mut memory = VerifierMemory::from_memory_and_key(oink_result, verifying_key);
         memory.relation_parameters.gate_challenges =
             Self::generate_gate_challenges(&mut transcript);
 
         let decider_verifier = DeciderVerifier::new(memory);
-        decider_verifier.verify(cicruit_size, &crs, transcript)
+        decider_verifier.verify::<S>(cicruit_size, opening_key, transcript)
     }
 }
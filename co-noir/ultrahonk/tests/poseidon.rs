@@ -2,8 +2,8 @@ use ark_bn254::Bn254;
 use sha3::Keccak256;
 use ultrahonk::{
     prelude::{
-        HonkProof, Poseidon2Sponge, ProvingKey, TranscriptFieldType, TranscriptHasher,
-        UltraCircuitBuilder, UltraHonk,
+        Crs, HonkProof, Ipa, Kzg, Poseidon2Sponge, ProvingKey, TranscriptFieldType,
+        TranscriptHasher, UltraCircuitBuilder, UltraHonk,
     },
     Utils,
 };
@@ -52,3 +52,112 @@ fn poseidon_test_keccak256() {
     // const PROOF_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon_keccaktranscript.proof";
     poseidon_test::<Keccak256>("");
 }
+
+#[test]
+fn poseidon_test_domain_separator() {
+    const CRS_PATH_G1: &str = "crs/bn254_g1.dat";
+    const CRS_PATH_G2: &str = "crs/bn254_g2.dat";
+    const CIRCUIT_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.json";
+    const WITNESS_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.gz";
+    const PROOF_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.proof";
+
+    let create_keys = || {
+        let constraint_system = Utils::get_constraint_system_from_file(CIRCUIT_FILE, true).unwrap();
+        let witness = Utils::get_witness_from_file(WITNESS_FILE).unwrap();
+        let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+            constraint_system,
+            0,
+            witness,
+            true,
+            false,
+        );
+        let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+        builder.create_keys(crs).unwrap()
+    };
+
+    // the empty tag does not change the proof
+    let (proving_key, _) = create_keys();
+    let proof =
+        UltraHonk::<_, Poseidon2Sponge>::prove_with_domain_separator(proving_key, b"").unwrap();
+    assert_eq!(proof.to_buffer(), std::fs::read(PROOF_FILE).unwrap());
+
+    let (proving_key, verifying_key) = create_keys();
+    let proof = UltraHonk::<_, Poseidon2Sponge>::prove_with_domain_separator(proving_key, b"app-a")
+        .unwrap();
+
+    let (_, verifying_key_b) = create_keys();
+    let (_, verifying_key_plain) = create_keys();
+    assert!(
+        !UltraHonk::<_, Poseidon2Sponge>::verify_with_domain_separator(
+            proof.clone(),
+            verifying_key_b,
+            b"app-b"
+        )
+        .unwrap_or(false)
+    );
+    assert!(
+        !UltraHonk::<_, Poseidon2Sponge>::verify(proof.clone(), verifying_key_plain)
+            .unwrap_or(false)
+    );
+    assert!(
+        UltraHonk::<_, Poseidon2Sponge>::verify_with_domain_separator(
+            proof,
+            verifying_key,
+            b"app-a"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn poseidon_test_ipa() {
+    const CRS_PATH_G1: &str = "crs/bn254_g1.dat";
+    const CRS_PATH_G2: &str = "crs/bn254_g2.dat";
+    const CIRCUIT_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.json";
+    const WITNESS_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.gz";
+
+    // the keys commit with the IPA generators, the G2 point of the KZG crs is unused by IPA
+    let create_keys = || {
+        let constraint_system = Utils::get_constraint_system_from_file(CIRCUIT_FILE, true).unwrap();
+        let witness = Utils::get_witness_from_file(WITNESS_FILE).unwrap();
+        let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+            constraint_system,
+            0,
+            witness,
+            true,
+            false,
+        );
+        let kzg_crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+        let size = kzg_crs.monomials.len();
+        let crs = Crs {
+            monomials: Ipa::crs::<Bn254>(size).monomials,
+            g2_x: kzg_crs.g2_x,
+        };
+        let (proving_key, verifying_key) = builder.create_keys(crs).unwrap();
+        (proving_key, verifying_key, size, kzg_crs.g2_x)
+    };
+
+    let (proving_key, verifying_key, size, g2_x) = create_keys();
+    let proof = UltraHonk::<_, Poseidon2Sponge>::prove_with_opening_scheme::<Ipa>(proving_key, b"")
+        .unwrap();
+
+    let (_, verifying_key_kzg, _, _) = create_keys();
+    assert!(
+        !UltraHonk::<_, Poseidon2Sponge>::verify_with_opening_scheme::<Kzg>(
+            proof.clone(),
+            verifying_key_kzg,
+            &g2_x,
+            b""
+        )
+        .unwrap_or(false)
+    );
+    assert!(
+        UltraHonk::<_, Poseidon2Sponge>::verify_with_opening_scheme::<Ipa>(
+            proof,
+            verifying_key,
+            &Ipa::crs::<Bn254>(size),
+            b""
+        )
+        .unwrap()
+    );
+}
@@ -22,6 +22,7 @@ impl RepToShamirNetwork<ShamirMpcNet> for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            rounds,
         } = self;
 
         let mut channels = HashMap::with_capacity(2);
@@ -33,6 +34,7 @@ impl RepToShamirNetwork<ShamirMpcNet> for Rep3MpcNet {
             num_parties: 3,
             net_handler,
             channels,
+            rounds,
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::protocols::{
     rep3::{Rep3PointShare, Rep3PrimeFieldShare},
-    shamir::{network::ShamirNetwork, ShamirPointShare, ShamirPrimeFieldShare, ShamirProtocol},
+    shamir::{
+        arithmetic, core, network::ShamirNetwork, ShamirPointShare, ShamirPrimeFieldShare,
+        ShamirProtocol,
+    },
 };
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
@@ -68,6 +71,54 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
         self.degree_reduce_vec(muls)
     }
 
+    /// Checks that a 3-party additive prime field share vector and its translation (see [`Self::translate_primefield_addshare_vec`]) hold the same values. The check does not use the translation itself, but opens both vectors independently.
+    ///
+    /// Both vectors are compressed with a jointly sampled random challenge r into sum_i x_i * r^i. Every party adds a random mask to its additive share of the compression and Shamir-shares the same mask to all parties, such that both compressions are masked with the sum of the masks and the opened values reveal nothing about the shared values.
+    /// The masked additive compression is then opened by summing the additive shares of all parties and the masked Shamir compression by Shamir reconstruction. Both opened values are equal if the translation was correct.
+    ///
+    /// This consumes one correlated randomness pair.
+    pub fn check_translated_primefield_addshare_vec(
+        &mut self,
+        input: &[F],
+        translated: &[ShamirPrimeFieldShare<F>],
+    ) -> std::io::Result<bool> {
+        if input.len() != translated.len() {
+            return Ok(false);
+        }
+        let challenge = self.rand()?;
+        let challenge = arithmetic::open(challenge, self)?;
+
+        let mut pow = F::one();
+        let mut input_comb = F::zero();
+        let mut translated_comb = F::zero();
+        for (x, y) in input.iter().zip(translated.iter()) {
+            input_comb += pow * x;
+            translated_comb += pow * y.a;
+            pow *= challenge;
+        }
+
+        let mask = F::rand(&mut self.rng);
+        let mask_shares = core::share_at_points(mask, &self.points, self.threshold, &mut self.rng)
+            .into_iter()
+            .map(|share| vec![share])
+            .collect();
+        for share in self.network.send_and_recv_each_many(mask_shares)? {
+            if share.len() != 1 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "During execution of check_translated_primefield_addshare_vec in MPC: Invalid number of elements received",
+                ));
+            }
+            translated_comb += share[0];
+        }
+        input_comb += mask;
+
+        let input_opened = self.network.broadcast(input_comb)?.into_iter().sum::<F>();
+        let translated_opened =
+            arithmetic::open(ShamirPrimeFieldShare::new(translated_comb), self)?;
+        Ok(input_opened == translated_opened)
+    }
+
     /// Translate a Rep3 point share into a 3-party Shamir point share, where the underlying sharing polynomial is of degree 1 (i.e., the threshold t = 1).
     pub fn translate_point_repshare<C>(
         &mut self,
@@ -25,12 +25,15 @@ use rand::{distributions::Standard, prelude::Distribution, CryptoRng, Rng, Seeda
 pub use arithmetic::types::Rep3PrimeFieldShare;
 pub use binary::types::Rep3BigUintShare;
 pub use pointshare::Rep3PointShare;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::RngType;
+
 pub(crate) type IoResult<T> = std::io::Result<T>;
 
 /// A type representing the different states a share can have. Either full replicated share, only an additive share, or both variants in compressed form.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub enum Rep3ShareVecType<F: PrimeField, U>
 where
@@ -322,6 +325,54 @@ pub fn share_field_elements_additive<F: PrimeField, R: Rng + CryptoRng>(
     [shares1, shares2, shares3]
 }
 
+/// The number of elements that are shared with the same random number generator in [share_field_elements_par] and [share_field_elements_additive_par].
+const PAR_SHARING_CHUNK_SIZE: usize = 1 << 12;
+
+/// Splits `vals` into chunks of [PAR_SHARING_CHUNK_SIZE] elements, which are shared in parallel with `share`. Every chunk gets its own random number generator, seeded from `rng` beforehand, thus the result only depends on `rng` and not on the number of threads.
+fn share_chunks_par<F, T, R, S>(vals: &[F], rng: &mut R, share: S) -> [Vec<T>; 3]
+where
+    F: PrimeField,
+    T: Default + Clone + Send,
+    R: Rng + CryptoRng,
+    S: Fn(F, &mut RngType) -> [T; 3] + Sync,
+{
+    let seeds = (0..vals.len().div_ceil(PAR_SHARING_CHUNK_SIZE))
+        .map(|_| rng.gen())
+        .collect::<Vec<_>>();
+    // the shares are written in place, to not copy them around once more after sharing
+    let mut shares1 = vec![T::default(); vals.len()];
+    let mut shares2 = vec![T::default(); vals.len()];
+    let mut shares3 = vec![T::default(); vals.len()];
+    vals.par_chunks(PAR_SHARING_CHUNK_SIZE)
+        .zip(shares1.par_chunks_mut(PAR_SHARING_CHUNK_SIZE))
+        .zip(shares2.par_chunks_mut(PAR_SHARING_CHUNK_SIZE))
+        .zip(shares3.par_chunks_mut(PAR_SHARING_CHUNK_SIZE))
+        .zip(seeds)
+        .for_each(|((((chunk, chunk1), chunk2), chunk3), seed)| {
+            let mut rng = RngType::from_seed(seed);
+            for (val, share1, share2, share3) in itertools::izip!(chunk, chunk1, chunk2, chunk3) {
+                [*share1, *share2, *share3] = share(*val, &mut rng);
+            }
+        });
+    [shares1, shares2, shares3]
+}
+
+/// Same as [share_field_elements], but the elements are shared in parallel, which pays off for large vectors. The shares are deterministic for a given `rng`, but differ from the ones [share_field_elements] produces for the same `rng`.
+pub fn share_field_elements_par<F: PrimeField, R: Rng + CryptoRng>(
+    vals: &[F],
+    rng: &mut R,
+) -> [Vec<Rep3PrimeFieldShare<F>>; 3] {
+    share_chunks_par(vals, rng, share_field_element)
+}
+
+/// Same as [share_field_elements_additive], but the elements are shared in parallel, which pays off for large vectors. The shares are deterministic for a given `rng`, but differ from the ones [share_field_elements_additive] produces for the same `rng`.
+pub fn share_field_elements_additive_par<F: PrimeField, R: Rng + CryptoRng>(
+    vals: &[F],
+    rng: &mut R,
+) -> [Vec<F>; 3] {
+    share_chunks_par(vals, rng, share_field_element_additive)
+}
+
 /// Secret shares a vector of field element using replicated secret sharing, whereas only one additive share is stored while the others are compressed as seeds derived form the provided random number generator. The outputs are of type [ReplicatedSeedType].
 pub fn share_field_elements_seeded<
     F: PrimeField,
@@ -380,10 +431,21 @@ where
     U::Seed: std::fmt::Debug + Clone + Serialize + for<'a> Deserialize<'a>,
     Standard: Distribution<U::Seed>,
 {
-    let len = vals.len();
     let seed_b = rng.gen::<U::Seed>();
     let seed_c = rng.gen::<U::Seed>();
+    share_field_elements_additive_from_seeds(vals, seed_b, seed_c)
+}
 
+/// Same as [share_field_elements_additive_seeded], but the additive shares of the second and third party are expanded from the given seeds instead of seeds drawn from a random number generator. The seeds must be fresh for every call.
+pub fn share_field_elements_additive_from_seeds<F: PrimeField, U: Rng + SeedableRng + CryptoRng>(
+    vals: &[F],
+    seed_b: U::Seed,
+    seed_c: U::Seed,
+) -> [SeededType<Vec<F>, U>; 3]
+where
+    U::Seed: std::fmt::Debug + Clone + Serialize + for<'a> Deserialize<'a>,
+{
+    let len = vals.len();
     let mut rng_b = U::from_seed(seed_b.to_owned());
     let mut rng_c = U::from_seed(seed_c.to_owned());
 
@@ -435,6 +497,23 @@ pub fn share_curve_point<C: CurveGroup, R: Rng + CryptoRng>(
     [share1, share2, share3]
 }
 
+/// Converts a party's (possibly compressed) REP3 share vector into its additive component. This requires no communication, since each replicated share already contains one of the three additive shares. Summing the outputs of all three parties thus reconstructs the shared values.
+pub fn rep3_to_additive<F: PrimeField, U: Rng + SeedableRng + CryptoRng>(
+    share: Rep3ShareVecType<F, U>,
+) -> Vec<F>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    match share {
+        Rep3ShareVecType::Replicated(vec) => vec.into_iter().map(|x| x.a).collect(),
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            replicated_seed_type.a.expand_vec()
+        }
+        Rep3ShareVecType::Additive(vec) => vec,
+        Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.expand_vec(),
+    }
+}
+
 //TODO RENAME ME TO COMBINE_ARITHMETIC_SHARE
 /// Reconstructs a field element from its arithmetic replicated shares.
 pub fn combine_field_element<F: PrimeField>(
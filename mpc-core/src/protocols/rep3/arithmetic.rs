@@ -153,6 +153,30 @@ pub fn io_mul_vec<F: PrimeField, N: Rep3Network>(
         .collect())
 }
 
+/// Converts an additive share into a replicated share, e.g., one obtained from [`rep3_to_additive`](crate::protocols::rep3::rep3_to_additive) or by translating a Shamir share.
+///
+/// Every party sends its additive share to the next party, masked with a fresh sharing of zero so that the additive shares themselves are not revealed. Needs one round of communication.
+pub fn additive_to_rep3<F: PrimeField, N: Rep3Network>(
+    additive: F,
+    io_context: &mut IoContext<N>,
+) -> IoResult<FieldShare<F>> {
+    let local_a = additive + io_context.rngs.rand.masking_field_element::<F>();
+    let local_b = io_context.network.reshare(local_a)?;
+    Ok(FieldShare::new(local_a, local_b))
+}
+
+/// Converts a vector of additive shares into replicated shares in a single round of communication, see [`additive_to_rep3`].
+pub fn additive_to_rep3_vec<F: PrimeField, N: Rep3Network>(
+    additive: Vec<F>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    let local_a = additive
+        .into_iter()
+        .map(|x| x + io_context.rngs.rand.masking_field_element::<F>())
+        .collect();
+    io_mul_vec(local_a, io_context)
+}
+
 /// Performs element-wise multiplication of two vectors of shared values.
 ///
 /// Use this function for small vecs. For large vecs see [`local_mul_vec`] and [`io_mul_vec`]
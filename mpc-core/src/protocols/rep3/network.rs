@@ -2,7 +2,7 @@
 //!
 //! This module contains implementation of the rep3 mpc network
 
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use crate::RngType;
 use ark_ff::PrimeField;
@@ -10,8 +10,10 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle, config::NetworkConfig, framing::FramingStats, rounds::RoundTracker,
+    MpcNetworkHandler, MpcNetworkHandlerWrapper,
 };
+use tokio::sync::oneshot;
 
 use super::{
     id::PartyID,
@@ -32,8 +34,7 @@ pub struct IoContext<N: Rep3Network> {
 }
 
 impl<N: Rep3Network> IoContext<N> {
-    fn setup_prf(network: &mut N) -> IoResult<Rep3Rand> {
-        let seed1: [u8; crate::SEED_SIZE] = RngType::from_entropy().gen();
+    fn setup_prf(network: &mut N, seed1: [u8; crate::SEED_SIZE]) -> IoResult<Rep3Rand> {
         network.send_next(seed1)?;
         let seed2: [u8; crate::SEED_SIZE] = network.recv_prev()?;
 
@@ -74,8 +75,15 @@ impl<N: Rep3Network> IoContext<N> {
     }
 
     /// Construct  a new [`IoContext`] with the given network
-    pub fn init(mut network: N) -> IoResult<Self> {
-        let mut rand = Self::setup_prf(&mut network)?;
+    pub fn init(network: N) -> IoResult<Self> {
+        Self::init_with_prf_seed(network, RngType::from_entropy().gen())
+    }
+
+    /// Construct a new [`IoContext`] with the given network, using `prf_seed` as our contribution to the shared randomness instead of a fresh seed.
+    ///
+    /// All randomness of this party is derived from this seed and the messages received during the setup. Thus, a computation can be replayed deterministically from the seed and the received messages, e.g., to resume it after a failure. The seed must be kept as secret as the shares and must not be reused for another computation.
+    pub fn init_with_prf_seed(mut network: N, prf_seed: [u8; crate::SEED_SIZE]) -> IoResult<Self> {
+        let mut rand = Self::setup_prf(&mut network, prf_seed)?;
         let bitcomps = Self::setup_bitcomp(&mut network, &mut rand)?;
         let rngs = Rep3CorrelatedRng::new(rand, bitcomps.0, bitcomps.1);
 
@@ -219,11 +227,20 @@ pub struct Rep3MpcNet {
     pub(crate) chan_next: ChannelHandle<Bytes, BytesMut>,
     pub(crate) chan_prev: ChannelHandle<Bytes, BytesMut>,
     pub(crate) net_handler: Arc<MpcNetworkHandlerWrapper>,
+    pub(crate) rounds: RoundTracker,
 }
 
 impl Rep3MpcNet {
     /// Takes a [NetworkConfig] struct and constructs the network interface. The network needs to contain exactly 3 parties with ids 0, 1, and 2.
     pub fn new(config: NetworkConfig) -> Result<Self, Report> {
+        Self::new_with_listener(config, |_| {})
+    }
+
+    /// Same as [`Self::new`], but calls `on_listening` once the other parties can connect to us, see [`MpcNetworkHandler::establish_with_listener`].
+    pub fn new_with_listener(
+        config: NetworkConfig,
+        on_listening: impl FnOnce(SocketAddr),
+    ) -> Result<Self, Report> {
         if config.parties.len() != 3 {
             bail!("REP3 protocol requires exactly 3 parties")
         }
@@ -232,7 +249,8 @@ impl Rep3MpcNet {
             .enable_all()
             .build()?;
         let (net_handler, chan_next, chan_prev) = runtime.block_on(async {
-            let net_handler = MpcNetworkHandler::establish(config).await?;
+            let net_handler =
+                MpcNetworkHandler::establish_with_listener(config, on_listening).await?;
             let mut channels = net_handler.get_byte_channels().await?;
             let chan_next = channels
                 .remove(&id.next_id().into())
@@ -253,6 +271,7 @@ impl Rep3MpcNet {
             net_handler: Arc::new(MpcNetworkHandlerWrapper::new(runtime, net_handler)),
             chan_next,
             chan_prev,
+            rounds: RoundTracker::new(),
         })
     }
 
@@ -274,36 +293,92 @@ impl Rep3MpcNet {
 
     /// Sends bytes over the network to the target party.
     pub fn send_bytes(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
-        if target == self.id.next_id() {
-            std::mem::drop(self.chan_next.blocking_send(data));
-            Ok(())
+        self.rounds.on_send();
+        let data = self.net_handler.inner.compress(data)?;
+        let mut ret = if target == self.id.next_id() {
+            self.chan_next.blocking_send(data)
         } else if target == self.id.prev_id() {
-            std::mem::drop(self.chan_prev.blocking_send(data));
-            Ok(())
+            self.chan_prev.blocking_send(data)
         } else {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "Cannot send to self",
             ));
+        };
+        // we do not wait for the write to finish, but report errors that are already known
+        match ret.try_recv() {
+            Ok(Err(err)) => Err(self.net_handler.inner.party_error(target.into(), err)),
+            _ => Ok(()),
         }
     }
 
+    /// Returns the messages in flight on the connections to the next and the previous party, in this order.
+    /// Meant for debugging stalls, see [`NetworkConfig::stall_log_secs`].
+    pub fn framing_stats(&self) -> [(PartyID, FramingStats); 2] {
+        [
+            (self.id.next_id(), self.chan_next.framing_stats()),
+            (self.id.prev_id(), self.chan_prev.framing_stats()),
+        ]
+    }
+
+    /// Waits for a requested message. If a stall log interval is configured, the framing stats are logged whenever the message did not arrive within the interval.
+    fn wait_for_message(
+        &self,
+        from: PartyID,
+        mut message: oneshot::Receiver<std::io::Result<BytesMut>>,
+    ) -> Result<std::io::Result<BytesMut>, oneshot::error::RecvError> {
+        let Some(interval) = self.net_handler.inner.stall_log_interval() else {
+            return message.blocking_recv();
+        };
+        self.net_handler.runtime.block_on(async {
+            let mut waited = std::time::Duration::ZERO;
+            loop {
+                match tokio::time::timeout(interval, &mut message).await {
+                    Ok(message) => return message,
+                    Err(_) => {
+                        waited += interval;
+                        tracing::warn!(
+                            "Party {}: waiting for a message from party {} for {} s",
+                            self.id,
+                            from,
+                            waited.as_secs()
+                        );
+                        for (peer, stats) in self.framing_stats() {
+                            tracing::warn!("party {}: {}", peer, stats);
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     /// Receives bytes over the network from the party with the given id.
     pub fn recv_bytes(&mut self, from: PartyID) -> std::io::Result<BytesMut> {
-        let data = if from == self.id.prev_id() {
-            self.chan_prev.blocking_recv().blocking_recv()
+        self.rounds.on_recv();
+        let message = if from == self.id.prev_id() {
+            self.chan_prev.blocking_recv()
         } else if from == self.id.next_id() {
-            self.chan_next.blocking_recv().blocking_recv()
+            self.chan_next.blocking_recv()
         } else {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "Cannot recv from self",
             ));
         };
-        let data = data.map_err(|_| {
-            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
-        })??;
-        Ok(data)
+        let data = self
+            .wait_for_message(from, message)
+            .map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
+            })
+            .and_then(|res| res)
+            .map_err(|err| match self.net_handler.inner.check_heartbeat() {
+                Ok(()) => self.net_handler.inner.party_error(from.into(), err),
+                Err(unresponsive) => unresponsive,
+            })?;
+        self.net_handler
+            .inner
+            .decompress(data)
+            .map_err(|err| self.net_handler.inner.party_error(from.into(), err))
     }
 }
 
@@ -378,6 +453,135 @@ impl Rep3Network for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            rounds: RoundTracker::new(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::{IpAddr, Ipv4Addr},
+        sync::{Barrier, Mutex},
+        time::Duration,
+    };
+
+    use mpc_net::config::{Address, Compression, NetworkParty};
+
+    use super::*;
+
+    fn local_configs(name: &str) -> Vec<NetworkConfig> {
+        let dir = std::env::temp_dir().join(format!("mpc-core-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let parties = (0..3)
+            .map(|id| {
+                let rcgen::CertifiedKey { cert, key_pair } =
+                    rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+                let cert_path = dir.join(format!("cert{id}.der"));
+                std::fs::write(&cert_path, cert.der()).unwrap();
+                std::fs::write(dir.join(format!("key{id}.der")), key_pair.serialize_der()).unwrap();
+                let port = std::net::UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+                    .unwrap()
+                    .local_addr()
+                    .unwrap()
+                    .port();
+                NetworkParty {
+                    id,
+                    dns_name: Address {
+                        hostname: "localhost".to_owned(),
+                        port,
+                    },
+                    cert_path,
+                }
+            })
+            .collect::<Vec<_>>();
+        (0..3)
+            .map(|my_id| NetworkConfig {
+                parties: parties.clone(),
+                my_id,
+                bind_addr: SocketAddr::new(
+                    IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    parties[my_id].dns_name.port,
+                ),
+                key_path: dir.join(format!("key{my_id}.der")),
+                heartbeat_interval_secs: None,
+                connect_timeout_secs: Some(10),
+                stall_log_secs: Some(1),
+                compression: Compression::None,
+                relay: None,
+            })
+            .collect()
+    }
+
+    /// Collects the formatted log lines of the current thread.
+    #[derive(Clone, Default)]
+    struct Logs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stalled_recv_logs_framing_stats() {
+        let mut configs = local_configs("stall");
+        let done = Arc::new(Barrier::new(3));
+        let peers = configs
+            .drain(1..)
+            .map(|config| {
+                let done = Arc::clone(&done);
+                std::thread::spawn(move || {
+                    let mut net = Rep3MpcNet::new(config).unwrap();
+                    if net.get_id() == PartyID::ID2 {
+                        // long enough for party 0 to log twice
+                        std::thread::sleep(Duration::from_millis(2500));
+                        net.send_bytes(PartyID::ID0, Bytes::from_static(b"late"))
+                            .unwrap();
+                    }
+                    done.wait();
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut net = Rep3MpcNet::new(configs.pop().unwrap()).unwrap();
+        let logs = Logs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        // the stall is logged by the thread that waits for the message
+        let data =
+            tracing::subscriber::with_default(subscriber, || net.recv_bytes(PartyID::ID2).unwrap());
+        assert_eq!(&data[..], b"late");
+        // the message arrived, so nothing is pending anymore
+        assert_eq!(
+            net.framing_stats()[1],
+            (
+                PartyID::ID2,
+                FramingStats {
+                    last_direction: Some(mpc_net::framing::Direction::Recv),
+                    ..Default::default()
+                }
+            )
+        );
+        done.wait();
+        // shutting down waits for the other parties, so all of them have to drop their network
+        drop(net);
+        for peer in peers {
+            peer.join().unwrap();
+        }
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("Party 0: waiting for a message from party 2 for 1 s"));
+        assert!(logs.contains("Party 0: waiting for a message from party 2 for 2 s"));
+        assert!(logs.contains("party 2: waiting to receive, 0 bytes in flight"));
+        assert!(logs.contains("party 1: idle, 0 bytes in flight"));
+    }
+}
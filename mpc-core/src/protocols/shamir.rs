@@ -4,7 +4,7 @@
 
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
-use itertools::izip;
+use itertools::{izip, Itertools};
 use network::ShamirNetwork;
 use rngs::ShamirRng;
 use std::time::Instant;
@@ -85,6 +85,57 @@ pub fn share_field_elements<F: PrimeField, R: Rng + CryptoRng>(
     result
 }
 
+/// Returns the evaluation points `id + 1` of the shares of the parties with the given ids.
+fn evaluation_points(party_ids: &[usize]) -> eyre::Result<Vec<usize>> {
+    party_ids
+        .iter()
+        .map(|id| {
+            id.checked_add(1)
+                .ok_or_else(|| eyre::eyre!("Party id {} is too large", id))
+        })
+        .collect()
+}
+
+/// Secret shares a vector of field elements using Shamir secret sharing for an explicit set of parties. In contrast to [share_field_elements], which produces shares for the parties 0..num_parties, the result contains one `Vec` of shares per entry of `party_ids` (in the same order). As in [share_field_elements], the share of the party with id `i` is the evaluation of the sharing polynomial at `i + 1`. Thus, to reconstruct, pass the ids incremented by one as `coeffs` to [combine_field_elements].
+///
+/// Returns an error if the party ids are not unique or if there are not enough parties to reconstruct a sharing of the given degree.
+pub fn share_field_elements_for_parties<F: PrimeField, R: Rng + CryptoRng>(
+    vals: &[F],
+    degree: usize,
+    party_ids: &[usize],
+    rng: &mut R,
+) -> eyre::Result<Vec<Vec<ShamirShare<F>>>> {
+    let mut sorted = party_ids.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != party_ids.len() {
+        eyre::bail!("Party ids must be unique, got {:?}", party_ids);
+    }
+    if party_ids.len() <= degree {
+        eyre::bail!(
+            "Not enough parties for a sharing of degree {}. Expected at least {}, got {}",
+            degree,
+            degree + 1,
+            party_ids.len()
+        );
+    }
+    let points = evaluation_points(party_ids)?;
+
+    let mut result = (0..party_ids.len())
+        .map(|_| Vec::with_capacity(vals.len()))
+        .collect::<Vec<_>>();
+
+    for val in vals {
+        let shares = core::share_at_points(*val, &points, degree, rng);
+        let shares = ShamirShare::convert_vec_rev(shares);
+        for (r, s) in izip!(&mut result, shares) {
+            r.push(s);
+        }
+    }
+
+    Ok(result)
+}
+
 /// Reconstructs a vector of field elements from its Shamir shares and lagrange coefficients. The input is a slice of `Vecs` of [ShamirPrimeFieldShare] per party. Thus, shares\[i\]\[j\] represents the j-th share of party i. Thereby at least `degree` + 1 shares need to be present per field element (i.e., i > degree).
 pub fn combine_field_elements<F: PrimeField>(
     shares: &[Vec<ShamirShare<F>>],
@@ -175,21 +226,45 @@ pub fn combine_curve_point<C: CurveGroup>(
 /// Preprocess `amount` number of corre;ated randomness pairs that are consumed while using the protocol.
 pub struct ShamirPreprocessing<F: PrimeField, N: ShamirNetwork> {
     threshold: usize,
+    points: Vec<usize>,
     rng_buffer: ShamirRng<F>,
     network: N,
 }
 
 impl<F: PrimeField, N: ShamirNetwork> ShamirPreprocessing<F, N> {
     /// Construct a new [`ShamirPreprocessing`] type and generate `amount` number of corr rand pairs
-    pub fn new(threshold: usize, mut network: N, amount: usize) -> eyre::Result<Self> {
+    pub fn new(threshold: usize, network: N, amount: usize) -> eyre::Result<Self> {
+        let party_ids = (0..network.get_num_parties()).collect::<Vec<_>>();
+        Self::new_for_parties(threshold, network, amount, &party_ids)
+    }
+
+    /// Same as [`Self::new`], but for shares created for an explicit set of parties, see [share_field_elements_for_parties]. The party with network id `i` holds the shares of party `party_ids[i]`, i.e., the evaluations at `party_ids[i] + 1`, and all shares created and opened by the protocol use these evaluation points.
+    #[tracing::instrument(level = "debug", name = "Shamir preprocessing", skip(network))]
+    pub fn new_for_parties(
+        threshold: usize,
+        mut network: N,
+        amount: usize,
+        party_ids: &[usize],
+    ) -> eyre::Result<Self> {
         let num_parties = network.get_num_parties();
 
         if 2 * threshold + 1 > num_parties {
             eyre::bail!("Threshold too large for number of parties")
         }
+        if party_ids.len() != num_parties {
+            eyre::bail!(
+                "Got {} party ids, but the number of parties is {}",
+                party_ids.len(),
+                num_parties
+            );
+        }
+        if let Some(id) = party_ids.iter().duplicates().next() {
+            eyre::bail!("Party id {} is used more than once", id);
+        }
+        let points = evaluation_points(party_ids)?;
 
         let seed: [u8; crate::SEED_SIZE] = RngType::from_entropy().gen();
-        let mut rng_buffer = ShamirRng::new(seed, threshold, &mut network)?;
+        let mut rng_buffer = ShamirRng::new(seed, threshold, &points, &mut network)?;
 
         tracing::info!(
             "Party {}: generating correlated randomness..",
@@ -207,6 +282,7 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirPreprocessing<F, N> {
 
         Ok(Self {
             threshold,
+            points,
             rng_buffer,
             network,
         })
@@ -218,19 +294,19 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
         let num_parties = value.network.get_num_parties();
         // We send in circles, so we need to receive from the last parties
         let id = value.network.get_id();
+        let points = value.points;
         let open_lagrange_t = core::lagrange_from_coeff(
             &(0..value.threshold + 1)
-                .map(|i| (id + num_parties - i) % num_parties + 1)
+                .map(|i| points[(id + num_parties - i) % num_parties])
                 .collect::<Vec<_>>(),
         );
         let open_lagrange_2t = core::lagrange_from_coeff(
             &(0..2 * value.threshold + 1)
-                .map(|i| (id + num_parties - i) % num_parties + 1)
+                .map(|i| points[(id + num_parties - i) % num_parties])
                 .collect::<Vec<_>>(),
         );
 
-        let mul_lagrange_2t =
-            core::lagrange_from_coeff(&(1..=2 * value.threshold + 1).collect::<Vec<_>>());
+        let mul_lagrange_2t = core::lagrange_from_coeff(&points[..=2 * value.threshold]);
 
         #[allow(clippy::assertions_on_constants)]
         {
@@ -239,8 +315,8 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
 
         // precompute the poly for interpolating a secret with known zero shares
         let num_non_zero = num_parties - value.threshold;
-        let zero_points = (num_non_zero + 1..=num_parties).collect::<Vec<_>>();
-        let mul_reconstruct_with_zeros = core::interpolation_poly_from_zero_points(&zero_points);
+        let mul_reconstruct_with_zeros =
+            core::interpolation_poly_from_zero_points(&points[num_non_zero..]);
 
         ShamirProtocol {
             threshold: value.threshold,
@@ -248,6 +324,7 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
             open_lagrange_2t,
             mul_lagrange_2t,
             mul_reconstruct_with_zeros,
+            points,
             rng: value.rng_buffer.rng,
             r_t: value.rng_buffer.r_t,
             r_2t: value.rng_buffer.r_2t,
@@ -268,7 +345,9 @@ pub struct ShamirProtocol<F: PrimeField, N: ShamirNetwork> {
     pub open_lagrange_2t: Vec<F>,
     mul_lagrange_2t: Vec<F>,
     mul_reconstruct_with_zeros: Vec<F>,
-    rng: RngType,
+    // the evaluation point of the shares of each party
+    pub(crate) points: Vec<usize>,
+    pub(crate) rng: RngType,
     pub(crate) r_t: Vec<F>,
     pub(crate) r_2t: Vec<F>,
     /// The underlying [`ShamirNetwork`]
@@ -286,6 +365,7 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
             open_lagrange_2t: self.open_lagrange_2t.clone(),
             mul_lagrange_2t: self.mul_lagrange_2t.clone(),
             mul_reconstruct_with_zeros: self.mul_reconstruct_with_zeros.clone(),
+            points: self.points.clone(),
             rng: RngType::from_seed(self.rng.gen()),
             r_t: self.r_t.drain(0..amount).collect(),
             r_2t: self.r_2t.drain(0..amount).collect(),
@@ -310,6 +390,40 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
         self.get_pair().map(|(r, _)| ShamirPrimeFieldShare::new(r))
     }
 
+    /// Refreshes the given shares for proactive security. Every party shares zero with a fresh random polynomial of degree `threshold` and all parties add the received sharings of zero to their shares.
+    /// The shared values stay the same, but the new shares are independent of the old ones, such that old shares cannot be combined with new ones.
+    pub fn reshare(
+        &mut self,
+        shares: &[ShamirPrimeFieldShare<F>],
+    ) -> std::io::Result<Vec<ShamirPrimeFieldShare<F>>> {
+        let num_parties = self.network.get_num_parties();
+        let len = shares.len();
+
+        let mut zero_shares = vec![Vec::with_capacity(len); num_parties];
+        for _ in 0..len {
+            let zero =
+                core::share_at_points(F::zero(), &self.points, self.threshold, &mut self.rng);
+            for (des, src) in izip!(&mut zero_shares, zero) {
+                des.push(src);
+            }
+        }
+        let received = self.network.send_and_recv_each_many(zero_shares)?;
+
+        let mut result = ShamirPrimeFieldShare::convert_slice(shares).to_vec();
+        for zero_shares in received {
+            if zero_shares.len() != len {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "During execution of reshare in MPC: Invalid number of elements received",
+                ));
+            }
+            for (res, zero) in izip!(&mut result, zero_shares) {
+                *res += zero;
+            }
+        }
+        Ok(ShamirPrimeFieldShare::convert_vec_rev(result))
+    }
+
     pub(crate) fn degree_reduce(&mut self, mut input: F) -> std::io::Result<ShamirShare<F>> {
         let num_non_zero = self.network.get_num_parties() - self.threshold;
 
@@ -341,7 +455,7 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
 
             let mut my_share = F::default();
             for id in 0..num_non_zero {
-                let val = core::evaluate_poly(&poly, F::from(id as u64 + 1));
+                let val = core::evaluate_poly(&poly, F::from(self.points[id] as u64));
                 if id == my_id {
                     my_share = val;
                 } else {
@@ -418,7 +532,7 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
 
             let mut my_share = Vec::new();
             for id in 0..num_non_zero {
-                let id_f = F::from(id as u64 + 1);
+                let id_f = F::from(self.points[id] as u64);
                 let vals = polys
                     .iter()
                     .map(|poly| core::evaluate_poly(poly, id_f))
@@ -494,7 +608,8 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
 
             let mut my_share = C::default();
             for id in 0..num_non_zero {
-                let val = core::evaluate_poly_point(&poly, C::ScalarField::from(id as u64 + 1));
+                let val =
+                    core::evaluate_poly_point(&poly, C::ScalarField::from(self.points[id] as u64));
                 if id == my_id {
                     my_share = val;
                 } else {
@@ -47,6 +47,24 @@ pub(crate) fn share<F: PrimeField, R: Rng>(
     shares
 }
 
+// evaluates a random sharing polynomial at the given (1-based) points
+pub(crate) fn share_at_points<F: PrimeField, R: Rng>(
+    secret: F,
+    points: &[usize],
+    degree: usize,
+    rng: &mut R,
+) -> Vec<F> {
+    let mut coeffs = Vec::with_capacity(degree + 1);
+    coeffs.push(secret);
+    for _ in 0..degree {
+        coeffs.push(F::rand(rng));
+    }
+    points
+        .iter()
+        .map(|&i| evaluate_poly(&coeffs, F::from(i as u64)))
+        .collect()
+}
+
 // sets the shares of parties in points to 0
 #[allow(unused)]
 pub(crate) fn share_with_zeros<F: PrimeField>(
@@ -308,6 +326,29 @@ mod shamir_test {
         }
     }
 
+    fn test_shamir_at_points<F: PrimeField, const DEGREE: usize>(points: &[usize]) {
+        let mut rng = ChaCha12Rng::from_entropy();
+
+        for _ in 0..TESTRUNS {
+            let secret = F::rand(&mut rng);
+            let shares = super::share_at_points(secret, points, DEGREE, &mut rng);
+
+            // Test random D+1 shares
+            let idx = (0..points.len()).choose_multiple(&mut rng, DEGREE + 1);
+            let shares = idx.iter().map(|&i| shares[i]).collect::<Vec<_>>();
+            let parties = idx.iter().map(|&i| points[i]).collect::<Vec<_>>();
+            let lagrange = super::lagrange_from_coeff(&parties);
+            let reconstructed = super::reconstruct(&shares, &lagrange);
+            assert_eq!(secret, reconstructed);
+        }
+    }
+
+    #[test]
+    fn test_shamir_non_contiguous_points() {
+        test_shamir_at_points::<ark_bn254::Fr, 1>(&[4, 8, 10]);
+        test_shamir_at_points::<ark_bn254::Fr, 2>(&[2, 3, 7, 11, 12]);
+    }
+
     fn test_shamir_point<C: CurveGroup, const NUM_PARTIES: usize, const DEGREE: usize>() {
         let mut rng = ChaCha12Rng::from_entropy();
 
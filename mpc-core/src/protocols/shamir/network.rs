@@ -6,9 +6,10 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle, config::NetworkConfig, rounds::RoundTracker, MpcNetworkHandler,
+    MpcNetworkHandlerWrapper,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 
 /// This trait defines the network interface for the Shamir protocol.
 pub trait ShamirNetwork: Send {
@@ -79,11 +80,20 @@ pub struct ShamirMpcNet {
     pub(crate) num_parties: usize,
     pub(crate) channels: HashMap<usize, ChannelHandle<Bytes, BytesMut>>,
     pub(crate) net_handler: Arc<MpcNetworkHandlerWrapper>,
+    pub(crate) rounds: RoundTracker,
 }
 
 impl ShamirMpcNet {
     /// Takes a [NetworkConfig] struct and constructs the network interface. The network needs to contain at least 3 parties and all ids need to be in the range of 0 <= id < num_parties.
     pub fn new(config: NetworkConfig) -> Result<Self, Report> {
+        Self::new_with_listener(config, |_| {})
+    }
+
+    /// Same as [`Self::new`], but calls `on_listening` once the other parties can connect to us, see [`MpcNetworkHandler::establish_with_listener`].
+    pub fn new_with_listener(
+        config: NetworkConfig,
+        on_listening: impl FnOnce(SocketAddr),
+    ) -> Result<Self, Report> {
         let num_parties = config.parties.len();
 
         if config.parties.len() <= 2 {
@@ -98,7 +108,8 @@ impl ShamirMpcNet {
             .enable_all()
             .build()?;
         let (net_handler, channels) = runtime.block_on(async {
-            let net_handler = MpcNetworkHandler::establish(config).await?;
+            let net_handler =
+                MpcNetworkHandler::establish_with_listener(config, on_listening).await?;
             let mut channels = net_handler.get_byte_channels().await?;
 
             let mut channels_ = HashMap::with_capacity(num_parties - 1);
@@ -123,6 +134,7 @@ impl ShamirMpcNet {
             num_parties,
             net_handler: Arc::new(MpcNetworkHandlerWrapper::new(runtime, net_handler)),
             channels,
+            rounds: RoundTracker::new(),
         })
     }
 
@@ -146,9 +158,14 @@ impl ShamirMpcNet {
 
     /// Sends bytes over the network to the target party.
     pub fn send_bytes(&mut self, target: usize, data: Bytes) -> std::io::Result<()> {
+        self.rounds.on_send();
+        let data = self.net_handler.inner.compress(data)?;
         if let Some(chan) = self.channels.get_mut(&target) {
-            std::mem::drop(chan.blocking_send(data));
-            Ok(())
+            // we do not wait for the write to finish, but report errors that are already known
+            match chan.blocking_send(data).try_recv() {
+                Ok(Err(err)) => Err(self.net_handler.inner.party_error(target, err)),
+                _ => Ok(()),
+            }
         } else {
             Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
@@ -159,6 +176,7 @@ impl ShamirMpcNet {
 
     /// Receives bytes over the network from the party with the given id.
     pub fn recv_bytes(&mut self, from: usize) -> std::io::Result<BytesMut> {
+        self.rounds.on_recv();
         let data = if let Some(chan) = self.channels.get_mut(&from) {
             chan.blocking_recv().blocking_recv()
         } else {
@@ -168,10 +186,19 @@ impl ShamirMpcNet {
             ));
         };
 
-        let data = data.map_err(|_| {
-            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
-        })??;
-        Ok(data)
+        let data = data
+            .map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
+            })
+            .and_then(|res| res)
+            .map_err(|err| match self.net_handler.inner.check_heartbeat() {
+                Ok(()) => self.net_handler.inner.party_error(from, err),
+                Err(unresponsive) => unresponsive,
+            })?;
+        self.net_handler
+            .inner
+            .decompress(data)
+            .map_err(|err| self.net_handler.inner.party_error(from, err))
     }
 
     pub(crate) fn _id(&self) -> usize {
@@ -307,6 +334,7 @@ impl ShamirNetwork for ShamirMpcNet {
             num_parties,
             net_handler,
             channels,
+            rounds: RoundTracker::new(),
         })
     }
 
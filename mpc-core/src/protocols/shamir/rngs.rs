@@ -13,6 +13,8 @@ pub(super) struct ShamirRng<F> {
     pub(super) rng: RngType,
     pub(super) threshold: usize,
     pub(super) num_parties: usize,
+    // the evaluation point of the shares of each party
+    points: Vec<usize>,
     precomputed_interpolation_r_t: Vec<Vec<F>>,
     precomputed_interpolation_r_2t: Vec<Vec<F>>,
     pub(super) shared_rngs: Vec<RngType>,
@@ -25,6 +27,7 @@ impl<F: PrimeField> ShamirRng<F> {
     pub fn new<N: ShamirNetwork>(
         seed: [u8; crate::SEED_SIZE],
         threshold: usize,
+        points: &[usize],
         network: &mut N,
     ) -> std::io::Result<Self> {
         let mut rng = RngType::from_seed(seed);
@@ -41,20 +44,20 @@ impl<F: PrimeField> ShamirRng<F> {
         let id = network.get_id();
         let mut ids = Vec::with_capacity(threshold + 1);
         for i in 1..=threshold + 1 {
-            let id_ = (id + i) % num_parties + 1;
-            ids.push(id_);
+            ids.push(points[(id + i) % num_parties]);
         }
         let precomputed_interpolation_r_t = super::core::precompute_interpolation_polys::<F>(&ids);
 
         // let p_r_t = Self::precompute_interpolation_polys(id, threshold + 1, num_parties);
         let precomputed_interpolation_r_2t =
-            Self::precompute_interpolation_polys(id, threshold * 2, num_parties);
+            Self::precompute_interpolation_polys(id, threshold * 2, points);
 
         Ok(Self {
             id,
             rng,
             threshold,
             num_parties,
+            points: points.to_vec(),
             shared_rngs,
             precomputed_interpolation_r_t,
             precomputed_interpolation_r_2t,
@@ -211,12 +214,12 @@ impl<F: PrimeField> ShamirRng<F> {
         }
     }
 
-    fn precompute_interpolation_polys(id: usize, degree: usize, num_parties: usize) -> Vec<Vec<F>> {
+    fn precompute_interpolation_polys(id: usize, degree: usize, points: &[usize]) -> Vec<Vec<F>> {
         let mut ids = Vec::with_capacity(degree + 1);
         ids.push(0); // my randomness acts as the secret
         for i in 1..=degree {
-            let rcv_id = (id + i) % num_parties;
-            ids.push(rcv_id + 1);
+            let rcv_id = (id + i) % points.len();
+            ids.push(points[rcv_id]);
         }
         super::core::precompute_interpolation_polys::<F>(&ids)
     }
@@ -257,7 +260,7 @@ impl<F: PrimeField> ShamirRng<F> {
     }
 
     fn set_my_share(&self, output: &mut [Vec<F>], polys: &[Vec<F>]) {
-        let id_f = F::from(self.id as u64 + 1);
+        let id_f = F::from(self.points[self.id] as u64);
         for (r, p) in output.iter_mut().zip(polys.iter()) {
             r[self.id] = super::core::evaluate_poly(p, id_f);
         }
@@ -276,7 +279,7 @@ impl<F: PrimeField> ShamirRng<F> {
         let mut to_send = vec![F::zero(); polys.len()]; // Allocate buffer only once
         for i in 1..=sending {
             let rcv_id = (self.id + i + seeded) % self.num_parties;
-            let rcv_id_f = F::from(rcv_id as u64 + 1);
+            let rcv_id_f = F::from(self.points[rcv_id] as u64);
             for (des, p) in to_send.iter_mut().zip(polys.iter()) {
                 *des = super::core::evaluate_poly(p, rcv_id_f);
             }
@@ -347,7 +350,7 @@ impl<F: PrimeField> ShamirRng<F> {
         // Set my rand on the polynomial and calculate the share
         let mut rands = Vec::with_capacity(amount);
         for (r, p) in rcv_t.iter_mut().zip(polys_t.iter()) {
-            r[self.id] = super::core::evaluate_poly(p, F::from(self.id as u64 + 1));
+            r[self.id] = super::core::evaluate_poly(p, F::from(self.points[self.id] as u64));
             rands.push(p[0]);
         }
 
@@ -0,0 +1,36 @@
+use std::net::IpAddr;
+
+use clap::Parser;
+use color_eyre::Result;
+
+/// Relay for MPC-NET parties that cannot reach each other directly
+#[derive(Debug, PartialEq, Parser)]
+struct CliArgs {
+    /// The IP address to listen on
+    #[clap(long, default_value = "0.0.0.0")]
+    ip: IpAddr,
+    /// The port of party 0, party i is reached at port + i
+    #[clap(short, long)]
+    port: u16,
+    /// The number of parties
+    #[clap(short, long, default_value_t = 3)]
+    num_parties: usize,
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+    let args = CliArgs::parse();
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(mpc_net::relay::run_relay(
+        args.ip,
+        args.port,
+        args.num_parties,
+    ))
+}
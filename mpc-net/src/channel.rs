@@ -1,12 +1,14 @@
 //! A channel abstraction for sending and receiving messages.
 use futures::{Sink, SinkExt, Stream, StreamExt};
-use std::{io, marker::Unpin, pin::Pin};
+use std::{io, marker::Unpin, pin::Pin, sync::Arc};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     sync::{mpsc, oneshot},
 };
 use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite, LengthDelimitedCodec};
 
+use crate::framing::{FrameLen, FramingCounters, FramingStats};
+
 /// A read end of the channel, just a type alias for [`FramedRead`].
 pub type ReadChannel<T, D> = FramedRead<T, D>;
 /// A write end of the channel, just a type alias for [`FramedWrite`].
@@ -135,6 +137,7 @@ where
 
 struct WriteJob<MSend> {
     data: MSend,
+    len: usize,
     ret: oneshot::Sender<Result<(), io::Error>>,
 }
 
@@ -147,12 +150,13 @@ struct ReadJob<MRecv> {
 pub struct ChannelHandle<MSend, MRecv> {
     write_job_queue: mpsc::Sender<WriteJob<MSend>>,
     read_job_queue: mpsc::Sender<ReadJob<MRecv>>,
+    framing: Arc<FramingCounters>,
 }
 
 impl<MSend, MRecv> ChannelHandle<MSend, MRecv>
 where
     MRecv: Send + std::fmt::Debug + 'static,
-    MSend: Send + std::fmt::Debug + 'static,
+    MSend: FrameLen + Send + std::fmt::Debug + 'static,
 {
     /// Create a new [`ChannelHandle`] from a [`Channel`]. This spawns a new tokio task that handles the read and write jobs so they can happen concurrently.
     pub fn manage<R, W, C>(chan: Channel<R, W, C>) -> ChannelHandle<MSend, MRecv>
@@ -167,12 +171,15 @@ where
         let (read_send, mut read_recv) = mpsc::channel::<ReadJob<MRecv>>(1024);
 
         let (mut write, mut read) = chan.split();
+        let framing = Arc::new(FramingCounters::default());
 
+        let read_framing = Arc::clone(&framing);
         tokio::spawn(async move {
             while let Some(frame) = read.next().await {
                 let job = read_recv.recv().await;
                 match job {
                     Some(job) => {
+                        read_framing.on_received();
                         if job.ret.send(frame).is_err() {
                             tracing::warn!("Warning: Read Job finished but receiver is gone!");
                         }
@@ -186,9 +193,11 @@ where
                 }
             }
         });
+        let write_framing = Arc::clone(&framing);
         tokio::spawn(async move {
             while let Some(write_job) = write_recv.recv().await {
                 let write_result = write.send(write_job.data).await;
+                write_framing.on_sent(write_job.len);
                 // we don't really care if the receiver for a write job is gone, as this is a common case
                 // therefore we only emit a trace message
                 match write_job.ret.send(write_result) {
@@ -203,23 +212,33 @@ where
         ChannelHandle {
             write_job_queue: write_send,
             read_job_queue: read_send,
+            framing,
         }
     }
 
+    /// Returns the messages on this channel that were not yet written or received, see [`crate::framing`].
+    pub fn framing_stats(&self) -> FramingStats {
+        self.framing.stats()
+    }
+
     /// Instructs the channel to send a message. Returns a [oneshot::Receiver] that will return the result of the send operation.
     pub async fn send(&mut self, data: MSend) -> oneshot::Receiver<Result<(), io::Error>> {
         let (ret, recv) = oneshot::channel();
-        let job = WriteJob { data, ret };
+        let len = data.frame_len();
+        self.framing.on_send(len);
+        let job = WriteJob { data, len, ret };
         match self.write_job_queue.send(job).await {
             Ok(_) => {}
-            Err(job) => job
-                .0
-                .ret
-                .send(Err(io::Error::new(
-                    io::ErrorKind::BrokenPipe,
-                    "ChannelHandle: send Channel is gone",
-                )))
-                .unwrap(),
+            Err(job) => {
+                self.framing.on_sent(len);
+                job.0
+                    .ret
+                    .send(Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "ChannelHandle: send Channel is gone",
+                    )))
+                    .unwrap()
+            }
         }
         recv
     }
@@ -227,17 +246,20 @@ where
     /// Instructs the channel to receive a message. Returns a [oneshot::Receiver] that will return the result of the receive operation.
     pub async fn recv(&mut self) -> oneshot::Receiver<Result<MRecv, io::Error>> {
         let (ret, recv) = oneshot::channel();
+        self.framing.on_recv();
         let job = ReadJob { ret };
         match self.read_job_queue.send(job).await {
             Ok(_) => {}
-            Err(job) => job
-                .0
-                .ret
-                .send(Err(io::Error::new(
-                    io::ErrorKind::BrokenPipe,
-                    "ChannelHandle: recv Channel is gone",
-                )))
-                .unwrap(),
+            Err(job) => {
+                self.framing.on_received();
+                job.0
+                    .ret
+                    .send(Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "ChannelHandle: recv Channel is gone",
+                    )))
+                    .unwrap()
+            }
         }
         recv
     }
@@ -245,17 +267,21 @@ where
     /// A blocking version of [ChannelHandle::send]. This will block until the send operation is complete.
     pub fn blocking_send(&mut self, data: MSend) -> oneshot::Receiver<Result<(), io::Error>> {
         let (ret, recv) = oneshot::channel();
-        let job = WriteJob { data, ret };
+        let len = data.frame_len();
+        self.framing.on_send(len);
+        let job = WriteJob { data, len, ret };
         match self.write_job_queue.blocking_send(job) {
             Ok(_) => {}
-            Err(job) => job
-                .0
-                .ret
-                .send(Err(io::Error::new(
-                    io::ErrorKind::BrokenPipe,
-                    "ChannelHandle: send Channel is gone",
-                )))
-                .unwrap(),
+            Err(job) => {
+                self.framing.on_sent(len);
+                job.0
+                    .ret
+                    .send(Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "ChannelHandle: send Channel is gone",
+                    )))
+                    .unwrap()
+            }
         }
         recv
     }
@@ -263,17 +289,20 @@ where
     /// A blocking version of [ChannelHandle::recv]. This will block until the receive operation is complete.
     pub fn blocking_recv(&mut self) -> oneshot::Receiver<Result<MRecv, io::Error>> {
         let (ret, recv) = oneshot::channel();
+        self.framing.on_recv();
         let job = ReadJob { ret };
         match self.read_job_queue.blocking_send(job) {
             Ok(_) => {}
-            Err(job) => job
-                .0
-                .ret
-                .send(Err(io::Error::new(
-                    io::ErrorKind::BrokenPipe,
-                    "ChannelHandle: recv Channel is gone",
-                )))
-                .unwrap(),
+            Err(job) => {
+                self.framing.on_received();
+                job.0
+                    .ret
+                    .send(Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "ChannelHandle: recv Channel is gone",
+                    )))
+                    .unwrap()
+            }
         }
         recv
     }
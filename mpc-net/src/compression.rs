@@ -0,0 +1,115 @@
+//! Compression of the messages sent over byte channels.
+//!
+//! The compression is configured via [`NetworkConfig::compression`](crate::config::NetworkConfig::compression) and is applied by the network implementations of the MPC protocols, which call [`MpcNetworkHandler::compress`](crate::MpcNetworkHandler::compress) and [`MpcNetworkHandler::decompress`](crate::MpcNetworkHandler::decompress) for every message.
+
+use std::{
+    io::{self, Read},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{config::Compression, MAX_FRAME_LENGTH};
+
+/// The zstd compression level, we prefer speed over ratio since compression happens on the critical path of the protocol.
+const ZSTD_LEVEL: i32 = 1;
+
+/// The number of message bytes before and after compression.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// The number of bytes handed to the network before compression.
+    pub raw_sent: u64,
+    /// The number of bytes actually sent after compression.
+    pub compressed_sent: u64,
+    /// The number of bytes actually received before decompression.
+    pub compressed_recv: u64,
+    /// The number of bytes handed to the protocol after decompression.
+    pub raw_recv: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Compressor {
+    compression: Compression,
+    raw_sent: AtomicU64,
+    compressed_sent: AtomicU64,
+    compressed_recv: AtomicU64,
+    raw_recv: AtomicU64,
+}
+
+impl Compressor {
+    pub(crate) fn new(compression: Compression) -> Self {
+        Self {
+            compression,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub(crate) fn compress(&self, data: Bytes) -> io::Result<Bytes> {
+        let compressed = match self.compression {
+            Compression::None => data.clone(),
+            Compression::Zstd => Bytes::from(zstd::bulk::compress(&data, ZSTD_LEVEL)?),
+        };
+        self.raw_sent
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        self.compressed_sent
+            .fetch_add(compressed.len() as u64, Ordering::Relaxed);
+        Ok(compressed)
+    }
+
+    pub(crate) fn decompress(&self, data: BytesMut) -> io::Result<BytesMut> {
+        let compressed_len = data.len();
+        let raw = match self.compression {
+            Compression::None => data,
+            Compression::Zstd => BytesMut::from(&decompress_zstd(&data, MAX_FRAME_LENGTH)?[..]),
+        };
+        self.compressed_recv
+            .fetch_add(compressed_len as u64, Ordering::Relaxed);
+        self.raw_recv.fetch_add(raw.len() as u64, Ordering::Relaxed);
+        Ok(raw)
+    }
+
+    pub(crate) fn stats(&self) -> CompressionStats {
+        CompressionStats {
+            raw_sent: self.raw_sent.load(Ordering::Relaxed),
+            compressed_sent: self.compressed_sent.load(Ordering::Relaxed),
+            compressed_recv: self.compressed_recv.load(Ordering::Relaxed),
+            raw_recv: self.raw_recv.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Decompresses `data`, but stops with an error once more than `max_len` bytes were decompressed.
+///
+/// A received frame is at most [MAX_FRAME_LENGTH] bytes long, but could decompress to an arbitrary amount of memory. As the other party could not have sent a larger message without compression, we use the same bound for the decompressed message.
+fn decompress_zstd(data: &[u8], max_len: usize) -> io::Result<Vec<u8>> {
+    let mut raw = Vec::new();
+    zstd::stream::read::Decoder::new(data)?
+        .take(max_len as u64 + 1)
+        .read_to_end(&mut raw)?;
+    if raw.len() > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decompressed message exceeds {max_len} bytes"),
+        ));
+    }
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompression_is_bounded() {
+        let data = vec![7u8; 1000];
+        let compressed = zstd::bulk::compress(&data, ZSTD_LEVEL).unwrap();
+        assert_eq!(decompress_zstd(&compressed, 1000).unwrap(), data);
+        let err = decompress_zstd(&compressed, 999).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "decompressed message exceeds 999 bytes");
+    }
+}
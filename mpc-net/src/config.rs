@@ -35,25 +35,34 @@ impl Serialize for Address {
     }
 }
 
-impl<'de> Deserialize<'de> for Address {
-    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let s = String::deserialize(deserializer)?;
+impl std::str::FromStr for Address {
+    type Err = eyre::Report;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.split(':').collect();
         if parts.len() != 2 {
-            return Err(serde::de::Error::custom("invalid address format"));
+            return Err(eyre::eyre!("invalid address format"));
         }
         let hostname = parts[0].to_string();
-        let port = parts[1].parse().map_err(serde::de::Error::custom)?;
+        let port = parts[1].parse()?;
         Ok(Address { hostname, port })
     }
 }
 
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// A party in the network.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct NetworkParty {
     /// The id of the party, 0-based indexing.
     pub id: usize,
-    /// The DNS name of the party.
+    /// The address the other parties use to connect to this party, will be DNS resolved. Can also be given as `connect_addr`.
+    /// This is the advertised address of the party, which may differ from the address the party binds to (see [NetworkConfig::bind_addr]), e.g., behind NAT.
+    #[serde(alias = "connect_addr")]
     pub dns_name: Address,
     /// The path to the public certificate of the party.
     pub cert_path: PathBuf,
@@ -66,12 +75,89 @@ pub struct NetworkConfig {
     pub parties: Vec<NetworkParty>,
     /// Our own id in the network.
     pub my_id: usize,
-    /// The [SocketAddr] we bind to.
+    /// The local [SocketAddr] we listen on. This is independent of the address the other parties connect to (our entry in `parties`),
+    /// so it can be a specific interface of a multi-homed host or a private address behind NAT.
+    /// If the IP is not unspecified (e.g., `0.0.0.0`), outgoing connections are made from this interface as well.
     pub bind_addr: SocketAddr,
     /// The path to our private key file.
     pub key_path: PathBuf,
+    /// If set, each party sends a heartbeat to every other party every `heartbeat_interval_secs` seconds.
+    /// A party from which no heartbeat was received for [`HEARTBEAT_TIMEOUT_FACTOR`] intervals is considered unresponsive and the run is aborted.
+    /// All parties have to use the same interval, which is checked when the connections are established.
+    /// Disabled by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_secs: Option<u64>,
+    /// If set, establishing the connection to a party is aborted after `connect_timeout_secs` seconds, naming the parties that did not connect.
+    /// Without a timeout, we wait for the other parties indefinitely.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// If set, a party that waits for a message for `stall_log_secs` seconds logs the messages in flight on its connections to all other parties, e.g., "party 1: waiting to receive, 0 bytes in flight", and repeats this every `stall_log_secs` seconds until the message arrives.
+    /// Disabled by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_log_secs: Option<u64>,
+    /// The compression of the messages sent over the byte channels of the MPC protocols. All parties have to use the same compression, which is checked when the connections are established.
+    /// Defaults to no compression.
+    #[serde(default)]
+    pub compression: Compression,
+    /// If set, the parties do not connect to each other directly, but through the relay at this address (see [`crate::relay`]), which forwards the encrypted datagrams between them.
+    /// Party `i` is reached at port `relay.port + i` of the relay. All parties have to use the same relay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relay: Option<Address>,
+}
+
+/// The compression applied to the messages of the MPC protocols.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Messages are sent as they are.
+    #[default]
+    None,
+    /// Messages are compressed with zstd.
+    Zstd,
 }
 
+impl Compression {
+    pub(crate) fn tag(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Zstd => 1,
+        }
+    }
+
+    pub(crate) fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::None => write!(f, "none"),
+            Compression::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+/// The number of heartbeat intervals after which a silent party is considered unresponsive.
+pub const HEARTBEAT_TIMEOUT_FACTOR: u32 = 3;
+
 impl NetworkConfig {
     /// Basic sanity checks for the configuration.
     pub fn check_config(&self) -> eyre::Result<()> {
@@ -94,6 +180,22 @@ impl NetworkConfig {
         if ids.len() != self.parties.len() {
             return Err(eyre::eyre!("duplicate party ids found"));
         }
+        // 3. check that the heartbeat interval is not zero
+        if self.heartbeat_interval_secs == Some(0) {
+            return Err(eyre::eyre!(
+                "heartbeat interval must be at least one second"
+            ));
+        }
+        // 4. check that the connect timeout is not zero
+        if self.connect_timeout_secs == Some(0) {
+            return Err(eyre::eyre!("connect timeout must be at least one second"));
+        }
+        // 5. check that the stall log interval is not zero
+        if self.stall_log_secs == Some(0) {
+            return Err(eyre::eyre!(
+                "stall log interval must be at least one second"
+            ));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,227 @@
+//! Message framing statistics of managed channels.
+//!
+//! When a protocol stalls, it is usually not clear whether a party waits for its own messages to be written or for the messages of a peer. Every [`ChannelHandle`](crate::channel::ChannelHandle) therefore counts the messages that were handed to it but not yet written, and the receives that were requested but not yet answered. A snapshot of these counters is a [`FramingStats`], whose [`Display`](std::fmt::Display) implementation gives a short diagnosis like "waiting to receive, 0 bytes in flight".
+
+use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
+
+use bytes::{Bytes, BytesMut};
+
+/// Messages whose size is reported in the [`FramingStats`] of a [`ChannelHandle`](crate::channel::ChannelHandle).
+pub trait FrameLen {
+    /// The number of bytes of the message.
+    fn frame_len(&self) -> usize;
+}
+
+impl FrameLen for Bytes {
+    fn frame_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl FrameLen for BytesMut {
+    fn frame_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl FrameLen for Vec<u8> {
+    fn frame_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The direction of a message on a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// A message was handed to the channel to be sent.
+    Send,
+    /// A message was requested from the channel.
+    Recv,
+}
+
+/// A snapshot of the messages on a channel that were not yet handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FramingStats {
+    /// The number of messages handed to the channel that were not yet written.
+    pub sends_in_flight: usize,
+    /// The number of bytes of the messages in [`Self::sends_in_flight`].
+    pub bytes_in_flight: u64,
+    /// The number of requested messages that were not yet received.
+    pub recvs_pending: usize,
+    /// The direction of the last message the channel was asked to handle, if any.
+    pub last_direction: Option<Direction>,
+}
+
+impl std::fmt::Display for FramingStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // a pending receive blocks the protocol, pending sends are only written in the background
+        if self.recvs_pending > 0 {
+            write!(f, "waiting to receive")?;
+        } else if self.sends_in_flight > 0 {
+            write!(f, "waiting to send")?;
+        } else {
+            match self.last_direction {
+                Some(Direction::Send) => write!(f, "idle after sending")?,
+                Some(Direction::Recv) => write!(f, "idle after receiving")?,
+                None => write!(f, "idle")?,
+            }
+        }
+        write!(f, ", {} bytes in flight", self.bytes_in_flight)
+    }
+}
+
+const NO_DIRECTION: u8 = 0;
+const SEND: u8 = 1;
+const RECV: u8 = 2;
+
+/// The counters behind [`FramingStats`], shared between a channel handle and the tasks that serve it.
+#[derive(Debug, Default)]
+pub(crate) struct FramingCounters {
+    sends_in_flight: AtomicUsize,
+    bytes_in_flight: AtomicU64,
+    recvs_pending: AtomicUsize,
+    last_direction: AtomicU8,
+}
+
+impl FramingCounters {
+    pub(crate) fn on_send(&self, len: usize) {
+        self.sends_in_flight.fetch_add(1, Ordering::Relaxed);
+        self.bytes_in_flight
+            .fetch_add(len as u64, Ordering::Relaxed);
+        self.last_direction.store(SEND, Ordering::Relaxed);
+    }
+
+    pub(crate) fn on_sent(&self, len: usize) {
+        self.sends_in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.bytes_in_flight
+            .fetch_sub(len as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn on_recv(&self) {
+        self.recvs_pending.fetch_add(1, Ordering::Relaxed);
+        self.last_direction.store(RECV, Ordering::Relaxed);
+    }
+
+    pub(crate) fn on_received(&self) {
+        self.recvs_pending.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> FramingStats {
+        FramingStats {
+            sends_in_flight: self.sends_in_flight.load(Ordering::Relaxed),
+            bytes_in_flight: self.bytes_in_flight.load(Ordering::Relaxed),
+            recvs_pending: self.recvs_pending.load(Ordering::Relaxed),
+            last_direction: match self.last_direction.load(Ordering::Relaxed) {
+                SEND => Some(Direction::Send),
+                RECV => Some(Direction::Recv),
+                NO_DIRECTION => None,
+                _ => unreachable!("only valid directions are stored"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use tokio_util::codec::LengthDelimitedCodec;
+
+    use super::*;
+    use crate::channel::{Channel, ChannelHandle};
+
+    #[test]
+    fn counters_balance() {
+        let counters = FramingCounters::default();
+        assert_eq!(counters.stats(), FramingStats::default());
+
+        counters.on_send(10);
+        counters.on_send(5);
+        counters.on_recv();
+        assert_eq!(
+            counters.stats(),
+            FramingStats {
+                sends_in_flight: 2,
+                bytes_in_flight: 15,
+                recvs_pending: 1,
+                last_direction: Some(Direction::Recv),
+            }
+        );
+
+        counters.on_sent(10);
+        counters.on_received();
+        counters.on_sent(5);
+        assert_eq!(
+            counters.stats(),
+            FramingStats {
+                last_direction: Some(Direction::Recv),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn display_diagnosis() {
+        let stats = |sends_in_flight, bytes_in_flight, recvs_pending, last_direction| {
+            FramingStats {
+                sends_in_flight,
+                bytes_in_flight,
+                recvs_pending,
+                last_direction,
+            }
+            .to_string()
+        };
+        assert_eq!(stats(0, 0, 0, None), "idle, 0 bytes in flight");
+        assert_eq!(
+            stats(0, 0, 0, Some(Direction::Send)),
+            "idle after sending, 0 bytes in flight"
+        );
+        assert_eq!(
+            stats(0, 0, 0, Some(Direction::Recv)),
+            "idle after receiving, 0 bytes in flight"
+        );
+        assert_eq!(
+            stats(2, 64, 0, Some(Direction::Send)),
+            "waiting to send, 64 bytes in flight"
+        );
+        // a pending receive is reported before pending sends
+        assert_eq!(
+            stats(2, 64, 1, Some(Direction::Send)),
+            "waiting to receive, 64 bytes in flight"
+        );
+    }
+
+    #[test]
+    fn closed_channel_does_not_leave_messages_in_flight() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (ours, _theirs) = tokio::io::duplex(64);
+        let (read, write) = tokio::io::split(ours);
+        let mut handle = {
+            let _guard = runtime.enter();
+            ChannelHandle::<Bytes, BytesMut>::manage(Channel::new::<Bytes>(
+                read,
+                write,
+                LengthDelimitedCodec::new(),
+            ))
+        };
+        // shutting down the runtime drops the tasks that serve the channel
+        drop(runtime);
+
+        let sent = handle
+            .blocking_send(Bytes::from_static(b"message"))
+            .blocking_recv()
+            .unwrap();
+        assert_eq!(sent.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(
+            handle.framing_stats().to_string(),
+            "idle after sending, 0 bytes in flight"
+        );
+
+        let received = handle.blocking_recv().blocking_recv().unwrap();
+        assert_eq!(received.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(
+            handle.framing_stats().to_string(),
+            "idle after receiving, 0 bytes in flight"
+        );
+    }
+}
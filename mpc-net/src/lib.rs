@@ -4,14 +4,19 @@ use std::{
     collections::{BTreeMap, HashMap},
     io,
     net::{SocketAddr, ToSocketAddrs},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     time::Duration,
 };
 
+use bytes::{Bytes, BytesMut};
 use channel::{BytesChannel, Channel};
 use codecs::BincodeCodec;
 use color_eyre::eyre::{self, Context, Report};
-use config::NetworkConfig;
+use compression::{CompressionStats, Compressor};
+use config::{Compression, NetworkConfig, HEARTBEAT_TIMEOUT_FACTOR};
 use quinn::{
     crypto::rustls::QuicClientConfig,
     rustls::{
@@ -20,8 +25,8 @@ use quinn::{
     },
 };
 use quinn::{
-    ClientConfig, Connection, Endpoint, IdleTimeout, RecvStream, SendStream, TransportConfig,
-    VarInt,
+    ClientConfig, Connection, Endpoint, EndpointConfig, IdleTimeout, RecvStream, SendStream,
+    TokioRuntime, TransportConfig, VarInt,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{
@@ -32,7 +37,14 @@ use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
 
 pub mod channel;
 pub mod codecs;
+pub mod compression;
 pub mod config;
+pub mod framing;
+pub mod relay;
+pub mod rounds;
+
+/// The maximum length of a message sent over a [BytesChannel] in bytes.
+pub(crate) const MAX_FRAME_LENGTH: usize = 1_000_000_000;
 
 /// A warapper for a runtime and a network handler for MPC protocols.
 /// Ensures a gracefull shutdown on drop
@@ -65,11 +77,47 @@ pub struct MpcNetworkHandler {
     connections: BTreeMap<usize, Connection>,
     endpoints: Vec<Endpoint>,
     my_id: usize,
+    heartbeat: Arc<HeartbeatState>,
+    compressor: Compressor,
+    stall_log_interval: Option<Duration>,
+}
+
+/// Awaits `fut`, but gives up after `timeout` (if any) with an error naming the party we waited for.
+async fn with_connect_timeout<T>(
+    timeout: Option<Duration>,
+    fut: impl std::future::Future<Output = T>,
+    waiting_for: &str,
+) -> Result<T, Report> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, fut).await.map_err(|_| {
+            eyre::eyre!(
+                "timed out after {}s waiting for {} to connect",
+                timeout.as_secs(),
+                waiting_for
+            )
+        }),
+        None => Ok(fut.await),
+    }
+}
+
+/// Shared state between the network handler and its heartbeat tasks.
+#[derive(Debug, Default)]
+struct HeartbeatState {
+    closing: AtomicBool,
+    unresponsive: Mutex<Option<usize>>,
 }
 
 impl MpcNetworkHandler {
     /// Tries to establish a connection to other parties in the network based on the provided [NetworkConfig].
     pub async fn establish(config: NetworkConfig) -> Result<Self, Report> {
+        Self::establish_with_listener(config, |_| {}).await
+    }
+
+    /// Same as [`Self::establish`], but calls `on_listening` with our local address as soon as we accept connections from the other parties, i.e., before we wait for them.
+    pub async fn establish_with_listener(
+        config: NetworkConfig,
+        on_listening: impl FnOnce(SocketAddr),
+    ) -> Result<Self, Report> {
         config.check_config()?;
         let certs: HashMap<usize, CertificateDer> = config
             .parties
@@ -113,42 +161,104 @@ impl MpcNetworkHandler {
         let our_socket_addr = config.bind_addr;
 
         let mut endpoints = Vec::new();
-        let server_endpoint = quinn::Endpoint::server(server_config.clone(), our_socket_addr)?;
+        let relay = match &config.relay {
+            Some(relay) => Some(
+                relay
+                    .to_socket_addrs()
+                    .with_context(|| format!("while resolving DNS name for relay {relay}"))?
+                    .next()
+                    .ok_or_else(|| eyre::eyre!("could not resolve DNS name {relay}"))?,
+            ),
+            None => None,
+        };
+        let server_endpoint = match relay {
+            // through the relay, we reach the other parties with our server endpoint, whose address we register at the relay
+            Some(relay) => {
+                let socket = tokio::net::UdpSocket::bind(our_socket_addr)
+                    .await
+                    .context("binding our socket")?;
+                relay::register(&socket, relay, config.my_id).await?;
+                quinn::Endpoint::new(
+                    EndpointConfig::default(),
+                    Some(server_config.clone()),
+                    socket.into_std()?,
+                    Arc::new(TokioRuntime),
+                )?
+            }
+            None => quinn::Endpoint::server(server_config.clone(), our_socket_addr)?,
+        };
+        on_listening(server_endpoint.local_addr()?);
 
         let mut connections = BTreeMap::new();
+        let connect_timeout = config.connect_timeout_secs.map(Duration::from_secs);
 
-        for party in config.parties {
+        for party in config.parties.iter() {
             if party.id == config.my_id {
                 // skip self
                 continue;
             }
             if party.id < config.my_id {
                 // connect to party, we are client
+                tracing::info!("connecting to party {} ({})", party.id, party.dns_name);
 
-                let party_addresses: Vec<SocketAddr> = party
-                    .dns_name
-                    .to_socket_addrs()
-                    .with_context(|| format!("while resolving DNS name for {}", party.dns_name))?
-                    .collect();
-                if party_addresses.is_empty() {
-                    return Err(eyre::eyre!("could not resolve DNS name {}", party.dns_name));
-                }
-                let party_addr = party_addresses[0];
-                let local_client_socket: SocketAddr = match party_addr {
-                    SocketAddr::V4(_) => {
-                        "0.0.0.0:0".parse().expect("hardcoded IP address is valid")
+                let (party_addr, endpoint) = match relay {
+                    Some(relay) => (relay::party_addr(relay, party.id)?, server_endpoint.clone()),
+                    None => {
+                        let party_addresses: Vec<SocketAddr> = party
+                            .dns_name
+                            .to_socket_addrs()
+                            .with_context(|| {
+                                format!("while resolving DNS name for {}", party.dns_name)
+                            })?
+                            .collect();
+                        if party_addresses.is_empty() {
+                            return Err(eyre::eyre!(
+                                "could not resolve DNS name {}",
+                                party.dns_name
+                            ));
+                        }
+                        let party_addr = party_addresses[0];
+                        let local_client_socket: SocketAddr = match party_addr {
+                            // use the interface we bind to, if one is given explicitly
+                            _ if !our_socket_addr.ip().is_unspecified()
+                                && our_socket_addr.is_ipv4() == party_addr.is_ipv4() =>
+                            {
+                                SocketAddr::new(our_socket_addr.ip(), 0)
+                            }
+                            SocketAddr::V4(_) => {
+                                "0.0.0.0:0".parse().expect("hardcoded IP address is valid")
+                            }
+                            SocketAddr::V6(_) => {
+                                "[::]:0".parse().expect("hardcoded IP address is valid")
+                            }
+                        };
+                        let endpoint =
+                            quinn::Endpoint::client(local_client_socket).with_context(|| {
+                                format!("creating client endpoint to party {}", party.id)
+                            })?;
+                        (party_addr, endpoint)
                     }
-                    SocketAddr::V6(_) => "[::]:0".parse().expect("hardcoded IP address is valid"),
                 };
-                let endpoint = quinn::Endpoint::client(local_client_socket)
-                    .with_context(|| format!("creating client endpoint to party {}", party.id))?;
-                let conn = endpoint
+                let connecting = endpoint
                     .connect_with(client_config.clone(), party_addr, &party.dns_name.hostname)
                     .with_context(|| {
-                        format!("setting up client connection with party {}", party.id)
-                    })?
-                    .await
-                    .with_context(|| format!("connecting as a client to party {}", party.id))?;
+                        format!(
+                            "setting up client connection with party {} ({})",
+                            party.id, party.dns_name
+                        )
+                    })?;
+                let conn = with_connect_timeout(
+                    connect_timeout,
+                    connecting,
+                    &format!("party {} ({})", party.id, party.dns_name),
+                )
+                .await?
+                .with_context(|| {
+                    format!(
+                        "connecting as a client to party {} ({})",
+                        party.id, party.dns_name
+                    )
+                })?;
                 let mut uni = conn.open_uni().await?;
                 uni.write_u32(u32::try_from(config.my_id).expect("party id fits into u32"))
                     .await?;
@@ -161,42 +271,174 @@ impl MpcNetworkHandler {
                     conn.remote_address(),
                 );
                 assert!(connections.insert(party.id, conn).is_none());
-                endpoints.push(endpoint);
+                if relay.is_none() {
+                    endpoints.push(endpoint);
+                }
+                tracing::info!("connected to party {}", party.id);
             } else {
                 // we are the server, accept a connection
-                if let Some(maybe_conn) = server_endpoint.accept().await {
-                    let conn = maybe_conn.await?;
+                // the parties with a higher id connect to us in any order, so we can only say which ones are still missing
+                let waiting_for = config
+                    .parties
+                    .iter()
+                    .filter(|p| p.id > config.my_id && !connections.contains_key(&p.id))
+                    .map(|p| p.id.to_string())
+                    .collect::<Vec<_>>();
+                let waiting_for = match waiting_for.as_slice() {
+                    [id] => format!("party {id}"),
+                    ids => format!("parties {}", ids.join(", ")),
+                };
+                tracing::info!("waiting for {waiting_for} to connect");
+                let accepted =
+                    with_connect_timeout(connect_timeout, server_endpoint.accept(), &waiting_for)
+                        .await?;
+                if let Some(maybe_conn) = accepted {
+                    // until the peer sent its id, we only know its address
+                    let remote = maybe_conn.remote_address();
+                    let conn = maybe_conn.await.with_context(|| {
+                        format!(
+                            "accepting a connection from {remote} while waiting for {waiting_for}"
+                        )
+                    })?;
                     tracing::trace!(
                         "Conn with id {} from {} to {}",
                         conn.stable_id(),
                         server_endpoint.local_addr().unwrap(),
                         conn.remote_address(),
                     );
-                    let mut uni = conn.accept_uni().await?;
-                    let other_party_id = uni.read_u32().await?;
-                    assert!(connections
-                        .insert(
-                            usize::try_from(other_party_id).expect("u32 fits into usize"),
-                            conn
-                        )
-                        .is_none());
+                    let mut uni = conn.accept_uni().await.with_context(|| {
+                        format!("waiting for the party id of the connection from {remote}")
+                    })?;
+                    let other_party_id = uni.read_u32().await.with_context(|| {
+                        format!("reading the party id of the connection from {remote}")
+                    })?;
+                    let other_party_id =
+                        usize::try_from(other_party_id).expect("u32 fits into usize");
+                    assert!(connections.insert(other_party_id, conn).is_none());
+                    tracing::info!("connected to party {other_party_id}");
                 } else {
                     return Err(eyre::eyre!(
-                        "server endpoint did not accept a connection from party {}",
-                        party.id
+                        "server endpoint closed while waiting for {waiting_for} to connect"
                     ));
                 }
             }
         }
         endpoints.push(server_endpoint);
 
+        // all parties have to agree on the compression and the heartbeat, so we exchange them on a dedicated stream
+        for (&id, conn) in connections.iter() {
+            let (mut send, mut recv) = if id < config.my_id {
+                conn.open_bi().await?
+            } else {
+                conn.accept_bi().await?
+            };
+            send.write_u8(config.compression.tag()).await?;
+            // a heartbeat interval of zero is rejected by the config check, so it encodes no heartbeat
+            send.write_u64(config.heartbeat_interval_secs.unwrap_or(0))
+                .await?;
+            let their_tag = recv.read_u8().await?;
+            let their_heartbeat = Some(recv.read_u64().await?).filter(|secs| *secs != 0);
+            send.finish()?;
+            let mismatch = match Compression::from_tag(their_tag) {
+                None => Some(eyre::eyre!("party {id} uses an unknown compression")),
+                Some(theirs) if theirs != config.compression => Some(eyre::eyre!(
+                    "party {id} uses compression {theirs}, but we use {}",
+                    config.compression
+                )),
+                _ if their_heartbeat != config.heartbeat_interval_secs => Some(eyre::eyre!(
+                    "party {id} uses {}, but we use {}",
+                    describe_heartbeat(their_heartbeat),
+                    describe_heartbeat(config.heartbeat_interval_secs)
+                )),
+                _ => None,
+            };
+            if let Some(mismatch) = mismatch {
+                // wait until the other party received our settings, so that it can report the mismatch as well
+                let _ = send.stopped().await;
+                return Err(mismatch);
+            }
+        }
+
+        let heartbeat = Arc::new(HeartbeatState::default());
+        if let Some(interval) = config.heartbeat_interval_secs {
+            // the heartbeats use their own stream, so they never interleave with protocol messages
+            let interval = Duration::from_secs(interval);
+            for (&id, conn) in connections.iter() {
+                let (mut send, recv) = if id < config.my_id {
+                    conn.open_bi().await?
+                } else {
+                    conn.accept_bi().await?
+                };
+                // the stream is only announced to the other party once we write to it
+                send.write_u8(HEARTBEAT).await?;
+                tokio::spawn(run_heartbeat(
+                    id,
+                    conn.clone(),
+                    send,
+                    recv,
+                    interval,
+                    Arc::clone(&heartbeat),
+                ));
+            }
+        }
+
         Ok(MpcNetworkHandler {
             connections,
             endpoints,
             my_id: config.my_id,
+            heartbeat,
+            compressor: Compressor::new(config.compression),
+            stall_log_interval: config.stall_log_secs.map(Duration::from_secs),
         })
     }
 
+    /// Returns the remote address of the connection to the party with the given id.
+    pub fn peer_addr(&self, id: usize) -> Option<SocketAddr> {
+        self.connections.get(&id).map(Connection::remote_address)
+    }
+
+    /// Adds the id and the remote address of the given party to an error that occurred while communicating with this party, e.g., "party 1 (10.0.0.5:9001): connection reset".
+    pub fn party_error(&self, id: usize, err: io::Error) -> io::Error {
+        let addr = self
+            .peer_addr(id)
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|| "unknown address".to_owned());
+        io::Error::new(err.kind(), format!("party {id} ({addr}): {err}"))
+    }
+
+    /// Returns an error if a party was detected to be unresponsive by the heartbeat (see [`NetworkConfig::heartbeat_interval_secs`]).
+    ///
+    /// The connection to an unresponsive party is closed, so pending receives fail. Network implementations can call this method to replace the resulting generic error with a more descriptive one.
+    pub fn check_heartbeat(&self) -> std::io::Result<()> {
+        match *self.heartbeat.unresponsive.lock().expect("not poisoned") {
+            Some(id) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("party {id} unresponsive"),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the interval after which a party that waits for a message logs the messages in flight, see [`NetworkConfig::stall_log_secs`].
+    pub fn stall_log_interval(&self) -> Option<Duration> {
+        self.stall_log_interval
+    }
+
+    /// Compresses a message according to [`NetworkConfig::compression`] before it is sent over a byte channel.
+    pub fn compress(&self, data: Bytes) -> std::io::Result<Bytes> {
+        self.compressor.compress(data)
+    }
+
+    /// Decompresses a message received over a byte channel, see [`Self::compress`].
+    pub fn decompress(&self, data: BytesMut) -> std::io::Result<BytesMut> {
+        self.compressor.decompress(data)
+    }
+
+    /// Returns the number of message bytes before and after compression.
+    pub fn compression_stats(&self) -> CompressionStats {
+        self.compressor.stats()
+    }
+
     /// Returns the number of sent and received bytes.
     pub fn get_send_receive(&self, i: usize) -> std::io::Result<(u64, u64)> {
         let conn = self
@@ -217,6 +459,18 @@ impl MpcNetworkHandler {
                 i, stats.udp_tx.bytes, stats.udp_rx.bytes
             )?;
         }
+        if self.compressor.compression() != Compression::None {
+            let stats = self.compressor.stats();
+            writeln!(
+                out,
+                "Compression ({}) stats:\n\tSENT: {} bytes compressed to {} bytes\n\tRECV: {} bytes decompressed to {} bytes",
+                self.compressor.compression(),
+                stats.raw_sent,
+                stats.compressed_sent,
+                stats.compressed_recv,
+                stats.raw_recv
+            )?;
+        }
         Ok(())
     }
 
@@ -225,7 +479,7 @@ impl MpcNetworkHandler {
         &self,
     ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
         let mut codec = LengthDelimitedCodec::new();
-        codec.set_max_frame_length(1_000_000_000);
+        codec.set_max_frame_length(MAX_FRAME_LENGTH);
         self.get_custom_channels(codec).await
     }
 
@@ -274,6 +528,19 @@ impl MpcNetworkHandler {
 
     /// Shutdown all connections, and call [`quinn::Endpoint::wait_idle`] on all of them
     pub async fn shutdown(&self) -> std::io::Result<()> {
+        // closed connections are expected from now on
+        self.heartbeat.closing.store(true, Ordering::SeqCst);
+        if self.compressor.compression() != Compression::None {
+            let stats = self.compressor.stats();
+            tracing::info!(
+                "party {}: sent {} bytes compressed to {} bytes, received {} bytes decompressed to {} bytes",
+                self.my_id,
+                stats.raw_sent,
+                stats.compressed_sent,
+                stats.compressed_recv,
+                stats.raw_recv
+            );
+        }
         tracing::debug!(
             "party {} shutting down, conns = {:?}",
             self.my_id,
@@ -306,3 +573,168 @@ impl MpcNetworkHandler {
         Ok(())
     }
 }
+
+const HEARTBEAT: u8 = 0x42;
+
+fn describe_heartbeat(interval_secs: Option<u64>) -> String {
+    match interval_secs {
+        Some(secs) => format!("a heartbeat every {secs} s"),
+        None => "no heartbeat".to_owned(),
+    }
+}
+
+/// Periodically sends heartbeats to the party with the given id and checks that heartbeats from this party arrive in time. If they do not, the connection is closed and the party is marked as unresponsive.
+///
+/// If the other party closes the connection normally, e.g., as it shuts down before us, the heartbeat stops without marking the party as unresponsive.
+async fn run_heartbeat(
+    party: usize,
+    conn: Connection,
+    mut send: SendStream,
+    mut recv: RecvStream,
+    interval: Duration,
+    state: Arc<HeartbeatState>,
+) {
+    let timeout = interval * HEARTBEAT_TIMEOUT_FACTOR;
+    let sender = async {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if send.write_u8(HEARTBEAT).await.is_err() {
+                break;
+            }
+        }
+    };
+    let receiver =
+        async { while let Ok(Ok(_)) = tokio::time::timeout(timeout, recv.read_u8()).await {} };
+    tokio::select! {
+        _ = sender => {}
+        _ = receiver => {}
+    }
+    if state.closing.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Some(quinn::ConnectionError::ApplicationClosed(close)) = conn.close_reason() {
+        if close.error_code == VarInt::from_u32(0) {
+            tracing::debug!("party {party} closed the connection");
+            return;
+        }
+    }
+    tracing::error!("party {party} unresponsive");
+    state
+        .unresponsive
+        .lock()
+        .expect("not poisoned")
+        .get_or_insert(party);
+    conn.close(
+        VarInt::from_u32(1),
+        format!("party {party} unresponsive").as_bytes(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use config::{Address, NetworkParty};
+
+    use super::*;
+
+    // configs of two parties on localhost with fresh certificates
+    fn local_configs(name: &str) -> [NetworkConfig; 2] {
+        let dir = std::env::temp_dir().join(format!("mpc-net-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let parties = (0..2)
+            .map(|id| {
+                let rcgen::CertifiedKey { cert, key_pair } =
+                    rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+                let cert_path = dir.join(format!("cert{id}.der"));
+                std::fs::write(&cert_path, cert.der()).unwrap();
+                std::fs::write(dir.join(format!("key{id}.der")), key_pair.serialize_der()).unwrap();
+                let port = std::net::UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+                    .unwrap()
+                    .local_addr()
+                    .unwrap()
+                    .port();
+                NetworkParty {
+                    id,
+                    dns_name: Address {
+                        hostname: "localhost".to_owned(),
+                        port,
+                    },
+                    cert_path,
+                }
+            })
+            .collect::<Vec<_>>();
+        [0, 1].map(|my_id| NetworkConfig {
+            parties: parties.clone(),
+            my_id,
+            bind_addr: SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                parties[my_id].dns_name.port,
+            ),
+            key_path: dir.join(format!("key{my_id}.der")),
+            heartbeat_interval_secs: None,
+            connect_timeout_secs: Some(10),
+            stall_log_secs: None,
+            compression: Compression::None,
+            relay: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn listener_is_called_once_bound() {
+        let [config0, config1] = local_configs("listener");
+        let bind_addr = config0.bind_addr;
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let net0 = tokio::spawn(MpcNetworkHandler::establish_with_listener(
+            config0,
+            move |addr| tx.send(addr).unwrap(),
+        ));
+
+        // party 0 listens before party 1 is started
+        let addr = tokio::time::timeout(Duration::from_secs(5), rx)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(addr, bind_addr);
+        assert!(std::net::UdpSocket::bind(addr).is_err());
+
+        let net1 = MpcNetworkHandler::establish(config1).await.unwrap();
+        let net0 = net0.await.unwrap().unwrap();
+        assert!(net0.peer_addr(1).is_some());
+        assert!(net1.peer_addr(0).is_some());
+    }
+
+    #[tokio::test]
+    async fn heartbeat_mismatch_is_rejected() {
+        let [config0, mut config1] = local_configs("heartbeat-mismatch");
+        config1.heartbeat_interval_secs = Some(1);
+        let (net0, net1) = tokio::join!(
+            MpcNetworkHandler::establish(config0),
+            MpcNetworkHandler::establish(config1)
+        );
+        let err = net0.unwrap_err().to_string();
+        assert!(err.contains("party 1 uses a heartbeat every 1 s, but we use no heartbeat"));
+        let err = net1.unwrap_err().to_string();
+        assert!(err.contains("party 0 uses no heartbeat, but we use a heartbeat every 1 s"));
+    }
+
+    #[tokio::test]
+    async fn closed_connection_is_not_unresponsive() {
+        let [mut config0, mut config1] = local_configs("heartbeat-close");
+        config0.heartbeat_interval_secs = Some(1);
+        config1.heartbeat_interval_secs = Some(1);
+        let (net0, net1) = tokio::join!(
+            MpcNetworkHandler::establish(config0),
+            MpcNetworkHandler::establish(config1)
+        );
+        let (net0, net1) = (net0.unwrap(), net1.unwrap());
+
+        // party 1 shuts down and closes the connection before party 0 starts to shut down
+        net1.heartbeat.closing.store(true, Ordering::SeqCst);
+        net1.connections[&0].close(0u32.into(), b"done");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(net0.check_heartbeat().is_ok());
+        assert!(net1.check_heartbeat().is_ok());
+    }
+}
@@ -0,0 +1,188 @@
+//! A relay for parties that cannot reach each other directly.
+//!
+//! If [`NetworkConfig::relay`](crate::config::NetworkConfig::relay) is set, the parties do not connect to each other, but to a relay that all of them can reach. The relay has one UDP port per party, party `i` is reached at port `relay.port + i`. Every party first registers the address of its endpoint at its own port, afterwards the relay forwards every datagram that party `i` sends to the port of party `j` to party `j`, from the port of party `i`. So each party sees the other parties at their relay ports and the QUIC connections are established end to end through the relay as usual.
+//!
+//! The relay only forwards UDP datagrams, the QUIC connections are encrypted and the certificates of the parties are checked by the parties themselves, so the relay learns neither the messages nor the shares. It can still drop datagrams or abort the run.
+//!
+//! The registrations are not authenticated: anyone who can reach the port of party `i` can register as party `i` and receives the datagrams for this party from then on. Such an impostor cannot complete the QUIC handshakes, as it does not have the key of party `i`, but like the relay itself it can stop the run. Only expose the relay ports to the parties if this is a concern.
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use color_eyre::eyre::{self, Context, Report};
+use tokio::net::UdpSocket;
+
+/// The prefix of a registration datagram, followed by the id of the party as big endian `u32`. The relay echoes the datagram as acknowledgement.
+const REGISTER_MAGIC: &[u8; 8] = b"MPCRELAY";
+/// How often a party sends its registration before giving up.
+const REGISTER_ATTEMPTS: u32 = 10;
+/// How long a party waits for the acknowledgement of its registration.
+const REGISTER_TIMEOUT: Duration = Duration::from_secs(1);
+/// The size of the receive buffer, which fits every UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = u16::MAX as usize;
+
+fn register_datagram(id: usize) -> Vec<u8> {
+    let mut datagram = REGISTER_MAGIC.to_vec();
+    datagram.extend(
+        u32::try_from(id)
+            .expect("party id fits into u32")
+            .to_be_bytes(),
+    );
+    datagram
+}
+
+/// Returns the address at which the relay forwards datagrams to the party with the given id.
+pub fn party_addr(relay: SocketAddr, id: usize) -> eyre::Result<SocketAddr> {
+    let port = u16::try_from(id)
+        .ok()
+        .and_then(|id| relay.port().checked_add(id))
+        .ok_or_else(|| eyre::eyre!("the relay has no port for party {id}"))?;
+    Ok(SocketAddr::new(relay.ip(), port))
+}
+
+/// Registers the address of `socket` as the address of party `id` at the relay. The socket is then used for the QUIC endpoint of the party.
+pub(crate) async fn register(socket: &UdpSocket, relay: SocketAddr, id: usize) -> eyre::Result<()> {
+    let relay_port = party_addr(relay, id)?;
+    let datagram = register_datagram(id);
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    for _ in 0..REGISTER_ATTEMPTS {
+        socket
+            .send_to(&datagram, relay_port)
+            .await
+            .with_context(|| format!("sending registration to relay {relay_port}"))?;
+        let ack = tokio::time::timeout(REGISTER_TIMEOUT, async {
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await?;
+                if from == relay_port && buf[..len] == datagram[..] {
+                    return io::Result::Ok(());
+                }
+            }
+        })
+        .await;
+        match ack {
+            Ok(result) => {
+                result
+                    .with_context(|| format!("receiving registration from relay {relay_port}"))?;
+                tracing::info!("registered at relay {relay_port}");
+                return Ok(());
+            }
+            Err(_) => tracing::debug!("no answer from relay {relay_port}, retrying"),
+        }
+    }
+    Err(eyre::eyre!(
+        "relay {relay_port} did not acknowledge our registration"
+    ))
+}
+
+/// Runs a relay for `num_parties` parties on the ports `port..port + num_parties` of `ip`, see the [module documentation](self). Runs until an error occurs.
+pub async fn run_relay(ip: IpAddr, port: u16, num_parties: usize) -> Result<(), Report> {
+    let mut sockets = Vec::with_capacity(num_parties);
+    for id in 0..num_parties {
+        let addr = party_addr(SocketAddr::new(ip, port), id)?;
+        let socket = UdpSocket::bind(addr)
+            .await
+            .with_context(|| format!("binding relay port {addr} of party {id}"))?;
+        tracing::info!("relaying to party {id} on {addr}");
+        sockets.push(Arc::new(socket));
+    }
+    // the registered address of each party
+    let parties = Arc::new(Mutex::new(HashMap::<usize, SocketAddr>::new()));
+    let mut tasks = tokio::task::JoinSet::new();
+    for id in 0..num_parties {
+        tasks.spawn(forward_to(id, sockets.clone(), Arc::clone(&parties)));
+    }
+    while let Some(result) = tasks.join_next().await {
+        result.context("relay task panicked")??;
+    }
+    Ok(())
+}
+
+/// Receives the datagrams at the port of party `to` and forwards them to this party from the port of their sender.
+async fn forward_to(
+    to: usize,
+    sockets: Vec<Arc<UdpSocket>>,
+    parties: Arc<Mutex<HashMap<usize, SocketAddr>>>,
+) -> Result<(), Report> {
+    let socket = &sockets[to];
+    let registration = register_datagram(to);
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        let (len, from) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            // an ICMP error from an earlier send, e.g., as a party is not up anymore
+            Err(err) if err.kind() == io::ErrorKind::ConnectionReset => continue,
+            Err(err) => {
+                return Err(err).with_context(|| format!("receiving at relay port of party {to}"))
+            }
+        };
+        let datagram = &buf[..len];
+        if datagram == registration {
+            tracing::info!("party {to} registered from {from}");
+            parties.lock().expect("not poisoned").insert(to, from);
+            if let Err(err) = socket.send_to(datagram, from).await {
+                tracing::debug!("acknowledging registration of party {to}: {err}");
+            }
+            continue;
+        }
+        let (sender, receiver) = {
+            let parties = parties.lock().expect("not poisoned");
+            let sender = parties
+                .iter()
+                .find(|(_, addr)| **addr == from)
+                .map(|(id, _)| *id);
+            (sender, parties.get(&to).copied())
+        };
+        match (sender, receiver) {
+            (Some(sender), Some(receiver)) if sender != to => {
+                if let Err(err) = sockets[sender].send_to(datagram, receiver).await {
+                    tracing::debug!("forwarding from party {sender} to party {to}: {err}");
+                }
+            }
+            // QUIC retransmits datagrams, so we drop everything we cannot forward yet
+            _ => tracing::trace!("dropping datagram from {from} to party {to}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn forwards_between_registered_parties() {
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        // a free port, the port after it is very likely free as well
+        let port = std::net::UdpSocket::bind((localhost, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let relay_task = tokio::spawn(run_relay(localhost, port, 2));
+        let relay = SocketAddr::new(localhost, port);
+
+        let party0 = UdpSocket::bind((localhost, 0)).await.unwrap();
+        let party1 = UdpSocket::bind((localhost, 0)).await.unwrap();
+        register(&party0, relay, 0).await.unwrap();
+        register(&party1, relay, 1).await.unwrap();
+
+        party0
+            .send_to(b"hello", party_addr(relay, 1).unwrap())
+            .await
+            .unwrap();
+        let mut buf = [0u8; 16];
+        let (len, from) = tokio::time::timeout(Duration::from_secs(5), party1.recv_from(&mut buf))
+            .await
+            .expect("datagram is forwarded")
+            .unwrap();
+        assert_eq!(&buf[..len], b"hello");
+        assert_eq!(from, party_addr(relay, 0).unwrap());
+
+        relay_task.abort();
+    }
+}
@@ -0,0 +1,33 @@
+//! Round counting for MPC networks.
+//!
+//! The latency of an MPC protocol on high-RTT links is dominated by the number of synchronous communication rounds rather than by the number of bytes sent. This module provides a small helper that network implementations use to detect rounds. Each detected round is emitted as a [`tracing`] event with target [`ROUND_TRACE_TARGET`], so that a subscriber can attribute it to the spans that are active at that point.
+
+/// The tracing target of the event that is emitted once per communication round.
+pub const ROUND_TRACE_TARGET: &str = "mpc_net::rounds";
+
+/// Detects synchronous communication rounds of a single network instance.
+///
+/// A round is counted whenever a party starts waiting for data after it has sent data since its last receive, i.e., sending a batch of messages and then receiving the answers counts as one round, no matter how many messages are involved.
+#[derive(Debug, Default)]
+pub struct RoundTracker {
+    sent_since_recv: bool,
+}
+
+impl RoundTracker {
+    /// Creates a new tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Must be called whenever data is sent.
+    pub fn on_send(&mut self) {
+        self.sent_since_recv = true;
+    }
+
+    /// Must be called whenever the party waits for data. Emits a round event if data was sent since the last receive.
+    pub fn on_recv(&mut self) {
+        if std::mem::take(&mut self.sent_since_recv) {
+            tracing::trace!(target: ROUND_TRACE_TARGET, "communication round");
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pragma circom 2.0.0;
+
+template CheckProduct() {
+    signal input a;
+    signal input b;
+    signal input c;
+    c === a * b;
+}
+
+component main = CheckProduct();
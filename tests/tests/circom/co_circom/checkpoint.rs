@@ -0,0 +1,55 @@
+use ark_bn254::Fr;
+use co_circom::checkpoint::CheckpointedNet;
+use itertools::izip;
+use mpc_core::protocols::rep3::network::Rep3Network;
+use std::{path::PathBuf, thread, time::Duration};
+use tests::rep3_network::Rep3TestNetwork;
+
+const ROUNDS: u64 = 4;
+
+fn checkpoint_dirs(name: &str) -> [PathBuf; 3] {
+    let dir = std::env::temp_dir().join(format!("co-circom-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    [0, 1, 2].map(|id| dir.join(format!("party{id}")))
+}
+
+/// Runs `rounds` rounds of resharing and broadcasting, on the network and on a fork of it, and returns everything that was received.
+/// The sent values depend on `offset`, so a replayed message can be told apart from a message that was sent again.
+fn run(dirs: &[PathBuf; 3], rounds: u64, offset: u64) -> Vec<Vec<Fr>> {
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, dir) in izip!(test_network.get_party_networks(), dirs.clone()) {
+        threads.push(thread::spawn(move || {
+            let (mut net, _) = CheckpointedNet::open(net, &dir, Duration::ZERO, [7; 32]).unwrap();
+            let mut fork = net.fork().unwrap();
+            let id = net.get_id() as u64;
+            let mut received = vec![];
+            for round in 0..rounds {
+                let value = Fr::from(offset + 10 * round + id);
+                received.extend(net.reshare_many(&[value, -value]).unwrap());
+                let (prev, next) = fork.broadcast(value).unwrap();
+                received.extend([prev, next]);
+            }
+            received
+        }));
+    }
+    threads.into_iter().map(|t| t.join().unwrap()).collect()
+}
+
+#[test]
+fn resumed_run_replays_the_recorded_transcript() {
+    let dirs = checkpoint_dirs("checkpoint-replay");
+    let recorded = run(&dirs, ROUNDS, 0);
+    // the recorded messages are replayed instead of the ones sent now, the remaining ones are received
+    let resumed = run(&dirs, 2 * ROUNDS, 1000);
+    let fresh_dirs = checkpoint_dirs("checkpoint-fresh");
+    let fresh = run(&fresh_dirs, 2 * ROUNDS, 1000);
+    for (recorded, resumed, fresh) in izip!(recorded, resumed, fresh) {
+        let (replayed, received) = resumed.split_at(recorded.len());
+        assert_eq!(replayed, recorded);
+        assert_eq!(received, &fresh[recorded.len()..]);
+    }
+    for dirs in [dirs, fresh_dirs] {
+        std::fs::remove_dir_all(dirs[0].parent().unwrap()).unwrap();
+    }
+}
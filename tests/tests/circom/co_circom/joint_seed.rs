@@ -0,0 +1,45 @@
+use co_circom::joint_seed;
+use mpc_core::protocols::rep3::id::PartyID;
+use rand::thread_rng;
+use std::thread;
+use tests::rep3_network::Rep3TestNetwork;
+
+#[test]
+fn parties_derive_the_same_seeds() {
+    let [mut net0, mut net1, mut net2] = Rep3TestNetwork::default().get_party_networks();
+    let party0 = thread::spawn(move || joint_seed::toss_mask_seeds(&mut net0, &mut thread_rng()));
+    let party1 =
+        thread::spawn(move || joint_seed::contribute_mask_seed(&mut net1, &mut thread_rng()));
+    let party2 =
+        thread::spawn(move || joint_seed::contribute_mask_seed(&mut net2, &mut thread_rng()));
+    let [toss1, toss2] = party0.join().unwrap().unwrap();
+    assert_eq!(toss1, party1.join().unwrap().unwrap());
+    assert_eq!(toss2, party2.join().unwrap().unwrap());
+    assert_eq!(toss1.party, PartyID::ID1);
+    assert_eq!(toss2.party, PartyID::ID2);
+    assert!(toss1.verify() && toss2.verify());
+    assert_ne!(toss1.seed, toss2.seed);
+}
+
+#[test]
+fn tampered_toss_does_not_verify() {
+    let [mut net0, mut net1, mut net2] = Rep3TestNetwork::default().get_party_networks();
+    let party0 = thread::spawn(move || joint_seed::toss_mask_seeds(&mut net0, &mut thread_rng()));
+    let party2 =
+        thread::spawn(move || joint_seed::contribute_mask_seed(&mut net2, &mut thread_rng()));
+    let toss = joint_seed::contribute_mask_seed(&mut net1, &mut thread_rng()).unwrap();
+    party0.join().unwrap().unwrap();
+    party2.join().unwrap().unwrap();
+    let mut tampered = toss.clone();
+    tampered.contribution0[0] ^= 1;
+    assert!(!tampered.verify());
+    let mut tampered = toss;
+    tampered.seed[0] ^= 1;
+    assert!(!tampered.verify());
+}
+
+#[test]
+fn only_party_0_tosses_the_seeds() {
+    let [_, mut net1, _] = Rep3TestNetwork::default().get_party_networks();
+    assert!(joint_seed::toss_mask_seeds(&mut net1, &mut thread_rng()).is_err());
+}
@@ -0,0 +1,4 @@
+mod checkpoint;
+mod joint_seed;
+mod reshare;
+mod witness_share;
@@ -0,0 +1,87 @@
+use ark_bn254::Fr;
+use ark_std::UniformRand;
+use co_circom::reshare;
+use co_circom_snarks::SharedWitness;
+use itertools::{izip, Itertools};
+use mpc_core::protocols::{rep3, shamir};
+use rand::thread_rng;
+use std::thread;
+use tests::shamir_network::ShamirTestNetwork;
+
+const VEC_SIZE: usize = 10;
+
+/// Splits `secret` into additive shares for the three REP3 parties.
+fn additive_shares(secret: &[Fr]) -> Vec<SharedWitness<Fr, Fr>> {
+    let mut rng = thread_rng();
+    let a = (0..secret.len()).map(|_| Fr::rand(&mut rng)).collect_vec();
+    let b = (0..secret.len()).map(|_| Fr::rand(&mut rng)).collect_vec();
+    let c = izip!(secret, &a, &b).map(|(x, a, b)| *x - a - b).collect();
+    [a, b, c]
+        .into_iter()
+        .map(|witness| SharedWitness {
+            public_inputs: vec![Fr::from(1u64), Fr::from(42u64)],
+            witness,
+        })
+        .collect()
+}
+
+#[test]
+fn rep3_to_shamir_keeps_secret() {
+    const NUM_PARTIES: usize = 5;
+    const THRESHOLD: usize = 2;
+    let mut rng = thread_rng();
+    let secret = (0..VEC_SIZE).map(|_| Fr::rand(&mut rng)).collect_vec();
+    let mut witnesses = additive_shares(&secret).into_iter();
+
+    let mut threads = vec![];
+    for mut net in ShamirTestNetwork::new(NUM_PARTIES).get_party_networks() {
+        let witness = witnesses.next();
+        threads.push(thread::spawn(move || {
+            reshare::reshare_rep3_to_shamir(
+                &mut net,
+                witness,
+                THRESHOLD,
+                NUM_PARTIES,
+                &mut thread_rng(),
+            )
+            .unwrap()
+            .expect("all parties receive a share")
+        }));
+    }
+    let reshared = threads.into_iter().map(|t| t.join().unwrap()).collect_vec();
+    for share in reshared.iter() {
+        assert_eq!(share.public_inputs, [Fr::from(1u64), Fr::from(42u64)]);
+    }
+    let shares = reshared
+        .into_iter()
+        .map(|share| share.witness)
+        .collect_vec();
+    let reconstructed =
+        shamir::combine_field_elements(&shares, &(1..=NUM_PARTIES).collect_vec(), THRESHOLD)
+            .unwrap();
+    assert_eq!(reconstructed, secret);
+}
+
+#[test]
+fn rep3_to_rep3_keeps_secret() {
+    let mut rng = thread_rng();
+    let secret = (0..VEC_SIZE).map(|_| Fr::rand(&mut rng)).collect_vec();
+
+    let mut threads = vec![];
+    for (mut net, witness) in ShamirTestNetwork::new(3)
+        .get_party_networks()
+        .into_iter()
+        .zip(additive_shares(&secret))
+    {
+        threads.push(thread::spawn(move || {
+            reshare::reshare_rep3_to_rep3(&mut net, witness, &mut thread_rng()).unwrap()
+        }));
+    }
+    let [a, b, c] = threads
+        .into_iter()
+        .map(|t| t.join().unwrap().witness)
+        .collect_vec()
+        .try_into()
+        .unwrap();
+    assert_eq!(rep3::combine_field_elements(a, b, c), secret);
+}
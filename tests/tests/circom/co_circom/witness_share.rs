@@ -0,0 +1,86 @@
+use ark_bn254::{Bn254, Fr};
+use circom_types::{Witness, R1CS};
+use co_circom::{MPCCurve, SeedRng};
+use co_circom_snarks::{SerializeableSharedRep3Witness, SharedWitness};
+use itertools::izip;
+use mpc_core::protocols::rep3::Rep3PrimeFieldShare;
+use rand::thread_rng;
+use std::{fs::File, thread};
+use tests::rep3_network::Rep3TestNetwork;
+
+type Rep3Witness = SharedWitness<Fr, Rep3PrimeFieldShare<Fr>>;
+
+fn serialize_shares(witness: Witness<Fr>, num_inputs: usize, seeded: bool) -> [Vec<u8>; 3] {
+    SerializeableSharedRep3Witness::<_, SeedRng>::share_rep3(
+        witness,
+        num_inputs,
+        &mut thread_rng(),
+        seeded,
+        true,
+    )
+    .map(|share| {
+        let mut bytes = vec![];
+        co_circom::write_witness_share_header(&mut bytes, MPCCurve::BN254).unwrap();
+        bincode::serialize_into(&mut bytes, &share).unwrap();
+        bytes
+    })
+}
+
+// every party picks its parser on its own
+fn parse_shares(shares: [Vec<u8>; 3], lazy: [bool; 3]) -> Vec<Rep3Witness> {
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (mut net, share, lazy) in izip!(test_network.get_party_networks(), shares, lazy) {
+        threads.push(thread::spawn(move || {
+            let share = co_circom::WitnessShareReader::new(share.as_slice()).unwrap();
+            if lazy {
+                co_circom::parse_witness_share_rep3_lazy(share, &mut net).unwrap()
+            } else {
+                co_circom::parse_witness_share_rep3(share, &mut net).unwrap()
+            }
+        }));
+    }
+    threads.into_iter().map(|t| t.join().unwrap()).collect()
+}
+
+fn assert_same(a: &[Rep3Witness], b: &[Rep3Witness]) {
+    for (a, b) in a.iter().zip(b) {
+        assert_eq!(a.public_inputs, b.public_inputs);
+        assert_eq!(a.witness, b.witness);
+    }
+}
+
+fn check_lazy_matches_eager(witness: Witness<Fr>, num_inputs: usize) {
+    for seeded in [false, true] {
+        let shares = serialize_shares(witness.clone(), num_inputs, seeded);
+        let eager = parse_shares(shares.clone(), [false; 3]);
+        let lazy = parse_shares(shares.clone(), [true; 3]);
+        assert_same(&eager, &lazy);
+        let mixed = parse_shares(shares, [true, false, true]);
+        assert_same(&eager, &mixed);
+        let [a, b, c] = <[_; 3]>::try_from(eager).unwrap();
+        let reconstructed =
+            mpc_core::protocols::rep3::combine_field_elements(a.witness, b.witness, c.witness);
+        assert_eq!(reconstructed, witness.values[num_inputs..]);
+    }
+}
+
+#[test]
+fn lazy_parser_matches_eager_parser() {
+    let r1cs = R1CS::<Bn254>::from_reader(
+        File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap(),
+    )
+    .unwrap();
+    let witness = Witness::<Fr>::from_reader(
+        File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap(),
+    )
+    .unwrap();
+    check_lazy_matches_eager(witness, r1cs.num_inputs);
+}
+
+#[test]
+fn lazy_parser_matches_eager_parser_over_several_chunks() {
+    // larger than the chunk size used for resharing
+    let values = (0..(1 << 17) + 3).map(Fr::from).collect();
+    check_lazy_matches_eager(Witness { values }, 2);
+}
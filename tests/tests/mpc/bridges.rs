@@ -81,6 +81,43 @@ mod translate_share {
         assert_eq!(is_result, x);
     }
 
+    #[test]
+    fn addshare_vec_self_check() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = (0..VEC_SIZE)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect_vec();
+        let x_shares = rep3::share_field_elements_additive(&x, &mut rng);
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for ((net, tx), x) in test_network
+            .get_party_networks()
+            .into_iter()
+            .zip([tx1, tx2, tx3])
+            .zip(x_shares)
+        {
+            thread::spawn(move || {
+                let preprecessing =
+                    ShamirPreprocessing::new(1, net.to_shamir_net(), x.len() + 2).unwrap();
+                let mut shamir = ShamirProtocol::from(preprecessing);
+                let mut share = shamir.translate_primefield_addshare_vec(x.clone()).unwrap();
+                let valid = shamir
+                    .check_translated_primefield_addshare_vec(&x, &share)
+                    .unwrap();
+                share[0] = shamir::arithmetic::add_public(share[0], ark_bn254::Fr::from(1u64));
+                let tampered = shamir
+                    .check_translated_primefield_addshare_vec(&x, &share)
+                    .unwrap();
+                tx.send((valid, tampered))
+            });
+        }
+        for rx in [rx1, rx2, rx3] {
+            assert_eq!(rx.recv().unwrap(), (true, false));
+        }
+    }
+
     #[test]
     fn pointshare() {
         let test_network = Rep3TestNetwork::default();
@@ -6,6 +6,7 @@ mod field_share {
     use mpc_core::protocols::rep3::id::PartyID;
     use mpc_core::protocols::rep3::{self, arithmetic, network::IoContext};
     use rand::thread_rng;
+    use rand_chacha::ChaCha12Rng;
     use std::sync::mpsc;
     use std::thread;
     use tests::rep3_network::Rep3TestNetwork;
@@ -33,6 +34,33 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_to_additive() {
+        let mut rng = thread_rng();
+        let x = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let combine = |shares: [rep3::Rep3ShareVecType<ark_bn254::Fr, ChaCha12Rng>; 3]| {
+            let [share1, share2, share3] = shares.map(rep3::rep3_to_additive);
+            izip!(share1, share2, share3)
+                .map(|(a, b, c)| a + b + c)
+                .collect::<Vec<_>>()
+        };
+
+        let shares =
+            rep3::share_field_elements(&x, &mut rng).map(rep3::Rep3ShareVecType::Replicated);
+        assert_eq!(combine(shares), x);
+        let shares = rep3::share_field_elements_seeded::<_, _, ChaCha12Rng>(&x, &mut rng)
+            .map(rep3::Rep3ShareVecType::SeededReplicated);
+        assert_eq!(combine(shares), x);
+        let shares =
+            rep3::share_field_elements_additive(&x, &mut rng).map(rep3::Rep3ShareVecType::Additive);
+        assert_eq!(combine(shares), x);
+        let shares = rep3::share_field_elements_additive_seeded::<_, _, ChaCha12Rng>(&x, &mut rng)
+            .map(rep3::Rep3ShareVecType::SeededAdditive);
+        assert_eq!(combine(shares), x);
+    }
+
     #[test]
     fn rep3_sub() {
         let mut rng = thread_rng();
@@ -102,6 +130,45 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_additive_to_rep3() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let x_shares = rep3::share_field_element_additive(x, &mut rng);
+        let y_shares = rep3::share_field_elements_additive(&y, &mut rng);
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut ctx = IoContext::init(net).unwrap();
+                let x_rep3 = arithmetic::additive_to_rep3(x, &mut ctx).unwrap();
+                let y_rep3 = arithmetic::additive_to_rep3_vec(y, &mut ctx).unwrap();
+                // the replicated shares are re-randomized, not just the additive shares
+                assert_ne!(x_rep3.a, x);
+                tx.send((x_rep3, y_rep3))
+            });
+        }
+        let (x1, y1) = rx1.recv().unwrap();
+        let (x2, y2) = rx2.recv().unwrap();
+        let (x3, y3) = rx3.recv().unwrap();
+        assert_eq!(rep3::combine_field_element(x1, x2, x3), x);
+        assert_eq!(rep3::combine_field_elements(y1, y2, y3), y);
+        // the shares are consistent replicated shares, i.e., every party holds the share of its predecessor
+        assert_eq!(x1.b, x3.a);
+        assert_eq!(x2.b, x1.a);
+        assert_eq!(x3.b, x2.a);
+    }
+
     #[test]
     fn rep3_mul() {
         let test_network = Rep3TestNetwork::default();
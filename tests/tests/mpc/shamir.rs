@@ -2,7 +2,7 @@ mod field_share {
     use ark_ff::Field;
     use ark_std::{UniformRand, Zero};
     use itertools::{izip, Itertools};
-    use mpc_core::protocols::shamir::{self, arithmetic, ShamirPreprocessing};
+    use mpc_core::protocols::shamir::{self, arithmetic, ShamirPreprocessing, ShamirProtocol};
     use rand::thread_rng;
     use std::{str::FromStr, sync::mpsc, thread};
     use tests::shamir_network::ShamirTestNetwork;
@@ -83,6 +83,46 @@ mod field_share {
         shamir_sub_inner(10, 4);
     }
 
+    fn shamir_reshare_inner(num_parties: usize, threshold: usize) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = (0..10).map(|_| ark_bn254::Fr::rand(&mut rng)).collect_vec();
+        let x_shares = shamir::share_field_elements(&x, threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x) in izip!(test_network.get_party_networks(), tx, x_shares.clone()) {
+            thread::spawn(move || {
+                let mut shamir: ShamirProtocol<_, _> =
+                    ShamirPreprocessing::new(threshold, net, 0).unwrap().into();
+                tx.send(shamir.reshare(&x).unwrap())
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            results.push(r.recv().unwrap());
+        }
+
+        assert_ne!(results, x_shares);
+        let is_result =
+            shamir::combine_field_elements(&results, &(1..=num_parties).collect_vec(), threshold)
+                .unwrap();
+        assert_eq!(is_result, x);
+    }
+
+    #[test]
+    fn shamir_reshare() {
+        shamir_reshare_inner(3, 1);
+        shamir_reshare_inner(10, 4);
+    }
+
     fn shamir_mul2_then_add_inner(num_parties: usize, threshold: usize) {
         let test_network = ShamirTestNetwork::new(num_parties);
         let mut rng = thread_rng();
@@ -224,6 +264,75 @@ mod field_share {
         shamir_mul_vec_bn_inner(10, 4);
     }
 
+    fn shamir_mul_vec_for_parties_inner(party_ids: &[usize], threshold: usize) {
+        let num_parties = party_ids.len();
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let y = (0..x.len())
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let should_result = izip!(&x, &y).map(|(x, y)| x * y).collect::<Vec<_>>();
+
+        let x_shares =
+            shamir::share_field_elements_for_parties(&x, threshold, party_ids, &mut rng).unwrap();
+        let y_shares =
+            shamir::share_field_elements_for_parties(&y, threshold, party_ids, &mut rng).unwrap();
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x, y) in izip!(test_network.get_party_networks(), tx, x_shares, y_shares) {
+            let party_ids = party_ids.to_vec();
+            thread::spawn(move || {
+                let mut shamir =
+                    ShamirPreprocessing::new_for_parties(threshold, net, x.len(), &party_ids)
+                        .unwrap()
+                        .into();
+                let mul = arithmetic::mul_vec(&x, &y, &mut shamir).unwrap();
+                let mul = shamir.reshare(&mul).unwrap();
+                let opened = arithmetic::open_vec(&mul, &mut shamir).unwrap();
+                tx.send((mul, opened))
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            let (mul, opened) = r.recv().unwrap();
+            assert_eq!(opened, should_result);
+            results.push(mul);
+        }
+
+        let points = party_ids.iter().map(|id| id + 1).collect_vec();
+        let is_result = shamir::combine_field_elements(&results, &points, threshold).unwrap();
+
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn shamir_mul_vec_for_parties() {
+        shamir_mul_vec_for_parties_inner(&[3, 7, 9], 1);
+        shamir_mul_vec_for_parties_inner(&[11, 2, 5, 20, 6], 2);
+    }
+
+    #[test]
+    fn shamir_preprocessing_rejects_invalid_party_ids() {
+        let nets = ShamirTestNetwork::new(3).get_party_networks();
+        for (net, party_ids) in izip!(nets, [vec![0, 1], vec![0, 1, 1], vec![0, 1, usize::MAX]]) {
+            assert!(ShamirPreprocessing::<ark_bn254::Fr, _>::new_for_parties(
+                1, net, 0, &party_ids
+            )
+            .is_err());
+        }
+    }
+
     fn shamir_mul_vec_inner(num_parties: usize, threshold: usize) {
         let test_network = ShamirTestNetwork::new(num_parties);
         let mut rng = thread_rng();